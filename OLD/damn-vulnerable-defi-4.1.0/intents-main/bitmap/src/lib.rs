@@ -41,6 +41,12 @@ where
         (byte, byte_mask)
     }
 
+    /// Returns whether any bit with given prefix has a word allocated
+    #[inline]
+    pub fn contains_prefix(&self, prefix: U248) -> bool {
+        self.0.get(&prefix).is_some()
+    }
+
     #[inline]
     pub fn cleanup_by_prefix(&mut self, prefix: U248) -> bool {
         self.0.remove(&prefix).is_some()
@@ -137,6 +143,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn contains_prefix() {
+        let mut m = BitMap256::<HashMap<U248, U256>>::default();
+        let [prefix @ .., _] = [1; 32];
+        assert!(!m.contains_prefix(prefix));
+
+        m.set_bit([1; 32]);
+        assert!(m.contains_prefix(prefix));
+        assert!(!m.contains_prefix([0; 31]));
+
+        assert!(m.cleanup_by_prefix(prefix));
+        assert!(!m.contains_prefix(prefix));
+    }
+
     #[rstest]
     #[case(&[])]
     #[case(&[hex!("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff")])]
@@ -1,10 +1,18 @@
 use defuse_crypto::PublicKey;
 use defuse_serde_utils::base64::Base64;
-use near_sdk::{AccountIdRef, near};
+use near_sdk::{
+    AccountIdRef,
+    json_types::U128,
+    near,
+    serde::{Deserialize, Deserializer},
+};
 use serde_with::serde_as;
-use std::{borrow::Cow, collections::BTreeSet};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+};
 
-use crate::{Nonce, Salt, amounts::Amounts};
+use crate::{Nonce, Salt, amounts::Amounts, token_id::TokenId};
 
 #[must_use = "make sure to `.emit()` this event"]
 #[near(serializers = [json])]
@@ -55,15 +63,57 @@ pub struct PublicKeyEvent<'a> {
 pub struct NonceEvent {
     #[serde_as(as = "Base64")]
     pub nonce: Nonce,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_ref: Option<String>,
 }
 
 impl NonceEvent {
     #[inline]
     pub const fn new(nonce: Nonce) -> Self {
-        Self { nonce }
+        Self {
+            nonce,
+            client_ref: None,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn with_client_ref(mut self, client_ref: impl Into<Option<String>>) -> Self {
+        self.client_ref = client_ref.into();
+        self
     }
 }
 
+#[must_use = "make sure to `.emit()` this event"]
+#[near(serializers = [json])]
+#[derive(Debug, Clone, Default)]
+pub struct AccountLockedEvent<'a> {
+    /// Reason given by the operator who locked the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<Cow<'a, str>>,
+}
+
+/// Emitted when a role is granted to or revoked from the account
+#[must_use = "make sure to `.emit()` this event"]
+#[near(serializers = [json])]
+#[derive(Debug, Clone)]
+pub struct RoleEvent<'a> {
+    pub role: Cow<'a, str>,
+    /// Account which granted or revoked the role
+    pub by: Cow<'a, AccountIdRef>,
+}
+
+/// Emitted when the balance of the account funding auto-registrations
+/// drops below the threshold
+#[must_use = "make sure to `.emit()` this event"]
+#[near(serializers = [json])]
+#[derive(Debug, Clone)]
+pub struct RegistrationReserveLowEvent {
+    /// Remaining wNEAR balance of the reserve
+    pub balance: U128,
+}
+
 #[must_use = "make sure to `.emit()` this event"]
 #[near(serializers = [json])]
 #[derive(Debug, Clone)]
@@ -85,8 +135,25 @@ pub struct SaltRotationEvent {
 pub struct TransferEvent<'a> {
     pub receiver_id: Cow<'a, AccountIdRef>,
 
+    #[serde(deserialize_with = "deserialize_flattened_amounts")]
     pub tokens: Cow<'a, Amounts>,
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub memo: Cow<'a, Option<String>>,
 }
+
+/// `u128` can't be deserialized from content buffered by `#[serde(flatten)]`,
+/// which events are always wrapped into, so only amounts fitting into `u64`
+/// can be read back from event logs.
+fn deserialize_flattened_amounts<'de, 'a, D>(deserializer: D) -> Result<Cow<'a, Amounts>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let amounts = BTreeMap::<TokenId, u64>::deserialize(deserializer)?;
+    Ok(Cow::Owned(Amounts::new(
+        amounts
+            .into_iter()
+            .map(|(token_id, amount)| (token_id, amount.into()))
+            .collect(),
+    )))
+}
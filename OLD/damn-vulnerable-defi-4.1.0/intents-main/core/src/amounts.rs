@@ -201,6 +201,30 @@ where
     }
 }
 
+/// Formats `amount` of `token` as a human-readable decimal string given
+/// its `decimals` (e.g. from cached `ft_metadata`), so that `1500000`
+/// with `6` decimals becomes `"1.5"`. Trailing zeros of the fractional
+/// part are trimmed.
+///
+/// NOTE: `decimals` are ignored for NEP-171 tokens, since they are
+/// non-fungible. No host functions are called, so it can be used
+/// off-chain.
+pub fn format_amount(token: &TokenId, amount: u128, decimals: u8) -> String {
+    let decimals = usize::from(decimals);
+    if decimals == 0 || matches!(token, TokenId::Nep171(_)) {
+        return amount.to_string();
+    }
+
+    let digits = format!("{amount:0>width$}", width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{integer}.{fraction}")
+    }
+}
+
 impl<T, As> SerializeAs<Amounts<T>> for Amounts<As>
 where
     As: SerializeAs<T>,
@@ -259,10 +283,37 @@ mod abi {
 #[cfg(test)]
 mod tests {
 
-    use crate::token_id::nep141::Nep141TokenId;
+    use crate::token_id::{nep141::Nep141TokenId, nep171::Nep171TokenId};
+    use rstest::rstest;
 
     use super::*;
 
+    #[rstest]
+    #[case(0, 0, "0")]
+    #[case(0, 24, "0")]
+    #[case(123, 0, "123")]
+    #[case(1_500_000, 6, "1.5")]
+    #[case(1_000_000, 6, "1")]
+    #[case(1_000_001, 6, "1.000001")]
+    #[case(1, 6, "0.000001")]
+    #[case(100, 6, "0.0001")]
+    #[case(10_000_000_000_000_000_000_000_000, 24, "10")]
+    #[case(1_234_500_000_000_000_000_000_000, 24, "1.2345")]
+    #[case(u128::MAX, 18, "340282366920938463463.374607431768211455")]
+    #[case(u128::MAX, 40, "0.0340282366920938463463374607431768211455")]
+    fn format_ft_amount(#[case] amount: u128, #[case] decimals: u8, #[case] expected: &str) {
+        let token = TokenId::Nep141(Nep141TokenId::new("ft.near".parse().unwrap()));
+        assert_eq!(format_amount(&token, amount, decimals), expected);
+    }
+
+    #[test]
+    fn format_nft_amount() {
+        let token = TokenId::Nep171(
+            Nep171TokenId::new("nft.near".parse().unwrap(), "1".to_string()).unwrap(),
+        );
+        assert_eq!(format_amount(&token, 1, 24), "1");
+    }
+
     #[test]
     fn invariant() {
         let [t1, t2] =
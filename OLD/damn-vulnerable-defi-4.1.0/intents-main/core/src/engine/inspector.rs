@@ -1,6 +1,6 @@
 use crate::{Deadline, Nonce, events::DefuseEvent};
 use impl_tools::autoimpl;
-use near_sdk::{AccountIdRef, CryptoHash};
+use near_sdk::{AccountIdRef, CryptoHash, Gas};
 
 #[autoimpl(for <T: trait + ?Sized> &mut T, Box<T>)]
 pub trait Inspector {
@@ -8,5 +8,18 @@ pub trait Inspector {
 
     fn on_event(&mut self, event: DefuseEvent<'_>);
 
-    fn on_intent_executed(&mut self, signer_id: &AccountIdRef, hash: CryptoHash, nonce: Nonce);
+    /// Called right before intents are executed with minimum gas
+    /// required by promises they create
+    #[inline]
+    fn on_promises_min_gas(&mut self, min_gas: Gas) {
+        let _ = min_gas;
+    }
+
+    fn on_intent_executed(
+        &mut self,
+        signer_id: &AccountIdRef,
+        hash: CryptoHash,
+        nonce: Nonce,
+        client_ref: Option<&str>,
+    );
 }
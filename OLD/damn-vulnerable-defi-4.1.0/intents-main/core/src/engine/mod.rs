@@ -3,19 +3,86 @@ mod state;
 
 pub use self::{inspector::*, state::*};
 
-use defuse_crypto::{Payload, SignedPayload};
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+};
+
+use defuse_crypto::{Payload, PublicKey, SignedPayload};
+use near_sdk::{AccountId, AccountIdRef, CryptoHash, borsh, env};
 
 use crate::{
-    Deadline, DefuseError, ExpirableNonce, Nonce, Result, SaltedNonce, VersionedNonce,
-    intents::{DefuseIntents, ExecutableIntent},
-    payload::{DefusePayload, ExtractDefusePayload, multi::MultiPayload},
+    Deadline, DefuseError, Nonce, Result, VersionedNonce,
+    intents::{
+        DefuseIntents, ExecutableIntent,
+        session::Session,
+        token_diff::{TokenDeltas, TokenDiff},
+    },
+    payload::{
+        DefusePayload, ExtractDefusePayload, MAX_CLIENT_REF_LEN,
+        multi::{MultiPayload, SigningStandard},
+        template::SignedTemplatePayload,
+    },
 };
 
 use self::deltas::{Deltas, Transfers};
 
+#[derive(Clone)]
 pub struct Engine<S, I> {
     pub state: Deltas<S>,
     pub inspector: I,
+
+    /// Signatures collected so far for intents of multisig accounts
+    /// which haven't met the threshold yet
+    pending_approvals: HashMap<CryptoHash, HashSet<PublicKey>>,
+
+    /// Deltas of `TokenDiff` intents executed so far which are bound
+    /// to a solver, along with that solver and hash of the intent
+    bound_token_diffs: Vec<(AccountId, TokenDeltas, CryptoHash)>,
+
+    /// Deltas of `TokenDiff` intents executed so far by their signers
+    token_diffs: Vec<(AccountId, TokenDeltas)>,
+
+    /// Partially fillable `TokenDiff` to be filled after the rest of
+    /// the batch is executed
+    partial_fill: Option<PendingPartialFill>,
+}
+
+/// Outcome of [`Engine::execute_signed_intents_detailed`]
+pub struct DetailedExecution<I> {
+    /// Outcomes of signed payloads in the order they were passed
+    pub outcomes: Vec<Result<()>>,
+    /// Result of the batch with failed payloads skipped
+    pub result: Result<Transfers>,
+    pub inspector: I,
+}
+
+/// Intent which signature was verified, but which is not executed yet
+struct VerifiedIntent {
+    standard: SigningStandard,
+    public_key: PublicKey,
+    hash: CryptoHash,
+    payload: DefusePayload<DefuseIntents>,
+}
+
+/// How the public key an intent was signed with is authorized
+/// to act on behalf of the signer
+enum SignerKey {
+    /// Public key registered for the signer
+    Registered,
+    /// Trusted attester of EIP-1271 signatures
+    Attested,
+    /// Key the signer has granted a session to
+    Session(Session),
+}
+
+#[derive(Clone)]
+struct PendingPartialFill {
+    signer_id: AccountId,
+    nonce: Nonce,
+    hash: CryptoHash,
+    client_ref: Option<String>,
+    token_diff: TokenDiff,
 }
 
 impl<S, I> Engine<S, I>
@@ -28,20 +95,246 @@ where
         Self {
             state: Deltas::new(state),
             inspector,
+            pending_approvals: HashMap::new(),
+            bound_token_diffs: Vec::new(),
+            token_diffs: Vec::new(),
+            partial_fill: None,
         }
     }
 
+    /// Verifies all of the signed payloads and executes them in the
+    /// order of their [`priority`](DefusePayload::priority): payloads
+    /// with higher priority are executed first, while the ones with the
+    /// same priority keep the order they were relayed in.
     pub fn execute_signed_intents(
         mut self,
         signed: impl IntoIterator<Item = MultiPayload>,
     ) -> Result<Transfers> {
+        let signed: Vec<MultiPayload> = signed.into_iter().collect();
+        Self::check_duplicates(&signed)?;
+        let mut verified = Vec::with_capacity(signed.len());
         for signed in signed {
-            self.execute_signed_intent(signed)?;
+            self.verify_eip1271_chain_id(&signed)?;
+            Self::verify_signed_intent(signed, &mut verified)?;
+        }
+        // sort is stable, so ties are broken by the input order
+        verified.sort_by_key(|intent| Reverse(intent.payload.priority.unwrap_or_default()));
+        for VerifiedIntent {
+            standard,
+            public_key,
+            hash,
+            payload,
+        } in verified
+        {
+            self.execute_verified_intent(standard, public_key, hash, payload)?;
+        }
+        self.fill_partial()?;
+        if !self.pending_approvals.is_empty() {
+            return Err(DefuseError::InsufficientSignatures);
+        }
+        if let Some((solver, _)) = self.unmatched_solvers().next() {
+            return Err(DefuseError::SolverNotMatched(solver.clone()));
         }
         self.finalize()
     }
 
-    fn execute_signed_intent(&mut self, signed: MultiPayload) -> Result<()> {
+    /// Same as [`Self::execute_signed_intents`], but rather than failing
+    /// the whole batch, skips signed payloads which fail and reports
+    /// outcome of each of them in the order they were passed.
+    ///
+    /// State is checkpointed before each intent and restored if it
+    /// fails, so that the batch is executed only once.
+    ///
+    /// NOTE: intents which `TokenDiff` is not matched by its solver
+    /// are only known after the batch is executed, so they are reported
+    /// as failed while their effects are kept
+    pub fn execute_signed_intents_detailed(
+        mut self,
+        signed: impl IntoIterator<Item = MultiPayload>,
+    ) -> DetailedExecution<I>
+    where
+        S: Clone,
+        I: Clone,
+    {
+        let mut seen = HashSet::new();
+        let mut outcomes = Vec::new();
+        let mut verified = Vec::new();
+        for (idx, signed) in signed.into_iter().enumerate() {
+            let encoded = borsh::to_vec(&signed).unwrap_or_else(|_| unreachable!());
+            let mut intents = Vec::new();
+            outcomes.push(if seen.insert(env::sha256_array(&encoded)) {
+                self.verify_eip1271_chain_id(&signed)
+                    .and_then(|()| Self::verify_signed_intent(signed, &mut intents))
+            } else {
+                Err(DefuseError::DuplicateIntent)
+            });
+            verified.extend(intents.into_iter().map(|intent| (idx, intent)));
+        }
+        verified.sort_by_key(|(_, intent)| Reverse(intent.payload.priority.unwrap_or_default()));
+
+        // indices of payloads by hashes of their intents and approvals,
+        // so that failures found after the batch is executed can be
+        // attributed to them
+        let mut payloads = HashMap::<CryptoHash, Vec<usize>>::new();
+        for (idx, intent) in verified {
+            if outcomes[idx].is_err() {
+                continue;
+            }
+            let checkpoint = self.clone();
+            let result = self.approval_hash(&intent).and_then(|approval_hash| {
+                payloads.entry(intent.hash).or_default().push(idx);
+                if approval_hash != intent.hash {
+                    payloads.entry(approval_hash).or_default().push(idx);
+                }
+                self.execute_verified_intent(
+                    intent.standard,
+                    intent.public_key,
+                    intent.hash,
+                    intent.payload,
+                )
+            });
+            if let Err(err) = result {
+                self = checkpoint;
+                outcomes[idx] = Err(err);
+            }
+        }
+
+        let partial_fill = self.partial_fill.as_ref().map(|fill| fill.hash);
+        let checkpoint = self.clone();
+        if let Err(err) = self.fill_partial() {
+            self = checkpoint;
+            self.partial_fill = None;
+            if let Some(idx) = partial_fill
+                .and_then(|hash| payloads.get(&hash))
+                .and_then(|idxs| idxs.first())
+            {
+                outcomes[*idx] = Err(err);
+            }
+        }
+
+        let mut fail = |hash: &CryptoHash, err: &dyn Fn() -> DefuseError| {
+            for idx in payloads.get(hash).into_iter().flatten() {
+                if outcomes[*idx].is_ok() {
+                    outcomes[*idx] = Err(err());
+                }
+            }
+        };
+        for hash in self.pending_approvals.keys() {
+            fail(hash, &|| DefuseError::InsufficientSignatures);
+        }
+        self.pending_approvals.clear();
+        for (solver, hash) in self.unmatched_solvers() {
+            fail(hash, &|| DefuseError::SolverNotMatched(solver.clone()));
+        }
+
+        DetailedExecution {
+            outcomes,
+            result: self
+                .state
+                .finalize()
+                .map_err(DefuseError::InvariantViolated),
+            inspector: self.inspector,
+        }
+    }
+
+    /// `TokenDiff` intents bound to a solver can only be matched by
+    /// its counter-intent in the same batch, i.e. the one trading the
+    /// same tokens in the opposite direction. Returns solvers and
+    /// hashes of the intents which are not matched.
+    fn unmatched_solvers(&self) -> impl Iterator<Item = (&AccountId, &CryptoHash)> {
+        self.bound_token_diffs
+            .iter()
+            .filter(|(solver, diff, _)| {
+                !self.token_diffs.iter().any(|(signer_id, counter)| {
+                    signer_id == solver && TokenDiff::is_offset_by(diff, counter)
+                })
+            })
+            .map(|(solver, _, hash)| (solver, hash))
+    }
+
+    /// Rejects the whole batch up-front if it includes the same signed
+    /// payload more than once, rather than failing on its nonce only
+    /// after the first one was executed.
+    ///
+    /// NOTE: payloads of multisig accounts share the hash while being
+    /// signed by different keys, so only identical ones are duplicates
+    fn check_duplicates(signed: &[MultiPayload]) -> Result<()> {
+        let mut seen = HashSet::with_capacity(signed.len());
+        for payload in signed {
+            let encoded = borsh::to_vec(payload).unwrap_or_else(|_| unreachable!());
+            if !seen.insert(env::sha256_array(&encoded)) {
+                return Err(DefuseError::DuplicateIntent);
+            }
+        }
+        Ok(())
+    }
+
+    /// Wallet contracts may have the same address on different chains
+    /// while being controlled by different owners, so EIP-1271
+    /// signatures are only accepted from the configured chain
+    fn verify_eip1271_chain_id(&self, signed: &MultiPayload) -> Result<()> {
+        let payload = match signed {
+            MultiPayload::Eip1271(payload) => payload,
+            MultiPayload::Template(template) => {
+                return self.verify_eip1271_chain_id(&template.signed);
+            }
+            _ => return Ok(()),
+        };
+        if self.state.eip1271_chain_id() != Some(payload.chain_id) {
+            return Err(DefuseError::WrongEip1271ChainId(payload.chain_id));
+        }
+        Ok(())
+    }
+
+    /// Verifies signature of the payload and appends the intents it
+    /// carries to `verified`
+    fn verify_signed_intent(
+        signed: MultiPayload,
+        verified: &mut Vec<VerifiedIntent>,
+    ) -> Result<()> {
+        // aggregated signature is verified once for all of the payloads,
+        // each of which is then executed as a separate intent
+        if let MultiPayload::BlsAggregate(aggregate) = signed {
+            if !aggregate.verify_aggregate() {
+                return Err(DefuseError::InvalidSignature);
+            }
+            for payload in aggregate.payloads {
+                verified.push(VerifiedIntent {
+                    standard: SigningStandard::BlsAggregate,
+                    public_key: PublicKey::Bls12381(payload.public_key),
+                    hash: payload.hash(),
+                    payload: payload.extract_defuse_payload()?,
+                });
+            }
+            return Ok(());
+        }
+
+        // only Merkle root of template recipients is covered by the
+        // signature, so templates are instantiated before execution
+        if let MultiPayload::Template(template) = signed {
+            let SignedTemplatePayload { signed, recipients } = template;
+            if matches!(
+                *signed,
+                MultiPayload::BlsAggregate(_) | MultiPayload::Template(_)
+            ) {
+                return Err(DefuseError::InvalidIntent);
+            }
+            let public_key = signed.verify().ok_or(DefuseError::InvalidSignature)?;
+            let standard = SigningStandard::from(&*signed);
+            let hash = signed.hash();
+            let payload: DefusePayload<DefuseIntents> = signed.extract_defuse_payload()?;
+            verified.push(VerifiedIntent {
+                standard,
+                public_key,
+                hash,
+                payload: DefusePayload {
+                    message: payload.message.instantiate(&recipients)?,
+                    ..payload
+                },
+            });
+            return Ok(());
+        }
+
         // verify signed payload and get public key
         let public_key = signed.verify().ok_or(DefuseError::InvalidSignature)?;
 
@@ -49,13 +342,49 @@ where
         let hash = signed.hash();
 
         // extract NEP-413 payload
-        let DefusePayload::<DefuseIntents> {
+        verified.push(VerifiedIntent {
+            standard: SigningStandard::from(&signed),
+            public_key,
+            hash,
+            payload: signed.extract_defuse_payload()?,
+        });
+        Ok(())
+    }
+
+    fn execute_verified_intent(
+        &mut self,
+        standard: SigningStandard,
+        public_key: PublicKey,
+        hash: CryptoHash,
+        payload: DefusePayload<DefuseIntents>,
+    ) -> Result<()> {
+        let approval_hash = self.approval_hash_of(hash, &payload)?;
+
+        let DefusePayload {
             signer_id,
             verifying_contract,
             deadline,
             nonce,
-            message: intents,
-        } = signed.extract_defuse_payload()?;
+            client_ref,
+            require_prepaid_gas,
+            priority: _,
+            signed_at,
+            message: mut intents,
+        } = payload;
+
+        if client_ref
+            .as_ref()
+            .is_some_and(|client_ref| client_ref.len() > MAX_CLIENT_REF_LEN)
+        {
+            return Err(DefuseError::ClientRefTooLong);
+        }
+
+        // signer can make sure the batch isn't relayed with too little gas
+        if let Some(required) =
+            require_prepaid_gas.filter(|required| env::prepaid_gas() < *required)
+        {
+            return Err(DefuseError::InsufficientGas { required });
+        }
 
         // check recipient
         if verifying_contract != *self.state.verifying_contract() {
@@ -63,23 +392,202 @@ where
         }
 
         self.inspector.on_deadline(deadline);
+        self.verify_intent_deadline(deadline)?;
 
-        // make sure message is still valid
-        if deadline.has_expired() {
-            return Err(DefuseError::DeadlineExpired);
+        // blocked accounts can't execute any intents
+        if self.state.is_account_blocked(&signer_id) {
+            return Err(DefuseError::AccountBlocked(signer_id));
         }
 
-        // make sure the account has this public key
-        if !self.state.has_public_key(&signer_id, &public_key) {
-            return Err(DefuseError::PublicKeyNotExist(signer_id, public_key));
+        // standards can be disabled for an account, e.g. when a key of
+        // that standard was compromised
+        if self
+            .state
+            .is_signing_standard_disabled(&signer_id, standard)
+        {
+            return Err(DefuseError::SigningStandardDisabled(signer_id, standard));
         }
 
-        // commit nonce
+        // intents signed before the cutoff set by `RevokeBefore` are
+        // revoked, as well as the ones not telling when they were signed
+        if self
+            .state
+            .revoked_before(&signer_id)
+            .is_some_and(|cutoff| signed_at.is_none_or(|signed_at| signed_at < cutoff))
+        {
+            return Err(DefuseError::IntentRevoked);
+        }
+
+        let key = self.authorize_key(standard, &signer_id, public_key)?;
+
         self.verify_intent_nonce(nonce, deadline)?;
+
+        // intents of multisig accounts are executed only once signed
+        // by enough distinct public keys, neither session keys nor
+        // attesters count towards the threshold
+        let session = match key {
+            SignerKey::Registered => {
+                if !self.approve(&signer_id, nonce, approval_hash, public_key)? {
+                    return Ok(());
+                }
+                None
+            }
+            SignerKey::Attested | SignerKey::Session(_)
+                if self.state.multisig_threshold(&signer_id) > 1 =>
+            {
+                return Err(DefuseError::MultisigApprovalRequired(signer_id));
+            }
+            SignerKey::Attested => None,
+            SignerKey::Session(session) => Some(session),
+        };
+
+        // partially fillable `TokenDiff` is filled after the rest of the
+        // batch, its nonce is committed only once it's filled completely
+        if let Some(token_diff) = intents.as_partial_fill() {
+            if session.is_some()
+                || self.partial_fill.is_some()
+                || token_diff.partial_fill_legs().is_none()
+            {
+                return Err(DefuseError::InvalidIntent);
+            }
+            if self.state.is_nonce_used(&signer_id, nonce) {
+                return Err(DefuseError::NonceUsed);
+            }
+            self.on_token_diff(&signer_id, token_diff, hash);
+            self.partial_fill = Some(PendingPartialFill {
+                signer_id,
+                nonce,
+                hash,
+                client_ref,
+                token_diff: token_diff.clone(),
+            });
+            return Ok(());
+        }
+
+        // commit nonce
         self.state.commit_nonce(signer_id.clone(), nonce)?;
 
+        // session spendings depend on opted in `storage_deposit`
+        intents.resolve_storage_deposits(&signer_id, &self.state);
+
+        // session keys can only spend up to the remaining allowance
+        if let Some(mut session) = session {
+            session.spend(
+                intents.session_spendings(&self.state.wnear_token_id(), &session.auth_calls)?,
+            )?;
+            self.state
+                .set_session(signer_id.clone(), public_key, session)?;
+        }
+
+        self.inspector
+            .on_promises_min_gas(intents.promises_min_gas());
         intents.execute_intent(&signer_id, self, hash)?;
-        self.inspector.on_intent_executed(&signer_id, hash, nonce);
+        self.inspector
+            .on_intent_executed(&signer_id, hash, nonce, client_ref.as_deref());
+
+        Ok(())
+    }
+
+    #[inline]
+    fn approval_hash(&self, intent: &VerifiedIntent) -> Result<CryptoHash> {
+        self.approval_hash_of(intent.hash, &intent.payload)
+    }
+
+    /// Signers of multisig accounts may use different wallets, so their
+    /// approvals are grouped by canonical hash of the payload rather
+    /// than by hash of the exact signed string
+    fn approval_hash_of(
+        &self,
+        hash: CryptoHash,
+        payload: &DefusePayload<DefuseIntents>,
+    ) -> Result<CryptoHash> {
+        if self.state.multisig_threshold(&payload.signer_id) > 1 {
+            return Ok(payload.canonical_hash()?);
+        }
+        Ok(hash)
+    }
+
+    /// Makes sure the account has this public key or has granted
+    /// a session to it, EIP-1271 signatures of smart-contract wallets
+    /// are vouched for by trusted attesters which the wallet opted in
+    /// to. Opt-ins are kept apart from public keys, so attesters can
+    /// only vouch for EIP-1271 signatures.
+    fn authorize_key(
+        &self,
+        standard: SigningStandard,
+        signer_id: &AccountIdRef,
+        public_key: PublicKey,
+    ) -> Result<SignerKey> {
+        if standard == SigningStandard::Eip1271 {
+            if !self.state.is_eip1271_attester(&public_key)
+                || !self.state.is_eip1271_opted_in(signer_id, &public_key)
+            {
+                return Err(DefuseError::PublicKeyNotExist(
+                    signer_id.to_owned(),
+                    public_key,
+                ));
+            }
+            return Ok(SignerKey::Attested);
+        }
+        if self.state.has_public_key(signer_id, &public_key) {
+            return Ok(SignerKey::Registered);
+        }
+
+        let session = self
+            .state
+            .session(signer_id, &public_key)
+            .ok_or_else(|| DefuseError::PublicKeyNotExist(signer_id.to_owned(), public_key))?;
+        if session.has_expired() {
+            return Err(DefuseError::SessionExpired);
+        }
+        Ok(SignerKey::Session(session))
+    }
+
+    /// Records approval of the intent by `public_key` and returns whether
+    /// the intent has enough approvals to be executed
+    fn approve(
+        &mut self,
+        signer_id: &AccountIdRef,
+        nonce: Nonce,
+        hash: CryptoHash,
+        public_key: PublicKey,
+    ) -> Result<bool> {
+        let threshold = self.state.multisig_threshold(signer_id);
+        if threshold <= 1 {
+            return Ok(true);
+        }
+
+        if self.state.is_nonce_used(signer_id, nonce) {
+            return Err(DefuseError::NonceUsed);
+        }
+
+        let approvals = self.pending_approvals.entry(hash).or_default();
+        approvals.insert(public_key);
+        if approvals.len() < usize::from(threshold) {
+            return Ok(false);
+        }
+
+        self.pending_approvals.remove(&hash);
+        Ok(true)
+    }
+
+    #[inline]
+    fn verify_intent_deadline(&self, deadline: Deadline) -> Result<()> {
+        // make sure message is still valid
+        if deadline.has_expired() {
+            return Err(DefuseError::DeadlineExpired);
+        }
+
+        // nonces can't outlive `max_nonce_ttl`
+        if deadline > self.state.max_allowed_deadline() {
+            return Err(DefuseError::DeadlineExceedsMaxNonceTtl);
+        }
+
+        // perpetual intents can be disallowed, so that all of the
+        // nonces can eventually be cleaned up
+        if deadline == Deadline::MAX && self.state.requires_bounded_deadline() {
+            return Err(DefuseError::UnboundedDeadline);
+        }
 
         Ok(())
     }
@@ -87,28 +595,105 @@ where
     #[inline]
     fn verify_intent_nonce(&self, nonce: Nonce, intent_deadline: Deadline) -> Result<()> {
         let Some(nonce) = VersionedNonce::maybe_from(nonce) else {
+            // legacy nonces carry neither a salt nor a deadline
+            if self.state.rejects_legacy_nonces() {
+                return Err(DefuseError::LegacyNonceRejected);
+            }
             return Ok(());
         };
 
-        match nonce {
-            VersionedNonce::V1(SaltedNonce {
-                salt,
-                nonce: ExpirableNonce { deadline, .. },
-            }) => {
-                if !self.state.is_valid_salt(salt) {
-                    return Err(DefuseError::InvalidSalt);
-                }
+        // all versions carry a salt and a deadline, namespaces
+        // only affect where the nonce is stored
+        if !self.state.is_valid_salt(nonce.salt()) {
+            return Err(DefuseError::InvalidSalt);
+        }
 
-                if intent_deadline > deadline {
-                    return Err(DefuseError::DeadlineGreaterThanNonce);
-                }
+        let deadline = nonce.deadline();
+        if intent_deadline > deadline {
+            return Err(DefuseError::DeadlineGreaterThanNonce);
+        }
 
-                if deadline.has_expired() {
-                    return Err(DefuseError::NonceExpired);
-                }
-            }
+        if deadline.has_expired() {
+            return Err(DefuseError::NonceExpired);
+        }
+
+        if deadline > self.state.max_allowed_deadline() {
+            return Err(DefuseError::DeadlineExceedsMaxNonceTtl);
+        }
+
+        Ok(())
+    }
+
+    /// Records execution of `TokenDiff` intent by `signer_id`, which
+    /// is optionally bound to a solver
+    pub(crate) fn on_token_diff(
+        &mut self,
+        signer_id: &AccountIdRef,
+        token_diff: &TokenDiff,
+        hash: CryptoHash,
+    ) {
+        self.token_diffs
+            .push((signer_id.to_owned(), token_diff.diff.clone()));
+        if let Some(solver) = &token_diff.solver {
+            self.bound_token_diffs
+                .push((solver.clone(), token_diff.diff.clone(), hash));
+        }
+    }
+
+    /// Fills pending partially fillable `TokenDiff` by closing deltas
+    /// left unmatched by the rest of the batch. If they can't be closed,
+    /// nothing is filled, so that the invariant is violated.
+    fn fill_partial(&mut self) -> Result<()> {
+        let Some(PendingPartialFill {
+            signer_id,
+            nonce,
+            hash,
+            client_ref,
+            token_diff,
+        }) = self.partial_fill.take()
+        else {
+            return Ok(());
+        };
+        let Some(((token_in, amount_in), (token_out, _))) = token_diff.partial_fill_legs() else {
+            return Err(DefuseError::InvalidIntent);
+        };
+        let (token_in, token_out) = (token_in.clone(), token_out.clone());
+
+        let filled = self.state.partially_filled(&signer_id, nonce);
+        let Some((fill_in, fill_out)) = self
+            .state
+            .unmatched_delta(&token_in)
+            .zip(self.state.unmatched_delta(&token_out))
+            .and_then(|(unmatched_in, unmatched_out)| {
+                token_diff.partial_fill(filled, unmatched_in, unmatched_out, self.state.fee())
+            })
+        else {
+            return Ok(());
+        };
+
+        TokenDiff {
+            diff: TokenDeltas::default()
+                .with_apply_deltas([(token_in, fill_in), (token_out, fill_out)])
+                .ok_or(DefuseError::BalanceOverflow)?,
+            partial_fill: false,
+            ..token_diff
+        }
+        .execute_intent(&signer_id, self, hash)?;
+
+        let filled = filled
+            .checked_add(fill_in.unsigned_abs())
+            .ok_or(DefuseError::BalanceOverflow)?;
+        if filled < amount_in {
+            self.state
+                .set_partially_filled(signer_id.clone(), nonce, filled)?;
+        } else {
+            self.state
+                .set_partially_filled(signer_id.clone(), nonce, 0)?;
+            self.state.commit_nonce(signer_id.clone(), nonce)?;
         }
 
+        self.inspector
+            .on_intent_executed(&signer_id, hash, nonce, client_ref.as_deref());
         Ok(())
     }
 
@@ -1,27 +1,30 @@
 use crate::{
-    DefuseError, Nonce, NoncePrefix, Nonces, Result, Salt,
+    Deadline, DefuseError, Nonce, NoncePrefix, Nonces, Result, Salt,
     amounts::Amounts,
     fees::Pips,
     intents::{
         auth::AuthCall,
+        session::Session,
         tokens::{
             FtWithdraw, MtWithdraw, NativeWithdraw, NftWithdraw, NotifyOnTransfer, StorageDeposit,
         },
     },
+    payload::multi::SigningStandard,
     token_id::{TokenId, nep141::Nep141TokenId, nep171::Nep171TokenId, nep245::Nep245TokenId},
 };
 use defuse_bitmap::{U248, U256};
 use defuse_crypto::PublicKey;
 use defuse_near_utils::Lock;
-use near_sdk::{AccountId, AccountIdRef};
+use near_sdk::{AccountId, AccountIdRef, Gas};
 use std::{
     borrow::Cow,
     collections::{HashMap, HashSet},
+    time::Duration,
 };
 
 use super::{State, StateView};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CachedState<W: StateView> {
     view: W,
     accounts: CachedAccounts,
@@ -112,6 +115,11 @@ where
             .map_or_else(|| self.view.is_account_locked(account_id), Lock::is_locked)
     }
 
+    #[inline]
+    fn is_account_blocked(&self, account_id: &AccountIdRef) -> bool {
+        self.view.is_account_blocked(account_id)
+    }
+
     fn is_auth_by_predecessor_id_enabled(&self, account_id: &AccountIdRef) -> bool {
         let was_enabled = self.view.is_auth_by_predecessor_id_enabled(account_id);
         let toggled = self
@@ -122,9 +130,122 @@ where
         was_enabled ^ toggled
     }
 
+    #[inline]
+    fn is_signing_standard_disabled(
+        &self,
+        account_id: &AccountIdRef,
+        standard: SigningStandard,
+    ) -> bool {
+        self.view.is_signing_standard_disabled(account_id, standard)
+    }
+
     fn is_valid_salt(&self, salt: Salt) -> bool {
         self.view.is_valid_salt(salt)
     }
+
+    fn session(&self, account_id: &AccountIdRef, public_key: &PublicKey) -> Option<Session> {
+        self.accounts
+            .get(account_id)
+            .map(Lock::as_inner_unchecked)
+            .and_then(|account| account.sessions.get(public_key).cloned())
+            .or_else(|| self.view.session(account_id, public_key))
+    }
+
+    fn multisig_threshold(&self, account_id: &AccountIdRef) -> u8 {
+        self.accounts
+            .get(account_id)
+            .map(Lock::as_inner_unchecked)
+            .and_then(|account| account.multisig_threshold)
+            .unwrap_or_else(|| self.view.multisig_threshold(account_id))
+    }
+
+    fn revoked_before(&self, account_id: &AccountIdRef) -> Option<Deadline> {
+        self.accounts
+            .get(account_id)
+            .map(Lock::as_inner_unchecked)
+            .and_then(|account| account.revoked_before)
+            .or_else(|| self.view.revoked_before(account_id))
+    }
+
+    fn default_min_gas(&self, account_id: &AccountIdRef) -> Option<Gas> {
+        self.accounts
+            .get(account_id)
+            .map(Lock::as_inner_unchecked)
+            .and_then(|account| account.default_min_gas)
+            .map_or_else(
+                || self.view.default_min_gas(account_id),
+                |gas| (gas.as_gas() != 0).then_some(gas),
+            )
+    }
+
+    fn nickname(&self, account_id: &AccountIdRef) -> Option<String> {
+        self.accounts
+            .get(account_id)
+            .map(Lock::as_inner_unchecked)
+            .and_then(|account| account.nickname.clone())
+            .map_or_else(
+                || self.view.nickname(account_id),
+                |nickname| (!nickname.is_empty()).then_some(nickname),
+            )
+    }
+
+    fn partially_filled(&self, account_id: &AccountIdRef, nonce: Nonce) -> u128 {
+        self.accounts
+            .get(account_id)
+            .map(Lock::as_inner_unchecked)
+            .and_then(|account| account.partially_filled.get(&nonce).copied())
+            .unwrap_or_else(|| self.view.partially_filled(account_id, nonce))
+    }
+
+    #[inline]
+    fn is_eip1271_attester(&self, public_key: &PublicKey) -> bool {
+        self.view.is_eip1271_attester(public_key)
+    }
+
+    #[inline]
+    fn is_eip1271_opted_in(&self, account_id: &AccountIdRef, public_key: &PublicKey) -> bool {
+        self.view.is_eip1271_opted_in(account_id, public_key)
+    }
+
+    #[inline]
+    fn eip1271_chain_id(&self) -> Option<u64> {
+        self.view.eip1271_chain_id()
+    }
+
+    #[inline]
+    fn max_nonce_ttl(&self) -> Option<Duration> {
+        self.view.max_nonce_ttl()
+    }
+
+    #[inline]
+    fn requires_bounded_deadline(&self) -> bool {
+        self.view.requires_bounded_deadline()
+    }
+
+    #[inline]
+    fn rejects_legacy_nonces(&self) -> bool {
+        self.view.rejects_legacy_nonces()
+    }
+
+    #[inline]
+    fn max_keys_to_check(&self) -> u32 {
+        self.view.max_keys_to_check()
+    }
+
+    #[inline]
+    fn oracle_price(&self, asset: &str) -> Option<u128> {
+        self.view.oracle_price(asset)
+    }
+
+    #[inline]
+    fn min_transfer_amount(&self, token_id: &TokenId) -> u128 {
+        self.view.min_transfer_amount(token_id)
+    }
+
+    #[inline]
+    fn is_referral_allowed(&self, account_id: &AccountIdRef) -> bool {
+        self.view.is_referral_allowed(account_id)
+    }
 }
 
 impl<W> State for CachedState<W>
@@ -204,6 +325,10 @@ where
         owner_id: AccountId,
         token_amounts: impl IntoIterator<Item = (TokenId, u128)>,
     ) -> Result<()> {
+        if owner_id != *self.view.fee_collector() && self.view.is_account_blocked(&owner_id) {
+            return Err(DefuseError::AccountBlocked(owner_id));
+        }
+
         let account = self
             .accounts
             .get_or_create(owner_id.clone(), |owner_id| {
@@ -230,6 +355,10 @@ where
         owner_id: &AccountIdRef,
         token_amounts: impl IntoIterator<Item = (TokenId, u128)>,
     ) -> Result<()> {
+        if self.view.is_account_blocked(owner_id) {
+            return Err(DefuseError::AccountBlocked(owner_id.to_owned()));
+        }
+
         let account = self
             .accounts
             .get_or_create(owner_id.to_owned(), |owner_id| {
@@ -324,6 +453,13 @@ where
 
     // NOTE: Simulation that uses a cached state cannot create promises, as it is a view call
     #[inline]
+    fn add_collected_fees(
+        &mut self,
+        _fees: impl IntoIterator<Item = (TokenId, u128)>,
+    ) -> Result<()> {
+        Ok(())
+    }
+
     fn notify_on_transfer(
         &self,
         _sender_id: &AccountIdRef,
@@ -376,9 +512,87 @@ where
 
         Ok(())
     }
+
+    fn set_session(
+        &mut self,
+        account_id: AccountId,
+        public_key: PublicKey,
+        session: Session,
+    ) -> Result<()> {
+        self.accounts
+            .get_or_create(account_id.clone(), |account_id| {
+                self.view.is_account_locked(account_id)
+            })
+            .get_mut()
+            .ok_or(DefuseError::AccountLocked(account_id))?
+            .sessions
+            .insert(public_key, session);
+        Ok(())
+    }
+
+    fn set_multisig_threshold(&mut self, account_id: AccountId, threshold: u8) -> Result<()> {
+        self.accounts
+            .get_or_create(account_id.clone(), |account_id| {
+                self.view.is_account_locked(account_id)
+            })
+            .get_mut()
+            .ok_or(DefuseError::AccountLocked(account_id))?
+            .multisig_threshold = Some(threshold);
+        Ok(())
+    }
+
+    fn set_revoked_before(&mut self, account_id: AccountId, timestamp: Deadline) -> Result<()> {
+        self.accounts
+            .get_or_create(account_id.clone(), |account_id| {
+                self.view.is_account_locked(account_id)
+            })
+            .get_mut()
+            .ok_or(DefuseError::AccountLocked(account_id))?
+            .revoked_before = Some(timestamp);
+        Ok(())
+    }
+
+    fn set_default_min_gas(&mut self, account_id: AccountId, gas: Gas) -> Result<()> {
+        self.accounts
+            .get_or_create(account_id.clone(), |account_id| {
+                self.view.is_account_locked(account_id)
+            })
+            .get_mut()
+            .ok_or(DefuseError::AccountLocked(account_id))?
+            .default_min_gas = Some(gas);
+        Ok(())
+    }
+
+    fn set_nickname(&mut self, account_id: AccountId, nickname: Option<String>) -> Result<()> {
+        self.accounts
+            .get_or_create(account_id.clone(), |account_id| {
+                self.view.is_account_locked(account_id)
+            })
+            .get_mut()
+            .ok_or(DefuseError::AccountLocked(account_id))?
+            .nickname = Some(nickname.unwrap_or_default());
+        Ok(())
+    }
+
+    fn set_partially_filled(
+        &mut self,
+        account_id: AccountId,
+        nonce: Nonce,
+        filled: u128,
+    ) -> Result<()> {
+        self.accounts
+            .get_or_create(account_id.clone(), |account_id| {
+                self.view.is_account_locked(account_id)
+            })
+            .get_mut()
+            .ok_or(DefuseError::AccountLocked(account_id))?
+            .partially_filled
+            .insert(nonce, filled);
+        Ok(())
+    }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct CachedAccounts(HashMap<AccountId, Lock<CachedAccount>>);
 
 impl CachedAccounts {
@@ -420,6 +634,22 @@ pub struct CachedAccount {
     public_keys_removed: HashSet<PublicKey>,
 
     token_amounts: Amounts<HashMap<TokenId, u128>>,
+
+    sessions: HashMap<PublicKey, Session>,
+
+    multisig_threshold: Option<u8>,
+
+    revoked_before: Option<Deadline>,
+
+    /// Zero if unset
+    default_min_gas: Option<Gas>,
+
+    /// Empty if cleared
+    nickname: Option<String>,
+
+    /// Amounts of `token_in` filled by partially fillable `TokenDiff`
+    /// intents per nonce, `0` if cleared
+    partially_filled: HashMap<Nonce, u128>,
 }
 
 impl CachedAccount {
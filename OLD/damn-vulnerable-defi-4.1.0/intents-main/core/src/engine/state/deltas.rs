@@ -1,30 +1,34 @@
 use crate::{
-    DefuseError, Nonce, NoncePrefix, Result, Salt,
+    Deadline, DefuseError, Nonce, NoncePrefix, Result, Salt,
     amounts::Amounts,
     fees::Pips,
     intents::{
         auth::AuthCall,
+        session::Session,
         token_diff::TokenDeltas,
         tokens::{
             FtWithdraw, MtWithdraw, NativeWithdraw, NftWithdraw, NotifyOnTransfer, StorageDeposit,
         },
     },
+    payload::multi::SigningStandard,
     token_id::TokenId,
 };
 use defuse_crypto::PublicKey;
 use defuse_map_utils::cleanup::DefaultMap;
 use defuse_nep245::{MtEvent, MtTransferEvent};
-use near_sdk::{AccountId, AccountIdRef, json_types::U128, near};
+use near_sdk::{AccountId, AccountIdRef, Gas, json_types::U128, near};
 use serde_with::{DisplayFromStr, serde_as};
 use std::{
     borrow::Cow,
     cmp::Reverse,
     collections::{BTreeMap, HashMap},
     iter,
+    time::Duration,
 };
 
 use super::{State, StateView};
 
+#[derive(Clone)]
 pub struct Deltas<S> {
     state: S,
     deltas: TransferMatcher,
@@ -43,6 +47,12 @@ impl<S> Deltas<S> {
     pub fn finalize(self) -> Result<Transfers, InvariantViolated> {
         self.deltas.finalize()
     }
+
+    /// Returns total supply delta on given token accumulated so far
+    #[inline]
+    pub fn unmatched_delta(&self, token_id: &TokenId) -> Option<i128> {
+        self.deltas.unmatched_delta(token_id)
+    }
 }
 
 impl<S> StateView for Deltas<S>
@@ -94,15 +104,110 @@ where
         self.state.is_account_locked(account_id)
     }
 
+    #[inline]
+    fn is_account_blocked(&self, account_id: &AccountIdRef) -> bool {
+        self.state.is_account_blocked(account_id)
+    }
+
     #[inline]
     fn is_auth_by_predecessor_id_enabled(&self, account_id: &AccountIdRef) -> bool {
         self.state.is_auth_by_predecessor_id_enabled(account_id)
     }
 
+    #[inline]
+    fn is_signing_standard_disabled(
+        &self,
+        account_id: &AccountIdRef,
+        standard: SigningStandard,
+    ) -> bool {
+        self.state
+            .is_signing_standard_disabled(account_id, standard)
+    }
+
     #[inline]
     fn is_valid_salt(&self, salt: Salt) -> bool {
         self.state.is_valid_salt(salt)
     }
+
+    #[inline]
+    fn session(&self, account_id: &AccountIdRef, public_key: &PublicKey) -> Option<Session> {
+        self.state.session(account_id, public_key)
+    }
+
+    #[inline]
+    fn multisig_threshold(&self, account_id: &AccountIdRef) -> u8 {
+        self.state.multisig_threshold(account_id)
+    }
+
+    #[inline]
+    fn revoked_before(&self, account_id: &AccountIdRef) -> Option<Deadline> {
+        self.state.revoked_before(account_id)
+    }
+
+    #[inline]
+    fn default_min_gas(&self, account_id: &AccountIdRef) -> Option<Gas> {
+        self.state.default_min_gas(account_id)
+    }
+
+    #[inline]
+    fn nickname(&self, account_id: &AccountIdRef) -> Option<String> {
+        self.state.nickname(account_id)
+    }
+
+    #[inline]
+    fn partially_filled(&self, account_id: &AccountIdRef, nonce: Nonce) -> u128 {
+        self.state.partially_filled(account_id, nonce)
+    }
+
+    #[inline]
+    fn is_eip1271_attester(&self, public_key: &PublicKey) -> bool {
+        self.state.is_eip1271_attester(public_key)
+    }
+
+    #[inline]
+    fn is_eip1271_opted_in(&self, account_id: &AccountIdRef, public_key: &PublicKey) -> bool {
+        self.state.is_eip1271_opted_in(account_id, public_key)
+    }
+
+    #[inline]
+    fn eip1271_chain_id(&self) -> Option<u64> {
+        self.state.eip1271_chain_id()
+    }
+
+    #[inline]
+    fn max_nonce_ttl(&self) -> Option<Duration> {
+        self.state.max_nonce_ttl()
+    }
+
+    #[inline]
+    fn requires_bounded_deadline(&self) -> bool {
+        self.state.requires_bounded_deadline()
+    }
+
+    #[inline]
+    fn rejects_legacy_nonces(&self) -> bool {
+        self.state.rejects_legacy_nonces()
+    }
+
+    #[inline]
+    fn max_keys_to_check(&self) -> u32 {
+        self.state.max_keys_to_check()
+    }
+
+    #[inline]
+    fn oracle_price(&self, asset: &str) -> Option<u128> {
+        self.state.oracle_price(asset)
+    }
+
+    #[inline]
+    fn min_transfer_amount(&self, token_id: &TokenId) -> u128 {
+        self.state.min_transfer_amount(token_id)
+    }
+
+    #[inline]
+    fn is_referral_allowed(&self, account_id: &AccountIdRef) -> bool {
+        self.state.is_referral_allowed(account_id)
+    }
 }
 
 impl<S> State for Deltas<S>
@@ -148,6 +253,14 @@ where
         Ok(())
     }
 
+    #[inline]
+    fn add_collected_fees(
+        &mut self,
+        fees: impl IntoIterator<Item = (TokenId, u128)>,
+    ) -> Result<()> {
+        self.state.add_collected_fees(fees)
+    }
+
     fn internal_sub_balance(
         &mut self,
         owner_id: &AccountIdRef,
@@ -213,6 +326,46 @@ where
     fn auth_call(&mut self, signer_id: &AccountIdRef, auth_call: AuthCall) -> Result<()> {
         self.state.auth_call(signer_id, auth_call)
     }
+
+    #[inline]
+    fn set_session(
+        &mut self,
+        account_id: AccountId,
+        public_key: PublicKey,
+        session: Session,
+    ) -> Result<()> {
+        self.state.set_session(account_id, public_key, session)
+    }
+
+    #[inline]
+    fn set_multisig_threshold(&mut self, account_id: AccountId, threshold: u8) -> Result<()> {
+        self.state.set_multisig_threshold(account_id, threshold)
+    }
+
+    #[inline]
+    fn set_revoked_before(&mut self, account_id: AccountId, timestamp: Deadline) -> Result<()> {
+        self.state.set_revoked_before(account_id, timestamp)
+    }
+
+    #[inline]
+    fn set_default_min_gas(&mut self, account_id: AccountId, gas: Gas) -> Result<()> {
+        self.state.set_default_min_gas(account_id, gas)
+    }
+
+    #[inline]
+    fn set_nickname(&mut self, account_id: AccountId, nickname: Option<String>) -> Result<()> {
+        self.state.set_nickname(account_id, nickname)
+    }
+
+    #[inline]
+    fn set_partially_filled(
+        &mut self,
+        account_id: AccountId,
+        nonce: Nonce,
+        filled: u128,
+    ) -> Result<()> {
+        self.state.set_partially_filled(account_id, nonce, filled)
+    }
 }
 
 /// Accumulates internal deposits and withdrawals on different tokens
@@ -223,7 +376,7 @@ where
 /// a set of transfers from one account to another.
 /// Note that this doesn't touch account balances. The balances were already changed
 /// in an earlier stage while executing the intent.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct TransferMatcher(HashMap<TokenId, TokenTransferMatcher>);
 
 impl TransferMatcher {
@@ -247,6 +400,20 @@ impl TransferMatcher {
         self.0.entry_or_default(token_id).add_delta(owner_id, delta)
     }
 
+    /// Returns total supply delta on given token, `None` on overflow
+    pub fn unmatched_delta(&self, token_id: &TokenId) -> Option<i128> {
+        let Some(matcher) = self.0.get(token_id) else {
+            return Some(0);
+        };
+        let [deposits, withdrawals] = [&matcher.deposits, &matcher.withdrawals].map(|amounts| {
+            amounts
+                .iter()
+                .try_fold(0u128, |total, (_, amount)| total.checked_add(*amount))
+                .and_then(|total| i128::try_from(total).ok())
+        });
+        deposits?.checked_sub(withdrawals?)
+    }
+
     // Finalizes all transfers, or returns unmatched deltas.
     // If unmatched deltas overflow, then Err(None) is returned.
     pub fn finalize(self) -> Result<Transfers, InvariantViolated> {
@@ -271,7 +438,7 @@ impl TransferMatcher {
 type AccountAmounts = Amounts<HashMap<AccountId, u128>>;
 
 // Accumulates internal deposits and withdrawals on a single token
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct TokenTransferMatcher {
     deposits: AccountAmounts,
     withdrawals: AccountAmounts,
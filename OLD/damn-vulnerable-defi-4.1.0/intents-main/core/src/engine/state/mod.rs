@@ -2,22 +2,24 @@ pub mod cached;
 pub mod deltas;
 
 use crate::{
-    Nonce, NoncePrefix, Result, Salt,
+    Deadline, Nonce, NoncePrefix, Result, Salt,
     amounts::Amounts,
     fees::Pips,
     intents::{
         auth::AuthCall,
+        session::Session,
         tokens::{
             FtWithdraw, MtWithdraw, NativeWithdraw, NftWithdraw, NotifyOnTransfer, StorageDeposit,
         },
     },
+    payload::multi::SigningStandard,
     token_id::{TokenId, nep141::Nep141TokenId},
 };
 use cached::CachedState;
 use defuse_crypto::PublicKey;
 use impl_tools::autoimpl;
-use near_sdk::{AccountId, AccountIdRef};
-use std::borrow::Cow;
+use near_sdk::{AccountId, AccountIdRef, Gas};
+use std::{borrow::Cow, time::Duration};
 
 #[autoimpl(for<T: trait + ?Sized> &T, &mut T, Box<T>)]
 pub trait StateView {
@@ -42,12 +44,95 @@ pub trait StateView {
 
     fn is_account_locked(&self, account_id: &AccountIdRef) -> bool;
 
+    /// Returns whether the account is in the denylist, i.e. it can
+    /// neither send nor receive any tokens.
+    fn is_account_blocked(&self, account_id: &AccountIdRef) -> bool;
+
     /// Returns whether authentication by `PREDECESSOR_ID` is enabled.
     fn is_auth_by_predecessor_id_enabled(&self, account_id: &AccountIdRef) -> bool;
 
+    /// Returns whether intents signed with given standard are rejected
+    /// for the account
+    fn is_signing_standard_disabled(
+        &self,
+        account_id: &AccountIdRef,
+        standard: SigningStandard,
+    ) -> bool;
+
     /// Returns whether salt in nonce is valid
     fn is_valid_salt(&self, salt: Salt) -> bool;
 
+    /// Returns session granted by the account to given public key, if any
+    fn session(&self, account_id: &AccountIdRef, public_key: &PublicKey) -> Option<Session>;
+
+    /// Returns number of distinct public keys required to sign intents
+    /// on behalf of the account, `1` if multisig is not enabled
+    fn multisig_threshold(&self, account_id: &AccountIdRef) -> u8;
+
+    /// Returns the cutoff before which all intents signed on behalf of
+    /// the account are revoked, if any
+    fn revoked_before(&self, account_id: &AccountIdRef) -> Option<Deadline>;
+
+    /// Returns `min_gas` used by outgoing calls of the account's intents
+    /// which don't specify it, if set
+    fn default_min_gas(&self, account_id: &AccountIdRef) -> Option<Gas>;
+
+    /// Returns display nickname of the account, if set
+    fn nickname(&self, account_id: &AccountIdRef) -> Option<String>;
+
+    /// Returns amount of `token_in` filled so far by partially fillable
+    /// `TokenDiff` signed with given nonce
+    fn partially_filled(&self, account_id: &AccountIdRef, nonce: Nonce) -> u128;
+
+    /// Returns whether given public key is trusted to attest EIP-1271
+    /// signatures of smart-contract wallets
+    #[must_use]
+    fn is_eip1271_attester(&self, public_key: &PublicKey) -> bool;
+
+    /// Returns whether the account opted in to given attester vouching
+    /// for its EIP-1271 signatures
+    #[must_use]
+    fn is_eip1271_opted_in(&self, account_id: &AccountIdRef, public_key: &PublicKey) -> bool;
+
+    /// Returns chain which wallet contracts are allowed to sign via
+    /// EIP-1271, if any
+    fn eip1271_chain_id(&self) -> Option<u64>;
+
+    /// Returns maximum time-to-live of nonces, if any
+    fn max_nonce_ttl(&self) -> Option<Duration>;
+
+    /// Returns whether intents with [`Deadline::MAX`] are rejected
+    fn requires_bounded_deadline(&self) -> bool;
+
+    /// Returns whether legacy nonces, i.e. the ones which are not
+    /// [`VersionedNonce`](crate::VersionedNonce), are rejected
+    fn rejects_legacy_nonces(&self) -> bool;
+
+    /// Returns maximum number of public keys of an account iterated
+    /// while verifying its intents
+    fn max_keys_to_check(&self) -> u32;
+
+    /// Returns price of given asset read from the price oracle before
+    /// executing intents, if any
+    fn oracle_price(&self, asset: &str) -> Option<u128>;
+
+    /// Returns minimum amount of given token allowed to be transferred
+    /// within the contract, `0` if not set
+    fn min_transfer_amount(&self, token_id: &TokenId) -> u128;
+
+    /// Returns whether given account can be specified as a referral,
+    /// i.e. strict referrals are off, or it's a registered account,
+    /// or it's on the referral allowlist
+    fn is_referral_allowed(&self, account_id: &AccountIdRef) -> bool;
+
+    /// Returns the furthest deadline intents can be signed with,
+    /// [`Deadline::MAX`] if `max_nonce_ttl` is not set
+    #[inline]
+    fn max_allowed_deadline(&self) -> Deadline {
+        self.max_nonce_ttl()
+            .map_or(Deadline::MAX, Deadline::timeout)
+    }
+
     #[inline]
     fn cached(self) -> CachedState<Self>
     where
@@ -99,6 +184,11 @@ pub trait State: StateView {
         Ok(())
     }
 
+    /// Adds fees deposited to `fee_collector` to running totals
+    /// collected over the contract's lifetime
+    fn add_collected_fees(&mut self, fees: impl IntoIterator<Item = (TokenId, u128)>)
+    -> Result<()>;
+
     fn ft_withdraw(&mut self, owner_id: &AccountIdRef, withdraw: FtWithdraw) -> Result<()>;
 
     fn nft_withdraw(&mut self, owner_id: &AccountIdRef, withdraw: NftWithdraw) -> Result<()>;
@@ -127,4 +217,36 @@ pub trait State: StateView {
     fn set_auth_by_predecessor_id(&mut self, account_id: AccountId, enable: bool) -> Result<bool>;
 
     fn auth_call(&mut self, signer_id: &AccountIdRef, auth_call: AuthCall) -> Result<()>;
+
+    /// Sets (or replaces) session for given public key
+    fn set_session(
+        &mut self,
+        account_id: AccountId,
+        public_key: PublicKey,
+        session: Session,
+    ) -> Result<()>;
+
+    /// Sets number of distinct public keys required to sign intents
+    /// on behalf of the account, `1` disables multisig
+    fn set_multisig_threshold(&mut self, account_id: AccountId, threshold: u8) -> Result<()>;
+
+    /// Revokes all intents signed on behalf of the account with deadline
+    /// earlier than `timestamp`
+    fn set_revoked_before(&mut self, account_id: AccountId, timestamp: Deadline) -> Result<()>;
+
+    /// Sets `min_gas` used by outgoing calls of the account's intents
+    /// which don't specify it, zero unsets it
+    fn set_default_min_gas(&mut self, account_id: AccountId, gas: Gas) -> Result<()>;
+
+    /// Sets display nickname of the account, `None` or empty one clears it
+    fn set_nickname(&mut self, account_id: AccountId, nickname: Option<String>) -> Result<()>;
+
+    /// Sets amount of `token_in` filled so far by partially fillable
+    /// `TokenDiff` signed with given nonce, `0` clears it
+    fn set_partially_filled(
+        &mut self,
+        account_id: AccountId,
+        nonce: Nonce,
+        filled: u128,
+    ) -> Result<()>;
 }
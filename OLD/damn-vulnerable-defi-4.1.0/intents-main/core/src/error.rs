@@ -1,9 +1,11 @@
 use crate::{
+    Deadline,
     engine::deltas::InvariantViolated,
+    payload::multi::SigningStandard,
     token_id::{TokenId, error::TokenIdError, nep171::Nep171TokenId},
 };
 use defuse_crypto::PublicKey;
-use near_sdk::{AccountId, FunctionError, serde_json};
+use near_sdk::{AccountId, FunctionError, Gas, serde_json};
 use thiserror::Error as ThisError;
 
 pub type Result<T, E = DefuseError> = ::core::result::Result<T, E>;
@@ -16,24 +18,120 @@ pub enum DefuseError {
     #[error("account '{0}' is locked")]
     AccountLocked(AccountId),
 
+    #[error("account '{0}' is blocked")]
+    AccountBlocked(AccountId),
+
+    #[error("`mt_on_transfer` failed or its return value was dropped")]
+    MtOnTransferFailed,
+
+    #[error("`mt_on_transfer` returned malformed refunds")]
+    MtOnTransferMalformedRefunds,
+
+    #[error("account '{0}' can't hold more than {1} distinct tokens")]
+    TooManyTokensHeld(AccountId, u32),
+
+    #[error("session has expired")]
+    SessionExpired,
+
+    #[error("session spending limit exceeded")]
+    SessionLimitExceeded,
+
+    #[error("session is not allowed to `AuthCall` '{0}'")]
+    SessionAuthCallNotAllowed(AccountId),
+
     #[error("authentication by PREDECESSOR_ID is disabled for account '{0}'")]
     AuthByPredecessorIdDisabled(AccountId),
 
+    #[error("signing standard '{1}' is disabled for account '{0}'")]
+    SigningStandardDisabled(AccountId, SigningStandard),
+
     #[error("insufficient balance or overflow")]
     BalanceOverflow,
 
+    #[error("amount of '{0}' is less than minimum transfer amount: {1}")]
+    AmountTooSmall(TokenId, u128),
+
+    #[error("unknown referral '{0}'")]
+    UnknownReferral(AccountId),
+
+    #[error("transfer of '{1}' on behalf of '{0}' is not approved")]
+    NotApproved(AccountId, TokenId),
+
+    #[error("withdrawals are on cooldown, retry after {}", retry_after.into_timestamp())]
+    WithdrawalCooldown { retry_after: Deadline },
+
+    #[error("client_ref is too long")]
+    ClientRefTooLong,
+
+    #[error("nickname is too long")]
+    NicknameTooLong,
+
     #[error("deadline has expired")]
     DeadlineExpired,
 
     #[error("deadline is greater than nonce")]
     DeadlineGreaterThanNonce,
 
+    #[error("deadline exceeds maximum nonce TTL")]
+    DeadlineExceedsMaxNonceTtl,
+
+    #[error("deadline must be bounded")]
+    UnboundedDeadline,
+
+    #[error("legacy nonces without a salt are rejected")]
+    LegacyNonceRejected,
+
+    #[error("fee overflow")]
+    FeeOverflow,
+
     #[error("gas overflow")]
     GasOverflow,
 
     #[error("invalid intent")]
     InvalidIntent,
 
+    #[error("the same intent is included in the batch more than once")]
+    DuplicateIntent,
+
+    #[error("intent was revoked by the signer")]
+    IntentRevoked,
+
+    #[error("invalid multisig threshold")]
+    InvalidMultisigThreshold,
+
+    #[error("not enough signatures to meet multisig threshold")]
+    InsufficientSignatures,
+
+    #[error("multisig account '{0}' can only be authorized by its own public keys")]
+    MultisigApprovalRequired(AccountId),
+
+    #[error("too many public keys to check")]
+    TooManyKeysToCheck,
+
+    #[error("fee exceeds maximum fee allowed by the signer")]
+    MaxFeeExceeded,
+
+    #[error("tokens can't be transferred to the sender itself")]
+    SelfTransfer,
+
+    #[error("token_diff is bound to solver '{0}' which didn't match it")]
+    SolverNotMatched(AccountId),
+
+    #[error("relayer key is not allowed to relay this intent")]
+    RelayerKeyOutOfScope,
+
+    #[error("relayer key has expired")]
+    RelayerKeyExpired,
+
+    #[error("insufficient gas attached, required: {required}")]
+    InsufficientGas { required: Gas },
+
+    #[error("min_gas requested by intents {requested} exceeds available gas {available}")]
+    GasRequestExceedsPrepaid { requested: Gas, available: Gas },
+
+    #[error("contract's NEAR balance is below its storage reserve")]
+    InsufficientContractReserve,
+
     #[error("invalid signature")]
     InvalidSignature,
 
@@ -64,12 +162,21 @@ pub enum DefuseError {
     #[error("public key '{1}' doesn't exist for account '{0}'")]
     PublicKeyNotExist(AccountId, PublicKey),
 
-    #[error("token_id: {0}")]
-    ParseTokenId(#[from] TokenIdError),
+    #[error("invalid token_id: {0}")]
+    InvalidTokenId(#[from] TokenIdError),
 
     #[error("wrong verifying_contract")]
     WrongVerifyingContract,
 
+    #[error("EIP-1271 signatures from chain {0} are not accepted")]
+    WrongEip1271ChainId(u64),
+
+    #[error("price of '{0}' is not available from the oracle")]
+    OraclePriceUnavailable(String),
+
+    #[error("price condition on '{0}' is not met")]
+    PriceConditionNotMet(String),
+
     #[error("invalid salt")]
     InvalidSalt,
 
@@ -4,13 +4,23 @@ use derive_more::derive::From;
 use near_sdk::{near, serde::Deserialize};
 
 use crate::{
-    accounts::{AccountEvent, NonceEvent, PublicKeyEvent, SaltRotationEvent, TransferEvent},
+    accounts::{
+        AccountEvent, AccountLockedEvent, NonceEvent, PublicKeyEvent, RegistrationReserveLowEvent,
+        RoleEvent, SaltRotationEvent, TransferEvent,
+    },
     fees::{FeeChangedEvent, FeeCollectorChangedEvent},
     intents::{
         IntentEvent,
-        account::SetAuthByPredecessorId,
+        account::{
+            RevokeBefore, SetAuthByPredecessorId, SetDefaultMinGas, SetMultisigThreshold,
+            SetNickname,
+        },
+        session::GrantSession,
         token_diff::TokenDiffEvent,
-        tokens::{FtWithdraw, MtWithdraw, NativeWithdraw, NftWithdraw, StorageDeposit},
+        tokens::{
+            FtWithdraw, MtWithdraw, NativeWithdraw, NftWithdraw, NotificationResolvedEvent,
+            StorageDeposit,
+        },
     },
 };
 
@@ -33,6 +43,10 @@ pub enum DefuseEvent<'a> {
     #[event_version("0.3.0")]
     Transfer(Cow<'a, [IntentEvent<AccountEvent<'a, TransferEvent<'a>>>]>),
 
+    #[event_version("0.4.0")]
+    #[from(skip)]
+    Donation(Cow<'a, [IntentEvent<AccountEvent<'a, TransferEvent<'a>>>]>),
+
     #[event_version("0.3.0")]
     TokenDiff(Cow<'a, [IntentEvent<AccountEvent<'a, TokenDiffEvent<'a>>>]>),
 
@@ -56,16 +70,51 @@ pub enum DefuseEvent<'a> {
 
     #[event_version("0.3.0")]
     #[from(skip)]
-    AccountLocked(AccountEvent<'a, ()>),
+    AccountLocked(AccountEvent<'a, AccountLockedEvent<'a>>),
     #[event_version("0.3.0")]
     #[from(skip)]
     AccountUnlocked(AccountEvent<'a, ()>),
 
+    #[event_version("0.4.0")]
+    #[from(skip)]
+    AccountBlocked(AccountEvent<'a, ()>),
+    #[event_version("0.4.0")]
+    #[from(skip)]
+    AccountUnblocked(AccountEvent<'a, ()>),
+
     #[event_version("0.3.0")]
     SetAuthByPredecessorId(AccountEvent<'a, SetAuthByPredecessorId>),
 
     #[event_version("0.4.0")]
     SaltRotation(SaltRotationEvent),
+
+    #[event_version("0.4.0")]
+    SessionGranted(AccountEvent<'a, Cow<'a, GrantSession>>),
+
+    #[event_version("0.4.0")]
+    SetMultisigThreshold(AccountEvent<'a, SetMultisigThreshold>),
+
+    #[event_version("0.4.0")]
+    RevokeBefore(AccountEvent<'a, RevokeBefore>),
+
+    #[event_version("0.4.0")]
+    RegistrationReserveLow(AccountEvent<'a, RegistrationReserveLowEvent>),
+
+    #[event_version("0.4.0")]
+    SetDefaultMinGas(AccountEvent<'a, SetDefaultMinGas>),
+
+    #[event_version("0.4.0")]
+    NotificationResolved(AccountEvent<'a, NotificationResolvedEvent<'a>>),
+
+    #[event_version("0.4.0")]
+    SetNickname(AccountEvent<'a, SetNickname>),
+
+    #[event_version("0.4.0")]
+    #[from(skip)]
+    RoleGranted(AccountEvent<'a, RoleEvent<'a>>),
+    #[event_version("0.4.0")]
+    #[from(skip)]
+    RoleRevoked(AccountEvent<'a, RoleEvent<'a>>),
 }
 
 pub trait DefuseIntentEmit<'a>: Into<DefuseEvent<'a>> {
@@ -82,3 +131,67 @@ impl defuse_near_utils::NearSdkLog for DefuseEvent<'_> {
         ::std::format!("EVENT_JSON:{}", self.to_json())
     }
 }
+
+impl DefuseEvent<'_> {
+    /// Parses a log previously produced by
+    /// [`to_near_sdk_log()`](defuse_near_utils::NearSdkLog::to_near_sdk_log).
+    /// Returns `None` for logs of other standards or malformed ones.
+    pub fn from_near_sdk_log(log: &str) -> Option<Self> {
+        let json = log.strip_prefix("EVENT_JSON:")?;
+        // `u128` amounts can't be deserialized from flattened JSON input directly
+        let event: near_sdk::serde_json::Value = near_sdk::serde_json::from_str(json).ok()?;
+        if event.get("standard")?.as_str()? != "dip4" {
+            return None;
+        }
+        near_sdk::serde_json::from_value(event).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use defuse_near_utils::NearSdkLog;
+    use near_sdk::AccountIdRef;
+
+    use crate::{amounts::Amounts, token_id::TokenId};
+
+    use super::*;
+
+    #[test]
+    fn from_near_sdk_log() {
+        let receiver_id = AccountIdRef::new_or_panic("receiver.near");
+        let tokens = Amounts::new([("nep141:ft.near".parse::<TokenId>().unwrap(), 100)].into());
+        let log = DefuseEvent::Transfer(
+            vec![IntentEvent::new(
+                AccountEvent::new(
+                    AccountIdRef::new_or_panic("sender.near"),
+                    TransferEvent {
+                        receiver_id: receiver_id.into(),
+                        tokens: Cow::Borrowed(&tokens),
+                        memo: Cow::Owned(None),
+                    },
+                ),
+                [1; 32],
+            )]
+            .into(),
+        )
+        .to_near_sdk_log();
+
+        let Some(DefuseEvent::Transfer(events)) = DefuseEvent::from_near_sdk_log(&log) else {
+            panic!("expected transfer event: {log}");
+        };
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].intent_hash, [1; 32]);
+        assert_eq!(events[0].event.account_id.as_ref(), "sender.near");
+        assert_eq!(events[0].event.event.receiver_id.as_ref(), receiver_id);
+        assert_eq!(events[0].event.event.tokens.as_ref(), &tokens);
+    }
+
+    #[test]
+    fn from_near_sdk_log_other_standard() {
+        assert!(DefuseEvent::from_near_sdk_log(
+            r#"EVENT_JSON:{"standard":"nep245","version":"1.0.0","event":"mt_transfer","data":[]}"#
+        )
+        .is_none());
+        assert!(DefuseEvent::from_near_sdk_log("not an event").is_none());
+    }
+}
@@ -100,17 +100,28 @@ impl Pips {
         Self(Self::MAX.as_pips() - self.as_pips())
     }
 
+    /// Returns fee for given `amount` rounded down. Multiplication is
+    /// performed with 256-bit intermediate, so the result is always
+    /// bounded by `amount` for valid [`Pips`].
+    #[inline]
+    pub fn checked_fee(self, amount: u128) -> Option<u128> {
+        amount.checked_mul_div(self.as_pips().into(), Self::MAX.as_pips().into())
+    }
+
+    /// Same as [`Pips::checked_fee`], but rounded up
+    #[inline]
+    pub fn checked_fee_ceil(self, amount: u128) -> Option<u128> {
+        amount.checked_mul_div_ceil(self.as_pips().into(), Self::MAX.as_pips().into())
+    }
+
     #[inline]
     pub fn fee(self, amount: u128) -> u128 {
-        amount
-            .checked_mul_div(self.as_pips().into(), Self::MAX.as_pips().into())
-            .unwrap_or_else(|| unreachable!())
+        self.checked_fee(amount).unwrap_or_else(|| unreachable!())
     }
 
     #[inline]
     pub fn fee_ceil(self, amount: u128) -> u128 {
-        amount
-            .checked_mul_div_ceil(self.as_pips().into(), Self::MAX.as_pips().into())
+        self.checked_fee_ceil(amount)
             .unwrap_or_else(|| unreachable!())
     }
 }
@@ -224,3 +235,43 @@ impl BorshDeserialize for Pips {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[test]
+    fn fee_does_not_overflow(
+        #[values(0, 1, 99, 1_000_000, u128::MAX / 2, u128::MAX - 1, u128::MAX)] amount: u128,
+        #[values(
+            Pips::ZERO,
+            Pips::ONE_PIP,
+            Pips::ONE_BIP,
+            Pips::ONE_PERCENT,
+            Pips::ONE_PERCENT * 50,
+            Pips::MAX
+        )]
+        fee: Pips,
+    ) {
+        let floor = fee.checked_fee(amount).unwrap();
+        let ceil = fee.checked_fee_ceil(amount).unwrap();
+        assert!(floor <= ceil && ceil <= amount);
+        assert!(ceil - floor <= 1);
+    }
+
+    #[test]
+    fn fee_u128_max() {
+        assert_eq!(
+            Pips::ONE_PERCENT.checked_fee(u128::MAX),
+            Some(u128::MAX / 100)
+        );
+        assert_eq!(
+            Pips::ONE_PERCENT.checked_fee_ceil(u128::MAX),
+            Some(u128::MAX.div_ceil(100))
+        );
+        assert_eq!(Pips::MAX.checked_fee_ceil(u128::MAX), Some(u128::MAX));
+    }
+}
@@ -1,13 +1,14 @@
 use std::borrow::Cow;
 
+use defuse_borsh_utils::adapters::{As, TimestampNanoSeconds};
 use defuse_crypto::PublicKey;
-use near_sdk::{AccountIdRef, CryptoHash, near};
+use near_sdk::{AccountIdRef, CryptoHash, Gas, near};
 use serde_with::serde_as;
 
 use crate::{
-    Result,
+    Deadline, DefuseError, Result,
     accounts::{AccountEvent, PublicKeyEvent},
-    engine::{Engine, Inspector, State},
+    engine::{Engine, Inspector, State, StateView},
 };
 
 use super::ExecutableIntent;
@@ -75,6 +76,13 @@ impl ExecutableIntent for RemovePublicKey {
         engine
             .state
             .remove_public_key(signer_id.to_owned(), self.public_key)?;
+
+        // multisig accounts should still have enough keys to meet threshold
+        let threshold = engine.state.multisig_threshold(signer_id);
+        if threshold > 1 && count_public_keys(&engine.state, signer_id)? < usize::from(threshold) {
+            return Err(DefuseError::InvalidMultisigThreshold);
+        }
+
         engine
             .inspector
             .on_event(crate::events::DefuseEvent::PublicKeyRemoved(
@@ -125,3 +133,195 @@ impl ExecutableIntent for SetAuthByPredecessorId {
         Ok(())
     }
 }
+
+/// Require intents signed on behalf of the signer to be accompanied by
+/// signatures of the same payload from `m` distinct registered public keys.
+/// `m` of `1` disables multisig.
+///
+/// `n_keys` must be equal to the number of public keys currently registered
+/// for the signer, so that the threshold doesn't silently change its meaning
+/// if the set of keys has changed since signing.
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct SetMultisigThreshold {
+    pub m: u8,
+    pub n_keys: u8,
+}
+
+impl ExecutableIntent for SetMultisigThreshold {
+    fn execute_intent<S, I>(
+        self,
+        signer_id: &AccountIdRef,
+        engine: &mut Engine<S, I>,
+        _intent_hash: CryptoHash,
+    ) -> Result<()>
+    where
+        S: State,
+        I: Inspector,
+    {
+        if self.m == 0
+            || self.m > self.n_keys
+            || count_public_keys(&engine.state, signer_id)? != usize::from(self.n_keys)
+        {
+            return Err(DefuseError::InvalidMultisigThreshold);
+        }
+
+        engine
+            .state
+            .set_multisig_threshold(signer_id.to_owned(), self.m)?;
+
+        engine
+            .inspector
+            .on_event(AccountEvent::new(signer_id, self).into());
+
+        Ok(())
+    }
+}
+
+/// Counts public keys registered for the account, giving up once more
+/// than [`StateView::max_keys_to_check`] of them are iterated
+fn count_public_keys<S>(state: &S, account_id: &AccountIdRef) -> Result<usize>
+where
+    S: StateView,
+{
+    let max_keys = usize::try_from(state.max_keys_to_check()).unwrap_or(usize::MAX);
+    let n_keys = state
+        .iter_public_keys(account_id)
+        .take(max_keys.saturating_add(1))
+        .count();
+    if n_keys > max_keys {
+        return Err(DefuseError::TooManyKeysToCheck);
+    }
+    Ok(n_keys)
+}
+
+/// Set default `min_gas` for outgoing cross-contract calls made by
+/// [`FtWithdraw`](super::tokens::FtWithdraw),
+/// [`NftWithdraw`](super::tokens::NftWithdraw),
+/// [`MtWithdraw`](super::tokens::MtWithdraw) and
+/// [`AuthCall`](super::auth::AuthCall) intents of the signer which
+/// don't specify `min_gas` explicitly.
+///
+/// Hard minimums of these intents still apply. Zero `gas` unsets it.
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct SetDefaultMinGas {
+    pub gas: Gas,
+}
+
+impl ExecutableIntent for SetDefaultMinGas {
+    fn execute_intent<S, I>(
+        self,
+        signer_id: &AccountIdRef,
+        engine: &mut Engine<S, I>,
+        _intent_hash: CryptoHash,
+    ) -> Result<()>
+    where
+        S: State,
+        I: Inspector,
+    {
+        engine
+            .state
+            .set_default_min_gas(signer_id.to_owned(), self.gas)?;
+
+        engine
+            .inspector
+            .on_event(AccountEvent::new(signer_id, self).into());
+
+        Ok(())
+    }
+}
+
+/// Maximum length of [`SetNickname::nickname`] in bytes
+pub const MAX_NICKNAME_LEN: usize = 64;
+
+/// Set display nickname of the signer to be shown by wallets, `None` or
+/// empty one clears it. It has no effect on balances or authentication.
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct SetNickname {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+}
+
+impl ExecutableIntent for SetNickname {
+    fn execute_intent<S, I>(
+        self,
+        signer_id: &AccountIdRef,
+        engine: &mut Engine<S, I>,
+        _intent_hash: CryptoHash,
+    ) -> Result<()>
+    where
+        S: State,
+        I: Inspector,
+    {
+        if self
+            .nickname
+            .as_ref()
+            .is_some_and(|nickname| nickname.len() > MAX_NICKNAME_LEN)
+        {
+            return Err(DefuseError::NicknameTooLong);
+        }
+
+        engine
+            .state
+            .set_nickname(signer_id.to_owned(), self.nickname.clone())?;
+
+        engine
+            .inspector
+            .on_event(AccountEvent::new(signer_id, self).into());
+
+        Ok(())
+    }
+}
+
+/// Revoke all intents signed on behalf of the signer with `signed_at`
+/// earlier than `timestamp`, so that already signed but not yet relayed
+/// intents can be cancelled all at once. Intents without `signed_at`
+/// are revoked as well once the cutoff is set.
+///
+/// The cutoff can only be moved forward: revoking before an earlier
+/// `timestamp` than the current one is a no-op. A `timestamp` in the
+/// future is clamped to the current block timestamp, so that intents
+/// signed afterwards remain valid.
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct RevokeBefore {
+    #[borsh(
+        serialize_with = "As::<TimestampNanoSeconds>::serialize",
+        deserialize_with = "As::<TimestampNanoSeconds>::deserialize"
+    )]
+    pub timestamp: Deadline,
+}
+
+impl ExecutableIntent for RevokeBefore {
+    fn execute_intent<S, I>(
+        self,
+        signer_id: &AccountIdRef,
+        engine: &mut Engine<S, I>,
+        _intent_hash: CryptoHash,
+    ) -> Result<()>
+    where
+        S: State,
+        I: Inspector,
+    {
+        // far-future cutoff would revoke all intents of the account forever
+        let timestamp = self.timestamp.min(Deadline::now());
+
+        if engine
+            .state
+            .revoked_before(signer_id)
+            .is_none_or(|cutoff| cutoff < timestamp)
+        {
+            engine
+                .state
+                .set_revoked_before(signer_id.to_owned(), timestamp)?;
+        }
+
+        engine
+            .inspector
+            .on_event(AccountEvent::new(signer_id, Self { timestamp }).into());
+
+        Ok(())
+    }
+}
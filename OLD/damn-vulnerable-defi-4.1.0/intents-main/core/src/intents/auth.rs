@@ -3,7 +3,7 @@ use serde_with::serde_as;
 
 use crate::{
     Result,
-    engine::{Engine, Inspector, State},
+    engine::{Engine, Inspector, State, StateView},
     intents::ExecutableIntent,
 };
 
@@ -55,7 +55,7 @@ impl AuthCall {
 
 impl ExecutableIntent for AuthCall {
     fn execute_intent<S, I>(
-        self,
+        mut self,
         signer_id: &AccountIdRef,
         engine: &mut Engine<S, I>,
         _intent_hash: CryptoHash,
@@ -64,6 +64,10 @@ impl ExecutableIntent for AuthCall {
         S: State,
         I: Inspector,
     {
+        self.min_gas = self
+            .min_gas
+            .or_else(|| engine.state.default_min_gas(signer_id));
+
         engine.state.auth_call(signer_id, self)
     }
 }
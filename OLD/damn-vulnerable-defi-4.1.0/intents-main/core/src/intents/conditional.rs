@@ -0,0 +1,96 @@
+use near_sdk::{AccountIdRef, CryptoHash, json_types::U128, near};
+
+use crate::{
+    DefuseError, Result,
+    engine::{Engine, Inspector, State, StateView},
+    intents::{ExecutableIntent, Intent},
+};
+
+/// Executes `inner` intent only if the price of `condition.asset`
+/// reported by the price oracle configured in the contract satisfies
+/// the `condition`.
+///
+/// Prices are read by `execute_conditional_intents` before executing
+/// the intents, so such intents fail when executed via `execute_intents`.
+/// If the condition isn't met, the whole batch fails without committing
+/// the nonce, so it can be retried later.
+#[near(serializers = [json])]
+#[derive(Debug, Clone)]
+pub struct ConditionalIntent {
+    pub condition: PriceCondition,
+    pub inner: Box<Intent>,
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceCondition {
+    /// Asset to query the price oracle for
+    pub asset: String,
+    pub comparison: PriceComparison,
+    pub price: U128,
+}
+
+#[near(serializers = [borsh, json])]
+#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceComparison {
+    /// Oracle price is greater than or equal to the threshold
+    AtLeast,
+    /// Oracle price is less than or equal to the threshold
+    AtMost,
+}
+
+impl PriceCondition {
+    #[inline]
+    pub const fn is_met(&self, price: u128) -> bool {
+        match self.comparison {
+            PriceComparison::AtLeast => price >= self.price.0,
+            PriceComparison::AtMost => price <= self.price.0,
+        }
+    }
+}
+
+impl ExecutableIntent for ConditionalIntent {
+    fn execute_intent<S, I>(
+        self,
+        signer_id: &AccountIdRef,
+        engine: &mut Engine<S, I>,
+        intent_hash: CryptoHash,
+    ) -> Result<()>
+    where
+        S: State,
+        I: Inspector,
+    {
+        let price = engine
+            .state
+            .oracle_price(&self.condition.asset)
+            .ok_or_else(|| DefuseError::OraclePriceUnavailable(self.condition.asset.clone()))?;
+        if !self.condition.is_met(price) {
+            return Err(DefuseError::PriceConditionNotMet(self.condition.asset));
+        }
+
+        self.inner.execute_intent(signer_id, engine, intent_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn price_condition() {
+        let condition = |comparison| PriceCondition {
+            asset: "near".to_string(),
+            comparison,
+            price: U128(100),
+        };
+
+        assert!(condition(PriceComparison::AtLeast).is_met(100));
+        assert!(condition(PriceComparison::AtLeast).is_met(101));
+        assert!(!condition(PriceComparison::AtLeast).is_met(99));
+
+        assert!(condition(PriceComparison::AtMost).is_met(100));
+        assert!(condition(PriceComparison::AtMost).is_met(99));
+        assert!(!condition(PriceComparison::AtMost).is_met(101));
+    }
+}
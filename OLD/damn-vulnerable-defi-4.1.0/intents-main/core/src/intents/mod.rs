@@ -1,24 +1,39 @@
 pub mod account;
 pub mod auth;
+pub mod conditional;
+pub mod session;
+pub mod template;
 pub mod token_diff;
 pub mod tokens;
 
 use defuse_serde_utils::base58::Base58;
 use derive_more::derive::From;
-use near_sdk::{AccountIdRef, CryptoHash, near};
+use near_sdk::{AccountId, AccountIdRef, CryptoHash, Gas, near};
 use serde_with::serde_as;
+use strum::EnumDiscriminants;
 use tokens::{NativeWithdraw, StorageDeposit};
 
 use crate::{
-    Result,
-    engine::{Engine, Inspector, State},
-    intents::{account::SetAuthByPredecessorId, auth::AuthCall},
+    DefuseError, Result,
+    engine::{Engine, Inspector, State, StateView},
+    intents::{
+        account::{
+            RevokeBefore, SetAuthByPredecessorId, SetDefaultMinGas, SetMultisigThreshold,
+            SetNickname,
+        },
+        auth::AuthCall,
+        conditional::{ConditionalIntent, PriceCondition},
+    },
 };
 
 use self::{
     account::{AddPublicKey, RemovePublicKey},
+    session::GrantSession,
+    template::TransferTemplate,
     token_diff::TokenDiff,
-    tokens::{FtWithdraw, MtWithdraw, NftWithdraw, Transfer},
+    tokens::{
+        Donate, FtWithdraw, MtBatchTransfer, MtWithdraw, NftWithdraw, NotifyOnTransfer, Transfer,
+    },
 };
 
 #[near(serializers = [json])]
@@ -34,7 +49,14 @@ pub struct DefuseIntents {
 
 #[near(serializers = [json])]
 #[serde(tag = "intent", rename_all = "snake_case")]
-#[derive(Debug, Clone, From)]
+#[derive(Debug, Clone, From, EnumDiscriminants)]
+#[strum_discriminants(
+    name(IntentKind),
+    vis(pub),
+    derive(PartialOrd, Ord, Hash),
+    near(serializers = [borsh, json]),
+    serde(rename_all = "snake_case")
+)]
 pub enum Intent {
     /// See [`AddPublicKey`]
     AddPublicKey(AddPublicKey),
@@ -68,6 +90,149 @@ pub enum Intent {
 
     /// See [`AuthCall`]
     AuthCall(AuthCall),
+
+    /// See [`GrantSession`]
+    GrantSession(GrantSession),
+
+    /// See [`SetMultisigThreshold`]
+    SetMultisigThreshold(SetMultisigThreshold),
+
+    /// See [`RevokeBefore`]
+    RevokeBefore(RevokeBefore),
+
+    /// See [`TransferTemplate`]
+    TransferTemplate(TransferTemplate),
+
+    /// See [`MtBatchTransfer`]
+    MtBatchTransfer(MtBatchTransfer),
+
+    /// See [`SetDefaultMinGas`]
+    SetDefaultMinGas(SetDefaultMinGas),
+
+    /// See [`ConditionalIntent`]
+    Conditional(ConditionalIntent),
+
+    /// See [`Donate`]
+    Donate(Donate),
+
+    /// See [`SetNickname`]
+    SetNickname(SetNickname),
+}
+
+impl DefuseIntents {
+    /// Returns the only intent if it's a [`TokenDiff`] which can be
+    /// filled partially
+    #[inline]
+    pub fn as_partial_fill(&self) -> Option<&TokenDiff> {
+        match self.intents.as_slice() {
+            [Intent::TokenDiff(token_diff)] if token_diff.partial_fill => Some(token_diff),
+            _ => None,
+        }
+    }
+
+    /// Replaces each [`TransferTemplate`] with transfers to given
+    /// `recipients`. Fails if there are no templates to instantiate.
+    pub fn instantiate(self, recipients: &[AccountId]) -> Result<Self> {
+        let mut instantiated = false;
+        let mut intents = Vec::with_capacity(self.intents.len());
+        for intent in self.intents {
+            if let Intent::TransferTemplate(template) = intent {
+                intents.extend(
+                    template
+                        .instantiate(recipients)?
+                        .into_iter()
+                        .map(Intent::Transfer),
+                );
+                instantiated = true;
+            } else {
+                intents.push(intent);
+            }
+        }
+        if !instantiated {
+            return Err(DefuseError::InvalidIntent);
+        }
+        Ok(Self { intents })
+    }
+
+    /// Resolves [`auto_storage_deposit`](FtWithdraw::auto_storage_deposit)
+    /// of [`FtWithdraw`] intents in order, given current `wNEAR` balance
+    /// of the signer
+    pub fn resolve_storage_deposits<S>(&mut self, signer_id: &AccountIdRef, state: &S)
+    where
+        S: StateView,
+    {
+        let wnear_id = state.wnear_id();
+        let mut wnear_balance = state.balance_of(signer_id, &state.wnear_token_id());
+        for intent in &mut self.intents {
+            if let Intent::FtWithdraw(withdraw) = intent {
+                withdraw.resolve_storage_deposit(&wnear_id, &mut wnear_balance);
+            }
+        }
+    }
+
+    /// Returns total minimum gas required by promises created by
+    /// the intents
+    #[inline]
+    pub fn promises_min_gas(&self) -> Gas {
+        self.intents
+            .iter()
+            .map(Intent::promise_min_gas)
+            .fold(Gas::from_gas(0), Gas::saturating_add)
+    }
+}
+
+impl Intent {
+    /// Returns minimum gas required by the promise created by the
+    /// intent, zero if it doesn't declare one. Doesn't account for
+    /// [`SetDefaultMinGas`] of the signer.
+    #[inline]
+    pub fn promise_min_gas(&self) -> Gas {
+        match self {
+            Self::Transfer(transfer) => transfer
+                .notification
+                .as_ref()
+                .map_or(Gas::from_gas(0), NotifyOnTransfer::min_gas),
+            Self::FtWithdraw(withdraw) => withdraw.min_gas(),
+            Self::NftWithdraw(withdraw) => withdraw.min_gas(),
+            Self::MtWithdraw(withdraw) => withdraw.min_gas(),
+            Self::MtBatchTransfer(transfer) => transfer
+                .notifications
+                .iter()
+                .flatten()
+                .flatten()
+                .map(NotifyOnTransfer::min_gas)
+                .fold(Gas::from_gas(0), Gas::saturating_add),
+            Self::AuthCall(auth_call) => auth_call.min_gas(),
+            Self::Conditional(conditional) => conditional.inner.promise_min_gas(),
+            _ => Gas::from_gas(0),
+        }
+    }
+}
+
+impl Intent {
+    /// Returns the intent wrapped into [`ConditionalIntent`]s, if any,
+    /// i.e. the one which gets executed once conditions are met
+    #[inline]
+    pub fn unconditional(&self) -> &Self {
+        let mut intent = self;
+        while let Self::Conditional(conditional) = intent {
+            intent = &conditional.inner;
+        }
+        intent
+    }
+
+    /// Returns price conditions the intent is gated on, outermost first
+    #[inline]
+    pub fn price_conditions(&self) -> impl Iterator<Item = &PriceCondition> {
+        let mut intent = self;
+        std::iter::from_fn(move || {
+            let Self::Conditional(conditional) = intent else {
+                return None;
+            };
+            intent = &conditional.inner;
+            Some(&conditional.condition)
+        })
+    }
 }
 
 pub trait ExecutableIntent {
@@ -125,6 +290,17 @@ impl ExecutableIntent for Intent {
                 intent.execute_intent(signer_id, engine, intent_hash)
             }
             Self::AuthCall(intent) => intent.execute_intent(signer_id, engine, intent_hash),
+            Self::GrantSession(intent) => intent.execute_intent(signer_id, engine, intent_hash),
+            Self::SetMultisigThreshold(intent) => {
+                intent.execute_intent(signer_id, engine, intent_hash)
+            }
+            Self::RevokeBefore(intent) => intent.execute_intent(signer_id, engine, intent_hash),
+            Self::TransferTemplate(intent) => intent.execute_intent(signer_id, engine, intent_hash),
+            Self::MtBatchTransfer(intent) => intent.execute_intent(signer_id, engine, intent_hash),
+            Self::SetDefaultMinGas(intent) => intent.execute_intent(signer_id, engine, intent_hash),
+            Self::Conditional(intent) => intent.execute_intent(signer_id, engine, intent_hash),
+            Self::Donate(intent) => intent.execute_intent(signer_id, engine, intent_hash),
+            Self::SetNickname(intent) => intent.execute_intent(signer_id, engine, intent_hash),
         }
     }
 }
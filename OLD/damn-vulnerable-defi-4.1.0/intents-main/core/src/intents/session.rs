@@ -0,0 +1,360 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+};
+
+use defuse_borsh_utils::adapters::{As, TimestampNanoSeconds};
+use defuse_crypto::PublicKey;
+use near_sdk::{AccountId, AccountIdRef, CryptoHash, near};
+use serde_with::{DisplayFromStr, serde_as};
+
+use crate::{
+    Deadline, DefuseError, Result,
+    accounts::AccountEvent,
+    amounts::Amounts,
+    engine::{Engine, Inspector, State},
+    events::DefuseEvent,
+    token_id::{TokenId, nep141::Nep141TokenId, nep171::Nep171TokenId, nep245::Nep245TokenId},
+};
+
+use super::{DefuseIntents, ExecutableIntent, Intent};
+
+/// Authorize a temporary `key` to sign intents on behalf of the signer
+/// until `expiry`. Intents signed by this key can only move up to the
+/// remaining per-token allowance in `limits`, while tokens not listed there
+/// can't be moved at all. `AuthCall` intents signed by this key are only
+/// allowed for contracts listed in `auth_calls`.
+///
+/// Granting a session for the same key again replaces its limits and expiry.
+#[cfg_attr(
+    all(feature = "abi", not(target_arch = "wasm32")),
+    serde_as(schemars = true)
+)]
+#[cfg_attr(
+    not(all(feature = "abi", not(target_arch = "wasm32"))),
+    serde_as(schemars = false)
+)]
+#[near(serializers = [json])]
+#[derive(Debug, Clone)]
+pub struct GrantSession {
+    pub key: PublicKey,
+
+    #[serde_as(as = "Amounts<BTreeMap<_, DisplayFromStr>>")]
+    pub limits: Amounts,
+
+    pub expiry: Deadline,
+
+    /// Contracts this key is allowed to `AuthCall`
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub auth_calls: BTreeSet<AccountId>,
+}
+
+impl ExecutableIntent for GrantSession {
+    fn execute_intent<S, I>(
+        self,
+        signer_id: &AccountIdRef,
+        engine: &mut Engine<S, I>,
+        _intent_hash: CryptoHash,
+    ) -> Result<()>
+    where
+        S: State,
+        I: Inspector,
+    {
+        if self.expiry.has_expired() {
+            return Err(DefuseError::SessionExpired);
+        }
+
+        engine
+            .inspector
+            .on_event(DefuseEvent::SessionGranted(AccountEvent::new(
+                signer_id,
+                Cow::Borrowed(&self),
+            )));
+
+        engine.state.set_session(
+            signer_id.to_owned(),
+            self.key,
+            Session {
+                limits: self.limits,
+                expiry: self.expiry,
+                auth_calls: self.auth_calls,
+            },
+        )
+    }
+}
+
+/// Temporary key authorization created by [`GrantSession`]
+#[cfg_attr(
+    all(feature = "abi", not(target_arch = "wasm32")),
+    serde_as(schemars = true)
+)]
+#[cfg_attr(
+    not(all(feature = "abi", not(target_arch = "wasm32"))),
+    serde_as(schemars = false)
+)]
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    /// Remaining allowance per token
+    #[serde_as(as = "Amounts<BTreeMap<_, DisplayFromStr>>")]
+    pub limits: Amounts,
+
+    #[borsh(
+        serialize_with = "As::<TimestampNanoSeconds>::serialize",
+        deserialize_with = "As::<TimestampNanoSeconds>::deserialize"
+    )]
+    pub expiry: Deadline,
+
+    /// Contracts this key is allowed to `AuthCall`
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub auth_calls: BTreeSet<AccountId>,
+}
+
+impl Session {
+    #[inline]
+    pub fn has_expired(&self) -> bool {
+        self.expiry.has_expired()
+    }
+
+    /// Subtracts given amounts from the remaining allowance
+    pub fn spend(&mut self, amounts: impl IntoIterator<Item = (TokenId, u128)>) -> Result<()> {
+        for (token_id, amount) in amounts {
+            self.limits
+                .sub(token_id, amount)
+                .ok_or(DefuseError::SessionLimitExceeded)?;
+        }
+        Ok(())
+    }
+}
+
+impl DefuseIntents {
+    /// Returns total amounts of tokens that would leave the signer's
+    /// account if these intents were signed by a session key.
+    /// Intents that manage keys or sessions are not allowed for session
+    /// keys, so [`DefuseError::InvalidIntent`] is returned for them, as well
+    /// as [`DefuseError::SessionAuthCallNotAllowed`] for `AuthCall` to
+    /// contracts outside of `auth_calls`.
+    pub fn session_spendings(
+        &self,
+        wnear_token_id: &TokenId,
+        auth_calls: &BTreeSet<AccountId>,
+    ) -> Result<Amounts> {
+        let mut spendings: Amounts = Amounts::default();
+        let mut spend = |token_id: TokenId, amount: u128| {
+            spendings
+                .add(token_id, amount)
+                .ok_or(DefuseError::BalanceOverflow)
+                .map(|_| ())
+        };
+
+        // conditions don't affect spendings
+        for intent in self.intents.iter().map(Intent::unconditional) {
+            match intent {
+                Intent::Transfer(transfer) => {
+                    for (token_id, amount) in transfer.tokens.iter() {
+                        spend(token_id.clone(), *amount)?;
+                    }
+                }
+                Intent::Donate(donate) => {
+                    for (token_id, amount) in donate.tokens.iter() {
+                        spend(token_id.clone(), *amount)?;
+                    }
+                }
+                Intent::MtBatchTransfer(transfer) => {
+                    for (token_id, amount) in transfer.token_ids.iter().zip(&transfer.amounts) {
+                        spend(token_id.clone(), amount.0)?;
+                    }
+                }
+                Intent::FtWithdraw(withdraw) => {
+                    spend(
+                        Nep141TokenId::new(withdraw.token.clone()).into(),
+                        withdraw.amount.0,
+                    )?;
+                    if let Some(storage_deposit) = withdraw.storage_deposit {
+                        spend(wnear_token_id.clone(), storage_deposit.as_yoctonear())?;
+                    }
+                }
+                Intent::NftWithdraw(withdraw) => {
+                    spend(
+                        Nep171TokenId::new(withdraw.token.clone(), withdraw.token_id.clone())?
+                            .into(),
+                        1,
+                    )?;
+                    if let Some(storage_deposit) = withdraw.storage_deposit {
+                        spend(wnear_token_id.clone(), storage_deposit.as_yoctonear())?;
+                    }
+                }
+                Intent::MtWithdraw(withdraw) => {
+                    for (token_id, amount) in withdraw.token_ids.iter().zip(&withdraw.amounts) {
+                        spend(
+                            Nep245TokenId::new(withdraw.token.clone(), token_id.clone())?.into(),
+                            amount.0,
+                        )?;
+                    }
+                    if let Some(storage_deposit) = withdraw.storage_deposit {
+                        spend(wnear_token_id.clone(), storage_deposit.as_yoctonear())?;
+                    }
+                }
+                Intent::NativeWithdraw(withdraw) => {
+                    spend(wnear_token_id.clone(), withdraw.amount.as_yoctonear())?;
+                }
+                Intent::StorageDeposit(storage_deposit) => {
+                    spend(
+                        wnear_token_id.clone(),
+                        storage_deposit.amount.as_yoctonear(),
+                    )?;
+                }
+                Intent::TokenDiff(token_diff) => {
+                    for (token_id, delta) in token_diff.diff.iter() {
+                        if delta.is_negative() {
+                            spend(token_id.clone(), delta.unsigned_abs())?;
+                        }
+                    }
+                }
+                Intent::AuthCall(auth_call) => {
+                    if !auth_calls.contains(&auth_call.contract_id) {
+                        return Err(DefuseError::SessionAuthCallNotAllowed(
+                            auth_call.contract_id.clone(),
+                        ));
+                    }
+                    if !auth_call.attached_deposit.is_zero() {
+                        spend(
+                            wnear_token_id.clone(),
+                            auth_call.attached_deposit.as_yoctonear(),
+                        )?;
+                    }
+                }
+                Intent::AddPublicKey(_)
+                | Intent::RemovePublicKey(_)
+                | Intent::SetAuthByPredecessorId(_)
+                | Intent::GrantSession(_)
+                | Intent::SetMultisigThreshold(_)
+                | Intent::SetDefaultMinGas(_)
+                | Intent::SetNickname(_)
+                | Intent::RevokeBefore(_)
+                | Intent::TransferTemplate(_) => return Err(DefuseError::InvalidIntent),
+                Intent::Conditional(_) => unreachable!(),
+            }
+        }
+
+        Ok(spendings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use near_sdk::{AccountId, NearToken, json_types::U128};
+
+    use crate::intents::{
+        auth::AuthCall,
+        tokens::{FtWithdraw, Transfer},
+    };
+
+    use super::*;
+
+    fn ft(token: &str) -> TokenId {
+        Nep141TokenId::new(token.parse::<AccountId>().unwrap()).into()
+    }
+
+    #[test]
+    fn session_spendings() {
+        let wnear = ft("wrap.near");
+        let intents = DefuseIntents {
+            intents: vec![
+                Transfer {
+                    receiver_id: "bob.near".parse().unwrap(),
+                    tokens: Amounts::new([(ft("ft.near"), 100)].into()),
+                    memo: None,
+                    notification: None,
+                }
+                .into(),
+                FtWithdraw {
+                    token: "ft.near".parse().unwrap(),
+                    receiver_id: "bob.near".parse().unwrap(),
+                    amount: U128(50),
+                    memo: None,
+                    msg: None,
+                    storage_deposit: Some(NearToken::from_yoctonear(7)),
+                    auto_storage_deposit: false,
+                    min_gas: None,
+                }
+                .into(),
+            ],
+        };
+
+        let spendings = intents.session_spendings(&wnear, &BTreeSet::new()).unwrap();
+        assert_eq!(spendings.amount_for(&ft("ft.near")), 150);
+        assert_eq!(spendings.amount_for(&wnear), 7);
+    }
+
+    #[test]
+    fn session_spendings_rejects_key_management() {
+        let intents = DefuseIntents {
+            intents: vec![
+                GrantSession {
+                    key: PublicKey::Ed25519([0; 32]),
+                    limits: Amounts::default(),
+                    expiry: Deadline::MAX,
+                    auth_calls: BTreeSet::new(),
+                }
+                .into(),
+            ],
+        };
+
+        assert!(matches!(
+            intents.session_spendings(&ft("wrap.near"), &BTreeSet::new()),
+            Err(DefuseError::InvalidIntent)
+        ));
+    }
+
+    #[test]
+    fn session_spendings_auth_call_allowlist() {
+        let wnear = ft("wrap.near");
+        let intents = DefuseIntents {
+            intents: vec![
+                AuthCall {
+                    contract_id: "app.near".parse().unwrap(),
+                    msg: String::new(),
+                    attached_deposit: NearToken::from_yoctonear(3),
+                    min_gas: None,
+                }
+                .into(),
+            ],
+        };
+
+        assert!(matches!(
+            intents.session_spendings(&wnear, &BTreeSet::new()),
+            Err(DefuseError::SessionAuthCallNotAllowed(contract_id)) if contract_id == "app.near"
+        ));
+        assert!(matches!(
+            intents.session_spendings(&wnear, &["other.near".parse().unwrap()].into()),
+            Err(DefuseError::SessionAuthCallNotAllowed(_))
+        ));
+
+        let spendings = intents
+            .session_spendings(&wnear, &["app.near".parse().unwrap()].into())
+            .unwrap();
+        assert_eq!(spendings.amount_for(&wnear), 3);
+    }
+
+    #[test]
+    fn session_spend() {
+        let mut session = Session {
+            limits: Amounts::new([(ft("ft.near"), 500)].into()),
+            expiry: Deadline::MAX,
+            auth_calls: BTreeSet::new(),
+        };
+
+        session.spend([(ft("ft.near"), 300)]).unwrap();
+        assert_eq!(session.limits.amount_for(&ft("ft.near")), 200);
+
+        assert!(matches!(
+            session.spend([(ft("ft.near"), 300)]),
+            Err(DefuseError::SessionLimitExceeded)
+        ));
+        assert!(matches!(
+            session.spend([(ft("other.near"), 1)]),
+            Err(DefuseError::SessionLimitExceeded)
+        ));
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+
+use defuse_serde_utils::base58::Base58;
+use near_sdk::{AccountId, AccountIdRef, CryptoHash, env, near};
+use serde_with::{DisplayFromStr, serde_as};
+
+use crate::{
+    DefuseError, Result,
+    amounts::Amounts,
+    engine::{Engine, Inspector, State},
+};
+
+use super::{ExecutableIntent, tokens::Transfer};
+
+/// Template of transfers of the same set of tokens to multiple
+/// recipients (e.g. airdrops), which is signed once and then
+/// instantiated with the list of recipients.
+///
+/// Only the [Merkle root](recipients_root) of the recipients is
+/// covered by the signature, while the list itself is provided along
+/// with the signed payload, see
+/// [`SignedTemplatePayload`](crate::payload::template::SignedTemplatePayload).
+#[cfg_attr(
+    all(feature = "abi", not(target_arch = "wasm32")),
+    serde_as(schemars = true)
+)]
+#[cfg_attr(
+    not(all(feature = "abi", not(target_arch = "wasm32"))),
+    serde_as(schemars = false)
+)]
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct TransferTemplate {
+    /// Merkle root of receiver ids, see [`recipients_root`]
+    #[serde_as(as = "Base58")]
+    pub recipients_root: CryptoHash,
+
+    /// Tokens to transfer to each of the recipients
+    #[serde_as(as = "Amounts<BTreeMap<_, DisplayFromStr>>")]
+    pub tokens: Amounts,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+impl TransferTemplate {
+    /// Expands the template into transfers to each of `recipients`,
+    /// which have to match [`Self::recipients_root`]
+    pub fn instantiate(self, recipients: &[AccountId]) -> Result<Vec<Transfer>> {
+        if recipients.is_empty() {
+            return Err(DefuseError::InvalidIntent);
+        }
+        if recipients_root(recipients) != self.recipients_root {
+            return Err(DefuseError::InvalidSignature);
+        }
+
+        Ok(recipients
+            .iter()
+            .map(|receiver_id| Transfer {
+                receiver_id: receiver_id.clone(),
+                tokens: self.tokens.clone(),
+                memo: self.memo.clone(),
+                notification: None,
+            })
+            .collect())
+    }
+}
+
+impl ExecutableIntent for TransferTemplate {
+    /// Templates can only be executed once instantiated
+    #[inline]
+    fn execute_intent<S, I>(
+        self,
+        _signer_id: &AccountIdRef,
+        _engine: &mut Engine<S, I>,
+        _intent_hash: CryptoHash,
+    ) -> Result<()>
+    where
+        S: State,
+        I: Inspector,
+    {
+        Err(DefuseError::InvalidIntent)
+    }
+}
+
+/// Merkle root of receiver ids, where leaves are
+/// `sha256(0x00 || RECEIVER_ID)` and inner nodes are
+/// `sha256(0x01 || LEFT || RIGHT)`. The last node on a level with
+/// odd number of nodes is carried to the next level as is.
+pub fn recipients_root(recipients: &[AccountId]) -> CryptoHash {
+    let mut level: Vec<CryptoHash> = recipients
+        .iter()
+        .map(|receiver_id| env::sha256_array(&[b"\x00", receiver_id.as_bytes()].concat()))
+        .collect();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => env::sha256_array(&[b"\x01", left.as_slice(), right].concat()),
+                [node] => *node,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    level.first().copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recipients(ids: &[&str]) -> Vec<AccountId> {
+        ids.iter().map(|id| id.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn root_depends_on_order_and_every_recipient() {
+        let root = recipients_root(&recipients(&["alice.near", "bob.near", "carol.near"]));
+        assert_ne!(
+            root,
+            recipients_root(&recipients(&["bob.near", "alice.near", "carol.near"]))
+        );
+        assert_ne!(
+            root,
+            recipients_root(&recipients(&["alice.near", "bob.near", "dave.near"]))
+        );
+        assert_ne!(
+            root,
+            recipients_root(&recipients(&["alice.near", "bob.near"]))
+        );
+    }
+
+    #[test]
+    fn instantiate_checks_root() {
+        let ids = recipients(&["alice.near", "bob.near", "carol.near"]);
+        let template = TransferTemplate {
+            recipients_root: recipients_root(&ids),
+            tokens: Amounts::default(),
+            memo: None,
+        };
+
+        let transfers = template.clone().instantiate(&ids).unwrap();
+        assert_eq!(
+            transfers
+                .into_iter()
+                .map(|transfer| transfer.receiver_id)
+                .collect::<Vec<_>>(),
+            ids
+        );
+
+        assert!(matches!(
+            template
+                .clone()
+                .instantiate(&recipients(&["alice.near", "bob.near", "mallory.near"])),
+            Err(DefuseError::InvalidSignature)
+        ));
+        assert!(matches!(
+            template.instantiate(&[]),
+            Err(DefuseError::InvalidIntent)
+        ));
+    }
+}
@@ -3,7 +3,10 @@ use crate::{
     DefuseError, Result,
     accounts::AccountEvent,
     amounts::Amounts,
-    engine::{Engine, Inspector, State, StateView},
+    engine::{
+        Engine, Inspector, State, StateView,
+        deltas::{InvariantViolated, TransferMatcher},
+    },
     events::DefuseEvent,
     fees::Pips,
     token_id::{TokenId, TokenIdType},
@@ -16,6 +19,9 @@ use std::{borrow::Cow, collections::BTreeMap};
 
 pub type TokenDeltas = Amounts<BTreeMap<TokenId, i128>>;
 
+/// Token and its absolute amount in partially fillable [`TokenDiff`]
+pub type PartialFillLeg<'a> = (&'a TokenId, u128);
+
 #[cfg_attr(
     all(feature = "abi", not(target_arch = "wasm32")),
     serde_as(schemars = true)
@@ -42,6 +48,27 @@ pub struct TokenDiff {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub referral: Option<AccountId>,
+
+    /// Maximum fee the signer agrees to pay. Execution fails if the
+    /// effective fee for any of `token_in` exceeds it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fee: Option<Pips>,
+
+    /// Solver the signer agrees to trade with (i.e. RFQ binding).
+    /// If set, execution fails unless the solver also executes
+    /// `TokenDiff` in the same batch on the same tokens in the
+    /// opposite direction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solver: Option<AccountId>,
+
+    /// Allow the diff to be filled partially by counter-intents in the
+    /// same batch, while the nonce stays available to fill the rest.
+    /// Only diffs on exactly two tokens, i.e. one `token_in` and one
+    /// `token_out`, signed as the only intent in the payload can be
+    /// filled partially. Each fill is executed at the price not worse
+    /// than the one given by the diff.
+    #[serde(default, skip_serializing_if = "::core::ops::Not::not")]
+    pub partial_fill: bool,
 }
 
 impl ExecutableIntent for TokenDiff {
@@ -55,10 +82,20 @@ impl ExecutableIntent for TokenDiff {
         S: State,
         I: Inspector,
     {
-        if self.diff.is_empty() {
+        // partially fillable diffs are filled by the engine only after
+        // the rest of the batch, see `Engine::execute_signed_intents()`
+        if self.diff.is_empty() || self.partial_fill {
             return Err(DefuseError::InvalidIntent);
         }
 
+        if let Some(referral) = &self.referral {
+            if !engine.state.is_referral_allowed(referral) {
+                return Err(DefuseError::UnknownReferral(referral.clone()));
+            }
+        }
+
+        engine.on_token_diff(signer_id, &self, intent_hash);
+
         let protocol_fee = engine.state.fee();
         let mut fees_collected: Amounts = Amounts::default();
 
@@ -75,7 +112,13 @@ impl ExecutableIntent for TokenDiff {
             // take fees only from negative deltas (i.e. token_in)
             if *delta < 0 {
                 let amount = delta.unsigned_abs();
-                let fee = Self::token_fee(token_id, amount, protocol_fee).fee_ceil(amount);
+                let token_fee = Self::token_fee(token_id, amount, protocol_fee);
+                if self.max_fee.is_some_and(|max_fee| token_fee > max_fee) {
+                    return Err(DefuseError::MaxFeeExceeded);
+                }
+                let fee = token_fee
+                    .checked_fee_ceil(amount)
+                    .ok_or(DefuseError::FeeOverflow)?;
 
                 // collect fee
                 fees_collected
@@ -101,9 +144,11 @@ impl ExecutableIntent for TokenDiff {
 
         // deposit fees to collector
         if !fees_collected.is_empty() {
-            engine
-                .state
-                .internal_add_balance(engine.state.fee_collector().into_owned(), fees_collected)?;
+            engine.state.internal_add_balance(
+                engine.state.fee_collector().into_owned(),
+                fees_collected.clone(),
+            )?;
+            engine.state.add_collected_fees(fees_collected)?;
         }
 
         Ok(())
@@ -179,7 +224,7 @@ impl TokenDiff {
 
     /// Returns total supply delta from token delta
     #[inline]
-    fn supply_delta(token_id: &TokenId, delta: i128, fee: Pips) -> Option<i128> {
+    pub(crate) fn supply_delta(token_id: &TokenId, delta: i128, fee: Pips) -> Option<i128> {
         if delta < 0 {
             // fee is taken only on negative deltas (i.e. token_in)
             delta.checked_mul_div_ceil(
@@ -216,6 +261,79 @@ impl TokenDiff {
         }
     }
 
+    /// Returns delta on `token_out` the user should request in
+    /// [`TokenDiff`] when giving `amount_in` of `token_in`, assuming the
+    /// counterparty fills the order at parity, i.e. gives as much of
+    /// `token_out` as it receives of `token_in`, and given `fee` is
+    /// charged on both legs.
+    #[inline]
+    pub fn quote(
+        token_in: &TokenId,
+        amount_in: u128,
+        token_out: &TokenId,
+        fee: Pips,
+    ) -> Option<i128> {
+        let delta_in = i128::try_from(amount_in).ok()?.checked_neg()?;
+        // amount of token_in the counterparty receives
+        let counterparty_in = Self::closure_delta(token_in, delta_in, fee)?;
+        Self::closure_delta(token_out, counterparty_in.checked_neg()?, fee)
+    }
+
+    /// Returns `(token_in, amount_in)` and `(token_out, amount_out)` of
+    /// a diff on exactly two tokens, i.e. which can be filled partially
+    #[inline]
+    pub fn partial_fill_legs(&self) -> Option<(PartialFillLeg<'_>, PartialFillLeg<'_>)> {
+        let mut deltas = self.diff.iter();
+        let (Some(first), Some(second), None) = (deltas.next(), deltas.next(), deltas.next())
+        else {
+            return None;
+        };
+        let ((token_in, delta_in), (token_out, delta_out)) = if first.1.is_negative() {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        if !delta_in.is_negative() || *delta_out <= 0 {
+            return None;
+        }
+        Some((
+            (token_in, delta_in.unsigned_abs()),
+            (token_out, delta_out.unsigned_abs()),
+        ))
+    }
+
+    /// Returns deltas on `token_in` and `token_out` that close given
+    /// unmatched (i.e. total supply) deltas on them assuming given
+    /// `fee`, or `None` if it's not possible without exceeding the
+    /// amount of `token_in` left after `filled` so far or the price
+    /// given by the diff.
+    pub fn partial_fill(
+        &self,
+        filled: u128,
+        unmatched_in: i128,
+        unmatched_out: i128,
+        fee: Pips,
+    ) -> Option<(i128, i128)> {
+        let ((token_in, amount_in), (token_out, amount_out)) = self.partial_fill_legs()?;
+
+        let fill_in = Self::closure_supply_delta(token_in, unmatched_in, fee)?;
+        let fill_out = Self::closure_supply_delta(token_out, unmatched_out, fee)?;
+        if !fill_in.is_negative() || fill_out <= 0 {
+            return None;
+        }
+
+        let fill_amount_in = fill_in.unsigned_abs();
+        if filled.checked_add(fill_amount_in)? > amount_in
+            // price can only be better for the signer
+            || fill_out.unsigned_abs()
+                < fill_amount_in.checked_mul_div_ceil(amount_out, amount_in)?
+        {
+            return None;
+        }
+
+        Some((fill_in, fill_out))
+    }
+
     #[inline]
     pub fn token_fee(token_id: impl Into<TokenIdType>, amount: u128, fee: Pips) -> Pips {
         let token_id = token_id.into();
@@ -228,6 +346,45 @@ impl TokenDiff {
         }
         fee
     }
+
+    /// Returns whether `counter` trades exactly the same tokens as
+    /// `diff` in the opposite direction, so that it can be its
+    /// counter-intent
+    pub fn is_offset_by(diff: &TokenDeltas, counter: &TokenDeltas) -> bool {
+        diff.len() == counter.len()
+            && diff
+                .iter()
+                .all(|(token_id, delta)| counter.amount_for(token_id).signum() == -delta.signum())
+    }
+
+    /// Nets deltas of given [`TokenDiff`] intents by their signers the
+    /// same way as the engine does, but without executing them, and
+    /// returns the same [`InvariantViolated`] the execution would fail
+    /// with. Fees are assumed to be deposited to `fee_collector`.
+    pub fn check_closure(
+        diffs: impl IntoIterator<Item = (AccountId, Self)>,
+        fee: Pips,
+        fee_collector: &AccountIdRef,
+    ) -> Result<(), InvariantViolated> {
+        let mut matcher = TransferMatcher::new();
+        for (signer_id, diff) in diffs {
+            for (token_id, delta) in diff.diff.into_inner() {
+                if !matcher.add_delta(signer_id.clone(), token_id.clone(), delta) {
+                    return Err(InvariantViolated::Overflow);
+                }
+                if delta < 0 {
+                    let amount = delta.unsigned_abs();
+                    let fee = Self::token_fee(&token_id, amount, fee)
+                        .checked_fee_ceil(amount)
+                        .ok_or(InvariantViolated::Overflow)?;
+                    if fee > 0 && !matcher.deposit(fee_collector.to_owned(), token_id, fee) {
+                        return Err(InvariantViolated::Overflow);
+                    }
+                }
+            }
+        }
+        matcher.finalize().map(|_| ())
+    }
 }
 
 #[cfg(test)]
@@ -273,6 +430,241 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[test]
+    fn closure_delta_extreme(
+        #[values(
+            i128::MIN,
+            i128::MIN + 1,
+            i128::MIN / 2,
+            i128::MAX / 2,
+            i128::MAX - 1,
+            i128::MAX
+        )]
+        delta: i128,
+        #[values(Pips::ZERO, Pips::ONE_PIP, Pips::ONE_PERCENT, Pips::ONE_PERCENT * 50)] fee: Pips,
+    ) {
+        let token_id: TokenId = Nep141TokenId::new("ft.near".parse().unwrap()).into();
+
+        // must never panic: either a valid closure or None if infeasible
+        let Some(closure) = TokenDiff::closure_delta(&token_id, delta, fee) else {
+            return;
+        };
+        assert_eq!(
+            TokenDiff::supply_delta(&token_id, delta, fee).unwrap()
+                + TokenDiff::supply_delta(&token_id, closure, fee).unwrap(),
+            0,
+            "invariant violated: delta: {delta}, closure: {closure}, fee: {fee}",
+        );
+    }
+
+    #[test]
+    fn closure_delta_infeasible() {
+        let token_id: TokenId = Nep141TokenId::new("ft.near".parse().unwrap()).into();
+
+        // negation of i128::MIN does not fit into i128
+        assert_eq!(
+            TokenDiff::closure_delta(&token_id, i128::MIN, Pips::ZERO),
+            None
+        );
+        // closure grossed up by fee exceeds i128::MAX
+        assert_eq!(
+            TokenDiff::closure_delta(&token_id, i128::MAX, Pips::ONE_PERCENT),
+            None
+        );
+        // supply delta of i128::MIN is still representable
+        assert!(TokenDiff::supply_delta(&token_id, i128::MIN, Pips::ONE_PERCENT).is_some());
+    }
+
+    #[rstest]
+    #[test]
+    fn check_closure(#[values(Pips::ZERO, Pips::ONE_BIP, Pips::ONE_PERCENT)] fee: Pips) {
+        let [ft1, ft2]: [TokenId; 2] =
+            ["ft1.near", "ft2.near"].map(|ft| Nep141TokenId::new(ft.parse().unwrap()).into());
+        let [user1, user2, fee_collector]: [AccountId; 3] =
+            ["user1.near", "user2.near", "fees.near"].map(|a| a.parse().unwrap());
+
+        let diff = |deltas: [(&TokenId, i128); 2]| TokenDiff {
+            diff: TokenDeltas::default()
+                .with_apply_deltas(deltas.map(|(token_id, delta)| (token_id.clone(), delta)))
+                .unwrap(),
+            ..Default::default()
+        };
+        let closure = |token_id, delta| TokenDiff::closure_delta(token_id, delta, fee).unwrap();
+
+        let balanced = [
+            (user1.clone(), diff([(&ft1, -1000), (&ft2, 2000)])),
+            (
+                user2.clone(),
+                diff([(&ft1, closure(&ft1, -1000)), (&ft2, closure(&ft2, 2000))]),
+            ),
+        ];
+        TokenDiff::check_closure(balanced, fee, &fee_collector).unwrap();
+
+        let imbalanced = [
+            (user1, diff([(&ft1, -1000), (&ft2, 2000)])),
+            (
+                user2,
+                diff([
+                    (&ft1, closure(&ft1, -1000) + 1),
+                    (&ft2, closure(&ft2, 2000)),
+                ]),
+            ),
+        ];
+        assert_eq!(
+            TokenDiff::check_closure(imbalanced, fee, &fee_collector)
+                .unwrap_err()
+                .into_unmatched_deltas(),
+            Some(TokenDeltas::new([(ft1, 1)].into())),
+        );
+    }
+
+    #[rstest]
+    #[test]
+    fn quote(
+        #[values(1, 1_000, 1_000_000, i128::MAX.unsigned_abs())] amount_in: u128,
+        #[values(Pips::ZERO, Pips::ONE_PIP, Pips::ONE_PERCENT, Pips::ONE_PERCENT * 50)] fee: Pips,
+    ) {
+        let token_in: TokenId = Nep141TokenId::new("ft1.near".parse().unwrap()).into();
+        let token_out: TokenId = Nep141TokenId::new("ft2.near".parse().unwrap()).into();
+
+        let delta_in = -i128::try_from(amount_in).unwrap();
+        let counterparty_in = TokenDiff::closure_delta(&token_in, delta_in, fee).unwrap();
+        let user_out = TokenDiff::quote(&token_in, amount_in, &token_out, fee).unwrap();
+
+        // both legs balance out when the counterparty fills at parity
+        assert_eq!(
+            TokenDiff::supply_delta(&token_out, -counterparty_in, fee).unwrap()
+                + TokenDiff::supply_delta(&token_out, user_out, fee).unwrap(),
+            0,
+        );
+        assert!(user_out <= i128::try_from(amount_in).unwrap());
+        if fee.is_zero() {
+            assert_eq!(user_out.unsigned_abs(), amount_in);
+        }
+    }
+
+    #[test]
+    fn quote_nft() {
+        let nft: TokenId = Nep171TokenId::new("nft.near".parse().unwrap(), "1".to_string())
+            .unwrap()
+            .into();
+        let ft: TokenId = Nep141TokenId::new("ft.near".parse().unwrap()).into();
+
+        // no fees are taken on NFTs
+        assert_eq!(TokenDiff::quote(&nft, 1, &nft, Pips::ONE_PERCENT), Some(1));
+        assert_eq!(
+            TokenDiff::quote(&ft, 1_000_000, &nft, Pips::ONE_PERCENT),
+            Some(990_000)
+        );
+        assert_eq!(TokenDiff::quote(&ft, u128::MAX, &nft, Pips::ZERO), None);
+    }
+
+    fn limit_order(amount_in: u128, amount_out: u128) -> TokenDiff {
+        TokenDiff {
+            diff: TokenDeltas::default()
+                .with_apply_deltas([
+                    (
+                        Nep141TokenId::new("ft1.near".parse().unwrap()).into(),
+                        -i128::try_from(amount_in).unwrap(),
+                    ),
+                    (
+                        Nep141TokenId::new("ft2.near".parse().unwrap()).into(),
+                        i128::try_from(amount_out).unwrap(),
+                    ),
+                ])
+                .unwrap(),
+            partial_fill: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn partial_fill_legs() {
+        let order = limit_order(1000, 2000);
+        let ((token_in, amount_in), (token_out, amount_out)) = order.partial_fill_legs().unwrap();
+        assert_eq!(token_in.to_string(), "nep141:ft1.near");
+        assert_eq!(amount_in, 1000);
+        assert_eq!(token_out.to_string(), "nep141:ft2.near");
+        assert_eq!(amount_out, 2000);
+
+        let ft3: TokenId = Nep141TokenId::new("ft3.near".parse().unwrap()).into();
+        let mut three_tokens = limit_order(1000, 2000);
+        three_tokens.diff.apply_delta(ft3.clone(), 100).unwrap();
+        assert!(three_tokens.partial_fill_legs().is_none());
+
+        let mut both_out = limit_order(1000, 2000);
+        both_out.diff = TokenDeltas::default()
+            .with_apply_deltas([
+                (Nep141TokenId::new("ft1.near".parse().unwrap()).into(), 1000),
+                (ft3, 2000),
+            ])
+            .unwrap();
+        assert!(both_out.partial_fill_legs().is_none());
+    }
+
+    #[rstest]
+    #[test]
+    fn partial_fill(
+        #[values(Pips::ZERO, Pips::ONE_BIP, Pips::ONE_PERCENT)] fee: Pips,
+        #[values(0, 500)] filled: u128,
+    ) {
+        let order = limit_order(1000, 2000);
+        let ((token_in, _), (token_out, _)) = order.partial_fill_legs().unwrap();
+
+        // counterparty matches 300 of token_in for 600 of token_out
+        let counter_in = TokenDiff::closure_delta(token_in, -300, fee).unwrap();
+        let unmatched_in = TokenDiff::supply_delta(token_in, counter_in, fee).unwrap();
+        let counter_out = |amount_out| {
+            let delta = TokenDiff::closure_delta(token_out, amount_out, fee).unwrap();
+            TokenDiff::supply_delta(token_out, delta, fee).unwrap()
+        };
+
+        assert_eq!(
+            order.partial_fill(filled, unmatched_in, counter_out(600), fee),
+            Some((-300, 600))
+        );
+        // better price for the signer
+        assert_eq!(
+            order.partial_fill(filled, unmatched_in, counter_out(700), fee),
+            Some((-300, 700))
+        );
+        // worse price
+        assert_eq!(
+            order.partial_fill(filled, unmatched_in, counter_out(599), fee),
+            None
+        );
+    }
+
+    #[test]
+    fn partial_fill_exceeds_remaining() {
+        let order = limit_order(1000, 1000);
+
+        assert_eq!(
+            order.partial_fill(0, 1000, -1000, Pips::ZERO),
+            Some((-1000, 1000))
+        );
+        assert_eq!(order.partial_fill(1, 1000, -1000, Pips::ZERO), None);
+        assert_eq!(order.partial_fill(0, 1001, -1001, Pips::ZERO), None);
+        // nothing to fill
+        assert_eq!(order.partial_fill(0, 0, 0, Pips::ZERO), None);
+        // wrong direction
+        assert_eq!(order.partial_fill(0, -100, 100, Pips::ZERO), None);
+    }
+
+    #[test]
+    fn closure_deltas_overflow() {
+        let token_id: TokenId = Nep141TokenId::new("ft.near".parse().unwrap()).into();
+
+        assert_eq!(
+            TokenDiff::closure_deltas(
+                [(token_id.clone(), i128::MAX), (token_id, i128::MAX)],
+                Pips::ONE_PERCENT,
+            ),
+            None
+        );
+    }
+
     #[test]
     fn closure_deltas_empty() {
         assert!(
@@ -355,4 +747,36 @@ mod tests {
         assert!(!closure.is_empty());
         assert!(closure.into_inner().into_values().all(i128::is_negative));
     }
+
+    #[test]
+    fn is_offset_by() {
+        let [t1, t2, t3] =
+            ["ft1", "ft2", "ft3"].map(|t| TokenId::from(Nep141TokenId::new(t.parse().unwrap())));
+        let deltas = |deltas: &[(&TokenId, i128)]| {
+            TokenDeltas::default()
+                .with_apply_deltas(deltas.iter().map(|(t, d)| ((*t).clone(), *d)))
+                .unwrap()
+        };
+
+        let diff = deltas(&[(&t1, -100), (&t2, 200)]);
+        assert!(TokenDiff::is_offset_by(
+            &diff,
+            &deltas(&[(&t1, 99), (&t2, -200)])
+        ));
+        // same direction
+        assert!(!TokenDiff::is_offset_by(
+            &diff,
+            &deltas(&[(&t1, -100), (&t2, 200)])
+        ));
+        // unrelated tokens
+        assert!(!TokenDiff::is_offset_by(
+            &diff,
+            &deltas(&[(&t2, -200), (&t3, 300)])
+        ));
+        // extra token
+        assert!(!TokenDiff::is_offset_by(
+            &diff,
+            &deltas(&[(&t1, 100), (&t2, -200), (&t3, 1)])
+        ));
+    }
 }
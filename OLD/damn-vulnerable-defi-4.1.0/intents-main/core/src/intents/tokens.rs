@@ -1,15 +1,20 @@
 use std::{borrow::Cow, collections::BTreeMap};
 
+use near_account_id::AccountType;
 use near_contract_standards::non_fungible_token;
-use near_sdk::{AccountId, AccountIdRef, CryptoHash, Gas, NearToken, json_types::U128, near};
+use near_sdk::{
+    AccountId, AccountIdRef, CryptoHash, Gas, NearToken, json_types::U128, near, serde::Serialize,
+    serde_json,
+};
 use serde_with::{DisplayFromStr, serde_as};
 
 use crate::{
     DefuseError, Result,
     accounts::{AccountEvent, TransferEvent},
     amounts::Amounts,
-    engine::{Engine, Inspector, State},
+    engine::{Engine, Inspector, State, StateView},
     events::DefuseEvent,
+    token_id::TokenId,
 };
 
 use super::{ExecutableIntent, IntentEvent};
@@ -43,8 +48,14 @@ impl NotifyOnTransfer {
     pub const MT_ON_TRANSFER_GAS_DEFAULT: Gas = Gas::from_tgas(30);
 
     pub fn min_gas(&self) -> Gas {
+        self.min_gas_or(Self::MT_ON_TRANSFER_GAS_DEFAULT)
+    }
+
+    /// Same as [`Self::min_gas`], but falls back to given `default`
+    /// if `min_gas` is not specified
+    pub fn min_gas_or(&self, default: Gas) -> Gas {
         self.min_gas
-            .unwrap_or(Self::MT_ON_TRANSFER_GAS_DEFAULT)
+            .unwrap_or(default)
             .max(Self::MT_ON_TRANSFER_GAS_MIN)
     }
 
@@ -52,11 +63,56 @@ impl NotifyOnTransfer {
         Self { msg, min_gas: None }
     }
 
+    /// Creates notification with `msg` serialized from given value as JSON
+    pub fn json<T>(msg: &T) -> serde_json::Result<Self>
+    where
+        T: Serialize + ?Sized,
+    {
+        serde_json::to_string(msg).map(Self::new)
+    }
+
     #[must_use]
     pub const fn with_min_gas(mut self, min_gas: Gas) -> Self {
         self.min_gas = Some(min_gas);
         self
     }
+
+    /// Same as [`Self::with_min_gas`], but returns `None` if `min_gas`
+    /// is below [`Self::MT_ON_TRANSFER_GAS_MIN`] instead of silently
+    /// raising it at execution
+    #[must_use]
+    pub fn checked_with_min_gas(self, min_gas: Gas) -> Option<Self> {
+        (min_gas >= Self::MT_ON_TRANSFER_GAS_MIN).then(|| self.with_min_gas(min_gas))
+    }
+}
+
+/// How the receiver handled `mt_on_transfer` notification
+#[near(serializers = [json])]
+#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationOutcome {
+    /// Receiver kept all transferred tokens
+    Accepted,
+    /// Receiver asked to refund some of transferred tokens
+    Refunded,
+    /// Receiver asked to refund more tokens than were transferred,
+    /// so only transferred amounts were refunded
+    OverRefunded,
+    /// `mt_on_transfer` failed, e.g. panicked or returned a value over
+    /// the runtime limit, so transferred tokens were refunded in full
+    Failed,
+    /// `mt_on_transfer` returned something other than refunds for each
+    /// of transferred tokens, so they were refunded in full
+    MalformedRefunds,
+}
+
+/// Emitted once receiver's `mt_on_transfer` is resolved
+#[must_use = "make sure to `.emit()` this event"]
+#[near(serializers = [json])]
+#[derive(Debug, Clone)]
+pub struct NotificationResolvedEvent<'a> {
+    pub receiver_id: Cow<'a, AccountIdRef>,
+    pub outcome: NotificationOutcome,
 }
 
 #[cfg_attr(
@@ -94,9 +150,18 @@ impl ExecutableIntent for Transfer {
         S: State,
         I: Inspector,
     {
-        if sender_id == self.receiver_id || self.tokens.is_empty() {
+        if sender_id == self.receiver_id {
+            return Err(DefuseError::SelfTransfer);
+        }
+        if self.tokens.is_empty() {
             return Err(DefuseError::InvalidIntent);
         }
+        for (token_id, &amount) in self.tokens.iter() {
+            let min_amount = engine.state.min_transfer_amount(token_id);
+            if amount < min_amount {
+                return Err(DefuseError::AmountTooSmall(token_id.clone(), min_amount));
+            }
+        }
 
         engine
             .inspector
@@ -132,6 +197,164 @@ impl ExecutableIntent for Transfer {
     }
 }
 
+#[cfg_attr(
+    all(feature = "abi", not(target_arch = "wasm32")),
+    serde_as(schemars = true)
+)]
+#[cfg_attr(
+    not(all(feature = "abi", not(target_arch = "wasm32"))),
+    serde_as(schemars = false)
+)]
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+/// Donate a set of tokens from the signer to the `fee_collector`
+/// configured in the contract, e.g. to voluntarily contribute to the
+/// protocol treasury.
+pub struct Donate {
+    #[serde_as(as = "Amounts<BTreeMap<_, DisplayFromStr>>")]
+    pub tokens: Amounts,
+}
+
+impl ExecutableIntent for Donate {
+    fn execute_intent<S, I>(
+        self,
+        sender_id: &AccountIdRef,
+        engine: &mut Engine<S, I>,
+        intent_hash: CryptoHash,
+    ) -> Result<()>
+    where
+        S: State,
+        I: Inspector,
+    {
+        let fee_collector = engine.state.fee_collector().into_owned();
+        if sender_id == fee_collector {
+            return Err(DefuseError::SelfTransfer);
+        }
+        if self.tokens.is_empty() {
+            return Err(DefuseError::InvalidIntent);
+        }
+
+        engine
+            .inspector
+            .on_event(DefuseEvent::Donation(Cow::Borrowed(
+                [IntentEvent::new(
+                    AccountEvent::new(
+                        sender_id,
+                        TransferEvent {
+                            receiver_id: Cow::Borrowed(&fee_collector),
+                            tokens: Cow::Borrowed(&self.tokens),
+                            memo: Cow::Owned(None),
+                        },
+                    ),
+                    intent_hash,
+                )]
+                .as_slice(),
+            )));
+
+        engine
+            .state
+            .internal_sub_balance(sender_id, self.tokens.clone())?;
+        engine
+            .state
+            .internal_add_balance(fee_collector, self.tokens)
+    }
+}
+
+#[cfg_attr(
+    all(feature = "abi", not(target_arch = "wasm32")),
+    serde_as(schemars = true)
+)]
+#[cfg_attr(
+    not(all(feature = "abi", not(target_arch = "wasm32"))),
+    serde_as(schemars = false)
+)]
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+/// Transfer a batch of tokens from the signer within the intents contract,
+/// where each token can go to its own receiver and carry its own
+/// notification. Each token is settled separately, i.e. it's transferred
+/// as a [`Transfer`] of its own, so that refunds from `mt_on_transfer()`
+/// are made per token.
+pub struct MtBatchTransfer {
+    pub receiver_id: AccountId,
+
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub token_ids: Vec<TokenId>,
+    pub amounts: Vec<U128>,
+
+    /// Optional receivers for corresponding `token_ids`, falling back to
+    /// `receiver_id` for `None` entries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receiver_ids: Option<Vec<Option<AccountId>>>,
+
+    /// Optional notifications for receivers of corresponding `token_ids`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notifications: Option<Vec<Option<NotifyOnTransfer>>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+impl MtBatchTransfer {
+    /// Splits into a [`Transfer`] per token.
+    ///
+    /// Returns `None` if the batch is empty or lengths of `amounts`,
+    /// `receiver_ids` and `notifications` differ from `token_ids`.
+    pub fn into_transfers(self) -> Option<Vec<Transfer>> {
+        let Self {
+            receiver_id,
+            token_ids,
+            amounts,
+            receiver_ids,
+            notifications,
+            memo,
+        } = self;
+
+        let receiver_ids = receiver_ids.unwrap_or_else(|| vec![None; token_ids.len()]);
+        let notifications = notifications.unwrap_or_else(|| vec![None; token_ids.len()]);
+        if token_ids.is_empty()
+            || amounts.len() != token_ids.len()
+            || receiver_ids.len() != token_ids.len()
+            || notifications.len() != token_ids.len()
+        {
+            return None;
+        }
+
+        Some(
+            token_ids
+                .into_iter()
+                .zip(amounts)
+                .zip(receiver_ids)
+                .zip(notifications)
+                .map(|(((token_id, amount), receiver), notification)| Transfer {
+                    receiver_id: receiver.unwrap_or_else(|| receiver_id.clone()),
+                    tokens: Amounts::new([(token_id, amount.0)].into()),
+                    memo: memo.clone(),
+                    notification,
+                })
+                .collect(),
+        )
+    }
+}
+
+impl ExecutableIntent for MtBatchTransfer {
+    fn execute_intent<S, I>(
+        self,
+        sender_id: &AccountIdRef,
+        engine: &mut Engine<S, I>,
+        intent_hash: CryptoHash,
+    ) -> Result<()>
+    where
+        S: State,
+        I: Inspector,
+    {
+        for transfer in self.into_transfers().ok_or(DefuseError::InvalidIntent)? {
+            transfer.execute_intent(sender_id, engine, intent_hash)?;
+        }
+        Ok(())
+    }
+}
+
 #[near(serializers = [borsh, json])]
 #[derive(Debug, Clone)]
 /// Withdraw given FT tokens from the intents contract to a given external account id (external being outside of intents).
@@ -154,6 +377,17 @@ pub struct FtWithdraw {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub storage_deposit: Option<NearToken>,
 
+    /// If `storage_deposit` is omitted, prepay
+    /// [`IMPLICIT_RECEIVER_STORAGE_DEPOSIT`](Self::IMPLICIT_RECEIVER_STORAGE_DEPOSIT)
+    /// when `receiver_id` is a NEAR implicit account, which might have
+    /// never been registered on `token` yet, as long as `wNEAR` balance
+    /// of the signer covers it before the intents are executed.
+    /// NOTE: it's resolved into `storage_deposit` before execution, so
+    /// it's not a part of borsh encoding
+    #[serde(default, skip_serializing_if = "::core::ops::Not::not")]
+    #[borsh(skip)]
+    pub auto_storage_deposit: bool,
+
     /// Optional minimum required Near gas for created Promise to succeed:
     /// * `ft_transfer`:      minimum: 15TGas, default: 15TGas
     /// * `ft_transfer_call`: minimum: 30TGas, default: 50TGas
@@ -165,6 +399,12 @@ pub struct FtWithdraw {
 }
 
 impl FtWithdraw {
+    /// `storage_deposit` prepaid for NEAR implicit receivers, i.e.
+    /// [storage balance bounds](https://github.com/near/near-sdk-rs/blob/985c16b8fffc623096d0b7e60b26746842a2d712/near-contract-standards/src/fungible_token/storage_impl.rs#L82)
+    /// of standard NEP-141 implementation
+    pub const IMPLICIT_RECEIVER_STORAGE_DEPOSIT: NearToken =
+        NearToken::from_yoctonear(1_250_000_000_000_000_000_000);
+
     const FT_TRANSFER_GAS_MIN: Gas = Gas::from_tgas(15);
     const FT_TRANSFER_GAS_DEFAULT: Gas = Gas::from_tgas(15);
 
@@ -199,6 +439,26 @@ impl FtWithdraw {
             // 2. We don't refund if `ft_transfer_call()` Promise fails
             .max(min)
     }
+
+    /// Resolves [`auto_storage_deposit`](Self::auto_storage_deposit) given
+    /// `wnear_balance` of the owner, which is then reduced by `wNEAR`
+    /// spent on this withdrawal
+    pub fn resolve_storage_deposit(&mut self, wnear_id: &AccountIdRef, wnear_balance: &mut u128) {
+        // withdrawn wNEAR can't pay for the storage at the same time
+        if self.token == *wnear_id {
+            *wnear_balance = wnear_balance.saturating_sub(self.amount.0);
+        }
+        if self.auto_storage_deposit
+            && self.storage_deposit.is_none()
+            && self.receiver_id.get_account_type() == AccountType::NearImplicitAccount
+            && *wnear_balance >= Self::IMPLICIT_RECEIVER_STORAGE_DEPOSIT.as_yoctonear()
+        {
+            self.storage_deposit = Some(Self::IMPLICIT_RECEIVER_STORAGE_DEPOSIT);
+        }
+        if let Some(amount) = self.storage_deposit {
+            *wnear_balance = wnear_balance.saturating_sub(amount.as_yoctonear());
+        }
+    }
 }
 
 impl ExecutableIntent for FtWithdraw {
@@ -213,17 +473,28 @@ impl ExecutableIntent for FtWithdraw {
         S: State,
         I: Inspector,
     {
+        // tokens withdrawn to this contract would end up either stuck
+        // or deposited back
+        if self.receiver_id == *engine.state.verifying_contract() {
+            return Err(DefuseError::SelfTransfer);
+        }
+
+        let mut withdraw = self;
+        withdraw.min_gas = withdraw
+            .min_gas
+            .or_else(|| engine.state.default_min_gas(owner_id));
+
         engine
             .inspector
             .on_event(DefuseEvent::FtWithdraw(Cow::Borrowed(
                 [IntentEvent::new(
-                    AccountEvent::new(owner_id, Cow::Borrowed(&self)),
+                    AccountEvent::new(owner_id, Cow::Borrowed(&withdraw)),
                     intent_hash,
                 )]
                 .as_slice(),
             )));
 
-        engine.state.ft_withdraw(owner_id, self)
+        engine.state.ft_withdraw(owner_id, withdraw)
     }
 }
 
@@ -299,7 +570,7 @@ impl NftWithdraw {
 impl ExecutableIntent for NftWithdraw {
     #[inline]
     fn execute_intent<S, I>(
-        self,
+        mut self,
         owner_id: &AccountIdRef,
         engine: &mut Engine<S, I>,
         intent_hash: CryptoHash,
@@ -308,6 +579,10 @@ impl ExecutableIntent for NftWithdraw {
         S: State,
         I: Inspector,
     {
+        self.min_gas = self
+            .min_gas
+            .or_else(|| engine.state.default_min_gas(owner_id));
+
         engine
             .inspector
             .on_event(DefuseEvent::NftWithdraw(Cow::Borrowed(
@@ -333,6 +608,13 @@ pub struct MtWithdraw {
     pub receiver_id: AccountId,
     pub token_ids: Vec<defuse_nep245::TokenId>,
     pub amounts: Vec<U128>,
+
+    /// Optional receivers for corresponding `token_ids`, falling back to
+    /// `receiver_id` for `None` entries. Tokens are transferred to each
+    /// distinct receiver in a separate batch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receiver_ids: Option<Vec<Option<AccountId>>>,
+
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub memo: Option<String>,
 
@@ -395,12 +677,82 @@ impl MtWithdraw {
             // 2. We don't refund if `mt_batch_transfer_call()` Promise fails
             .max(min)
     }
+
+    /// Splits into withdrawals to distinct receivers in order of their
+    /// first occurrence, along with positions of their tokens in
+    /// original `token_ids`. `storage_deposit` is made only for
+    /// `receiver_id`.
+    ///
+    /// Returns `None` if lengths of `receiver_ids` and `token_ids` differ
+    /// or `storage_deposit` is given, but no tokens go to `receiver_id`.
+    pub fn split_by_receiver(self) -> Option<Vec<(Self, Vec<usize>)>> {
+        let Self {
+            token,
+            receiver_id,
+            token_ids,
+            amounts,
+            receiver_ids,
+            memo,
+            msg,
+            storage_deposit,
+            min_gas,
+        } = self;
+
+        let receiver_ids = receiver_ids.unwrap_or_else(|| vec![None; token_ids.len()]);
+        if receiver_ids.len() != token_ids.len() || token_ids.len() != amounts.len() {
+            return None;
+        }
+
+        let mut withdrawals: Vec<(Self, Vec<usize>)> = Vec::new();
+        for (position, ((receiver, token_id), amount)) in receiver_ids
+            .into_iter()
+            .zip(token_ids)
+            .zip(amounts)
+            .enumerate()
+        {
+            let receiver = receiver.unwrap_or_else(|| receiver_id.clone());
+            let index = withdrawals
+                .iter()
+                .position(|(w, _)| w.receiver_id == receiver)
+                .unwrap_or_else(|| {
+                    withdrawals.push((
+                        Self {
+                            token: token.clone(),
+                            storage_deposit: storage_deposit.filter(|_| receiver == receiver_id),
+                            receiver_id: receiver,
+                            token_ids: Vec::new(),
+                            amounts: Vec::new(),
+                            receiver_ids: None,
+                            memo: memo.clone(),
+                            msg: msg.clone(),
+                            min_gas,
+                        },
+                        Vec::new(),
+                    ));
+                    withdrawals.len() - 1
+                });
+            let (withdraw, positions) = &mut withdrawals[index];
+            withdraw.token_ids.push(token_id);
+            withdraw.amounts.push(amount);
+            positions.push(position);
+        }
+
+        if storage_deposit.is_some()
+            && !withdrawals
+                .iter()
+                .any(|(w, _)| w.receiver_id == receiver_id)
+        {
+            return None;
+        }
+
+        Some(withdrawals)
+    }
 }
 
 impl ExecutableIntent for MtWithdraw {
     #[inline]
     fn execute_intent<S, I>(
-        self,
+        mut self,
         owner_id: &AccountIdRef,
         engine: &mut Engine<S, I>,
         intent_hash: CryptoHash,
@@ -409,6 +761,22 @@ impl ExecutableIntent for MtWithdraw {
         S: State,
         I: Inspector,
     {
+        let verifying_contract = engine.state.verifying_contract();
+        if self.receiver_id == *verifying_contract
+            || self
+                .receiver_ids
+                .iter()
+                .flatten()
+                .flatten()
+                .any(|receiver_id| *receiver_id == *verifying_contract)
+        {
+            return Err(DefuseError::SelfTransfer);
+        }
+
+        self.min_gas = self
+            .min_gas
+            .or_else(|| engine.state.default_min_gas(owner_id));
+
         engine
             .inspector
             .on_event(DefuseEvent::MtWithdraw(Cow::Borrowed(
@@ -509,3 +877,229 @@ impl ExecutableIntent for StorageDeposit {
         engine.state.storage_deposit(owner_id, self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use near_sdk::serde_json::json;
+
+    use super::*;
+
+    #[near(serializers = [json])]
+    struct Message {
+        receiver_id: AccountId,
+        amount: U128,
+    }
+
+    #[test]
+    fn notify_on_transfer_json() {
+        let notification = NotifyOnTransfer::json(&Message {
+            receiver_id: "user.near".parse().unwrap(),
+            amount: U128(100),
+        })
+        .unwrap();
+
+        assert_eq!(
+            notification.msg,
+            r#"{"receiver_id":"user.near","amount":"100"}"#
+        );
+        assert_eq!(notification.min_gas, None);
+
+        assert_eq!(
+            NotifyOnTransfer::json(&json!(["a", 1])).unwrap().msg,
+            r#"["a",1]"#
+        );
+    }
+
+    #[test]
+    fn mt_batch_transfer_into_transfers() {
+        let token_ids: Vec<TokenId> = ["nep141:ft1.near", "nep141:ft2.near"]
+            .into_iter()
+            .map(|token_id| token_id.parse().unwrap())
+            .collect();
+        let batch = MtBatchTransfer {
+            receiver_id: "alice.near".parse().unwrap(),
+            token_ids: token_ids.clone(),
+            amounts: vec![U128(100), U128(200)],
+            receiver_ids: Some(vec![None, Some("bob.near".parse().unwrap())]),
+            notifications: Some(vec![Some(NotifyOnTransfer::new("msg".to_string())), None]),
+            memo: None,
+        };
+
+        let transfers = batch.clone().into_transfers().unwrap();
+        assert_eq!(transfers.len(), 2);
+        assert_eq!(transfers[0].receiver_id, "alice.near");
+        assert_eq!(transfers[0].tokens.amount_for(&token_ids[0]), 100);
+        assert_eq!(
+            transfers[0].notification.as_ref().map(|n| n.msg.as_str()),
+            Some("msg")
+        );
+        assert_eq!(transfers[1].receiver_id, "bob.near");
+        assert_eq!(transfers[1].tokens.amount_for(&token_ids[1]), 200);
+        assert!(transfers[1].notification.is_none());
+
+        assert!(
+            MtBatchTransfer {
+                amounts: vec![U128(100)],
+                ..batch.clone()
+            }
+            .into_transfers()
+            .is_none()
+        );
+        assert!(
+            MtBatchTransfer {
+                token_ids: Vec::new(),
+                amounts: Vec::new(),
+                receiver_ids: None,
+                notifications: None,
+                ..batch
+            }
+            .into_transfers()
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn ft_withdraw_resolve_storage_deposit() {
+        let withdraw =
+            |token: &str, receiver_id: &str, storage_deposit, auto_storage_deposit| FtWithdraw {
+                token: token.parse().unwrap(),
+                receiver_id: receiver_id.parse().unwrap(),
+                amount: U128(100),
+                memo: None,
+                msg: None,
+                storage_deposit,
+                auto_storage_deposit,
+                min_gas: None,
+            };
+        let resolve = |mut withdraw: FtWithdraw, mut wnear_balance| {
+            withdraw.resolve_storage_deposit(
+                AccountIdRef::new_or_panic("wnear.near"),
+                &mut wnear_balance,
+            );
+            (withdraw.storage_deposit, wnear_balance)
+        };
+        let implicit = "0".repeat(64);
+        let deposit = FtWithdraw::IMPLICIT_RECEIVER_STORAGE_DEPOSIT;
+        let enough = deposit.as_yoctonear();
+
+        assert_eq!(
+            resolve(withdraw("ft.near", &implicit, None, true), enough),
+            (Some(deposit), 0),
+        );
+        // not enough wNEAR
+        assert_eq!(
+            resolve(withdraw("ft.near", &implicit, None, true), enough - 1),
+            (None, enough - 1),
+        );
+        // withdrawn wNEAR doesn't count towards the balance
+        assert_eq!(
+            resolve(withdraw("wnear.near", &implicit, None, true), enough + 99),
+            (None, enough - 1),
+        );
+        // only opted in withdrawals are affected
+        assert_eq!(
+            resolve(withdraw("ft.near", &implicit, None, false), enough),
+            (None, enough),
+        );
+        // explicit storage_deposit is kept
+        assert_eq!(
+            resolve(
+                withdraw(
+                    "ft.near",
+                    &implicit,
+                    Some(NearToken::from_yoctonear(1)),
+                    true
+                ),
+                enough
+            ),
+            (Some(NearToken::from_yoctonear(1)), enough - 1),
+        );
+        // named accounts are not affected
+        assert_eq!(
+            resolve(withdraw("ft.near", "user.near", None, true), enough),
+            (None, enough),
+        );
+    }
+
+    #[test]
+    fn ft_withdraw_borsh_layout_is_unchanged() {
+        /// Layout of [`FtWithdraw`] before `auto_storage_deposit`
+        #[derive(near_sdk::borsh::BorshSerialize)]
+        #[borsh(crate = "::near_sdk::borsh")]
+        struct LegacyFtWithdraw {
+            token: AccountId,
+            receiver_id: AccountId,
+            amount: U128,
+            memo: Option<String>,
+            msg: Option<String>,
+            storage_deposit: Option<NearToken>,
+            min_gas: Option<Gas>,
+        }
+
+        let legacy = near_sdk::borsh::to_vec(&LegacyFtWithdraw {
+            token: "ft.near".parse().unwrap(),
+            receiver_id: "user.near".parse().unwrap(),
+            amount: U128(100),
+            memo: Some("memo".to_string()),
+            msg: None,
+            storage_deposit: Some(NearToken::from_yoctonear(1)),
+            min_gas: Some(Gas::from_tgas(20)),
+        })
+        .unwrap();
+
+        let withdraw: FtWithdraw = near_sdk::borsh::from_slice(&legacy).unwrap();
+        assert_eq!(withdraw.storage_deposit, Some(NearToken::from_yoctonear(1)));
+        assert!(!withdraw.auto_storage_deposit);
+        assert_eq!(near_sdk::borsh::to_vec(&withdraw).unwrap(), legacy);
+    }
+
+    #[test]
+    fn ft_withdraw_auto_storage_deposit_json() {
+        let withdraw: FtWithdraw = serde_json::from_value(serde_json::json!({
+            "token": "ft.near",
+            "receiver_id": "user.near",
+            "amount": "100",
+        }))
+        .unwrap();
+        assert!(!withdraw.auto_storage_deposit);
+        assert!(
+            !serde_json::to_string(&withdraw)
+                .unwrap()
+                .contains("auto_storage_deposit")
+        );
+
+        let withdraw: FtWithdraw = serde_json::from_value(serde_json::json!({
+            "token": "ft.near",
+            "receiver_id": "user.near",
+            "amount": "100",
+            "auto_storage_deposit": true,
+        }))
+        .unwrap();
+        assert!(withdraw.auto_storage_deposit);
+    }
+
+    #[test]
+    fn notify_on_transfer_checked_min_gas() {
+        let notification = NotifyOnTransfer::json("msg").unwrap();
+        assert_eq!(notification.msg, r#""msg""#);
+
+        assert!(
+            notification
+                .clone()
+                .checked_with_min_gas(Gas::from_tgas(4))
+                .is_none()
+        );
+
+        let notification = notification
+            .checked_with_min_gas(NotifyOnTransfer::MT_ON_TRANSFER_GAS_MIN)
+            .unwrap();
+        assert_eq!(
+            notification.min_gas,
+            Some(NotifyOnTransfer::MT_ON_TRANSFER_GAS_MIN)
+        );
+        assert_eq!(
+            notification.min_gas(),
+            NotifyOnTransfer::MT_ON_TRANSFER_GAS_MIN
+        );
+    }
+}
@@ -8,6 +8,7 @@ pub mod fees;
 pub mod intents;
 mod nonce;
 pub mod payload;
+pub mod rfq;
 
 pub use self::{deadline::*, error::*, nonce::*};
 
@@ -18,3 +19,4 @@ pub use defuse_sep53 as sep53;
 pub use defuse_tip191 as tip191;
 pub use defuse_token_id as token_id;
 pub use defuse_ton_connect as ton_connect;
+pub use defuse_webauthn as webauthn;
@@ -1,9 +1,11 @@
 mod expirable;
+mod namespaced;
 mod salted;
 mod versioned;
 
 pub use {
     expirable::ExpirableNonce,
+    namespaced::NamespacedNonce,
     salted::SaltedNonce,
     salted::{Salt, SaltRegistry},
     versioned::VersionedNonce,
@@ -18,6 +20,20 @@ use crate::{DefuseError, Result};
 pub type Nonce = U256;
 pub type NoncePrefix = U248;
 
+/// Extension methods for [`Nonce`]
+pub trait NonceExt {
+    /// Returns the [`Salt`] this nonce was created with,
+    /// or `None` for legacy (non-versioned) nonces.
+    fn salt(&self) -> Option<Salt>;
+}
+
+impl NonceExt for Nonce {
+    #[inline]
+    fn salt(&self) -> Option<Salt> {
+        VersionedNonce::maybe_from(*self).map(|versioned| versioned.salt())
+    }
+}
+
 /// See [permit2 nonce schema](https://docs.uniswap.org/contracts/permit2/reference/signature-transfer#nonce-schema)
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[near(serializers = [borsh, json])]
@@ -47,6 +63,12 @@ where
         Ok(())
     }
 
+    /// Returns whether any nonce with given prefix was committed
+    #[inline]
+    pub fn has_prefix(&self, prefix: NoncePrefix) -> bool {
+        self.0.contains_prefix(prefix)
+    }
+
     #[inline]
     pub fn cleanup_by_prefix(&mut self, prefix: NoncePrefix) -> bool {
         self.0.cleanup_by_prefix(prefix)
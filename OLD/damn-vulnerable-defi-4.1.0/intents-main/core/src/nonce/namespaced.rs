@@ -0,0 +1,23 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+
+/// Namespaced nonces contain a namespace byte, so that a wallet can
+/// issue nonces in independent lanes, e.g. one per concurrent relayer.
+/// Nonces in different namespaces never share a bitmap word.
+#[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+#[borsh(crate = "::near_sdk::borsh")]
+pub struct NamespacedNonce<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    pub namespace: u8,
+    pub nonce: T,
+}
+
+impl<T> NamespacedNonce<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    pub const fn new(namespace: u8, nonce: T) -> Self {
+        Self { namespace, nonce }
+    }
+}
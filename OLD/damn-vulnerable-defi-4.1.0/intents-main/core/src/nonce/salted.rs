@@ -1,16 +1,19 @@
 use core::mem;
+use defuse_near_utils::time;
 use hex::FromHex;
 use near_sdk::{
     IntoStorageKey,
     borsh::{BorshDeserialize, BorshSerialize},
     env::{self, sha256_array},
     near,
-    store::{IterableMap, key::Identity},
+    store::{IterableMap, LookupMap, key::Identity},
 };
 use serde_with::{DeserializeFromStr, SerializeDisplay};
 use std::{
+    collections::BTreeSet,
     fmt::{self, Debug},
     str::FromStr,
+    time::Duration,
 };
 
 use crate::{DefuseError, Result};
@@ -100,6 +103,9 @@ mod abi {
 pub struct SaltRegistry {
     previous: IterableMap<Salt, bool, Identity>,
     current: Salt,
+    /// Block timestamps in nanoseconds at which previous salts
+    /// were rotated
+    rotated_at: LookupMap<Salt, u64, Identity>,
 }
 
 impl SaltRegistry {
@@ -109,7 +115,10 @@ impl SaltRegistry {
     where
         S: IntoStorageKey,
     {
+        let prefix = prefix.into_storage_key();
+
         Self {
+            rotated_at: LookupMap::with_hasher([prefix.as_slice(), b"r"].concat()),
             previous: IterableMap::with_hasher(prefix),
             current: Salt::derive(0),
         }
@@ -129,6 +138,7 @@ impl SaltRegistry {
 
         let previous = mem::replace(&mut self.current, salt);
         self.previous.insert(previous, true);
+        self.rotated_at.insert(previous, time::block_timestamp());
 
         Ok(previous)
     }
@@ -146,11 +156,49 @@ impl SaltRegistry {
             .ok_or(DefuseError::InvalidSalt)
     }
 
+    /// Rotates the current salt and invalidates all of the previous
+    /// ones, so that only the new current salt remains valid.
+    /// Returns salts which were invalidated.
+    pub fn rotate_and_invalidate_all(&mut self) -> Result<BTreeSet<Salt>> {
+        self.set_new()?;
+
+        Ok(self
+            .previous
+            .iter_mut()
+            .filter(|(_, valid)| **valid)
+            .map(|(salt, valid)| {
+                *valid = false;
+                *salt
+            })
+            .collect())
+    }
+
     #[inline]
     pub fn is_valid(&self, salt: Salt) -> bool {
         salt == self.current || self.previous.get(&salt).is_some_and(|v| *v)
     }
 
+    /// Same as [`is_valid`](Self::is_valid), but previous salts also
+    /// become invalid once `grace_period` has passed since they were
+    /// rotated. Salts rotated before rotation timestamps were recorded
+    /// stay valid until explicitly invalidated.
+    #[inline]
+    pub fn is_valid_within(&self, salt: Salt, grace_period: Option<Duration>) -> bool {
+        if salt == self.current {
+            return true;
+        }
+        if !self.is_valid(salt) {
+            return false;
+        }
+        let Some(grace_period) = grace_period else {
+            return true;
+        };
+        self.rotated_at.get(&salt).is_none_or(|rotated_at| {
+            u128::from(time::block_timestamp().saturating_sub(*rotated_at))
+                < grace_period.as_nanos()
+        })
+    }
+
     #[inline]
     fn is_used(&self, salt: Salt) -> bool {
         salt == self.current || self.previous.contains_key(&salt)
@@ -262,6 +310,49 @@ mod tests {
         assert_eq!(salts.current(), new_salt);
     }
 
+    #[rstest]
+    fn grace_period_test(random_bytes: Vec<u8>, mut rng: impl Rng) {
+        const GRACE_PERIOD: Duration = Duration::from_secs(60);
+
+        let mut salts = SaltRegistry::new(random_bytes);
+
+        set_random_seed(&mut rng);
+        let previous_salt = salts.set_new().expect("should set new salt");
+        let current = salts.current();
+
+        testing_env!(
+            VMContextBuilder::new()
+                .block_timestamp(GRACE_PERIOD.as_nanos().try_into().unwrap())
+                .build()
+        );
+        assert!(salts.is_valid_within(previous_salt, None));
+        assert!(!salts.is_valid_within(previous_salt, Some(GRACE_PERIOD)));
+        assert!(salts.is_valid_within(previous_salt, Some(GRACE_PERIOD * 2)));
+        assert!(salts.is_valid_within(current, Some(Duration::ZERO)));
+
+        salts.invalidate(previous_salt).unwrap();
+        assert!(!salts.is_valid_within(previous_salt, Some(GRACE_PERIOD * 2)));
+    }
+
+    #[rstest]
+    fn rotate_and_invalidate_all_test(random_bytes: Vec<u8>, mut rng: impl Rng) {
+        let mut salts = SaltRegistry::new(random_bytes);
+
+        set_random_seed(&mut rng);
+        let first = salts.set_new().expect("should set new salt");
+        let second = salts.set_new().expect("should set new salt");
+        let third = salts.current();
+        salts.invalidate(first).unwrap();
+
+        let invalidated = salts.rotate_and_invalidate_all().unwrap();
+        assert_eq!(invalidated, [second, third].into());
+
+        assert!(salts.is_valid(salts.current()));
+        for salt in [first, second, third] {
+            assert!(!salts.is_valid(salt));
+        }
+    }
+
     #[rstest]
     fn derive_next_test(random_bytes: Vec<u8>) {
         let mut salt_registry = SaltRegistry::new(random_bytes);
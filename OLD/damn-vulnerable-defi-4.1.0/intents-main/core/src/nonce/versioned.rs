@@ -2,8 +2,12 @@ use hex_literal::hex;
 use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
 
 use crate::{
-    Nonce,
-    nonce::{expirable::ExpirableNonce, salted::SaltedNonce},
+    Deadline, DefuseError, Nonce, Result,
+    nonce::{
+        expirable::ExpirableNonce,
+        namespaced::NamespacedNonce,
+        salted::{Salt, SaltedNonce},
+    },
 };
 
 /// To distinguish between legacy nonces and versioned nonces
@@ -12,10 +16,12 @@ use crate::{
 ///     `VERSIONED_MAGIC_PREFIX (4 bytes) || VERSION (1 byte) || NONCE_BYTES (27 bytes)`
 /// Currently supported versions:
 ///     - V1: `SALT (4 bytes) || DEADLINE (8 bytes) || NONCE (15 random bytes)`
+///     - V2: `SALT (4 bytes) || NAMESPACE (1 byte) || DEADLINE (8 bytes) || NONCE (14 random bytes)`
 #[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
 #[borsh(crate = "::near_sdk::borsh")]
 pub enum VersionedNonce {
     V1(SaltedNonce<ExpirableNonce<[u8; 15]>>),
+    V2(SaltedNonce<NamespacedNonce<ExpirableNonce<[u8; 14]>>>),
 }
 
 // NOTE: Legacy nonces can still be used at this time, but will be prohibited out in the near future.
@@ -23,22 +29,96 @@ impl VersionedNonce {
     /// Magic prefixes (first 4 bytes of `sha256(<versioned_nonce>)`) used to mark versioned nonces:
     pub const VERSIONED_MAGIC_PREFIX: [u8; 4] = hex!("5628f6c6");
 
+    /// Size of serialized [`Self::V1`] including the version tag
+    pub const V1_SIZE: usize = 1 + size_of::<Salt>() + size_of::<i64>() + 15;
+
+    /// Size of serialized [`Self::V2`] including the version tag
+    pub const V2_SIZE: usize = 1 + size_of::<Salt>() + size_of::<u8>() + size_of::<i64>() + 14;
+
     pub fn maybe_from(n: Nonce) -> Option<Self> {
         let mut versioned = n.strip_prefix(&Self::VERSIONED_MAGIC_PREFIX)?;
         Self::deserialize_reader(&mut versioned).ok()
     }
+
+    /// Returns the salt this nonce was created with
+    #[inline]
+    pub const fn salt(&self) -> Salt {
+        match self {
+            Self::V1(SaltedNonce { salt, .. }) | Self::V2(SaltedNonce { salt, .. }) => *salt,
+        }
+    }
+
+    /// Returns the deadline after which this nonce expires
+    #[inline]
+    pub const fn deadline(&self) -> Deadline {
+        match self {
+            Self::V1(SaltedNonce {
+                nonce: ExpirableNonce { deadline, .. },
+                ..
+            })
+            | Self::V2(SaltedNonce {
+                nonce:
+                    NamespacedNonce {
+                        nonce: ExpirableNonce { deadline, .. },
+                        ..
+                    },
+                ..
+            }) => *deadline,
+        }
+    }
+
+    /// Returns the namespace this nonce was issued in, nonces prior
+    /// to V2 belong to the default namespace `0`
+    #[inline]
+    pub const fn namespace(&self) -> u8 {
+        match self {
+            Self::V1(_) => 0,
+            Self::V2(SaltedNonce {
+                nonce: NamespacedNonce { namespace, .. },
+                ..
+            }) => *namespace,
+        }
+    }
 }
 
-impl From<VersionedNonce> for Nonce {
-    fn from(value: VersionedNonce) -> Self {
-        const SIZE: usize = size_of::<Nonce>();
-        let mut result = [0u8; SIZE];
+// every version has to fit into the nonce along with the magic prefix,
+// otherwise packing would fail
+const _: () = {
+    const PREFIX_SIZE: usize = VersionedNonce::VERSIONED_MAGIC_PREFIX.len();
+    assert!(
+        PREFIX_SIZE + VersionedNonce::V1_SIZE <= size_of::<Nonce>(),
+        "V1 nonce doesn't fit into 256 bits",
+    );
+    assert!(
+        PREFIX_SIZE + VersionedNonce::V2_SIZE <= size_of::<Nonce>(),
+        "V2 nonce doesn't fit into 256 bits",
+    );
+};
+
+impl VersionedNonce {
+    /// Packs the nonce along with [`Self::VERSIONED_MAGIC_PREFIX`] into
+    /// [`Nonce`]. Fails if the deadline can't be represented as a
+    /// timestamp in nanoseconds, e.g. [`Deadline::MAX`].
+    pub fn try_into_nonce(&self) -> Result<Nonce> {
+        let mut result = [0u8; size_of::<Nonce>()];
 
-        (VersionedNonce::VERSIONED_MAGIC_PREFIX, value)
+        // writing past the end of the slice fails rather than truncates
+        (Self::VERSIONED_MAGIC_PREFIX, self)
             .serialize(&mut result.as_mut_slice())
-            .unwrap_or_else(|_| unreachable!());
+            .map_err(|_| DefuseError::InvalidNonce)?;
+
+        Ok(result)
+    }
+}
 
-        result
+impl From<VersionedNonce> for Nonce {
+    /// See [`VersionedNonce::try_into_nonce`].
+    ///
+    /// Panics if the nonce can't be packed
+    fn from(value: VersionedNonce) -> Self {
+        value
+            .try_into_nonce()
+            .unwrap_or_else(|err| panic!("can't pack versioned nonce: {err}"))
     }
 }
 
@@ -46,11 +126,13 @@ impl From<VersionedNonce> for Nonce {
 mod tests {
     use super::*;
 
-    use crate::{Deadline, nonce::salted::Salt};
+    use crate::{NonceExt, Nonces};
     use arbitrary::Unstructured;
-    use chrono::Utc;
+    use chrono::{DateTime, Utc};
+    use core::str::FromStr;
     use defuse_test_utils::random::random_bytes;
     use rstest::rstest;
+    use std::collections::HashMap;
 
     #[rstest]
     fn maybe_from_test(random_bytes: Vec<u8>) {
@@ -71,4 +153,93 @@ mod tests {
         let exp = VersionedNonce::maybe_from(nonce);
         assert_eq!(exp, Some(VersionedNonce::V1(salted)));
     }
+
+    #[rstest]
+    fn salt_test(random_bytes: Vec<u8>) {
+        let mut u = Unstructured::new(&random_bytes);
+        let legacy_nonce: Nonce = u.arbitrary().unwrap();
+        assert!(legacy_nonce.salt().is_none());
+
+        let salt: Salt = u.arbitrary().unwrap();
+        let salted = SaltedNonce::new(
+            salt,
+            ExpirableNonce::new(Deadline::new(Utc::now()), u.arbitrary().unwrap()),
+        );
+        let versioned = VersionedNonce::V1(salted);
+        assert_eq!(versioned.salt(), salt);
+
+        let nonce: Nonce = versioned.into();
+        assert_eq!(nonce.salt(), Some(salt));
+    }
+
+    #[test]
+    fn pack_maximal_components() {
+        let deadline = Deadline::new(DateTime::from_timestamp_nanos(i64::MAX));
+        for versioned in [
+            VersionedNonce::V1(SaltedNonce::new(
+                Salt::from_str("ffffffff").unwrap(),
+                ExpirableNonce::new(deadline, [u8::MAX; 15]),
+            )),
+            VersionedNonce::V2(SaltedNonce::new(
+                Salt::from_str("ffffffff").unwrap(),
+                NamespacedNonce::new(u8::MAX, ExpirableNonce::new(deadline, [u8::MAX; 14])),
+            )),
+        ] {
+            let nonce = versioned.try_into_nonce().unwrap();
+            assert_eq!(VersionedNonce::maybe_from(nonce), Some(versioned));
+        }
+    }
+
+    #[test]
+    fn pack_overflow() {
+        let versioned = VersionedNonce::V1(SaltedNonce::new(
+            Salt::from_str("ffffffff").unwrap(),
+            ExpirableNonce::new(Deadline::MAX, [u8::MAX; 15]),
+        ));
+        assert!(matches!(
+            versioned.try_into_nonce(),
+            Err(DefuseError::InvalidNonce)
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "can't pack versioned nonce")]
+    fn pack_overflow_panics() {
+        let _: Nonce = VersionedNonce::V2(SaltedNonce::new(
+            Salt::from_str("ffffffff").unwrap(),
+            NamespacedNonce::new(0, ExpirableNonce::new(Deadline::MAX, [0; 14])),
+        ))
+        .into();
+    }
+
+    #[rstest]
+    fn namespaced_test(random_bytes: Vec<u8>) {
+        let mut u = Unstructured::new(&random_bytes);
+        let salt: Salt = u.arbitrary().unwrap();
+        let deadline = Deadline::new(Utc::now());
+        let nonce_bytes: [u8; 14] = u.arbitrary().unwrap();
+
+        let namespaced = |namespace| -> Nonce {
+            VersionedNonce::V2(SaltedNonce::new(
+                salt,
+                NamespacedNonce::new(namespace, ExpirableNonce::new(deadline, nonce_bytes)),
+            ))
+            .into()
+        };
+
+        let versioned = VersionedNonce::maybe_from(namespaced(7)).unwrap();
+        assert_eq!(versioned.salt(), salt);
+        assert_eq!(versioned.deadline(), deadline);
+        assert_eq!(versioned.namespace(), 7);
+
+        // same random bytes in different namespaces don't collide
+        let mut nonces = Nonces::new(HashMap::new());
+        for namespace in 0..=u8::MAX {
+            nonces.commit(namespaced(namespace)).unwrap();
+        }
+        for namespace in 0..=u8::MAX {
+            assert!(nonces.is_used(namespaced(namespace)));
+            nonces.commit(namespaced(namespace)).unwrap_err();
+        }
+    }
 }
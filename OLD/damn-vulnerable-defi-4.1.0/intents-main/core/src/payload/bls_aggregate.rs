@@ -0,0 +1,249 @@
+use defuse_crypto::{Bls12381, Curve, Payload, PublicKey, SignedPayload, serde::AsCurve};
+use near_sdk::{
+    env, near,
+    serde::de::{DeserializeOwned, Error as _},
+    serde_json,
+};
+use serde_with::serde_as;
+
+use super::{DefusePayload, ExtractDefusePayload};
+
+/// Single message covered by [`SignedBlsAggregatePayload`]
+#[cfg_attr(
+    all(feature = "abi", not(target_arch = "wasm32")),
+    serde_as(schemars = true)
+)]
+#[cfg_attr(
+    not(all(feature = "abi", not(target_arch = "wasm32"))),
+    serde_as(schemars = false)
+)]
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct BlsPayload {
+    pub payload: String,
+
+    #[serde_as(as = "AsCurve<Bls12381>")]
+    pub public_key: <Bls12381 as Curve>::PublicKey,
+}
+
+impl Payload for BlsPayload {
+    #[inline]
+    fn hash(&self) -> [u8; 32] {
+        env::sha256_array(self.payload.as_bytes())
+    }
+}
+
+impl<T> ExtractDefusePayload<T> for BlsPayload
+where
+    T: DeserializeOwned,
+{
+    type Error = serde_json::Error;
+
+    #[inline]
+    fn extract_defuse_payload(self) -> Result<DefusePayload<T>, Self::Error> {
+        serde_json::from_str(&self.payload)
+    }
+}
+
+/// Multiple payloads, possibly by different signers, with their BLS
+/// signatures aggregated into a single one, so that all of them are
+/// verified at once
+#[cfg_attr(
+    all(feature = "abi", not(target_arch = "wasm32")),
+    serde_as(schemars = true)
+)]
+#[cfg_attr(
+    not(all(feature = "abi", not(target_arch = "wasm32"))),
+    serde_as(schemars = false)
+)]
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct SignedBlsAggregatePayload {
+    pub payloads: Vec<BlsPayload>,
+
+    #[serde_as(as = "AsCurve<Bls12381>")]
+    pub signature: <Bls12381 as Curve>::Signature,
+}
+
+impl SignedBlsAggregatePayload {
+    /// Verifies aggregated signature over all of the payloads.
+    /// Payloads must be distinct.
+    #[inline]
+    pub fn verify_aggregate(&self) -> bool {
+        Bls12381::aggregate_verify(
+            &self.signature,
+            self.payloads
+                .iter()
+                .map(|p| (&p.public_key, p.payload.as_bytes())),
+        )
+    }
+}
+
+impl Payload for SignedBlsAggregatePayload {
+    /// Hash of concatenated hashes of all payloads
+    #[inline]
+    fn hash(&self) -> [u8; 32] {
+        env::sha256_array(
+            &self
+                .payloads
+                .iter()
+                .flat_map(Payload::hash)
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl SignedPayload for SignedBlsAggregatePayload {
+    type PublicKey = PublicKey;
+
+    /// Returns public key only if the aggregate consists of a single
+    /// payload, see [`Self::verify_aggregate`] otherwise
+    #[inline]
+    fn verify(&self) -> Option<Self::PublicKey> {
+        let [payload] = self.payloads.as_slice() else {
+            return None;
+        };
+        self.verify_aggregate()
+            .then_some(PublicKey::Bls12381(payload.public_key))
+    }
+}
+
+impl<T> ExtractDefusePayload<T> for SignedBlsAggregatePayload
+where
+    T: DeserializeOwned,
+{
+    type Error = serde_json::Error;
+
+    /// Extracts the payload only if the aggregate consists of a single
+    /// one, payloads should be extracted one by one otherwise
+    #[inline]
+    fn extract_defuse_payload(self) -> Result<DefusePayload<T>, Self::Error> {
+        let [payload] = <[_; 1]>::try_from(self.payloads).map_err(|_| {
+            serde_json::Error::custom("aggregate consists of more than a single payload")
+        })?;
+        payload.extract_defuse_payload()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use blst::min_pk::{AggregateSignature, SecretKey};
+    use near_sdk::serde_json::json;
+
+    use crate::{DefuseError, intents::DefuseIntents, payload::multi::MultiPayload};
+
+    use super::*;
+
+    fn payload(signer_id: &str) -> String {
+        json!({
+            "signer_id": signer_id,
+            "verifying_contract": "intents.near",
+            "deadline": "2100-01-01T00:00:00Z",
+            "nonce": "XVoKfmScb3G+XqH9ke/fSlJ/3xO59sNhCxhpG821BH8=",
+            "intents": [],
+        })
+        .to_string()
+    }
+
+    fn sign_aggregate(payloads: &[String]) -> SignedBlsAggregatePayload {
+        let keys: Vec<_> = (0..payloads.len())
+            .map(|i| SecretKey::key_gen(&[u8::try_from(i).unwrap(); 32], &[]).unwrap())
+            .collect();
+        let signatures: Vec<_> = keys
+            .iter()
+            .zip(payloads)
+            .map(|(sk, payload)| sk.sign(payload.as_bytes(), Bls12381::DST, &[]))
+            .collect();
+
+        SignedBlsAggregatePayload {
+            payloads: keys
+                .iter()
+                .zip(payloads)
+                .map(|(sk, payload)| BlsPayload {
+                    payload: payload.clone(),
+                    public_key: sk.sk_to_pk().compress(),
+                })
+                .collect(),
+            signature: AggregateSignature::aggregate(&signatures.iter().collect::<Vec<_>>(), true)
+                .unwrap()
+                .to_signature()
+                .compress(),
+        }
+    }
+
+    #[test]
+    fn verify_aggregate() {
+        let signed = sign_aggregate(&[
+            payload("alice.near"),
+            payload("bob.near"),
+            payload("carol.near"),
+        ]);
+        assert!(signed.verify_aggregate());
+
+        // round-trip through JSON
+        let signed: SignedBlsAggregatePayload =
+            serde_json::from_str(&serde_json::to_string(&signed).unwrap()).unwrap();
+        assert!(signed.verify_aggregate());
+
+        // only single payload can be verified on its own
+        assert_eq!(signed.verify(), None);
+        assert!(
+            ExtractDefusePayload::<DefuseIntents>::extract_defuse_payload(signed.clone()).is_err()
+        );
+
+        for payload in signed.payloads {
+            let p: DefusePayload<DefuseIntents> = payload.extract_defuse_payload().unwrap();
+            assert!(p.intents.is_empty());
+        }
+    }
+
+    #[test]
+    fn tampered() {
+        let mut signed = sign_aggregate(&[
+            payload("alice.near"),
+            payload("bob.near"),
+            payload("carol.near"),
+        ]);
+        signed.payloads[1].payload = payload("mallory.near");
+
+        assert!(!signed.verify_aggregate());
+    }
+
+    #[test]
+    fn single() {
+        let signed = sign_aggregate(&[payload("alice.near")]);
+
+        assert_eq!(
+            signed.verify(),
+            Some(PublicKey::Bls12381(signed.payloads[0].public_key))
+        );
+        let p: DefusePayload<DefuseIntents> = signed.extract_defuse_payload().unwrap();
+        assert_eq!(p.signer_id, "alice.near");
+    }
+
+    #[test]
+    fn recover_signer() {
+        // aggregated messages have to be distinct
+        let other_nonce = payload("alice.near").replace("XVoK", "YVoK");
+
+        let signed = sign_aggregate(&[payload("alice.near"), other_nonce.clone()]);
+        assert_eq!(
+            MultiPayload::from(signed).recover_signer().unwrap(),
+            "alice.near"
+        );
+
+        // payloads are signed on behalf of different accounts
+        let signed = sign_aggregate(&[payload("alice.near"), payload("bob.near")]);
+        assert!(matches!(
+            MultiPayload::from(signed).recover_signer(),
+            Err(DefuseError::InvalidIntent)
+        ));
+
+        let mut signed = sign_aggregate(&[payload("alice.near"), other_nonce]);
+        signed.payloads[1].payload = payload("mallory.near");
+        assert!(matches!(
+            MultiPayload::from(signed).recover_signer(),
+            Err(DefuseError::InvalidSignature)
+        ));
+    }
+}
@@ -0,0 +1,178 @@
+use defuse_crypto::{Curve, Ed25519, Payload, SignedPayload, serde::AsCurve};
+use defuse_erc191::Erc191Payload;
+use defuse_serde_utils::base64::Base64;
+use near_account_id::AccountType;
+use near_sdk::{
+    env, near,
+    serde::de::{self, DeserializeOwned},
+    serde_json,
+};
+use serde_with::serde_as;
+
+use super::{DefusePayload, ExtractDefusePayload};
+
+/// Signature of a smart-contract wallet (e.g. Safe) which can only be
+/// checked by calling `isValidSignature()` on the wallet contract.
+/// See [EIP-1271](https://eips.ethereum.org/EIPS/eip-1271).
+///
+/// Since the wallet lives on another chain, the check is performed
+/// off-chain by an attester, who signs [`Self::attestation_hash`] if
+/// `isValidSignature()` returned the magic value. The wallet is the
+/// `signer_id` of the payload, which has to be an Ethereum implicit
+/// account, i.e. `0x` followed by the address of the wallet contract.
+/// It has to opt in to the attester, which doesn't make the attester
+/// a public key of the account, while `chain_id` has to match the one
+/// configured on the contract.
+#[cfg_attr(
+    all(feature = "abi", not(target_arch = "wasm32")),
+    serde_as(schemars = true)
+)]
+#[cfg_attr(
+    not(all(feature = "abi", not(target_arch = "wasm32"))),
+    serde_as(schemars = false)
+)]
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct SignedEip1271Payload {
+    /// Message which hash was passed to `isValidSignature()`, hashed
+    /// the same way as in ERC-191
+    pub payload: Erc191Payload,
+
+    /// Chain the wallet contract is deployed on
+    pub chain_id: u64,
+
+    /// Signature passed to `isValidSignature()`
+    #[serde_as(as = "Base64")]
+    pub signature: Vec<u8>,
+
+    #[serde_as(as = "AsCurve<Ed25519>")]
+    pub attester_public_key: <Ed25519 as Curve>::PublicKey,
+    /// Attester's signature of [`Self::attestation_hash`]
+    #[serde_as(as = "AsCurve<Ed25519>")]
+    pub attestation: <Ed25519 as Curve>::Signature,
+}
+
+impl SignedEip1271Payload {
+    const ATTESTATION_PREFIX: &[u8] = b"EIP-1271";
+
+    /// Hash attesters sign once `isValidSignature()` succeeded:
+    /// `sha256("EIP-1271" || CHAIN_ID (8 bytes, BE) || PAYLOAD_HASH || keccak256(SIGNATURE))`
+    #[inline]
+    pub fn attestation_hash(&self) -> [u8; 32] {
+        env::sha256_array(
+            &[
+                Self::ATTESTATION_PREFIX,
+                &self.chain_id.to_be_bytes(),
+                &self.payload.hash(),
+                &env::keccak256_array(&self.signature),
+            ]
+            .concat(),
+        )
+    }
+}
+
+impl Payload for SignedEip1271Payload {
+    #[inline]
+    fn hash(&self) -> [u8; 32] {
+        self.payload.hash()
+    }
+}
+
+impl SignedPayload for SignedEip1271Payload {
+    /// Public key of the attester
+    type PublicKey = <Ed25519 as Curve>::PublicKey;
+
+    #[inline]
+    fn verify(&self) -> Option<Self::PublicKey> {
+        Ed25519::verify(
+            &self.attestation,
+            &self.attestation_hash(),
+            &self.attester_public_key,
+        )
+    }
+}
+
+impl<T> ExtractDefusePayload<T> for SignedEip1271Payload
+where
+    T: DeserializeOwned,
+{
+    type Error = serde_json::Error;
+
+    fn extract_defuse_payload(self) -> Result<DefusePayload<T>, Self::Error> {
+        let payload: DefusePayload<T> = serde_json::from_str(&self.payload.0)?;
+        // only wallet contracts can sign via EIP-1271
+        if payload.signer_id.get_account_type() != AccountType::EthImplicitAccount {
+            return Err(de::Error::invalid_value(
+                de::Unexpected::Str(payload.signer_id.as_str()),
+                &"Ethereum implicit account",
+            ));
+        }
+        Ok(payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use near_sdk::serde_json::json;
+
+    use crate::intents::DefuseIntents;
+
+    use super::*;
+
+    fn signed(signer_id: &str) -> SignedEip1271Payload {
+        SignedEip1271Payload {
+            payload: Erc191Payload(
+                json!({
+                    "signer_id": signer_id,
+                    "verifying_contract": "intents.near",
+                    "deadline": "2100-01-01T00:00:00Z",
+                    "nonce": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+                })
+                .to_string(),
+            ),
+            chain_id: 1,
+            signature: vec![1; 65],
+            attester_public_key: [0; 32],
+            attestation: [0; 64],
+        }
+    }
+
+    #[test]
+    fn attestation_hash() {
+        let payload = signed("0x0000000000000000000000000000000000000001");
+        let hash = payload.attestation_hash();
+
+        // attestation covers chain, payload and signature
+        for tampered in [
+            SignedEip1271Payload {
+                chain_id: 10,
+                ..payload.clone()
+            },
+            SignedEip1271Payload {
+                signature: vec![2; 65],
+                ..payload
+            },
+            signed("0x0000000000000000000000000000000000000002"),
+        ] {
+            assert_ne!(tampered.attestation_hash(), hash);
+        }
+    }
+
+    #[test]
+    fn invalid_attestation() {
+        assert_eq!(
+            signed("0x0000000000000000000000000000000000000001").verify(),
+            None
+        );
+    }
+
+    #[test]
+    fn signer_is_wallet() {
+        ExtractDefusePayload::<DefuseIntents>::extract_defuse_payload(signed(
+            "0x0000000000000000000000000000000000000001",
+        ))
+        .unwrap();
+        ExtractDefusePayload::<DefuseIntents>::extract_defuse_payload(signed("user.near"))
+            .unwrap_err();
+    }
+}
@@ -1,8 +1,11 @@
+pub mod bls_aggregate;
+pub mod eip1271;
 pub mod erc191;
 pub mod multi;
 pub mod nep413;
 pub mod raw;
 pub mod sep53;
+pub mod template;
 pub mod tip191;
 pub mod ton_connect;
 pub mod webauthn;
@@ -11,11 +14,14 @@ use core::convert::Infallible;
 
 use defuse_serde_utils::base64::Base64;
 use impl_tools::autoimpl;
-use near_sdk::{AccountId, near};
+use near_sdk::{AccountId, CryptoHash, Gas, env, near, serde::Serialize, serde_json};
 use serde_with::serde_as;
 
 use crate::{Deadline, Nonce};
 
+/// Maximum length of [`DefusePayload::client_ref`] in bytes
+pub const MAX_CLIENT_REF_LEN: usize = 64;
+
 // TODO: add version
 #[cfg_attr(
     all(feature = "abi", not(target_arch = "wasm32")),
@@ -40,10 +46,64 @@ pub struct DefusePayload<T> {
     )]
     pub nonce: Nonce,
 
+    /// Optional reference id provided by the client (e.g. relayer's
+    /// internal job id), which is echoed in `intents_executed` event.
+    /// It's purely informational, but still covered by the signature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_ref: Option<String>,
+
+    /// Optional minimum gas the batch must be relayed with, so that
+    /// intents with chained callbacks don't run out of gas
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub require_prepaid_gas: Option<Gas>,
+
+    /// Optional priority of the payload within the batch: payloads
+    /// with higher priority are executed first, while the ones with
+    /// the same priority are executed in the order they were relayed.
+    /// `None` is the same as `0`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+
+    /// Optional time the payload was signed at. Once the signer has
+    /// set a cutoff with `RevokeBefore`, payloads signed before it or
+    /// not specifying when they were signed are revoked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signed_at: Option<Deadline>,
+
     #[serde(flatten)]
     pub message: T,
 }
 
+impl<T> DefusePayload<T>
+where
+    T: Serialize,
+{
+    /// Hash of [canonical JSON](canonical_payload_json) of the payload,
+    /// which doesn't depend on how exactly it was serialized by the
+    /// signer's wallet
+    #[inline]
+    pub fn canonical_hash(&self) -> Result<CryptoHash, serde_json::Error> {
+        canonical_payload_json(self).map(|json| env::sha256_array(json.as_bytes()))
+    }
+}
+
+/// Serializes the payload into canonical JSON: object keys are sorted
+/// lexicographically at every level of nesting and there is no
+/// insignificant whitespace. Wallets are advised to sign payloads
+/// serialized this way, so that the signed string can be reproduced
+/// by any implementation.
+///
+/// NOTE: signatures are still verified over the exact string that was
+/// signed, since it can't be recovered from the deserialized payload.
+pub fn canonical_payload_json<T>(payload: &DefusePayload<T>) -> Result<String, serde_json::Error>
+where
+    T: Serialize,
+{
+    // `serde_json::Map` is backed by `BTreeMap` unless `preserve_order`
+    // feature is enabled, so going through `Value` sorts the keys
+    serde_json::to_value(payload).and_then(|value| serde_json::to_string(&value))
+}
+
 pub trait ExtractDefusePayload<T> {
     type Error;
 
@@ -69,3 +129,60 @@ mod examples {
         base64::engine::general_purpose::STANDARD.encode(Nonce::default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use near_sdk::serde_json::json;
+
+    use crate::intents::DefuseIntents;
+
+    use super::*;
+
+    const PAYLOAD: &str = r#"{"signer_id":"alice.near","verifying_contract":"intents.near","deadline":"2100-01-01T00:00:00Z","nonce":"XVoKfmScb3G+XqH9ke/fSlJ/3xO59sNhCxhpG821BH8=","intents":[{"intent":"transfer","receiver_id":"bob.near","tokens":{"nep141:ft.near":"100"}}]}"#;
+
+    fn parse(json: &str) -> DefusePayload<DefuseIntents> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn canonical_json_is_sorted_and_compact() {
+        assert_eq!(
+            canonical_payload_json(&parse(PAYLOAD)).unwrap(),
+            r#"{"deadline":"2100-01-01T00:00:00Z","intents":[{"intent":"transfer","receiver_id":"bob.near","tokens":{"nep141:ft.near":"100"}}],"nonce":"XVoKfmScb3G+XqH9ke/fSlJ/3xO59sNhCxhpG821BH8=","signer_id":"alice.near","verifying_contract":"intents.near"}"#,
+        );
+    }
+
+    #[test]
+    fn canonical_json_is_stable() {
+        let canonical = canonical_payload_json(&parse(PAYLOAD)).unwrap();
+        assert_eq!(
+            canonical_payload_json(&parse(&canonical)).unwrap(),
+            canonical
+        );
+    }
+
+    #[test]
+    fn canonical_hash_ignores_ordering_and_whitespace() {
+        let reordered = serde_json::to_string_pretty(&json!({
+            "intents": [{
+                "tokens": {"nep141:ft.near": "100"},
+                "receiver_id": "bob.near",
+                "intent": "transfer",
+            }],
+            "nonce": "XVoKfmScb3G+XqH9ke/fSlJ/3xO59sNhCxhpG821BH8=",
+            "deadline": "2100-01-01T00:00:00Z",
+            "verifying_contract": "intents.near",
+            "signer_id": "alice.near",
+        }))
+        .unwrap();
+        assert_ne!(
+            env::sha256_array(reordered.as_bytes()),
+            env::sha256_array(PAYLOAD.as_bytes())
+        );
+
+        assert_eq!(
+            parse(&reordered).canonical_hash().unwrap(),
+            parse(PAYLOAD).canonical_hash().unwrap(),
+        );
+    }
+}
@@ -5,16 +5,28 @@ use defuse_sep53::SignedSep53Payload;
 use defuse_tip191::SignedTip191Payload;
 use defuse_ton_connect::SignedTonConnectPayload;
 use derive_more::derive::From;
-use near_sdk::{CryptoHash, near, serde::de::DeserializeOwned, serde_json};
+use near_sdk::{AccountId, CryptoHash, near, serde::de::DeserializeOwned, serde_json};
+use strum::{Display, EnumDiscriminants};
+
+use crate::{DefuseError, Result};
 
 use super::{
-    DefusePayload, ExtractDefusePayload, raw::SignedRawEd25519Payload,
+    DefusePayload, ExtractDefusePayload, bls_aggregate::SignedBlsAggregatePayload,
+    eip1271::SignedEip1271Payload, raw::SignedRawEd25519Payload, template::SignedTemplatePayload,
     webauthn::SignedWebAuthnPayload,
 };
 
-#[near(serializers = [json])]
+#[near(serializers = [borsh, json])]
 #[serde(tag = "standard", rename_all = "snake_case")]
-#[derive(Debug, Clone, From)]
+#[derive(Debug, Clone, From, EnumDiscriminants)]
+#[strum_discriminants(
+    name(SigningStandard),
+    vis(pub),
+    derive(PartialOrd, Ord, Hash, Display),
+    near(serializers = [borsh, json]),
+    serde(rename_all = "snake_case"),
+    strum(serialize_all = "snake_case")
+)]
 /// Assuming wallets want to interact with Intents protocol, besides preparing the data in a certain
 /// form, they have to have the capability to sign raw messages (off-chain signatures) using an algorithm we understand.
 /// This enum solves that problem.
@@ -42,6 +54,8 @@ pub enum MultiPayload {
     /// WebAuthn: The standard for Passkeys.
     /// For more details, refer to [WebAuthn specification](https://w3c.github.io/webauthn/).
     #[serde(rename = "webauthn")]
+    #[strum_discriminants(serde(rename = "webauthn"))]
+    #[strum_discriminants(strum(serialize = "webauthn"))]
     WebAuthn(SignedWebAuthnPayload),
 
     /// TonConnect: The standard for data signing in TON blockchain platform.
@@ -51,6 +65,22 @@ pub enum MultiPayload {
     /// SEP-53: The standard for signing data off-chain for Stellar accounts.
     /// See [SEP-53](https://github.com/stellar/stellar-protocol/blob/master/ecosystem/sep-0053.md)
     Sep53(SignedSep53Payload),
+
+    /// BLS12-381: multiple payloads with signatures aggregated into a
+    /// single one, e.g. by rollup relayers.
+    /// See [draft-irtf-cfrg-bls-signature](https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05)
+    BlsAggregate(SignedBlsAggregatePayload),
+
+    /// EIP-1271: The standard for signatures of smart-contract wallets in
+    /// Ethereum, checked by a trusted attester via `isValidSignature()`.
+    /// For more details, refer to [EIP-1271](https://eips.ethereum.org/EIPS/eip-1271).
+    Eip1271(SignedEip1271Payload),
+
+    /// Payload signed with any of the other standards, which
+    /// [`TransferTemplate`](crate::intents::template::TransferTemplate)
+    /// intents are instantiated with given recipients.
+    /// See [`SignedTemplatePayload`]
+    Template(SignedTemplatePayload),
 }
 
 impl Payload for MultiPayload {
@@ -68,6 +98,9 @@ impl Payload for MultiPayload {
             Self::WebAuthn(payload) => payload.hash(),
             Self::TonConnect(payload) => payload.hash(),
             Self::Sep53(payload) => payload.hash(),
+            Self::BlsAggregate(payload) => payload.hash(),
+            Self::Eip1271(payload) => payload.hash(),
+            Self::Template(payload) => payload.hash(),
         }
     }
 }
@@ -85,6 +118,9 @@ impl SignedPayload for MultiPayload {
             Self::WebAuthn(payload) => payload.verify(),
             Self::TonConnect(payload) => payload.verify().map(PublicKey::Ed25519),
             Self::Sep53(payload) => payload.verify().map(PublicKey::Ed25519),
+            Self::BlsAggregate(payload) => payload.verify(),
+            Self::Eip1271(payload) => payload.verify().map(PublicKey::Ed25519),
+            Self::Template(payload) => payload.verify(),
         }
     }
 }
@@ -105,15 +141,106 @@ where
             Self::WebAuthn(payload) => payload.extract_defuse_payload(),
             Self::TonConnect(payload) => payload.extract_defuse_payload(),
             Self::Sep53(payload) => payload.extract_defuse_payload(),
+            Self::BlsAggregate(payload) => payload.extract_defuse_payload(),
+            Self::Eip1271(payload) => payload.extract_defuse_payload(),
+            Self::Template(payload) => payload.extract_defuse_payload(),
         }
     }
 }
 
+impl MultiPayload {
+    /// Extracts all payloads covered by the signature, i.e. each of
+    /// [`Self::BlsAggregate`] payloads or a single one for other standards
+    pub fn extract_defuse_payloads<T>(self) -> Result<Vec<DefusePayload<T>>, serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        match self {
+            Self::BlsAggregate(aggregate) => aggregate
+                .payloads
+                .into_iter()
+                .map(ExtractDefusePayload::extract_defuse_payload)
+                .collect(),
+            payload => payload.extract_defuse_payload().map(|p| vec![p]),
+        }
+    }
+
+    /// Verifies the signature and returns the account the intents are
+    /// executed on behalf of. Regardless of the standard it's the
+    /// `signer_id` declared in the signed payload, e.g. the implicit
+    /// account of the recovered secp256k1 key for ERC-191 payloads of
+    /// Ethereum wallets. All of [`Self::BlsAggregate`] payloads have to
+    /// be signed on behalf of the same account.
+    ///
+    /// NOTE: it's not checked whether the account has the public key
+    pub fn recover_signer(self) -> Result<AccountId> {
+        let verified = match &self {
+            Self::BlsAggregate(aggregate) => aggregate.verify_aggregate(),
+            signed => signed.verify().is_some(),
+        };
+        if !verified {
+            return Err(DefuseError::InvalidSignature);
+        }
+
+        let mut signers = self
+            .extract_defuse_payloads::<serde_json::Value>()?
+            .into_iter()
+            .map(|payload| payload.signer_id);
+        let signer_id = signers.next().ok_or(DefuseError::InvalidIntent)?;
+        if signers.any(|other| other != signer_id) {
+            return Err(DefuseError::InvalidIntent);
+        }
+        Ok(signer_id)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use near_sdk::bs58;
 
     use super::*;
+    use crate::intents::DefuseIntents;
+
+    /// Golden vector of [`hash_vectors.json`](hash_vectors.json)
+    #[near(serializers = [json])]
+    struct HashVector {
+        name: String,
+        payload: MultiPayload,
+        hash: String,
+    }
+
+    /// Hashes of payloads signed with each of the standards, as well as
+    /// of payloads with each of the intents, which wallets can validate
+    /// their implementations against. Any change of hashing breaks them.
+    #[test]
+    fn hash_vectors() {
+        let vectors: Vec<HashVector> =
+            serde_json::from_str(include_str!("hash_vectors.json")).unwrap();
+
+        for HashVector {
+            name,
+            payload,
+            hash,
+        } in vectors
+        {
+            assert_eq!(bs58::encode(payload.hash()).into_string(), hash, "{name}");
+            ExtractDefusePayload::<DefuseIntents>::extract_defuse_payload(payload)
+                .unwrap_or_else(|err| panic!("{name}: {err}"));
+        }
+    }
+
+    #[test]
+    fn signing_standard_matches_tag() {
+        for (standard, tag) in [
+            (SigningStandard::Nep413, "nep413"),
+            (SigningStandard::RawEd25519, "raw_ed25519"),
+            (SigningStandard::WebAuthn, "webauthn"),
+            (SigningStandard::BlsAggregate, "bls_aggregate"),
+        ] {
+            assert_eq!(standard.to_string(), tag);
+            assert_eq!(serde_json::to_value(standard).unwrap(), tag);
+        }
+    }
 
     #[test]
     fn raw_ed25519() {
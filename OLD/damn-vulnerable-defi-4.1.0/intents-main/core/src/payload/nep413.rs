@@ -1,7 +1,7 @@
 use defuse_nep413::{Nep413Payload, SignedNep413Payload};
 use impl_tools::autoimpl;
 use near_sdk::{
-    AccountId, near,
+    AccountId, Gas, near,
     serde::de::{self, DeserializeOwned},
     serde_json,
 };
@@ -19,6 +19,18 @@ pub struct Nep413DefuseMessage<T> {
 
     pub deadline: Deadline,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_ref: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub require_prepaid_gas: Option<Gas>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signed_at: Option<Deadline>,
+
     #[serde(flatten)]
     pub message: T,
 }
@@ -33,6 +45,10 @@ where
         let Nep413DefuseMessage {
             signer_id,
             deadline,
+            client_ref,
+            require_prepaid_gas,
+            priority,
+            signed_at,
             message,
         } = serde_json::from_str(&self.message)?;
 
@@ -43,6 +59,10 @@ where
             })?,
             deadline,
             nonce: self.nonce,
+            client_ref,
+            require_prepaid_gas,
+            priority,
+            signed_at,
             message,
         })
     }
@@ -0,0 +1,48 @@
+use defuse_crypto::{Payload, PublicKey, SignedPayload};
+use near_sdk::{AccountId, CryptoHash, near, serde::de::DeserializeOwned, serde_json};
+
+use super::{DefusePayload, ExtractDefusePayload, multi::MultiPayload};
+
+/// Signed payload with [`TransferTemplate`](crate::intents::template::TransferTemplate)
+/// intents along with the list of recipients to instantiate them with.
+///
+/// Recipients are not covered by the signature, only their Merkle root
+/// in each of the templates is, so that the same signed payload can be
+/// relayed with any list of recipients that matches it.
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct SignedTemplatePayload {
+    pub signed: Box<MultiPayload>,
+
+    pub recipients: Vec<AccountId>,
+}
+
+impl Payload for SignedTemplatePayload {
+    #[inline]
+    fn hash(&self) -> CryptoHash {
+        self.signed.hash()
+    }
+}
+
+impl SignedPayload for SignedTemplatePayload {
+    type PublicKey = PublicKey;
+
+    #[inline]
+    fn verify(&self) -> Option<Self::PublicKey> {
+        self.signed.verify()
+    }
+}
+
+impl<T> ExtractDefusePayload<T> for SignedTemplatePayload
+where
+    T: DeserializeOwned,
+{
+    type Error = serde_json::Error;
+
+    /// Extracts the payload as it was signed, i.e. templates are not
+    /// instantiated
+    #[inline]
+    fn extract_defuse_payload(self) -> Result<DefusePayload<T>, Self::Error> {
+        self.signed.extract_defuse_payload()
+    }
+}
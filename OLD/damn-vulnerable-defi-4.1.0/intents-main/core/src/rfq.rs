@@ -0,0 +1,182 @@
+use crate::{
+    fees::Pips,
+    intents::token_diff::{TokenDeltas, TokenDiff},
+    token_id::TokenId,
+};
+
+/// Request for quote (RFQ) of exact `amount_in` of `token_in` for
+/// `token_out`, which calculates deltas for solvers and the user
+/// assuming given `fee` is charged on both [`TokenDiff`] intents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RfqQuoter {
+    pub token_in: TokenId,
+    pub amount_in: u128,
+    pub token_out: TokenId,
+    pub fee: Pips,
+}
+
+impl RfqQuoter {
+    #[inline]
+    pub const fn new(token_in: TokenId, amount_in: u128, token_out: TokenId, fee: Pips) -> Self {
+        Self {
+            token_in,
+            amount_in,
+            token_out,
+            fee,
+        }
+    }
+
+    /// Returns delta on `token_in` the user gives in their [`TokenDiff`]
+    #[inline]
+    pub fn user_delta_in(&self) -> Option<i128> {
+        i128::try_from(self.amount_in).ok()?.checked_neg()
+    }
+
+    /// Returns delta on `token_in` to be propagated to solvers, i.e.
+    /// how much they receive after the user's intent is charged fee
+    #[inline]
+    pub fn solver_delta_in(&self) -> Option<i128> {
+        TokenDiff::closure_delta(&self.token_in, self.user_delta_in()?, self.fee)
+    }
+
+    /// Returns deltas the user should sign in [`TokenDiff`] to close
+    /// `unmatched_deltas` left after simulating solver's commitment
+    /// alone, or `None` if the commitment doesn't correspond to this RFQ
+    pub fn user_deltas(&self, unmatched_deltas: &TokenDeltas) -> Option<TokenDeltas> {
+        // solver's commitment should be unmatched only on token_in
+        // and token_out
+        if unmatched_deltas.len() != 2
+            || unmatched_deltas.amount_for(&self.token_in) != self.solver_delta_in()?
+        {
+            return None;
+        }
+
+        let user_delta_out = TokenDiff::closure_supply_delta(
+            &self.token_out,
+            unmatched_deltas.amount_for(&self.token_out),
+            self.fee,
+        )?;
+        if user_delta_out <= 0 {
+            return None;
+        }
+
+        Some(TokenDeltas::new(
+            [
+                (self.token_in.clone(), self.user_delta_in()?),
+                (self.token_out.clone(), user_delta_out),
+            ]
+            .into_iter()
+            .collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::{amounts::Amounts, token_id::nep141::Nep141TokenId};
+
+    use super::*;
+
+    /// Returns total supply deltas of given [`TokenDiff`] intents,
+    /// i.e. what simulation reports as unmatched deltas
+    fn unmatched_deltas<'a>(
+        diffs: impl IntoIterator<Item = &'a TokenDeltas>,
+        fee: Pips,
+    ) -> TokenDeltas {
+        diffs
+            .into_iter()
+            .flat_map(Amounts::iter)
+            .try_fold(TokenDeltas::default(), |unmatched, (token_id, delta)| {
+                unmatched.with_apply_delta(
+                    token_id.clone(),
+                    TokenDiff::supply_delta(token_id, *delta, fee)?,
+                )
+            })
+            .unwrap()
+    }
+
+    fn quoter(amount_in: u128, fee: Pips) -> RfqQuoter {
+        RfqQuoter::new(
+            Nep141TokenId::new("ft1.near".parse().unwrap()).into(),
+            amount_in,
+            Nep141TokenId::new("ft2.near".parse().unwrap()).into(),
+            fee,
+        )
+    }
+
+    #[rstest]
+    fn solver_user_closure(
+        #[values(Pips::ZERO, Pips::ONE_BIP, Pips::ONE_PERCENT)] fee: Pips,
+        #[values(1000, 1_000_000)] amount_in: u128,
+    ) {
+        let quoter = quoter(amount_in, fee);
+
+        // solver trades 1:2
+        let solver_delta_in = quoter.solver_delta_in().unwrap();
+        let solver_deltas = TokenDeltas::new(
+            [
+                (quoter.token_in.clone(), solver_delta_in),
+                (quoter.token_out.clone(), solver_delta_in * -2),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        let unmatched = unmatched_deltas([&solver_deltas], fee);
+        let user_deltas = quoter.user_deltas(&unmatched).unwrap();
+        assert_eq!(
+            user_deltas.amount_for(&quoter.token_in),
+            -i128::try_from(amount_in).unwrap()
+        );
+        assert!(user_deltas.amount_for(&quoter.token_out) > 0);
+
+        // both intents close each other
+        assert!(
+            unmatched_deltas([&solver_deltas, &user_deltas], fee)
+                .iter()
+                .all(|(_, delta)| *delta == 0)
+        );
+    }
+
+    #[test]
+    fn not_matching_commitment() {
+        let quoter = quoter(1000, Pips::ONE_BIP);
+        let solver_delta_in = quoter.solver_delta_in().unwrap();
+
+        // solver receives less than the user gives
+        let unmatched = TokenDeltas::new(
+            [
+                (quoter.token_in.clone(), solver_delta_in - 1),
+                (quoter.token_out.clone(), -2000),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        assert_eq!(quoter.user_deltas(&unmatched), None);
+
+        // solver's commitment is unmatched on other tokens
+        let unmatched = TokenDeltas::new(
+            [
+                (quoter.token_in.clone(), solver_delta_in),
+                (quoter.token_out.clone(), -2000),
+                (Nep141TokenId::new("ft3.near".parse().unwrap()).into(), -1),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        assert_eq!(quoter.user_deltas(&unmatched), None);
+
+        // solver gives nothing
+        let unmatched = TokenDeltas::new(
+            [
+                (quoter.token_in.clone(), solver_delta_in),
+                (quoter.token_out.clone(), 1),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        assert_eq!(quoter.user_deltas(&unmatched), None);
+    }
+}
@@ -0,0 +1,292 @@
+use crypto_bigint::{Encoding, NonZero, U512};
+use hex_literal::hex;
+use near_sdk::{env, sys};
+
+use super::{Curve, CurveType, TypedCurve};
+
+/// BLS signatures over BLS12-381 with public keys in G1 and signatures
+/// in G2, i.e. "minimal-pubkey-size" variant of the basic scheme, see
+/// [draft-irtf-cfrg-bls-signature](https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05)
+pub struct Bls12381;
+
+impl Bls12381 {
+    /// Domain separation tag of the basic scheme
+    pub const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+    /// Verifies `signature` aggregated from signatures of distinct
+    /// `messages` by corresponding public keys.
+    ///
+    /// Messages must be distinct, since the basic scheme is
+    /// vulnerable to rogue key attacks otherwise.
+    pub fn aggregate_verify<'a>(
+        signature: &<Self as Curve>::Signature,
+        messages: impl IntoIterator<Item = (&'a <Self as Curve>::PublicKey, &'a [u8])>,
+    ) -> bool {
+        let mut seen = Vec::new();
+        // e(-G1, signature) * Π e(public_key, H(message)) == 1
+        let Some(mut pairs) =
+            p2_decompress(signature).map(|sig| [NEG_G1.as_slice(), &sig].concat())
+        else {
+            return false;
+        };
+
+        for (public_key, message) in messages {
+            if seen.contains(&message) {
+                return false;
+            }
+            seen.push(message);
+
+            // public key at infinity would verify any signature
+            if public_key[0] & INFINITY_FLAG != 0 {
+                return false;
+            }
+            let Some(public_key) = p1_decompress(public_key) else {
+                return false;
+            };
+            pairs.extend(public_key);
+            pairs.extend(hash_to_g2(message));
+        }
+
+        // at least one message is required
+        !seen.is_empty() && env::bls12381_pairing_check(&pairs)
+    }
+}
+
+impl Curve for Bls12381 {
+    /// Compressed G1 point
+    type PublicKey = [u8; 48];
+
+    /// Compressed G2 point
+    type Signature = [u8; 96];
+
+    type Message = [u8];
+
+    type VerifyingKey = Self::PublicKey;
+
+    #[inline]
+    fn verify(
+        signature: &Self::Signature,
+        message: &Self::Message,
+        public_key: &Self::VerifyingKey,
+    ) -> Option<Self::PublicKey> {
+        Self::aggregate_verify(signature, [(public_key, message)])
+            .then_some(public_key)
+            .copied()
+    }
+}
+
+impl TypedCurve for Bls12381 {
+    const CURVE_TYPE: CurveType = CurveType::Bls12381;
+}
+
+const INFINITY_FLAG: u8 = 0x40;
+
+/// Uncompressed negated generator of G1
+const NEG_G1: [u8; 96] = hex!(
+    "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"
+    "114d1d6855d545a8aa7d76c8cf2e21f267816aef1db507c96655b9d5caac42364e6f38ba0ecb751bad54dcd6b939c2ca"
+);
+
+/// Base field modulus
+const P: U512 = U512::from_be_hex(
+    "00000000000000000000000000000000\
+     1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab",
+);
+
+const FP_LEN: usize = 48;
+/// Length of uncompressed G2 point
+const G2_LEN: usize = 4 * FP_LEN;
+/// Length of uniform bytes per field element: `ceil((ceil(log2(p)) + k) / 8)`
+const L: usize = 64;
+const SHA256_LEN: usize = 32;
+
+/// `hash_to_curve` for G2 as defined in
+/// [RFC 9380](https://datatracker.ietf.org/doc/html/rfc9380#name-bls12-381-g2)
+/// with `BLS12381G2_XMD:SHA-256_SSWU_RO_` suite. Returns uncompressed point.
+fn hash_to_g2(message: &[u8]) -> Vec<u8> {
+    let uniform = expand_message_xmd(message, Bls12381::DST, 4 * L);
+    // u_i = (c0, c1), while host function expects `c1 || c0`
+    let mut fp2s = Vec::with_capacity(4 * FP_LEN);
+    for u in uniform.chunks_exact(2 * L) {
+        let (c0, c1) = u.split_at(L);
+        fp2s.extend(reduce(c1));
+        fp2s.extend(reduce(c0));
+    }
+    // cofactor is cleared by the host function for each of points,
+    // so it's also cleared for their sum
+    let summands: Vec<u8> = env::bls12381_map_fp2_to_g2(&fp2s)
+        .chunks_exact(G2_LEN)
+        // positive sign
+        .flat_map(|point| [[0].as_slice(), point].concat())
+        .collect();
+    env::bls12381_p2_sum(&summands)
+}
+
+/// Reduces big-endian integer modulo base field modulus
+fn reduce(bytes: &[u8]) -> [u8; FP_LEN] {
+    let reduced = U512::from_be_slice(bytes)
+        .rem(&NonZero::new(P).unwrap())
+        .to_be_bytes();
+    let mut fp = [0; FP_LEN];
+    fp.copy_from_slice(&reduced[reduced.len() - FP_LEN..]);
+    fp
+}
+
+/// See [RFC 9380](https://datatracker.ietf.org/doc/html/rfc9380#name-expand_message_xmd)
+fn expand_message_xmd(message: &[u8], dst: &[u8], len: usize) -> Vec<u8> {
+    const S_IN_BYTES: usize = 64;
+
+    let ell = len.div_ceil(SHA256_LEN);
+    let dst_prime = [dst, &[u8::try_from(dst.len()).unwrap()]].concat();
+    let len_bytes = u16::try_from(len).unwrap().to_be_bytes();
+
+    let b_0 = env::sha256_array(
+        &[
+            [0; S_IN_BYTES].as_slice(),
+            message,
+            &len_bytes,
+            &[0],
+            &dst_prime,
+        ]
+        .concat(),
+    );
+    let mut b_i = env::sha256_array(&[b_0.as_slice(), &[1], &dst_prime].concat());
+
+    let mut uniform = Vec::with_capacity(ell * SHA256_LEN);
+    uniform.extend(b_i);
+    for i in 2..=ell {
+        let xored: Vec<u8> = b_0.iter().zip(b_i).map(|(a, b)| a ^ b).collect();
+        b_i = env::sha256_array(
+            &[xored.as_slice(), &[u8::try_from(i).unwrap()], &dst_prime].concat(),
+        );
+        uniform.extend(b_i);
+    }
+    uniform.truncate(len);
+    uniform
+}
+
+/// Like [`env::bls12381_p1_decompress`], but returns `None` on invalid
+/// points instead of panicking
+fn p1_decompress(compressed: &[u8]) -> Option<Vec<u8>> {
+    decompress(compressed, sys::bls12381_p1_decompress)
+}
+
+/// Like [`env::bls12381_p2_decompress`], but returns `None` on invalid
+/// points instead of panicking
+fn p2_decompress(compressed: &[u8]) -> Option<Vec<u8>> {
+    decompress(compressed, sys::bls12381_p2_decompress)
+}
+
+fn decompress(
+    compressed: &[u8],
+    host_fn: unsafe extern "C" fn(u64, u64, u64) -> u64,
+) -> Option<Vec<u8>> {
+    const REGISTER: u64 = u64::MAX - 3;
+
+    let len = u64::try_from(compressed.len()).ok()?;
+    let ptr = u64::try_from(compressed.as_ptr().addr()).ok()?;
+    // SAFETY: pointer and length are of a valid slice
+    if unsafe { host_fn(len, ptr, REGISTER) } != 0 {
+        return None;
+    }
+    env::read_register(REGISTER)
+}
+
+#[cfg(test)]
+mod tests {
+    use blst::min_pk::{AggregateSignature, SecretKey};
+
+    use super::*;
+
+    fn secret_key(seed: u8) -> SecretKey {
+        SecretKey::key_gen(&[seed; 32], &[]).unwrap()
+    }
+
+    fn sign_all(messages: &[&[u8]]) -> ([u8; 96], Vec<[u8; 48]>) {
+        let keys: Vec<_> = (0..).map(secret_key).take(messages.len()).collect();
+        let signatures: Vec<_> = keys
+            .iter()
+            .zip(messages)
+            .map(|(sk, msg)| sk.sign(msg, Bls12381::DST, &[]))
+            .collect();
+        let signature = AggregateSignature::aggregate(&signatures.iter().collect::<Vec<_>>(), true)
+            .unwrap()
+            .to_signature()
+            .compress();
+        (
+            signature,
+            keys.iter().map(|sk| sk.sk_to_pk().compress()).collect(),
+        )
+    }
+
+    #[test]
+    fn verify() {
+        let message = b"hello";
+        let sk = secret_key(0);
+        let signature = sk.sign(message, Bls12381::DST, &[]).compress();
+        let public_key = sk.sk_to_pk().compress();
+
+        assert_eq!(
+            Bls12381::verify(&signature, message, &public_key),
+            Some(public_key)
+        );
+        assert_eq!(Bls12381::verify(&signature, b"hellO", &public_key), None);
+    }
+
+    #[test]
+    fn aggregate_verify() {
+        let messages: [&[u8]; 3] = [b"first", b"second", b"third"];
+        let (signature, public_keys) = sign_all(&messages);
+
+        assert!(Bls12381::aggregate_verify(
+            &signature,
+            public_keys.iter().zip(messages)
+        ));
+
+        // tampered message
+        assert!(!Bls12381::aggregate_verify(
+            &signature,
+            public_keys
+                .iter()
+                .zip([b"first".as_slice(), b"second", b"thirD"])
+        ));
+        // missing message
+        assert!(!Bls12381::aggregate_verify(
+            &signature,
+            public_keys.iter().zip(messages).take(2)
+        ));
+        // swapped keys
+        assert!(!Bls12381::aggregate_verify(
+            &signature,
+            public_keys.iter().rev().zip(messages)
+        ));
+    }
+
+    #[test]
+    fn rejects_duplicate_messages() {
+        let messages: [&[u8]; 2] = [b"same", b"same"];
+        let (signature, public_keys) = sign_all(&messages);
+
+        assert!(!Bls12381::aggregate_verify(
+            &signature,
+            public_keys.iter().zip(messages)
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_points() {
+        let (signature, public_keys) = sign_all(&[b"message"]);
+
+        let mut infinity = [0; 48];
+        infinity[0] = 0xc0;
+        assert!(!Bls12381::aggregate_verify(
+            &signature,
+            [(&infinity, b"message".as_slice())]
+        ));
+        assert!(!Bls12381::aggregate_verify(
+            &[0xff; 96],
+            [(&public_keys[0], b"message".as_slice())]
+        ));
+        assert!(!Bls12381::aggregate_verify(&signature, []));
+    }
+}
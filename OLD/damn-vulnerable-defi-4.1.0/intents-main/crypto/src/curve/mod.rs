@@ -1,10 +1,11 @@
+mod bls12381;
 mod ed25519;
 mod p256;
 mod secp256k1;
 
 use crate::{ParseCurveError, parse::checked_base58_decode_array};
 
-pub use self::{ed25519::*, p256::*, secp256k1::*};
+pub use self::{bls12381::*, ed25519::*, p256::*, secp256k1::*};
 
 use near_sdk::bs58;
 use strum::{Display, EnumString, IntoStaticStr};
@@ -32,6 +33,7 @@ pub enum CurveType {
     Ed25519,
     Secp256k1,
     P256,
+    Bls12381,
 }
 
 pub trait TypedCurve: Curve {
@@ -6,7 +6,8 @@ use core::{
 use near_sdk::{AccountId, AccountIdRef, bs58, env, near};
 
 use crate::{
-    Curve, CurveType, Ed25519, P256, ParseCurveError, Secp256k1, parse::checked_base58_decode_array,
+    Bls12381, Curve, CurveType, Ed25519, P256, ParseCurveError, Secp256k1,
+    parse::checked_base58_decode_array,
 };
 
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -20,6 +21,7 @@ pub enum PublicKey {
     Ed25519(<Ed25519 as Curve>::PublicKey),
     Secp256k1(<Secp256k1 as Curve>::PublicKey),
     P256(<P256 as Curve>::PublicKey),
+    Bls12381(<Bls12381 as Curve>::PublicKey),
 }
 
 impl PublicKey {
@@ -29,6 +31,7 @@ impl PublicKey {
             Self::Ed25519(_) => CurveType::Ed25519,
             Self::Secp256k1(_) => CurveType::Secp256k1,
             Self::P256(_) => CurveType::P256,
+            Self::Bls12381(_) => CurveType::Bls12381,
         }
     }
 
@@ -39,6 +42,7 @@ impl PublicKey {
             Self::Ed25519(data) => data,
             Self::Secp256k1(data) => data,
             Self::P256(data) => data,
+            Self::Bls12381(data) => data,
         }
     }
 
@@ -72,6 +76,16 @@ impl PublicKey {
                     hex::encode(&env::keccak256_array(&[b"p256".as_slice(), pk].concat())[12..32])
                 )
             }
+            Self::Bls12381(pk) => {
+                // Same schema as for P256, but with "bls12381" prefix:
+                // "0x" .. hex(keccak256("bls12381" .. pk)[12..32])
+                format!(
+                    "0x{}",
+                    hex::encode(
+                        &env::keccak256_array(&[b"bls12381".as_slice(), pk].concat())[12..32]
+                    )
+                )
+            }
         }
         .try_into()
         .unwrap_or_else(|_| unreachable!())
@@ -122,6 +136,7 @@ impl FromStr for PublicKey {
             CurveType::Ed25519 => checked_base58_decode_array(data).map(Self::Ed25519),
             CurveType::Secp256k1 => checked_base58_decode_array(data).map(Self::Secp256k1),
             CurveType::P256 => checked_base58_decode_array(data).map(Self::P256),
+            CurveType::Bls12381 => checked_base58_decode_array(data).map(Self::Bls12381),
         }
     }
 }
@@ -6,7 +6,8 @@ use core::{
 use near_sdk::{bs58, near};
 
 use crate::{
-    Curve, CurveType, Ed25519, P256, ParseCurveError, Secp256k1, parse::checked_base58_decode_array,
+    Bls12381, Curve, CurveType, Ed25519, P256, ParseCurveError, Secp256k1,
+    parse::checked_base58_decode_array,
 };
 
 #[near(serializers = [borsh])]
@@ -19,6 +20,7 @@ pub enum Signature {
     Ed25519(<Ed25519 as Curve>::Signature),
     Secp256k1(<Secp256k1 as Curve>::Signature),
     P256(<P256 as Curve>::Signature),
+    Bls12381(<Bls12381 as Curve>::Signature),
 }
 
 impl Signature {
@@ -28,6 +30,7 @@ impl Signature {
             Self::Ed25519(_) => CurveType::Ed25519,
             Self::Secp256k1(_) => CurveType::Secp256k1,
             Self::P256(_) => CurveType::P256,
+            Self::Bls12381(_) => CurveType::Bls12381,
         }
     }
 
@@ -38,6 +41,7 @@ impl Signature {
             Self::Ed25519(data) => data,
             Self::Secp256k1(data) => data,
             Self::P256(data) => data,
+            Self::Bls12381(data) => data,
         }
     }
 }
@@ -78,6 +82,7 @@ impl FromStr for Signature {
             CurveType::Ed25519 => checked_base58_decode_array(data).map(Self::Ed25519),
             CurveType::Secp256k1 => checked_base58_decode_array(data).map(Self::Secp256k1),
             CurveType::P256 => checked_base58_decode_array(data).map(Self::P256),
+            CurveType::Bls12381 => checked_base58_decode_array(data).map(Self::Bls12381),
         }
     }
 }
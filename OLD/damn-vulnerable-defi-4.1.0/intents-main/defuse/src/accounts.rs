@@ -1,9 +1,52 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 
-use defuse_core::{Nonce, crypto::PublicKey};
+use defuse_core::{Nonce, crypto::PublicKey, payload::multi::SigningStandard};
 use defuse_serde_utils::base64::AsBase64;
 use near_plugins::AccessControllable;
-use near_sdk::{AccountId, ext_contract};
+use near_sdk::{AccountId, NearToken, ext_contract, near};
+
+/// Maximum number of nonces queried at once in
+/// [`AccountManager::are_nonces_used`], so that view gas stays bounded
+pub const MAX_NONCES_PER_QUERY: usize = 256;
+
+/// Maximum number of accounts queried at once in
+/// [`AccountManager::are_auth_by_predecessor_id_enabled`]
+pub const MAX_ACCOUNTS_PER_QUERY: usize = 256;
+
+/// Maximum number of accounts locked at once by
+/// [`ForceAccountManager::force_lock_accounts`]
+pub const MAX_ACCOUNTS_PER_FORCE_LOCK: usize = 256;
+
+/// Maximum number of public keys returned at once by
+/// [`AccountManager::account_public_keys`]
+pub const MAX_PUBLIC_KEYS_PER_QUERY: usize = 256;
+
+/// Maximum number of public keys of an account iterated while verifying
+/// its intents, unless overridden by [`AccountKeysLimit::set_max_keys_to_check`]
+pub const DEFAULT_MAX_KEYS_TO_CHECK: u32 = 256;
+
+/// wNEAR balance of [registration reserve](AccountRegistrationReserve)
+/// below which `registration_reserve_low` event is emitted
+pub const REGISTRATION_RESERVE_LOW_BALANCE: NearToken = NearToken::from_near(1);
+
+/// Status of a nonce returned by [`AccountManager::nonce_status`]
+#[near(serializers = [json])]
+#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonceStatus {
+    /// Nonce was never used by the account
+    Unused,
+    /// Nonce is committed, so intents signed with it can't be
+    /// executed again
+    Committed,
+    /// Nonce was committed and then cleaned up by garbage collector
+    /// after it had expired.
+    ///
+    /// NOTE: cleanup works on whole pages of nonces sharing the same
+    /// prefix, so never used nonces from a cleaned page are reported
+    /// with this status as well.
+    CleanedExpired,
+}
 
 #[ext_contract(ext_account_manager)]
 pub trait AccountManager {
@@ -13,6 +56,18 @@ pub trait AccountManager {
     /// Returns set of public keys registered for given account
     fn public_keys_of(&self, account_id: &AccountId) -> HashSet<PublicKey>;
 
+    /// Returns up to `limit` public keys registered for given account,
+    /// starting right after `from` or from the first key if it's `None`.
+    /// The last returned key can be passed as `from` to get the next page.
+    ///
+    /// NOTE: at most [`MAX_PUBLIC_KEYS_PER_QUERY`] keys can be queried at once.
+    fn account_public_keys(
+        &self,
+        account_id: &AccountId,
+        from: Option<PublicKey>,
+        limit: u32,
+    ) -> Vec<PublicKey>;
+
     /// Registers or re-activates `public_key` under the caller account_id.
     ///
     /// NOTE: MUST attach 1 yⓃ for security purposes.
@@ -29,6 +84,19 @@ pub trait AccountManager {
     /// [permit2 nonce schema](https://docs.uniswap.org/contracts/permit2/reference/signature-transfer#nonce-schema).
     fn is_nonce_used(&self, account_id: &AccountId, nonce: AsBase64<Nonce>) -> bool;
 
+    /// Batched version of [`is_nonce_used`](AccountManager::is_nonce_used):
+    /// returns whether each of given nonces was already used by the
+    /// account, in the same order.
+    ///
+    /// NOTE: at most [`MAX_NONCES_PER_QUERY`] nonces can be queried at once.
+    fn are_nonces_used(&self, account_id: &AccountId, nonces: Vec<AsBase64<Nonce>>) -> Vec<bool>;
+
+    /// Returns status of given nonce of the account. Unlike
+    /// [`is_nonce_used`](AccountManager::is_nonce_used), tells apart
+    /// nonces which were never used from the ones cleaned up by
+    /// [garbage collector](crate::garbage_collector::GarbageCollector).
+    fn nonce_status(&self, account_id: &AccountId, nonce: AsBase64<Nonce>) -> NonceStatus;
+
     /// Returns whether authentication by PREDECESSOR_ID is enabled
     /// for given `account_id`.
     ///
@@ -36,6 +104,14 @@ pub trait AccountManager {
     /// when creating new accounts.
     fn is_auth_by_predecessor_id_enabled(&self, account_id: &AccountId) -> bool;
 
+    /// Batched version of
+    /// [`is_auth_by_predecessor_id_enabled`](AccountManager::is_auth_by_predecessor_id_enabled):
+    /// returns whether authentication by PREDECESSOR_ID is enabled for
+    /// each of given accounts, in the same order.
+    ///
+    /// NOTE: at most [`MAX_ACCOUNTS_PER_QUERY`] accounts can be queried at once.
+    fn are_auth_by_predecessor_id_enabled(&self, account_ids: Vec<AccountId>) -> Vec<bool>;
+
     /// Disables authentication by PREDECESSOR_ID for the caller,
     /// i.e. PREDECESSOR_ID itself.
     ///
@@ -44,6 +120,17 @@ pub trait AccountManager {
     ///
     /// NOTE: MUST attach 1 yⓃ for security purposes.
     fn disable_auth_by_predecessor_id(&mut self);
+
+    /// Returns estimated number of bytes taken by the account in storage,
+    /// including its public keys, nonces and token balances.
+    ///
+    /// NOTE: nonces committed before this estimation was introduced
+    /// are not accounted for.
+    fn account_storage_usage(&self, account_id: &AccountId) -> u64;
+
+    /// Returns display nickname set by the account via `SetNickname`
+    /// intent, if any
+    fn nickname(&self, account_id: &AccountId) -> Option<String>;
 }
 
 #[ext_contract(ext_force_account_manager)]
@@ -60,12 +147,38 @@ pub trait ForceAccountManager: AccessControllable {
     /// NOTE: this still allows for force withdrawals/transfers
     fn force_lock_account(&mut self, account_id: AccountId) -> bool;
 
+    /// Same as [`Self::force_lock_account`], but locks multiple accounts
+    /// at once with optional `reason` included in emitted events.
+    /// Returns whether each of `account_ids` was newly locked.
+    ///
+    /// At most [`MAX_ACCOUNTS_PER_FORCE_LOCK`] accounts can be locked
+    /// in a single call.
+    ///
+    /// Attached deposit of 1yN is required for security purposes.
+    fn force_lock_accounts(
+        &mut self,
+        account_ids: Vec<AccountId>,
+        reason: Option<String>,
+    ) -> Vec<bool>;
+
     /// Unlocks given `account_id`.
     /// Returns `false` if the account wasn't in locked state.
     ///
     /// Attached deposit of 1yN is required for security purposes.
     fn force_unlock_account(&mut self, account_id: &AccountId) -> bool;
 
+    /// Allows the next deposit to `account_id` made with
+    /// [`DepositMessage::lock`](crate::tokens::DepositMessage::lock) to
+    /// lock it, so that escrowed funds are credited and locked at once.
+    /// Returns `false` if it was already allowed.
+    ///
+    /// Attached deposit of 1yN is required for security purposes.
+    fn force_allow_lock_on_deposit(&mut self, account_id: AccountId) -> bool;
+
+    /// Returns whether the next deposit to `account_id` is allowed to
+    /// lock it, see [`Self::force_allow_lock_on_deposit`]
+    fn is_lock_on_deposit_allowed(&self, account_id: &AccountId) -> bool;
+
     /// Disables authentication by PREDECESSOR_ID for given account ids.
     ///
     /// **WARN**: Doing so might lock these accounts out of your funds if
@@ -82,4 +195,117 @@ pub trait ForceAccountManager: AccessControllable {
     ///
     /// NOTE: MUST attach 1 yⓃ for security purposes.
     fn force_enable_auth_by_predecessor_ids(&mut self, account_ids: Vec<AccountId>);
+
+    /// Returns signing standards disabled for given `account_id`
+    fn disabled_signing_standards(&self, account_id: &AccountId) -> BTreeSet<SigningStandard>;
+
+    /// Disables given signing `standards` for `account_id`, i.e. intents
+    /// signed on its behalf with these standards are rejected, while
+    /// other standards still can be used.
+    ///
+    /// NOTE: MUST attach 1 yⓃ for security purposes.
+    fn force_disable_signing_standards(
+        &mut self,
+        account_id: AccountId,
+        standards: Vec<SigningStandard>,
+    );
+
+    /// Re-enables given signing `standards` for `account_id`.
+    ///
+    /// NOTE: MUST attach 1 yⓃ for security purposes.
+    fn force_enable_signing_standards(
+        &mut self,
+        account_id: AccountId,
+        standards: Vec<SigningStandard>,
+    );
+}
+
+#[ext_contract(ext_account_denylist)]
+pub trait AccountDenylist: AccessControllable {
+    /// Returns whether the given `account_id` is blocked
+    fn is_account_blocked(&self, account_id: &AccountId) -> bool;
+
+    /// Adds given `account_ids` to the denylist, so that they can
+    /// neither send nor receive any tokens.
+    ///
+    /// Attached deposit of 1yN is required for security purposes.
+    ///
+    /// NOTE: unlike [`ForceAccountManager::force_lock_account`], this
+    /// also blocks incoming deposits and transfers. Force
+    /// withdrawals/transfers from blocked accounts are still allowed.
+    fn block_accounts(&mut self, account_ids: Vec<AccountId>);
+
+    /// Removes given `account_ids` from the denylist.
+    ///
+    /// Attached deposit of 1yN is required for security purposes.
+    fn unblock_accounts(&mut self, account_ids: Vec<AccountId>);
+}
+
+#[ext_contract(ext_account_tokens_limit)]
+pub trait AccountTokensLimit: AccessControllable {
+    /// Returns maximum number of distinct tokens a single account can
+    /// hold, if any
+    fn max_tokens_per_account(&self) -> Option<u32>;
+
+    /// Sets maximum number of distinct tokens a single account can hold.
+    /// `None` means unlimited.
+    ///
+    /// NOTE: accounts already holding more tokens are not affected, but
+    /// they can't receive any new tokens until they withdraw enough of
+    /// their holdings.
+    ///
+    /// Attached deposit of 1yN is required for security purposes.
+    fn set_max_tokens_per_account(&mut self, max_tokens_per_account: Option<u32>);
+}
+
+#[ext_contract(ext_account_keys_limit)]
+pub trait AccountKeysLimit: AccessControllable {
+    /// Returns maximum number of public keys of an account iterated
+    /// while verifying its intents
+    fn max_keys_to_check(&self) -> u32;
+
+    /// Sets maximum number of public keys of an account iterated while
+    /// verifying its intents, so that an account holding too many keys
+    /// can't make verification arbitrarily expensive. `None` resets it
+    /// to [`DEFAULT_MAX_KEYS_TO_CHECK`].
+    ///
+    /// NOTE: intents which need to iterate more keys, e.g. changes of
+    /// multisig threshold, fail with `TooManyKeysToCheck`.
+    ///
+    /// Attached deposit of 1yN is required for security purposes.
+    fn set_max_keys_to_check(&mut self, max_keys_to_check: Option<u32>);
+}
+
+#[ext_contract(ext_account_withdrawal_cooldown)]
+pub trait AccountWithdrawalCooldown: AccessControllable {
+    /// Returns duration in seconds for which withdrawals of the account
+    /// are blocked after each withdrawal, if any
+    fn withdrawal_cooldown(&self, account_id: AccountId) -> Option<u32>;
+
+    /// Sets duration in seconds for which withdrawals of the account are
+    /// blocked after each withdrawal. `None` means no cooldown.
+    ///
+    /// NOTE: transfers within the contract are not affected.
+    ///
+    /// Attached deposit of 1yN is required for security purposes.
+    fn set_withdrawal_cooldown(&mut self, account_id: AccountId, cooldown: Option<u32>);
+}
+
+#[ext_contract(ext_account_registration_reserve)]
+pub trait AccountRegistrationReserve: AccessControllable {
+    /// Returns the account which funds storage of accounts registered
+    /// on their first deposit, if any
+    fn registration_reserve(&self) -> Option<AccountId>;
+
+    /// Sets the account which funds storage of accounts registered on
+    /// their first deposit. `None` means the contract covers it itself.
+    ///
+    /// Storage cost of each new account is burnt from wNEAR balance of
+    /// the reserve held on this contract. Deposits are never rejected
+    /// due to insufficient reserve, but `registration_reserve_low` event
+    /// is emitted once its balance drops below
+    /// [`REGISTRATION_RESERVE_LOW_BALANCE`].
+    ///
+    /// Attached deposit of 1yN is required for security purposes.
+    fn set_registration_reserve(&mut self, reserve_id: Option<AccountId>);
 }
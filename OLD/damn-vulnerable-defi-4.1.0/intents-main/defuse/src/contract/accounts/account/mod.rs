@@ -16,7 +16,7 @@ use defuse_near_utils::NestPrefix;
 use impl_tools::autoimpl;
 use near_sdk::{
     AccountIdRef, BorshStorageKey, IntoStorageKey,
-    borsh::BorshSerialize,
+    borsh::{self, BorshSerialize},
     near,
     store::{IterableSet, LookupMap},
 };
@@ -110,6 +110,13 @@ impl Account {
         self.nonces.commit(nonce)
     }
 
+    /// Returns whether a bitmap page for nonces with given prefix
+    /// is allocated
+    #[inline]
+    pub fn has_nonce_page(&self, prefix: NoncePrefix) -> bool {
+        self.nonces.has_prefix(prefix)
+    }
+
     /// Clears the all nonces with corresponding prefix if it was expired/invalidated.
     /// Returns whether the nonces was cleared,
     /// regardless of whether it was previously committed or not.
@@ -137,6 +144,61 @@ impl Account {
             .contains(AccountFlags::AUTH_BY_PREDECESSOR_ID_DISABLED)
     }
 
+    /// Returns estimated number of bytes taken by the account in storage,
+    /// given the number of bitmap pages with its committed nonces.
+    pub fn storage_usage(&self, me: &AccountIdRef, nonce_pages: u32) -> u64 {
+        // Each trie record takes extra storage besides its key and value,
+        // see `storage_num_extra_bytes_record` in NEAR runtime config
+        const RECORD_OVERHEAD: usize = 40;
+        // `u32` index in iterable collections
+        const INDEX_LEN: usize = size_of::<u32>();
+
+        fn object_length(value: &impl BorshSerialize) -> usize {
+            borsh::object_length(value).unwrap_or_default()
+        }
+
+        let prefix = self.prefix.len();
+
+        // entry itself along with its index in accounts map
+        let entry = 2 * RECORD_OVERHEAD + 2 * prefix + me.len() + object_length(self);
+
+        // value and index records in `IterableSet`
+        let public_keys: usize = self
+            .public_keys
+            .iter()
+            .map(|pk| {
+                2 * RECORD_OVERHEAD + 2 * (prefix + 1) + 2 * object_length(pk) + 2 * INDEX_LEN
+            })
+            .sum();
+
+        // value and index records in `IterableMap`
+        let token_balances: usize = self
+            .state
+            .token_balances
+            .iter()
+            .map(|(token_id, _)| {
+                2 * RECORD_OVERHEAD
+                    + 2 * (prefix + 2)
+                    + 2 * object_length(token_id)
+                    + size_of::<u128>()
+                    + 2 * INDEX_LEN
+            })
+            .sum();
+
+        // hashed key and `U256` bitmap
+        let nonces = usize::try_from(nonce_pages)
+            .unwrap_or(usize::MAX)
+            .saturating_mul(RECORD_OVERHEAD + prefix + 1 + 2 * size_of::<U256>());
+
+        u64::try_from(
+            entry
+                .saturating_add(public_keys)
+                .saturating_add(token_balances)
+                .saturating_add(nonces),
+        )
+        .unwrap_or(u64::MAX)
+    }
+
     /// Sets whether authentication by `PREDECESSOR_ID` is enabled.
     /// Returns whether authentication by `PREDECESSOR_ID` was enabled
     /// before.
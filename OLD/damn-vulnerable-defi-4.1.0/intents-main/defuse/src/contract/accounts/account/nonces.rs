@@ -68,6 +68,13 @@ where
                 .is_some_and(|legacy| legacy.is_used(nonce))
     }
 
+    /// Returns whether a page for given prefix is allocated in the new
+    /// map, where new nonces are committed to
+    #[inline]
+    pub fn has_prefix(&self, prefix: NoncePrefix) -> bool {
+        self.nonces.has_prefix(prefix)
+    }
+
     #[inline]
     pub fn cleanup_by_prefix(&mut self, prefix: NoncePrefix) -> bool {
         self.nonces.cleanup_by_prefix(prefix)
@@ -0,0 +1,51 @@
+use defuse_core::{
+    DefuseError, Result, accounts::AccountEvent, engine::StateView, events::DefuseEvent,
+};
+use near_plugins::{AccessControllable, access_control_any};
+use near_sdk::{AccountId, AccountIdRef, assert_one_yocto, near};
+
+use crate::{
+    accounts::AccountDenylist,
+    contract::{Contract, ContractExt, Role},
+};
+
+#[near]
+impl AccountDenylist for Contract {
+    fn is_account_blocked(&self, account_id: &AccountId) -> bool {
+        StateView::is_account_blocked(self, account_id)
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    #[payable]
+    fn block_accounts(&mut self, account_ids: Vec<AccountId>) {
+        assert_one_yocto();
+
+        for account_id in account_ids {
+            if self.denylist.insert(account_id.clone()) {
+                DefuseEvent::AccountBlocked(AccountEvent::new(account_id, ())).emit();
+            }
+        }
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    #[payable]
+    fn unblock_accounts(&mut self, account_ids: Vec<AccountId>) {
+        assert_one_yocto();
+
+        for account_id in account_ids {
+            if self.denylist.remove(&account_id) {
+                DefuseEvent::AccountUnblocked(AccountEvent::new(account_id, ())).emit();
+            }
+        }
+    }
+}
+
+impl Contract {
+    #[inline]
+    pub(crate) fn ensure_not_blocked(&self, account_id: &AccountIdRef) -> Result<()> {
+        if StateView::is_account_blocked(self, account_id) {
+            return Err(DefuseError::AccountBlocked(account_id.to_owned()));
+        }
+        Ok(())
+    }
+}
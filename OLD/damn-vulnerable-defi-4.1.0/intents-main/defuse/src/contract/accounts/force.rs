@@ -1,13 +1,20 @@
+use std::{borrow::Cow, collections::BTreeSet};
+
 use defuse_core::{
-    DefuseError, Result, accounts::AccountEvent, engine::StateView, events::DefuseEvent,
+    DefuseError, Result,
+    accounts::{AccountEvent, AccountLockedEvent},
+    engine::StateView,
+    events::DefuseEvent,
+    payload::multi::SigningStandard,
 };
 use defuse_near_utils::Lock;
 use near_plugins::{AccessControllable, access_control_any};
-use near_sdk::{AccountId, assert_one_yocto, near};
+use near_sdk::{AccountId, assert_one_yocto, near, require};
 
 use crate::{
-    accounts::ForceAccountManager,
+    accounts::{ForceAccountManager, MAX_ACCOUNTS_PER_FORCE_LOCK},
     contract::{Contract, ContractExt, Role},
+    tokens::DepositMessage,
 };
 
 #[near]
@@ -20,15 +27,26 @@ impl ForceAccountManager for Contract {
     #[payable]
     fn force_lock_account(&mut self, account_id: AccountId) -> bool {
         assert_one_yocto();
-        let locked = self
-            .accounts
-            .get_or_create(account_id.clone())
-            .lock()
-            .is_some();
-        if locked {
-            DefuseEvent::AccountLocked(AccountEvent::new(account_id, ())).emit();
-        }
-        locked
+        self.internal_lock_account(account_id, None)
+    }
+
+    #[access_control_any(roles(Role::DAO, Role::UnrestrictedAccountLocker))]
+    #[payable]
+    fn force_lock_accounts(
+        &mut self,
+        account_ids: Vec<AccountId>,
+        reason: Option<String>,
+    ) -> Vec<bool> {
+        assert_one_yocto();
+        require!(
+            account_ids.len() <= MAX_ACCOUNTS_PER_FORCE_LOCK,
+            "too many accounts"
+        );
+
+        account_ids
+            .into_iter()
+            .map(|account_id| self.internal_lock_account(account_id, reason.as_deref()))
+            .collect()
     }
 
     #[access_control_any(roles(Role::DAO, Role::UnrestrictedAccountUnlocker))]
@@ -46,6 +64,17 @@ impl ForceAccountManager for Contract {
         unlocked
     }
 
+    #[access_control_any(roles(Role::DAO, Role::UnrestrictedAccountLocker))]
+    #[payable]
+    fn force_allow_lock_on_deposit(&mut self, account_id: AccountId) -> bool {
+        assert_one_yocto();
+        self.state.lock_on_deposit_allowed.insert(account_id)
+    }
+
+    fn is_lock_on_deposit_allowed(&self, account_id: &AccountId) -> bool {
+        self.state.lock_on_deposit_allowed.contains(account_id)
+    }
+
     #[access_control_any(roles(Role::DAO, Role::UnrestrictedAccountLocker))]
     #[payable]
     fn force_disable_auth_by_predecessor_ids(&mut self, account_ids: Vec<AccountId>) {
@@ -67,9 +96,91 @@ impl ForceAccountManager for Contract {
             let _ = self.internal_set_auth_by_predecessor_id(&account_id, true, true);
         }
     }
+
+    fn disabled_signing_standards(&self, account_id: &AccountId) -> BTreeSet<SigningStandard> {
+        self.state
+            .disabled_signing_standards
+            .get(account_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    #[access_control_any(roles(Role::DAO, Role::UnrestrictedAccountLocker))]
+    #[payable]
+    fn force_disable_signing_standards(
+        &mut self,
+        account_id: AccountId,
+        standards: Vec<SigningStandard>,
+    ) {
+        assert_one_yocto();
+        if standards.is_empty() {
+            return;
+        }
+
+        self.state
+            .disabled_signing_standards
+            .entry(account_id)
+            .or_default()
+            .extend(standards);
+    }
+
+    #[access_control_any(roles(Role::DAO, Role::UnrestrictedAccountUnlocker))]
+    #[payable]
+    fn force_enable_signing_standards(
+        &mut self,
+        account_id: AccountId,
+        standards: Vec<SigningStandard>,
+    ) {
+        assert_one_yocto();
+        let Some(disabled) = self.state.disabled_signing_standards.get_mut(&account_id) else {
+            return;
+        };
+
+        for standard in standards {
+            disabled.remove(&standard);
+        }
+        if disabled.is_empty() {
+            self.state.disabled_signing_standards.remove(&account_id);
+        }
+    }
 }
 
 impl Contract {
+    pub(crate) fn internal_lock_account(
+        &mut self,
+        account_id: AccountId,
+        reason: Option<&str>,
+    ) -> bool {
+        let locked = self
+            .accounts
+            .get_or_create(account_id.clone())
+            .lock()
+            .is_some();
+        if locked {
+            DefuseEvent::AccountLocked(AccountEvent::new(
+                account_id,
+                AccountLockedEvent {
+                    reason: reason.map(Cow::Borrowed),
+                },
+            ))
+            .emit();
+        }
+        locked
+    }
+
+    /// Locks receiver of the deposit if requested in `msg`, consuming
+    /// permission given by [`ForceAccountManager::force_allow_lock_on_deposit`]
+    pub(crate) fn lock_on_deposit(&mut self, msg: &DepositMessage) {
+        if !msg.lock {
+            return;
+        }
+        require!(
+            self.state.lock_on_deposit_allowed.remove(&msg.receiver_id),
+            "lock on deposit is not allowed"
+        );
+        self.internal_lock_account(msg.receiver_id.clone(), None);
+    }
+
     pub(crate) fn internal_set_auth_by_predecessor_id(
         &mut self,
         account_id: &AccountId,
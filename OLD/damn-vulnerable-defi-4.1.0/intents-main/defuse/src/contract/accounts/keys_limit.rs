@@ -0,0 +1,23 @@
+use defuse_core::engine::StateView;
+use near_plugins::{AccessControllable, access_control_any};
+use near_sdk::{assert_one_yocto, near, require};
+
+use crate::{
+    accounts::AccountKeysLimit,
+    contract::{Contract, ContractExt, Role},
+};
+
+#[near]
+impl AccountKeysLimit for Contract {
+    fn max_keys_to_check(&self) -> u32 {
+        StateView::max_keys_to_check(self)
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    #[payable]
+    fn set_max_keys_to_check(&mut self, max_keys_to_check: Option<u32>) {
+        assert_one_yocto();
+        require!(self.state.max_keys_to_check != max_keys_to_check, "same");
+        self.storage.state.max_keys_to_check = max_keys_to_check;
+    }
+}
@@ -1,8 +1,13 @@
 mod account;
+mod denylist;
 mod force;
+mod keys_limit;
+mod registration_reserve;
 mod state;
+mod tokens_limit;
+mod withdrawal_cooldown;
 
-pub use self::{account::*, state::*};
+pub use self::{account::*, state::*, withdrawal_cooldown::WithdrawalCooldown};
 
 use std::{borrow::Cow, collections::HashSet};
 
@@ -14,16 +19,21 @@ use defuse_core::{
     events::DefuseEvent,
 };
 
-use defuse_near_utils::{Lock, NestPrefix, PREDECESSOR_ACCOUNT_ID, UnwrapOrPanic};
+use defuse_near_utils::{
+    Lock, NestPrefix, PREDECESSOR_ACCOUNT_ID, UnwrapOrPanic, UnwrapOrPanicError,
+};
 use defuse_serde_utils::base64::AsBase64;
 
 use near_sdk::{
     AccountId, AccountIdRef, BorshStorageKey, FunctionError, IntoStorageKey, assert_one_yocto,
-    borsh::BorshSerialize, near, store::IterableMap,
+    borsh::BorshSerialize, near, require, store::IterableMap,
 };
 
 use crate::{
-    accounts::AccountManager,
+    accounts::{
+        AccountManager, MAX_ACCOUNTS_PER_QUERY, MAX_NONCES_PER_QUERY, MAX_PUBLIC_KEYS_PER_QUERY,
+        NonceStatus,
+    },
     contract::{Contract, ContractExt, accounts::AccountEntry},
 };
 
@@ -37,6 +47,25 @@ impl AccountManager for Contract {
         StateView::iter_public_keys(self, account_id).collect()
     }
 
+    fn account_public_keys(
+        &self,
+        account_id: &AccountId,
+        from: Option<PublicKey>,
+        limit: u32,
+    ) -> Vec<PublicKey> {
+        let limit: usize = limit.try_into().unwrap_or_panic_display();
+        require!(limit <= MAX_PUBLIC_KEYS_PER_QUERY, "limit is too large");
+        let mut public_keys = StateView::iter_public_keys(self, account_id);
+        // keys are iterated in the order they were added, so skip
+        // everything up to and including the cursor
+        if let Some(from) = from {
+            if !public_keys.any(|public_key| public_key == from) {
+                return Vec::new();
+            }
+        }
+        public_keys.take(limit).collect()
+    }
+
     #[payable]
     fn add_public_key(&mut self, public_key: PublicKey) {
         assert_one_yocto();
@@ -71,21 +100,92 @@ impl AccountManager for Contract {
         StateView::is_nonce_used(self, account_id, nonce.into_inner())
     }
 
+    fn are_nonces_used(&self, account_id: &AccountId, nonces: Vec<AsBase64<Nonce>>) -> Vec<bool> {
+        require!(nonces.len() <= MAX_NONCES_PER_QUERY, "too many nonces");
+        nonces
+            .into_iter()
+            .map(|nonce| StateView::is_nonce_used(self, account_id, nonce.into_inner()))
+            .collect()
+    }
+
+    fn nonce_status(&self, account_id: &AccountId, nonce: AsBase64<Nonce>) -> NonceStatus {
+        let nonce = nonce.into_inner();
+        if StateView::is_nonce_used(self, account_id, nonce) {
+            return NonceStatus::Committed;
+        }
+
+        let [prefix @ .., _] = nonce;
+        if self
+            .state
+            .cleaned_nonce_prefixes
+            .contains(&(account_id.clone(), prefix))
+        {
+            NonceStatus::CleanedExpired
+        } else {
+            NonceStatus::Unused
+        }
+    }
+
     fn is_auth_by_predecessor_id_enabled(&self, account_id: &AccountId) -> bool {
         StateView::is_auth_by_predecessor_id_enabled(self, account_id)
     }
 
+    fn are_auth_by_predecessor_id_enabled(&self, account_ids: Vec<AccountId>) -> Vec<bool> {
+        require!(
+            account_ids.len() <= MAX_ACCOUNTS_PER_QUERY,
+            "too many accounts"
+        );
+        account_ids
+            .iter()
+            .map(|account_id| StateView::is_auth_by_predecessor_id_enabled(self, account_id))
+            .collect()
+    }
+
     #[payable]
     fn disable_auth_by_predecessor_id(&mut self) {
         assert_one_yocto();
-        State::set_auth_by_predecessor_id(self, self.ensure_auth_predecessor_id().clone(), false)
-            .unwrap_or_panic();
+        // disabling only narrows down authentication, so multisig
+        // accounts are allowed to do it as well
+        State::set_auth_by_predecessor_id(
+            self,
+            self.ensure_auth_predecessor_id_enabled().clone(),
+            false,
+        )
+        .unwrap_or_panic();
+    }
+
+    fn account_storage_usage(&self, account_id: &AccountId) -> u64 {
+        self.accounts.get(account_id).map_or(0, |account| {
+            account.as_inner_unchecked().storage_usage(
+                account_id,
+                self.state
+                    .nonce_pages
+                    .get(account_id)
+                    .copied()
+                    .unwrap_or_default(),
+            )
+        })
+    }
+
+    fn nickname(&self, account_id: &AccountId) -> Option<String> {
+        StateView::nickname(self, account_id)
     }
 }
 
 impl Contract {
+    /// Authenticates the predecessor, which is not allowed for multisig
+    /// accounts as it would bypass the threshold
     #[inline]
     pub fn ensure_auth_predecessor_id(&self) -> &'static AccountId {
+        let account_id = self.ensure_auth_predecessor_id_enabled();
+        if StateView::multisig_threshold(self, account_id) > 1 {
+            DefuseError::MultisigApprovalRequired(account_id.clone()).panic();
+        }
+        account_id
+    }
+
+    #[inline]
+    fn ensure_auth_predecessor_id_enabled(&self) -> &'static AccountId {
         if !StateView::is_auth_by_predecessor_id_enabled(self, &PREDECESSOR_ACCOUNT_ID) {
             DefuseError::AuthByPredecessorIdDisabled(PREDECESSOR_ACCOUNT_ID.clone()).panic();
         }
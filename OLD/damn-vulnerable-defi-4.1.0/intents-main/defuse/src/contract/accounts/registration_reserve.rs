@@ -0,0 +1,76 @@
+use defuse_core::{
+    accounts::{AccountEvent, RegistrationReserveLowEvent},
+    events::DefuseEvent,
+    token_id::{TokenId, nep141::Nep141TokenId},
+};
+use near_plugins::{AccessControllable, access_control_any};
+use near_sdk::{AccountId, assert_one_yocto, env, json_types::U128, near, require};
+
+use crate::{
+    accounts::{AccountManager, AccountRegistrationReserve, REGISTRATION_RESERVE_LOW_BALANCE},
+    contract::{Contract, ContractExt, Role},
+};
+
+#[near]
+impl AccountRegistrationReserve for Contract {
+    fn registration_reserve(&self) -> Option<AccountId> {
+        self.state.registration_reserve.clone()
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    #[payable]
+    fn set_registration_reserve(&mut self, reserve_id: Option<AccountId>) {
+        assert_one_yocto();
+        require!(self.state.registration_reserve != reserve_id, "same");
+        self.storage.state.registration_reserve = reserve_id;
+    }
+}
+
+impl Contract {
+    /// Burns storage cost of just registered `account_id` from wNEAR
+    /// balance of the registration reserve, if any. The contract
+    /// covers the cost itself if the reserve can't.
+    pub(crate) fn fund_registration(&mut self, account_id: &AccountId) {
+        let Some(reserve_id) = self.state.registration_reserve.clone() else {
+            return;
+        };
+        if reserve_id == *account_id {
+            return;
+        }
+
+        let wnear = TokenId::from(Nep141TokenId::new(self.state.wnear_id.clone()));
+        let cost = env::storage_byte_cost()
+            .saturating_mul(self.account_storage_usage(account_id).into())
+            .as_yoctonear();
+
+        let mut balance = self
+            .accounts
+            .get(&reserve_id)
+            .map(|reserve| {
+                reserve
+                    .as_inner_unchecked()
+                    .token_balances
+                    .amount_for(&wnear)
+            })
+            .unwrap_or_default();
+
+        if cost > 0
+            && balance >= cost
+            && self
+                .withdraw(&reserve_id, [(wnear, cost)], Some("registration"), true)
+                .is_ok()
+        {
+            balance -= cost;
+        }
+
+        if balance < REGISTRATION_RESERVE_LOW_BALANCE.as_yoctonear() {
+            DefuseEvent::RegistrationReserveLow(AccountEvent::new(
+                reserve_id,
+                RegistrationReserveLowEvent {
+                    balance: U128(balance),
+                },
+            ))
+            .emit();
+        }
+    }
+}
@@ -1,6 +1,6 @@
-use defuse_core::{amounts::Amounts, token_id::TokenId};
+use defuse_core::{DefuseError, Result, amounts::Amounts, token_id::TokenId};
 use defuse_near_utils::NestPrefix;
-use near_sdk::{BorshStorageKey, IntoStorageKey, near, store::IterableMap};
+use near_sdk::{AccountIdRef, BorshStorageKey, IntoStorageKey, near, store::IterableMap};
 
 #[derive(Debug)]
 #[near(serializers = [borsh])]
@@ -23,6 +23,30 @@ impl AccountState {
     }
 }
 
+impl AccountState {
+    /// Makes sure that `token_id` can be credited without exceeding
+    /// `max_tokens` distinct tokens held
+    #[inline]
+    pub fn ensure_can_hold(
+        &self,
+        owner_id: &AccountIdRef,
+        token_id: &TokenId,
+        max_tokens: Option<u32>,
+    ) -> Result<()> {
+        if let Some(max_tokens) = max_tokens {
+            if !self.token_balances.contains_key(token_id)
+                && u32::try_from(self.token_balances.len()).unwrap_or(u32::MAX) >= max_tokens
+            {
+                return Err(DefuseError::TooManyTokensHeld(
+                    owner_id.to_owned(),
+                    max_tokens,
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(BorshStorageKey)]
 #[near(serializers = [borsh])]
 enum AccountStatePrefix {
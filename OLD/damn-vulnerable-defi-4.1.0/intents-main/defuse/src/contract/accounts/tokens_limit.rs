@@ -0,0 +1,25 @@
+use near_plugins::{AccessControllable, access_control_any};
+use near_sdk::{assert_one_yocto, near, require};
+
+use crate::{
+    accounts::AccountTokensLimit,
+    contract::{Contract, ContractExt, Role},
+};
+
+#[near]
+impl AccountTokensLimit for Contract {
+    fn max_tokens_per_account(&self) -> Option<u32> {
+        self.state.max_tokens_per_account
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    #[payable]
+    fn set_max_tokens_per_account(&mut self, max_tokens_per_account: Option<u32>) {
+        assert_one_yocto();
+        require!(
+            self.state.max_tokens_per_account != max_tokens_per_account,
+            "same"
+        );
+        self.storage.state.max_tokens_per_account = max_tokens_per_account;
+    }
+}
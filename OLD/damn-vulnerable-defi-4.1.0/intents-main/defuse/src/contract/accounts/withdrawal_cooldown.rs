@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use defuse_borsh_utils::adapters::{As, TimestampNanoSeconds};
+use defuse_core::{Deadline, DefuseError, Result};
+use near_plugins::{AccessControllable, access_control_any};
+use near_sdk::{AccountId, AccountIdRef, assert_one_yocto, near, require};
+
+use crate::{
+    accounts::AccountWithdrawalCooldown,
+    contract::{Contract, ContractExt, Role},
+};
+
+/// Cooldown between withdrawals of an account
+#[near(serializers = [borsh])]
+#[derive(Debug, Clone, Copy)]
+pub struct WithdrawalCooldown {
+    /// Duration of the cooldown in seconds
+    pub duration: u32,
+
+    /// Time before which next withdrawal is blocked
+    #[borsh(
+        serialize_with = "As::<TimestampNanoSeconds>::serialize",
+        deserialize_with = "As::<TimestampNanoSeconds>::deserialize"
+    )]
+    pub retry_after: Deadline,
+}
+
+#[near]
+impl AccountWithdrawalCooldown for Contract {
+    fn withdrawal_cooldown(&self, account_id: AccountId) -> Option<u32> {
+        self.state
+            .withdrawal_cooldowns
+            .get(&account_id)
+            .map(|cooldown| cooldown.duration)
+    }
+
+    #[access_control_any(roles(Role::DAO, Role::RiskManager))]
+    #[payable]
+    fn set_withdrawal_cooldown(&mut self, account_id: AccountId, cooldown: Option<u32>) {
+        assert_one_yocto();
+        require!(
+            self.withdrawal_cooldown(account_id.clone()) != cooldown,
+            "same"
+        );
+
+        if let Some(duration) = cooldown {
+            // keep already started cooldown, if any
+            self.state
+                .withdrawal_cooldowns
+                .entry(account_id)
+                .or_insert(WithdrawalCooldown {
+                    duration,
+                    retry_after: Deadline::now(),
+                })
+                .duration = duration;
+        } else {
+            self.state.withdrawal_cooldowns.remove(&account_id);
+        }
+    }
+}
+
+impl Contract {
+    /// Fails if the account is on withdrawal cooldown, otherwise starts
+    /// a new one if configured for the account
+    pub(crate) fn start_withdrawal_cooldown(&mut self, account_id: &AccountIdRef) -> Result<()> {
+        let Some(cooldown) = self.state.withdrawal_cooldowns.get_mut(account_id) else {
+            return Ok(());
+        };
+        if cooldown.retry_after > Deadline::now() {
+            return Err(DefuseError::WithdrawalCooldown {
+                retry_after: cooldown.retry_after,
+            });
+        }
+        cooldown.retry_after = Deadline::timeout(Duration::from_secs(cooldown.duration.into()));
+        Ok(())
+    }
+}
@@ -1,7 +1,15 @@
+use std::borrow::Cow;
+
 use defuse_admin_utils::full_access_keys::FullAccessKeys;
-use defuse_near_utils::CURRENT_ACCOUNT_ID;
+use defuse_core::{
+    accounts::{AccountEvent, RoleEvent},
+    events::DefuseEvent,
+};
+use defuse_near_utils::{CURRENT_ACCOUNT_ID, PREDECESSOR_ACCOUNT_ID};
 use near_plugins::{AccessControllable, access_control_any};
-use near_sdk::{Promise, PublicKey, assert_one_yocto, near};
+use near_sdk::{AccountId, Promise, PublicKey, assert_one_yocto, env, near};
+
+use crate::roles::RoleManager;
 
 use super::{Contract, ContractExt, Role};
 
@@ -21,3 +29,44 @@ impl FullAccessKeys for Contract {
         Promise::new(CURRENT_ACCOUNT_ID.clone()).delete_key(public_key)
     }
 }
+
+#[near]
+impl RoleManager for Contract {
+    #[payable]
+    fn grant_role(&mut self, role: String, account_id: AccountId) -> bool {
+        assert_one_yocto();
+        let granted = self
+            .acl_grant_role(role.clone(), account_id.clone())
+            .unwrap_or_else(|| env::panic_str("insufficient permissions"));
+        if granted {
+            DefuseEvent::RoleGranted(AccountEvent::new(
+                account_id,
+                RoleEvent {
+                    role: Cow::Owned(role),
+                    by: Cow::Borrowed(PREDECESSOR_ACCOUNT_ID.as_ref()),
+                },
+            ))
+            .emit();
+        }
+        granted
+    }
+
+    #[payable]
+    fn revoke_role(&mut self, role: String, account_id: AccountId) -> bool {
+        assert_one_yocto();
+        let revoked = self
+            .acl_revoke_role(role.clone(), account_id.clone())
+            .unwrap_or_else(|| env::panic_str("insufficient permissions"));
+        if revoked {
+            DefuseEvent::RoleRevoked(AccountEvent::new(
+                account_id,
+                RoleEvent {
+                    role: Cow::Owned(role),
+                    by: Cow::Borrowed(PREDECESSOR_ACCOUNT_ID.as_ref()),
+                },
+            ))
+            .emit();
+        }
+        revoked
+    }
+}
@@ -3,6 +3,8 @@ use std::collections::{HashMap, HashSet};
 use defuse_core::fees::FeesConfig;
 use near_sdk::{AccountId, near};
 
+use crate::intents::EventVerbosity;
+
 use super::Role;
 
 #[near(serializers = [json])]
@@ -11,6 +13,35 @@ pub struct DefuseConfig {
     pub wnear_id: AccountId,
     pub fees: FeesConfig,
     pub roles: RolesConfig,
+
+    /// Maximum number of distinct tokens a single account can hold.
+    /// Unlimited if not specified.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens_per_account: Option<u32>,
+
+    /// Verbosity of events emitted by `execute_intents`
+    #[serde(default, skip_serializing_if = "EventVerbosity::is_full")]
+    pub event_verbosity: EventVerbosity,
+
+    /// Account which funds storage of accounts registered on their
+    /// first deposit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registration_reserve: Option<AccountId>,
+
+    /// Maximum time-to-live of nonces in seconds.
+    /// Unlimited if not specified.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_nonce_ttl: Option<u32>,
+
+    /// Reject intents with `Deadline::MAX`, so that all of them are
+    /// time-bounded
+    #[serde(default, skip_serializing_if = "::core::ops::Not::not")]
+    pub require_bounded_deadline: bool,
+
+    /// Reject legacy nonces without a salt, so that all of them are
+    /// salted and expire
+    #[serde(default, skip_serializing_if = "::core::ops::Not::not")]
+    pub reject_legacy_nonces: bool,
 }
 
 #[near(serializers = [json])]
@@ -2,13 +2,21 @@ use core::mem;
 use std::borrow::Cow;
 
 use defuse_core::{
+    DefuseError,
     events::DefuseIntentEmit,
     fees::{FeeChangedEvent, FeeCollectorChangedEvent, Pips},
+    intents::token_diff::TokenDiff,
+    token_id::TokenId,
 };
+use defuse_near_utils::{UnwrapOrPanic, UnwrapOrPanicError};
 use near_plugins::{AccessControllable, Pausable, access_control_any, pause};
-use near_sdk::{AccountId, assert_one_yocto, near, require};
+use near_sdk::{
+    AccountId, assert_one_yocto,
+    json_types::{I128, U128},
+    near, require,
+};
 
-use crate::fees::FeesManager;
+use crate::fees::{FeesManager, MAX_COLLECTED_FEES_PER_QUERY};
 
 use super::{Contract, ContractExt, Role};
 
@@ -49,4 +57,71 @@ impl FeesManager for Contract {
     fn fee_collector(&self) -> &AccountId {
         &self.fees.fee_collector
     }
+
+    fn collected_fees(&self, from: Option<TokenId>, limit: u32) -> Vec<(TokenId, U128)> {
+        let limit: usize = limit.try_into().unwrap_or_panic_display();
+        require!(limit <= MAX_COLLECTED_FEES_PER_QUERY, "limit is too large");
+        let mut collected_fees = self.state.collected_fees.iter();
+        // tokens are iterated in the order they were first collected, so
+        // skip everything up to and including the cursor
+        if let Some(from) = from {
+            if !collected_fees.any(|(token_id, _)| *token_id == from) {
+                return Vec::new();
+            }
+        }
+        collected_fees
+            .take(limit)
+            .map(|(token_id, amount)| (token_id.clone(), U128(*amount)))
+            .collect()
+    }
+
+    fn quote_token_diff(
+        &self,
+        token_in: TokenId,
+        amount_in: U128,
+        token_out: TokenId,
+        referral: Option<AccountId>,
+    ) -> I128 {
+        // NOTE: referrals don't take a share of fees, so they don't
+        // affect the quote
+        let _ = referral;
+        require!(token_in != token_out, "same token");
+
+        TokenDiff::quote(&token_in, amount_in.0, &token_out, self.fees.fee)
+            .ok_or(DefuseError::BalanceOverflow)
+            .unwrap_or_panic()
+            .into()
+    }
+
+    #[access_control_any(roles(Role::DAO, Role::FeesManager))]
+    #[payable]
+    fn set_strict_referrals(&mut self, strict: bool) {
+        assert_one_yocto();
+        require!(self.state.strict_referrals != strict, "same");
+        self.state.strict_referrals = strict;
+    }
+
+    fn strict_referrals(&self) -> bool {
+        self.state.strict_referrals
+    }
+
+    #[access_control_any(roles(Role::DAO, Role::FeesManager))]
+    #[payable]
+    fn add_allowed_referrals(&mut self, referrals: Vec<AccountId>) {
+        assert_one_yocto();
+        self.state.referral_allowlist.extend(referrals);
+    }
+
+    #[access_control_any(roles(Role::DAO, Role::FeesManager))]
+    #[payable]
+    fn remove_allowed_referrals(&mut self, referrals: Vec<AccountId>) {
+        assert_one_yocto();
+        for referral in &referrals {
+            self.state.referral_allowlist.remove(referral);
+        }
+    }
+
+    fn is_referral_allowlisted(&self, referral: AccountId) -> bool {
+        self.state.referral_allowlist.contains(&referral)
+    }
 }
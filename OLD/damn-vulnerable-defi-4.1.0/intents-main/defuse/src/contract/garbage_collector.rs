@@ -1,7 +1,8 @@
-use defuse_core::{ExpirableNonce, Nonce, SaltedNonce, VersionedNonce, engine::State};
+use defuse_core::{Nonce, VersionedNonce, engine::State};
+use defuse_near_utils::time;
 use defuse_serde_utils::base64::AsBase64;
 use near_plugins::{AccessControllable, access_control_any};
-use near_sdk::{AccountId, assert_one_yocto, near};
+use near_sdk::{AccountId, assert_one_yocto, near, require};
 
 use super::{Contract, ContractExt, Role};
 use crate::{garbage_collector::GarbageCollector, salts::SaltManager};
@@ -15,30 +16,67 @@ impl GarbageCollector for Contract {
 
         for (account_id, nonces) in nonces {
             for nonce in nonces.into_iter().map(AsBase64::into_inner) {
-                if !self.is_nonce_cleanable(nonce) {
+                if !self.is_nonce_cleanable(&account_id, nonce) {
                     continue;
                 }
 
                 // NOTE: all errors are omitted
                 let [prefix @ .., _] = nonce;
-                let _ = State::cleanup_nonce_by_prefix(self, &account_id, prefix);
+                if matches!(
+                    State::cleanup_nonce_by_prefix(self, &account_id, prefix),
+                    Ok(true)
+                ) {
+                    self.state
+                        .cleaned_nonce_prefixes
+                        .insert((account_id.clone(), prefix));
+                }
+                self.state
+                    .nonce_committed_at
+                    .remove(&(account_id.clone(), prefix));
             }
         }
     }
+
+    fn unexpirable_nonce_max_age(&self) -> Option<u32> {
+        self.state.unexpirable_nonce_max_age
+    }
+
+    #[access_control_any(roles(Role::DAO, Role::GarbageCollector))]
+    #[payable]
+    fn set_unexpirable_nonce_max_age(&mut self, max_age: Option<u32>) {
+        assert_one_yocto();
+        require!(self.state.unexpirable_nonce_max_age != max_age, "same");
+        self.storage.state.unexpirable_nonce_max_age = max_age;
+    }
 }
 
 impl Contract {
     #[inline]
-    fn is_nonce_cleanable(&self, nonce: Nonce) -> bool {
-        let Some(versioned_nonce) = VersionedNonce::maybe_from(nonce) else {
+    fn is_nonce_cleanable(&self, account_id: &AccountId, nonce: Nonce) -> bool {
+        if let Some(versioned_nonce) = VersionedNonce::maybe_from(nonce) {
+            if versioned_nonce.deadline().has_expired()
+                || !self.is_valid_salt(versioned_nonce.salt())
+            {
+                return true;
+            }
+        }
+
+        // legacy nonces and nonces with `Deadline::MAX` never expire,
+        // so they are cleaned up once old enough
+        self.is_unexpirable_nonce_stale(account_id, nonce)
+    }
+
+    fn is_unexpirable_nonce_stale(&self, account_id: &AccountId, nonce: Nonce) -> bool {
+        let Some(max_age) = self.state.unexpirable_nonce_max_age else {
             return false;
         };
-
-        match versioned_nonce {
-            VersionedNonce::V1(SaltedNonce {
-                salt,
-                nonce: ExpirableNonce { deadline, .. },
-            }) => deadline.has_expired() || !self.is_valid_salt(salt),
-        }
+        let [prefix @ .., _] = nonce;
+        self.state
+            .nonce_committed_at
+            .get(&(account_id.clone(), prefix))
+            .is_some_and(|committed_at| {
+                time::block_timestamp().saturating_sub(*committed_at)
+                    >= u64::from(max_age).saturating_mul(1_000_000_000)
+            })
     }
 }
@@ -0,0 +1,115 @@
+use std::collections::BTreeSet;
+
+use defuse_core::{
+    DefuseError,
+    intents::DefuseIntents,
+    payload::{DefusePayload, multi::MultiPayload},
+};
+use defuse_near_utils::{CURRENT_ACCOUNT_ID, UnwrapOrPanic};
+use near_plugins::{AccessControllable, Pausable, access_control_any, pause};
+use near_sdk::{
+    AccountId, FunctionError, Gas, Promise, PromiseResult, assert_one_yocto, env, json_types::U128,
+    near, require, serde_json,
+};
+
+use crate::{
+    contract::{Contract, ContractExt, Role},
+    intents::{ConditionalIntents, execute_intents_min_gas, ext_price_oracle},
+};
+
+#[near]
+impl ConditionalIntents for Contract {
+    #[pause(name = "intents")]
+    fn execute_conditional_intents(&mut self, signed: Vec<MultiPayload>) -> Promise {
+        let oracle_id = self
+            .state
+            .price_oracle
+            .clone()
+            .unwrap_or_else(|| env::panic_str("price oracle is not set"));
+
+        let mut assets = BTreeSet::new();
+        for signed in signed.iter().cloned() {
+            for DefusePayload { message, .. } in signed
+                .extract_defuse_payloads::<DefuseIntents>()
+                .map_err(DefuseError::from)
+                .unwrap_or_panic()
+            {
+                assets.extend(message.intents.iter().flat_map(|intent| {
+                    intent
+                        .price_conditions()
+                        .map(|condition| condition.asset.clone())
+                }));
+            }
+        }
+        let assets: Vec<String> = assets.into_iter().collect();
+        require!(!assets.is_empty(), "no conditional intents");
+
+        let execute_gas = execute_intents_min_gas(&signed);
+        let required = execute_gas.saturating_add(
+            Self::GET_PRICE_MIN_GAS.saturating_mul(u64::try_from(assets.len()).unwrap_or(u64::MAX)),
+        );
+        if env::prepaid_gas() < required {
+            DefuseError::InsufficientGas { required }.panic();
+        }
+
+        self.check_relayer_key(&signed).unwrap_or_panic();
+
+        assets
+            .iter()
+            .map(|asset| {
+                ext_price_oracle::ext(oracle_id.clone())
+                    .with_static_gas(Self::GET_PRICE_MIN_GAS)
+                    .get_price(asset.clone())
+            })
+            .reduce(Promise::and)
+            .unwrap_or_else(|| unreachable!())
+            .then(
+                Self::ext(CURRENT_ACCOUNT_ID.clone())
+                    .with_static_gas(execute_gas)
+                    .do_execute_conditional_intents(signed, assets),
+            )
+    }
+
+    fn price_oracle(&self) -> Option<AccountId> {
+        self.state.price_oracle.clone()
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    #[payable]
+    fn set_price_oracle(&mut self, oracle_id: Option<AccountId>) {
+        assert_one_yocto();
+        require!(self.state.price_oracle != oracle_id, "same");
+        self.state.price_oracle = oracle_id;
+    }
+}
+
+#[near]
+impl Contract {
+    const GET_PRICE_MIN_GAS: Gas = Gas::from_tgas(5);
+
+    /// Executes signed intents with prices of `assets` returned by the
+    /// oracle. Prices which failed to be read are left unavailable, so
+    /// that intents conditioned on them fail.
+    #[private]
+    #[pause(name = "intents")]
+    pub fn do_execute_conditional_intents(
+        &mut self,
+        signed: Vec<MultiPayload>,
+        assets: Vec<String>,
+    ) {
+        // relayer key could have expired or been rescoped while
+        // prices were being read
+        self.check_relayer_key(&signed).unwrap_or_panic();
+
+        for (result_idx, asset) in (0..).zip(assets) {
+            let PromiseResult::Successful(data) = env::promise_result(result_idx) else {
+                continue;
+            };
+            if let Ok(Some(U128(price))) = serde_json::from_slice::<Option<U128>>(&data) {
+                self.runtime.oracle_prices.insert(asset, price);
+            }
+        }
+
+        self.execute_signed_intents(signed);
+    }
+}
@@ -0,0 +1,97 @@
+use defuse_core::{crypto::PublicKey, engine::StateView};
+use near_plugins::{AccessControllable, access_control_any};
+use near_sdk::{AccountId, assert_one_yocto, near, require};
+
+use crate::{
+    contract::{Contract, ContractExt, Role},
+    intents::Eip1271Attesters,
+};
+
+#[near]
+impl Eip1271Attesters for Contract {
+    #[access_control_any(roles(Role::DAO))]
+    #[payable]
+    fn add_eip1271_attester(&mut self, public_key: PublicKey) {
+        assert_one_yocto();
+        require!(
+            self.state.eip1271_attesters.insert(public_key),
+            "attester already exists",
+        );
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    #[payable]
+    fn remove_eip1271_attester(&mut self, public_key: PublicKey) {
+        assert_one_yocto();
+        require!(
+            self.state.eip1271_attesters.remove(&public_key),
+            "attester not found",
+        );
+    }
+
+    fn eip1271_attesters(&self) -> Vec<PublicKey> {
+        self.state.eip1271_attesters.iter().copied().collect()
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    #[payable]
+    fn set_eip1271_chain_id(&mut self, chain_id: Option<u64>) {
+        assert_one_yocto();
+        require!(self.state.eip1271_chain_id != chain_id, "same");
+        self.state.eip1271_chain_id = chain_id;
+    }
+
+    fn eip1271_chain_id(&self) -> Option<u64> {
+        self.state.eip1271_chain_id
+    }
+
+    #[payable]
+    fn opt_in_eip1271_attester(&mut self, public_key: PublicKey) {
+        assert_one_yocto();
+        let account_id = self.ensure_auth_predecessor_id().clone();
+        self.set_eip1271_opt_in_unchecked(account_id, public_key, true);
+    }
+
+    #[payable]
+    fn opt_out_eip1271_attester(&mut self, public_key: PublicKey) {
+        assert_one_yocto();
+        let account_id = self.ensure_auth_predecessor_id().clone();
+        self.set_eip1271_opt_in_unchecked(account_id, public_key, false);
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    #[payable]
+    fn set_eip1271_opt_in(&mut self, account_id: AccountId, public_key: PublicKey, opted_in: bool) {
+        assert_one_yocto();
+        self.set_eip1271_opt_in_unchecked(account_id, public_key, opted_in);
+    }
+
+    fn is_eip1271_opted_in(&self, account_id: AccountId, public_key: PublicKey) -> bool {
+        StateView::is_eip1271_opted_in(self, &account_id, &public_key)
+    }
+}
+
+impl Contract {
+    fn set_eip1271_opt_in_unchecked(
+        &mut self,
+        account_id: AccountId,
+        public_key: PublicKey,
+        opted_in: bool,
+    ) {
+        if !opted_in {
+            require!(
+                self.state.eip1271_opt_ins.remove(&(account_id, public_key)),
+                "not opted in",
+            );
+            return;
+        }
+        require!(
+            self.state.eip1271_attesters.contains(&public_key),
+            "attester not found",
+        );
+        require!(
+            self.state.eip1271_opt_ins.insert((account_id, public_key)),
+            "already opted in",
+        );
+    }
+}
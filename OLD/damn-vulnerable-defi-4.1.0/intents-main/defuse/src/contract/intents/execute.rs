@@ -6,17 +6,32 @@ use defuse_core::{
 };
 use near_sdk::{AccountIdRef, CryptoHash};
 
+use crate::intents::EventVerbosity;
+
 #[derive(Debug, Default)]
 pub struct ExecuteInspector {
+    pub verbosity: EventVerbosity,
     pub intents_executed: Vec<IntentEvent<AccountEvent<'static, NonceEvent>>>,
 }
 
+impl ExecuteInspector {
+    #[inline]
+    pub const fn new(verbosity: EventVerbosity) -> Self {
+        Self {
+            verbosity,
+            intents_executed: Vec::new(),
+        }
+    }
+}
+
 impl Inspector for ExecuteInspector {
     #[inline]
     fn on_deadline(&mut self, _deadline: Deadline) {}
 
     fn on_event(&mut self, event: DefuseEvent<'_>) {
-        event.emit();
+        if self.verbosity.allows(&event) {
+            event.emit();
+        }
     }
 
     #[inline]
@@ -25,9 +40,13 @@ impl Inspector for ExecuteInspector {
         signer_id: &AccountIdRef,
         intent_hash: CryptoHash,
         nonce: Nonce,
+        client_ref: Option<&str>,
     ) {
         self.intents_executed.push(IntentEvent::new(
-            AccountEvent::new(Cow::Owned(signer_id.to_owned()), NonceEvent::new(nonce)),
+            AccountEvent::new(
+                Cow::Owned(signer_id.to_owned()),
+                NonceEvent::new(nonce).with_client_ref(client_ref.map(ToOwned::to_owned)),
+            ),
             intent_hash,
         ));
     }
@@ -35,8 +54,12 @@ impl Inspector for ExecuteInspector {
 
 impl Drop for ExecuteInspector {
     fn drop(&mut self) {
-        if !self.intents_executed.is_empty() {
-            DefuseEvent::IntentsExecuted(self.intents_executed.as_slice().into()).emit();
+        if self.intents_executed.is_empty() {
+            return;
+        }
+        let event = DefuseEvent::IntentsExecuted(self.intents_executed.as_slice().into());
+        if self.verbosity.allows(&event) {
+            event.emit();
         }
     }
 }
@@ -0,0 +1,62 @@
+use std::collections::VecDeque;
+
+use near_sdk::{Gas, near};
+
+use crate::intents::GasReport;
+
+/// Gas burnt by the most recent `execute_intents` calls
+#[near(serializers = [borsh])]
+#[derive(Debug, Default)]
+pub struct RecentGasUsage(VecDeque<Gas>);
+
+impl RecentGasUsage {
+    /// Number of most recent calls to aggregate
+    pub const WINDOW: usize = 64;
+
+    pub fn record(&mut self, gas: Gas) {
+        if self.0.len() >= Self::WINDOW {
+            self.0.pop_front();
+        }
+        self.0.push_back(gas);
+    }
+
+    pub fn report(&self) -> GasReport {
+        GasReport {
+            calls: u32::try_from(self.0.len()).unwrap_or(u32::MAX),
+            total_gas: self
+                .0
+                .iter()
+                .fold(Gas::from_gas(0), |total, gas| total.saturating_add(*gas)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_most_recent_calls() {
+        let mut usage = RecentGasUsage::default();
+        assert_eq!(
+            usage.report(),
+            GasReport {
+                calls: 0,
+                total_gas: Gas::from_gas(0),
+            }
+        );
+
+        for _ in 0..RecentGasUsage::WINDOW {
+            usage.record(Gas::from_tgas(1));
+        }
+        usage.record(Gas::from_tgas(2));
+
+        assert_eq!(
+            usage.report(),
+            GasReport {
+                calls: RecentGasUsage::WINDOW.try_into().unwrap(),
+                total_gas: Gas::from_tgas(u64::try_from(RecentGasUsage::WINDOW).unwrap() + 1),
+            }
+        );
+    }
+}
@@ -1,24 +1,31 @@
 mod auth_call;
+mod conditional;
+mod eip1271;
 mod execute;
-mod relayer;
+pub mod gas_report;
+pub mod relayer;
 pub mod simulate;
 mod state;
+pub mod stored;
 
 use defuse_core::{
-    DefuseError,
-    engine::{Engine, StateView},
-    payload::multi::MultiPayload,
+    DefuseError, Result,
+    engine::{DetailedExecution, Engine, StateView, deltas::InvariantViolated},
+    intents::{DefuseIntents, Intent, token_diff::TokenDiff},
+    payload::{DefusePayload, ExtractDefusePayload, multi::MultiPayload},
 };
 use defuse_near_utils::UnwrapOrPanic;
 use defuse_nep245::MtEvent;
 use execute::ExecuteInspector;
-use near_plugins::{Pausable, pause};
-use near_sdk::{FunctionError, near};
+use gas_report::RecentGasUsage;
+use near_plugins::{AccessControllable, Pausable, access_control_any, pause};
+use near_sdk::{FunctionError, Gas, assert_one_yocto, env, near, require};
 use simulate::SimulateInspector;
 
 use crate::{
-    intents::Intents,
-    simulation_output::{SimulationOutput, StateOutput},
+    contract::Role,
+    intents::{EventVerbosity, GasReport, Intents, execute_intents_min_gas},
+    simulation_output::{DetailedSimulationOutput, SimulationOutput, StateOutput},
 };
 
 use super::{Contract, ContractExt};
@@ -28,12 +35,29 @@ impl Intents for Contract {
     #[pause(name = "intents")]
     #[inline]
     fn execute_intents(&mut self, signed: Vec<MultiPayload>) {
-        Engine::new(self, ExecuteInspector::default())
-            .execute_signed_intents(signed)
-            .unwrap_or_panic()
-            .as_mt_event()
-            .as_ref()
-            .map(MtEvent::emit);
+        // fail early rather than in the middle of execution
+        let required = execute_intents_min_gas(&signed);
+        if env::prepaid_gas() < required {
+            DefuseError::InsufficientGas { required }.panic();
+        }
+        Self::check_promises_gas(&signed, env::prepaid_gas().saturating_sub(required))
+            .unwrap_or_panic();
+
+        self.check_relayer_key(&signed).unwrap_or_panic();
+
+        self.execute_signed_intents(signed);
+
+        self.state
+            .recent_gas_usage
+            .get_mut()
+            .get_or_insert_with(Default::default)
+            .record(env::used_gas());
+    }
+
+    #[pause(name = "intents")]
+    #[inline]
+    fn execute_intents_borsh(&mut self, #[serializer(borsh)] signed: Vec<MultiPayload>) {
+        self.execute_intents(signed);
     }
 
     #[pause(name = "intents")]
@@ -58,4 +82,127 @@ impl Intents for Contract {
             },
         }
     }
+
+    #[pause(name = "intents")]
+    fn simulate_intents_detailed(&self, signed: Vec<MultiPayload>) -> DetailedSimulationOutput {
+        let DetailedExecution {
+            outcomes,
+            result,
+            inspector,
+        } = Engine::new(self.cached(), SimulateInspector::default())
+            .execute_signed_intents_detailed(signed);
+
+        let invariant_violated = match result {
+            Ok(_) => None,
+            Err(DefuseError::InvariantViolated(v)) => Some(v),
+            Err(err) => err.panic(),
+        };
+
+        DetailedSimulationOutput {
+            outcomes: outcomes
+                .into_iter()
+                .map(|outcome| outcome.map_err(|err| err.to_string()))
+                .collect(),
+            output: SimulationOutput {
+                report: inspector.into_report(),
+                invariant_violated,
+                state: StateOutput {
+                    fee: self.fee(),
+                    current_salt: self.salts.current(),
+                },
+            },
+        }
+    }
+
+    #[pause(name = "intents")]
+    fn check_batch_consistency(&self, signed: Vec<MultiPayload>) -> Option<InvariantViolated> {
+        let mut diffs = Vec::new();
+        for signed in signed {
+            for DefusePayload {
+                signer_id, message, ..
+            } in signed
+                .extract_defuse_payloads::<DefuseIntents>()
+                .map_err(DefuseError::from)
+                .unwrap_or_panic()
+            {
+                diffs.extend(
+                    message
+                        .intents
+                        .into_iter()
+                        .filter_map(|intent| match intent {
+                            Intent::TokenDiff(diff) if !diff.partial_fill => {
+                                Some((signer_id.clone(), diff))
+                            }
+                            _ => None,
+                        }),
+                );
+            }
+        }
+
+        TokenDiff::check_closure(diffs, self.fee(), &self.state.fees.fee_collector).err()
+    }
+
+    fn decode_payload(&self, signed: MultiPayload) -> DefusePayload<DefuseIntents> {
+        signed
+            .extract_defuse_payload()
+            .map_err(DefuseError::from)
+            .unwrap_or_panic()
+    }
+
+    fn execute_intents_gas_report(&self) -> GasReport {
+        self.state.recent_gas_usage.get().as_ref().map_or_else(
+            || RecentGasUsage::default().report(),
+            RecentGasUsage::report,
+        )
+    }
+
+    fn event_verbosity(&self) -> EventVerbosity {
+        self.state.event_verbosity
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    #[payable]
+    fn set_event_verbosity(&mut self, verbosity: EventVerbosity) {
+        assert_one_yocto();
+        require!(self.state.event_verbosity != verbosity, "same");
+        self.state.event_verbosity = verbosity;
+    }
+}
+
+impl Contract {
+    /// Executes signed intents and emits NEP-245 events for transfers
+    /// made by them
+    fn execute_signed_intents(&mut self, signed: impl IntoIterator<Item = MultiPayload>) {
+        let inspector = ExecuteInspector::new(self.state.event_verbosity);
+        Engine::new(&mut *self, inspector)
+            .execute_signed_intents(signed)
+            .unwrap_or_panic()
+            .as_mt_event()
+            .as_ref()
+            .map(MtEvent::emit);
+    }
+
+    /// Makes sure `min_gas` explicitly requested by all of the intents
+    /// in the batch fits into `available` gas, so that it fails with
+    /// a meaningful error rather than by exceeding the prepaid gas.
+    ///
+    /// NOTE: defaults applied to intents without `min_gas` aren't
+    /// accounted, while payloads which can't be extracted are left for
+    /// the engine to reject
+    fn check_promises_gas(signed: &[MultiPayload], available: Gas) -> Result<()> {
+        let requested = signed
+            .iter()
+            .cloned()
+            .filter_map(|signed| signed.extract_defuse_payloads::<DefuseIntents>().ok())
+            .flatten()
+            .map(|payload| payload.message.promises_min_gas())
+            .fold(Gas::from_gas(0), Gas::saturating_add);
+        if requested > available {
+            return Err(DefuseError::GasRequestExceedsPrepaid {
+                requested,
+                available,
+            });
+        }
+        Ok(())
+    }
 }
@@ -1,14 +1,31 @@
+use defuse_borsh_utils::adapters::{As, TimestampNanoSeconds};
+use defuse_core::{
+    Deadline, DefuseError, Result,
+    intents::DefuseIntents,
+    payload::{DefusePayload, multi::MultiPayload},
+};
 use defuse_near_utils::{CURRENT_ACCOUNT_ID, method_name};
 use near_plugins::{AccessControllable, Pausable, access_control_any, pause};
-use near_sdk::{Allowance, Promise, PublicKey, assert_one_yocto, near, require};
+use near_sdk::{Allowance, Promise, PublicKey, assert_one_yocto, env, near, require};
 
 use crate::{
     contract::{Contract, ContractExt, Role},
-    intents::{Intents, RelayerKeys},
+    intents::{Intents, RelayerKeyScope, RelayerKeys},
 };
 
 const EXECUTE_INTENTS_FUNC: &str = method_name!(Contract::execute_intents);
 
+/// Deadline after which a relayer key can no longer be used
+#[near(serializers = [borsh])]
+#[derive(Debug, Clone, Copy)]
+pub struct RelayerKeyExpiry {
+    #[borsh(
+        serialize_with = "As::<TimestampNanoSeconds>::serialize",
+        deserialize_with = "As::<TimestampNanoSeconds>::deserialize"
+    )]
+    pub expires_at: Deadline,
+}
+
 #[near]
 impl RelayerKeys for Contract {
     #[pause(name = "intents")]
@@ -28,7 +45,11 @@ impl RelayerKeys for Contract {
 
     #[private]
     fn do_add_relayer_key(&mut self, public_key: PublicKey) {
-        require!(self.relayer_keys.insert(public_key), "key already exists",);
+        require!(
+            self.relayer_keys.insert(public_key.clone()),
+            "key already exists",
+        );
+        self.state.relayer_keys_index.insert(public_key);
     }
 
     #[pause(name = "intents")]
@@ -37,7 +58,104 @@ impl RelayerKeys for Contract {
     fn delete_relayer_key(&mut self, public_key: PublicKey) -> Promise {
         assert_one_yocto();
         require!(self.relayer_keys.remove(&public_key), "key not found");
+        self.state.relayer_keys_index.remove(&public_key);
+        self.state.relayer_key_scopes.remove(&public_key);
+        self.state.relayer_key_expiries.remove(&public_key);
 
         Promise::new(CURRENT_ACCOUNT_ID.clone()).delete_key(public_key)
     }
+
+    fn relayer_keys(&self) -> Vec<PublicKey> {
+        self.state.relayer_keys_index.iter().cloned().collect()
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    #[payable]
+    fn index_relayer_keys(&mut self, public_keys: Vec<PublicKey>) {
+        assert_one_yocto();
+        for public_key in public_keys {
+            require!(self.relayer_keys.contains(&public_key), "key not found");
+            self.state.relayer_keys_index.insert(public_key);
+        }
+    }
+
+    #[pause(name = "intents")]
+    #[access_control_any(roles(Role::DAO, Role::RelayerKeysManager))]
+    #[payable]
+    fn set_relayer_key_scope(&mut self, public_key: PublicKey, scope: Option<RelayerKeyScope>) {
+        assert_one_yocto();
+        require!(self.relayer_keys.contains(&public_key), "key not found");
+
+        if let Some(scope) = scope {
+            self.state.relayer_key_scopes.insert(public_key, scope);
+        } else {
+            self.state.relayer_key_scopes.remove(&public_key);
+        }
+    }
+
+    fn relayer_key_scope(&self, public_key: PublicKey) -> Option<RelayerKeyScope> {
+        self.state.relayer_key_scopes.get(&public_key).cloned()
+    }
+
+    #[pause(name = "intents")]
+    #[access_control_any(roles(Role::DAO, Role::RelayerKeysManager))]
+    #[payable]
+    fn set_relayer_key_expiry(&mut self, public_key: PublicKey, expires_at: Option<Deadline>) {
+        assert_one_yocto();
+        require!(self.relayer_keys.contains(&public_key), "key not found");
+
+        if let Some(expires_at) = expires_at {
+            self.state
+                .relayer_key_expiries
+                .insert(public_key, RelayerKeyExpiry { expires_at });
+        } else {
+            self.state.relayer_key_expiries.remove(&public_key);
+        }
+    }
+
+    fn relayer_key_expiry(&self, public_key: PublicKey) -> Option<Deadline> {
+        self.state
+            .relayer_key_expiries
+            .get(&public_key)
+            .map(|expiry| expiry.expires_at)
+    }
+}
+
+impl Contract {
+    /// Ensures that intents relayed with a relayer key are within
+    /// its validity period and don't go beyond its scope
+    pub(crate) fn check_relayer_key(&self, signed: &[MultiPayload]) -> Result<()> {
+        if env::signer_account_id() != *CURRENT_ACCOUNT_ID {
+            return Ok(());
+        }
+        let public_key = env::signer_account_pk();
+
+        if self
+            .state
+            .relayer_key_expiries
+            .get(&public_key)
+            .is_some_and(|expiry| expiry.expires_at.has_expired())
+        {
+            return Err(DefuseError::RelayerKeyExpired);
+        }
+
+        let Some(scope) = self.state.relayer_key_scopes.get(&public_key) else {
+            return Ok(());
+        };
+
+        for payload in signed {
+            for DefusePayload::<DefuseIntents> {
+                signer_id,
+                message: intents,
+                ..
+            } in payload.clone().extract_defuse_payloads()?
+            {
+                if !scope.allows(&signer_id, &intents) {
+                    return Err(DefuseError::RelayerKeyOutOfScope);
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
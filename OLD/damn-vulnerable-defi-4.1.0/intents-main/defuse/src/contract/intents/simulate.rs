@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, mem};
 
 use defuse_core::{
     Deadline, Nonce,
@@ -7,14 +7,19 @@ use defuse_core::{
     events::DefuseEvent,
     intents::IntentEvent,
 };
-use near_sdk::{AccountIdRef, CryptoHash, serde_json::Value as JsonValue};
+use near_sdk::{AccountIdRef, CryptoHash, Gas, env, serde_json::Value as JsonValue};
 
 use crate::simulation_output::SimulationReport;
 
+#[derive(Clone)]
 pub struct SimulateInspector {
     intents_executed: Vec<IntentEvent<AccountEvent<'static, NonceEvent>>>,
     recorded_events: Vec<JsonValue>,
     min_deadline: Deadline,
+    gas_estimates: Vec<Gas>,
+    /// Gas used so far when the previous intent was executed
+    used_gas: Gas,
+    promises_min_gas: Gas,
 }
 
 impl SimulateInspector {
@@ -33,6 +38,7 @@ impl SimulateInspector {
                 .collect(),
             intents_executed: self.intents_executed,
             min_deadline: self.min_deadline,
+            gas_estimates: self.gas_estimates,
         }
     }
 }
@@ -43,6 +49,9 @@ impl Default for SimulateInspector {
             intents_executed: Vec::new(),
             min_deadline: Deadline::MAX,
             recorded_events: Vec::new(),
+            gas_estimates: Vec::new(),
+            used_gas: env::used_gas(),
+            promises_min_gas: Gas::from_gas(0),
         }
     }
 }
@@ -57,16 +66,35 @@ impl Inspector for SimulateInspector {
         self.recorded_events.push(event.to_json());
     }
 
+    #[inline]
+    fn on_promises_min_gas(&mut self, min_gas: Gas) {
+        self.promises_min_gas = min_gas;
+    }
+
     #[inline]
     fn on_intent_executed(
         &mut self,
         signer_id: &AccountIdRef,
         intent_hash: CryptoHash,
         nonce: Nonce,
+        client_ref: Option<&str>,
     ) {
         self.intents_executed.push(IntentEvent::new(
-            AccountEvent::new(signer_id.to_owned(), NonceEvent::new(nonce)),
+            AccountEvent::new(
+                signer_id.to_owned(),
+                NonceEvent::new(nonce).with_client_ref(client_ref.map(ToOwned::to_owned)),
+            ),
             intent_hash,
         ));
+
+        // simulation can't create promises, so their gas is added
+        // to the gas burnt since the previous intent
+        let used_gas = env::used_gas();
+        self.gas_estimates.push(
+            used_gas
+                .saturating_sub(self.used_gas)
+                .saturating_add(mem::take(&mut self.promises_min_gas)),
+        );
+        self.used_gas = used_gas;
     }
 }
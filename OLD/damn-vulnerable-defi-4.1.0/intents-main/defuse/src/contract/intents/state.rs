@@ -1,23 +1,30 @@
 use defuse_core::{
-    DefuseError, Nonce, NoncePrefix, Result, Salt,
+    Deadline, DefuseError, Nonce, NoncePrefix, Result, Salt, VersionedNonce,
     amounts::Amounts,
     crypto::PublicKey,
     engine::{State, StateView},
     fees::Pips,
     intents::{
+        account::RevokeBefore,
         auth::AuthCall,
+        session::Session,
         tokens::{
             FtWithdraw, MtWithdraw, NativeWithdraw, NftWithdraw, NotifyOnTransfer, StorageDeposit,
         },
     },
+    payload::multi::SigningStandard,
     token_id::{TokenId, nep141::Nep141TokenId},
 };
-use defuse_near_utils::{CURRENT_ACCOUNT_ID, Lock};
+use defuse_near_utils::{CURRENT_ACCOUNT_ID, Lock, time};
 use defuse_wnear::{NEAR_WITHDRAW_GAS, ext_wnear};
-use near_sdk::{AccountId, AccountIdRef, NearToken, json_types::U128};
-use std::borrow::Cow;
+use near_sdk::{AccountId, AccountIdRef, Gas, NearToken, json_types::U128};
+use std::{borrow::Cow, time::Duration};
 
-use crate::contract::{Contract, accounts::Account};
+use crate::{
+    accounts::DEFAULT_MAX_KEYS_TO_CHECK,
+    contract::{Contract, accounts::Account},
+    tokens::nep245::MultiTokenOnTransferGas,
+};
 
 impl StateView for Contract {
     #[inline]
@@ -86,6 +93,11 @@ impl StateView for Contract {
         self.accounts.get(account_id).is_some_and(Lock::is_locked)
     }
 
+    #[inline]
+    fn is_account_blocked(&self, account_id: &AccountIdRef) -> bool {
+        self.state.denylist.contains(account_id)
+    }
+
     #[inline]
     fn is_auth_by_predecessor_id_enabled(&self, account_id: &AccountIdRef) -> bool {
         self.accounts
@@ -94,8 +106,131 @@ impl StateView for Contract {
             .is_none_or(Account::is_auth_by_predecessor_id_enabled)
     }
 
+    #[inline]
+    fn is_signing_standard_disabled(
+        &self,
+        account_id: &AccountIdRef,
+        standard: SigningStandard,
+    ) -> bool {
+        self.state
+            .disabled_signing_standards
+            .get(account_id)
+            .is_some_and(|disabled| disabled.contains(&standard))
+    }
+
     fn is_valid_salt(&self, salt: Salt) -> bool {
-        self.salts.is_valid(salt)
+        self.salts.is_valid_within(
+            salt,
+            self.state
+                .salt_grace_period
+                .map(|secs| Duration::from_secs(secs.into())),
+        )
+    }
+
+    #[inline]
+    fn session(&self, account_id: &AccountIdRef, public_key: &PublicKey) -> Option<Session> {
+        self.state
+            .sessions
+            .get(&(account_id.to_owned(), *public_key))
+            .cloned()
+    }
+
+    #[inline]
+    fn multisig_threshold(&self, account_id: &AccountIdRef) -> u8 {
+        self.state
+            .multisig_thresholds
+            .get(account_id)
+            .copied()
+            .unwrap_or(1)
+    }
+
+    #[inline]
+    fn revoked_before(&self, account_id: &AccountIdRef) -> Option<Deadline> {
+        self.state
+            .revoked_before
+            .get(account_id)
+            .map(|revoke| revoke.timestamp)
+    }
+
+    #[inline]
+    fn default_min_gas(&self, account_id: &AccountIdRef) -> Option<Gas> {
+        self.state.default_min_gas.get(account_id).copied()
+    }
+
+    #[inline]
+    fn nickname(&self, account_id: &AccountIdRef) -> Option<String> {
+        self.state.nicknames.get(account_id).cloned()
+    }
+
+    #[inline]
+    fn partially_filled(&self, account_id: &AccountIdRef, nonce: Nonce) -> u128 {
+        self.state
+            .partially_filled
+            .get(&(account_id.to_owned(), nonce))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    fn is_eip1271_attester(&self, public_key: &PublicKey) -> bool {
+        self.state.eip1271_attesters.contains(public_key)
+    }
+
+    #[inline]
+    fn is_eip1271_opted_in(&self, account_id: &AccountIdRef, public_key: &PublicKey) -> bool {
+        self.state
+            .eip1271_opt_ins
+            .contains(&(account_id.to_owned(), *public_key))
+    }
+
+    #[inline]
+    fn eip1271_chain_id(&self) -> Option<u64> {
+        self.state.eip1271_chain_id
+    }
+
+    #[inline]
+    fn max_nonce_ttl(&self) -> Option<Duration> {
+        self.state
+            .max_nonce_ttl
+            .map(|secs| Duration::from_secs(secs.into()))
+    }
+
+    #[inline]
+    fn requires_bounded_deadline(&self) -> bool {
+        self.state.require_bounded_deadline
+    }
+
+    #[inline]
+    fn rejects_legacy_nonces(&self) -> bool {
+        self.state.reject_legacy_nonces
+    }
+
+    #[inline]
+    fn max_keys_to_check(&self) -> u32 {
+        self.state
+            .max_keys_to_check
+            .unwrap_or(DEFAULT_MAX_KEYS_TO_CHECK)
+    }
+
+    #[inline]
+    fn oracle_price(&self, asset: &str) -> Option<u128> {
+        self.runtime.oracle_prices.get(asset).copied()
+    }
+
+    #[inline]
+    fn min_transfer_amount(&self, token_id: &TokenId) -> u128 {
+        self.state
+            .min_transfer_amounts
+            .get(token_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    fn is_referral_allowed(&self, account_id: &AccountIdRef) -> bool {
+        !self.state.strict_referrals
+            || self.accounts.get(account_id).is_some()
+            || self.state.referral_allowlist.contains(account_id)
     }
 }
 
@@ -124,11 +259,36 @@ impl State for Contract {
 
     #[inline]
     fn commit_nonce(&mut self, account_id: AccountId, nonce: Nonce) -> Result<()> {
-        self.accounts
+        let account = self
+            .accounts
             .get_or_create(account_id.clone())
             .get_mut()
-            .ok_or(DefuseError::AccountLocked(account_id))?
-            .commit_nonce(nonce)
+            .ok_or_else(|| DefuseError::AccountLocked(account_id.clone()))?;
+
+        let [prefix @ .., _] = nonce;
+        let new_page = !account.has_nonce_page(prefix);
+        account.commit_nonce(nonce)?;
+
+        if new_page {
+            let pages = self
+                .state
+                .nonce_pages
+                .entry(account_id.clone())
+                .or_default();
+            *pages = pages.saturating_add(1);
+        }
+
+        // remember when nonces without deadline were committed to this
+        // page, so that it can eventually be cleaned up
+        if self.state.unexpirable_nonce_max_age.is_some()
+            && VersionedNonce::maybe_from(nonce)
+                .is_none_or(|versioned| versioned.deadline() == Deadline::MAX)
+        {
+            self.state
+                .nonce_committed_at
+                .insert((account_id, prefix), time::block_timestamp());
+        }
+        Ok(())
     }
 
     #[inline]
@@ -143,7 +303,13 @@ impl State for Contract {
             .ok_or_else(|| DefuseError::AccountNotFound(account_id.to_owned()))?
             .as_inner_unchecked_mut();
 
-        Ok(account.cleanup_nonce_by_prefix(prefix))
+        let cleaned = account.cleanup_nonce_by_prefix(prefix);
+        if cleaned {
+            if let Some(pages) = self.state.nonce_pages.get_mut(account_id) {
+                *pages = pages.saturating_sub(1);
+            }
+        }
+        Ok(cleaned)
     }
 
     fn internal_add_balance(
@@ -151,9 +317,18 @@ impl State for Contract {
         owner_id: AccountId,
         tokens: impl IntoIterator<Item = (TokenId, u128)>,
     ) -> Result<()> {
+        // fees are credited to `fee_collector` by intents of other
+        // accounts, so neither it being blocked nor holding too many
+        // tokens must break them
+        let is_fee_collector = owner_id == self.state.fees.fee_collector;
+        if !is_fee_collector {
+            self.ensure_not_blocked(&owner_id)?;
+        }
+
+        let max_tokens = self.max_tokens_per_account.filter(|_| !is_fee_collector);
         let owner = self
             .accounts
-            .get_or_create(owner_id)
+            .get_or_create(owner_id.clone())
             // we allow locked accounts to accept deposits and incoming deposits
             .as_inner_unchecked_mut();
 
@@ -161,6 +336,7 @@ impl State for Contract {
             if amount == 0 {
                 return Err(DefuseError::InvalidIntent);
             }
+            owner.ensure_can_hold(&owner_id, &token_id, max_tokens)?;
             owner
                 .token_balances
                 .add(token_id, amount)
@@ -170,11 +346,26 @@ impl State for Contract {
         Ok(())
     }
 
+    fn add_collected_fees(
+        &mut self,
+        fees: impl IntoIterator<Item = (TokenId, u128)>,
+    ) -> Result<()> {
+        for (token_id, amount) in fees {
+            self.state
+                .collected_fees
+                .add(token_id, amount)
+                .ok_or(DefuseError::BalanceOverflow)?;
+        }
+        Ok(())
+    }
+
     fn internal_sub_balance(
         &mut self,
         owner_id: &AccountIdRef,
         tokens: impl IntoIterator<Item = (TokenId, u128)>,
     ) -> Result<()> {
+        self.ensure_not_blocked(owner_id)?;
+
         let owner = self
             .accounts
             .get_mut(owner_id)
@@ -215,6 +406,8 @@ impl State for Contract {
     }
 
     fn native_withdraw(&mut self, owner_id: &AccountIdRef, withdraw: NativeWithdraw) -> Result<()> {
+        Self::check_contract_reserve()?;
+
         self.withdraw(
             owner_id,
             [(
@@ -257,7 +450,7 @@ impl State for Contract {
             .map(|(token_id, amount)| (token_id.to_string(), U128(*amount)))
             .unzip();
 
-        let min_gas = notification.min_gas();
+        let min_gas = notification.min_gas_or(self.mt_on_transfer_default_gas());
 
         Self::call_receiver_mt_on_transfer(
             sender_id.to_owned(),
@@ -274,6 +467,8 @@ impl State for Contract {
         owner_id: &AccountIdRef,
         storage_deposit: StorageDeposit,
     ) -> Result<()> {
+        Self::check_contract_reserve()?;
+
         self.withdraw(
             owner_id,
             [(
@@ -341,4 +536,90 @@ impl State for Contract {
 
         Ok(())
     }
+
+    fn set_session(
+        &mut self,
+        account_id: AccountId,
+        public_key: PublicKey,
+        session: Session,
+    ) -> Result<()> {
+        if self.is_account_locked(&account_id) {
+            return Err(DefuseError::AccountLocked(account_id));
+        }
+
+        self.state
+            .sessions
+            .insert((account_id, public_key), session);
+        Ok(())
+    }
+
+    fn set_multisig_threshold(&mut self, account_id: AccountId, threshold: u8) -> Result<()> {
+        if self.is_account_locked(&account_id) {
+            return Err(DefuseError::AccountLocked(account_id));
+        }
+
+        if threshold > 1 {
+            self.state.multisig_thresholds.insert(account_id, threshold);
+        } else {
+            self.state.multisig_thresholds.remove(&account_id);
+        }
+        Ok(())
+    }
+
+    fn set_revoked_before(&mut self, account_id: AccountId, timestamp: Deadline) -> Result<()> {
+        if self.is_account_locked(&account_id) {
+            return Err(DefuseError::AccountLocked(account_id));
+        }
+
+        self.state
+            .revoked_before
+            .insert(account_id, RevokeBefore { timestamp });
+        Ok(())
+    }
+
+    fn set_default_min_gas(&mut self, account_id: AccountId, gas: Gas) -> Result<()> {
+        if self.is_account_locked(&account_id) {
+            return Err(DefuseError::AccountLocked(account_id));
+        }
+
+        if gas.as_gas() == 0 {
+            self.state.default_min_gas.remove(&account_id);
+        } else {
+            self.state.default_min_gas.insert(account_id, gas);
+        }
+        Ok(())
+    }
+
+    fn set_nickname(&mut self, account_id: AccountId, nickname: Option<String>) -> Result<()> {
+        if self.is_account_locked(&account_id) {
+            return Err(DefuseError::AccountLocked(account_id));
+        }
+
+        if let Some(nickname) = nickname.filter(|nickname| !nickname.is_empty()) {
+            self.state.nicknames.insert(account_id, nickname);
+        } else {
+            self.state.nicknames.remove(&account_id);
+        }
+        Ok(())
+    }
+
+    fn set_partially_filled(
+        &mut self,
+        account_id: AccountId,
+        nonce: Nonce,
+        filled: u128,
+    ) -> Result<()> {
+        if self.is_account_locked(&account_id) {
+            return Err(DefuseError::AccountLocked(account_id));
+        }
+
+        if filled > 0 {
+            self.state
+                .partially_filled
+                .insert((account_id, nonce), filled);
+        } else {
+            self.state.partially_filled.remove(&(account_id, nonce));
+        }
+        Ok(())
+    }
 }
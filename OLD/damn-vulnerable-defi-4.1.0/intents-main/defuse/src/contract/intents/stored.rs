@@ -0,0 +1,247 @@
+use std::collections::BTreeMap;
+
+use defuse_core::{
+    DefuseError, Nonce,
+    crypto::SignedPayload,
+    engine::{State, StateView},
+    intents::{DefuseIntents, Intent},
+    payload::{DefusePayload, ExtractDefusePayload, multi::MultiPayload},
+    token_id::TokenId,
+};
+use defuse_near_utils::{CURRENT_ACCOUNT_ID, PREDECESSOR_ACCOUNT_ID, UnwrapOrPanic};
+use near_plugins::{Pausable, pause};
+use near_sdk::{
+    AccountId, FunctionError, NearToken, Promise, assert_one_yocto, env, json_types::U128, near,
+    require,
+};
+
+use crate::{
+    contract::{Contract, ContractExt},
+    intents::{
+        MAX_STORED_INTENTS_PER_QUERY, OrderBookPair, StoredIntent, StoredIntents, StoredOrder,
+        execute_intents_min_gas,
+    },
+};
+
+/// [`StoredIntent`] as it's kept in storage
+#[near(serializers = [borsh])]
+#[derive(Debug)]
+pub struct StoredIntentEntry {
+    pub signed: MultiPayload,
+    pub signer_id: AccountId,
+    pub nonce: Nonce,
+    pub storage_payer: AccountId,
+    pub storage_deposit: NearToken,
+}
+
+impl StoredIntentEntry {
+    fn refund_storage_deposit(self) {
+        if !self.storage_deposit.is_zero() {
+            Promise::new(self.storage_payer).transfer(self.storage_deposit);
+        }
+    }
+}
+
+#[near]
+impl StoredIntents for Contract {
+    #[pause(name = "intents")]
+    #[payable]
+    fn store_signed_intent(&mut self, signed: MultiPayload) -> u64 {
+        // each stored intent is bound to a single signer and nonce
+        if matches!(
+            signed,
+            MultiPayload::BlsAggregate(_) | MultiPayload::Template(_)
+        ) {
+            DefuseError::InvalidIntent.panic();
+        }
+        if signed.verify().is_none() {
+            DefuseError::InvalidSignature.panic();
+        }
+        let DefusePayload {
+            signer_id,
+            verifying_contract,
+            deadline,
+            nonce,
+            ..
+        } = ExtractDefusePayload::<DefuseIntents>::extract_defuse_payload(signed.clone())
+            .map_err(DefuseError::from)
+            .unwrap_or_panic();
+        if verifying_contract != *CURRENT_ACCOUNT_ID {
+            DefuseError::WrongVerifyingContract.panic();
+        }
+        if deadline.has_expired() {
+            DefuseError::DeadlineExpired.panic();
+        }
+        if StateView::is_nonce_used(self, &signer_id, nonce) {
+            DefuseError::NonceUsed.panic();
+        }
+
+        let id = self.state.next_stored_intent_id;
+        self.state.next_stored_intent_id = id.checked_add(1).unwrap_or_panic();
+
+        let initial_storage_usage = env::storage_usage();
+        self.state.stored_intents.insert(
+            id,
+            StoredIntentEntry {
+                signed,
+                signer_id,
+                nonce,
+                storage_payer: PREDECESSOR_ACCOUNT_ID.clone(),
+                storage_deposit: NearToken::from_yoctonear(0),
+            },
+        );
+        // storage is written on flush only
+        self.state.stored_intents.flush();
+        let storage_deposit = env::storage_byte_cost().saturating_mul(
+            env::storage_usage()
+                .saturating_sub(initial_storage_usage)
+                .into(),
+        );
+        let refund = env::attached_deposit()
+            .checked_sub(storage_deposit)
+            .unwrap_or_else(|| env::panic_str("insufficient deposit for storage"));
+
+        if let Some(entry) = self.state.stored_intents.get_mut(&id) {
+            entry.storage_deposit = storage_deposit;
+        }
+        if !refund.is_zero() {
+            Promise::new(PREDECESSOR_ACCOUNT_ID.clone()).transfer(refund);
+        }
+
+        id
+    }
+
+    fn stored_intent(&self, id: u64) -> Option<StoredIntent> {
+        self.state
+            .stored_intents
+            .get(&id)
+            .map(|entry| StoredIntent {
+                signed: entry.signed.clone(),
+                signer_id: entry.signer_id.clone(),
+                storage_payer: entry.storage_payer.clone(),
+                storage_deposit: entry.storage_deposit,
+            })
+    }
+
+    #[pause(name = "intents")]
+    fn execute_stored_intent(&mut self, id: u64) {
+        let entry = self
+            .state
+            .stored_intents
+            .remove(&id)
+            .unwrap_or_else(|| env::panic_str("stored intent not found"));
+
+        let signed = [entry.signed.clone()];
+        let required = execute_intents_min_gas(&signed);
+        if env::prepaid_gas() < required {
+            DefuseError::InsufficientGas { required }.panic();
+        }
+        Self::check_promises_gas(&signed, env::prepaid_gas().saturating_sub(required))
+            .unwrap_or_panic();
+
+        self.execute_signed_intents(signed);
+
+        entry.refund_storage_deposit();
+
+        self.state
+            .recent_gas_usage
+            .get_mut()
+            .get_or_insert_with(Default::default)
+            .record(env::used_gas());
+    }
+
+    #[pause(name = "intents")]
+    #[payable]
+    fn cancel_stored_intent(&mut self, id: u64) {
+        assert_one_yocto();
+        let entry = self
+            .state
+            .stored_intents
+            .remove(&id)
+            .unwrap_or_else(|| env::panic_str("stored intent not found"));
+
+        // signers which can't call the contract directly, e.g. multisig
+        // or implicit accounts, commit the nonce by a signed intent of
+        // their own, after which anyone can clean the entry up
+        if !StateView::is_nonce_used(self, &entry.signer_id, entry.nonce) {
+            require!(
+                *self.ensure_auth_predecessor_id() == entry.signer_id,
+                "stored intent not found",
+            );
+            State::commit_nonce(self, entry.signer_id.clone(), entry.nonce).unwrap_or_panic();
+        }
+
+        entry.refund_storage_deposit();
+    }
+
+    fn stored_order_book(&self, from_id: u64, limit: u32) -> Vec<OrderBookPair> {
+        require!(limit <= MAX_STORED_INTENTS_PER_QUERY, "limit is too large");
+
+        let mut pairs: BTreeMap<(TokenId, TokenId), Vec<StoredOrder>> = BTreeMap::new();
+        let to_id = from_id
+            .saturating_add(limit.into())
+            .min(self.state.next_stored_intent_id);
+        for id in from_id..to_id {
+            let Some(entry) = self.state.stored_intents.get(&id) else {
+                continue;
+            };
+            let Some((token_in, token_out, order)) = self.stored_order(id, entry) else {
+                continue;
+            };
+            pairs.entry((token_in, token_out)).or_default().push(order);
+        }
+
+        pairs
+            .into_iter()
+            .map(|((token_in, token_out), orders)| OrderBookPair {
+                token_in,
+                token_out,
+                orders,
+            })
+            .collect()
+    }
+}
+
+impl Contract {
+    /// Returns `token_in`, `token_out` and the order if the stored intent
+    /// is an open order
+    fn stored_order(
+        &self,
+        id: u64,
+        entry: &StoredIntentEntry,
+    ) -> Option<(TokenId, TokenId, StoredOrder)> {
+        let DefusePayload {
+            deadline, message, ..
+        } = ExtractDefusePayload::<DefuseIntents>::extract_defuse_payload(entry.signed.clone())
+            .ok()?;
+        if deadline.has_expired() || StateView::is_nonce_used(self, &entry.signer_id, entry.nonce) {
+            return None;
+        }
+
+        let [Intent::TokenDiff(token_diff)] = message.intents.as_slice() else {
+            return None;
+        };
+        let ((token_in, amount_in), (token_out, amount_out)) = token_diff.partial_fill_legs()?;
+        let remaining_in = amount_in.checked_sub(StateView::partially_filled(
+            self,
+            &entry.signer_id,
+            entry.nonce,
+        ))?;
+        if remaining_in == 0 {
+            return None;
+        }
+
+        Some((
+            token_in.clone(),
+            token_out.clone(),
+            StoredOrder {
+                id,
+                signer_id: entry.signer_id.clone(),
+                amount_in: U128(amount_in),
+                amount_out: U128(amount_out),
+                remaining_in: U128(remaining_in),
+                deadline,
+            },
+        ))
+    }
+}
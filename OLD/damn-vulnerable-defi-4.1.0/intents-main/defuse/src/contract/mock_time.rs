@@ -0,0 +1,16 @@
+use core::time::Duration;
+
+use defuse_near_utils::time::advance_mock_time;
+use near_sdk::near;
+
+use super::{Contract, ContractExt};
+
+#[near]
+impl Contract {
+    /// Moves the clock observed by the contract forward by given number
+    /// of seconds. Only available in builds with `mock_time` feature.
+    #[private]
+    pub fn mock_advance_time(seconds: u64) {
+        advance_mock_time(Duration::from_secs(seconds));
+    }
+}
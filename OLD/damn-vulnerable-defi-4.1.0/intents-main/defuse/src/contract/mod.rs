@@ -7,13 +7,18 @@ mod events;
 mod fees;
 mod garbage_collector;
 mod intents;
+#[cfg(feature = "mock_time")]
+mod mock_time;
+mod pause;
 mod salts;
 mod state;
 mod tokens;
 mod upgrade;
 mod versioned;
+mod wnear;
 
 use core::iter;
+use std::collections::HashMap;
 
 use defuse_borsh_utils::adapters::As;
 use defuse_core::Result;
@@ -33,6 +38,9 @@ use self::{
     state::ContractState,
 };
 
+/// NOTE: grants and revocations of roles made via
+/// [`RoleManager`](crate::roles::RoleManager) are additionally emitted as
+/// `role_granted` and `role_revoked` events of `dip4` standard
 #[near(serializers = [json])]
 #[derive(AccessControlRole, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Role {
@@ -53,6 +61,8 @@ pub enum Role {
     SaltManager,
 
     GarbageCollector,
+
+    RiskManager,
 }
 
 #[access_control(role_type(Role))]
@@ -96,6 +106,9 @@ pub struct ContractStorage {
 #[derive(Debug, Default)]
 pub struct Runtime {
     pub postponed_burns: PostponedMtBurnEvents,
+
+    /// Prices read from the price oracle by `execute_conditional_intents`
+    pub oracle_prices: HashMap<String, u128>,
 }
 
 #[near]
@@ -107,7 +120,19 @@ impl Contract {
         let mut contract = Self {
             storage: ContractStorage {
                 accounts: Accounts::new(Prefix::Accounts),
-                state: ContractState::new(Prefix::State, config.wnear_id, config.fees),
+                state: ContractState {
+                    require_bounded_deadline: config.require_bounded_deadline,
+                    reject_legacy_nonces: config.reject_legacy_nonces,
+                    ..ContractState::new(
+                        Prefix::State,
+                        config.wnear_id,
+                        config.fees,
+                        config.max_tokens_per_account,
+                        config.event_verbosity,
+                        config.registration_reserve,
+                        config.max_nonce_ttl,
+                    )
+                },
                 relayer_keys: LookupSet::new(Prefix::RelayerKeys),
             },
             runtime: Runtime::default(),
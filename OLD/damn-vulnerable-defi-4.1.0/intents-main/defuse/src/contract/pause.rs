@@ -0,0 +1,15 @@
+use near_plugins::Pausable;
+use near_sdk::near;
+
+use crate::{
+    contract::{Contract, ContractExt},
+    pause::{PauseState, PauseStateView},
+};
+
+#[near]
+impl PauseStateView for Contract {
+    fn pause_state(&self) -> PauseState {
+        let paused = self.pa_all_paused().unwrap_or_default();
+        PauseState::new(|key| paused.contains(key))
+    }
+}
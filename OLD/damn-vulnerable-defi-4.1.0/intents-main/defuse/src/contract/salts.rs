@@ -1,9 +1,11 @@
 use std::collections::BTreeSet;
 
-use defuse_core::{Salt, accounts::SaltRotationEvent, events::DefuseIntentEmit};
+use defuse_core::{
+    Deadline, Salt, accounts::SaltRotationEvent, engine::StateView, events::DefuseIntentEmit,
+};
 use defuse_near_utils::UnwrapOrPanic;
 use near_plugins::{AccessControllable, access_control_any};
-use near_sdk::{assert_one_yocto, near};
+use near_sdk::{assert_one_yocto, near, require};
 
 use super::{Contract, ContractExt, Role};
 use crate::salts::SaltManager;
@@ -49,13 +51,88 @@ impl SaltManager for Contract {
         current
     }
 
+    #[access_control_any(roles(Role::DAO, Role::SaltManager))]
+    #[payable]
+    fn rotate_and_invalidate_all_salts(&mut self) -> Salt {
+        assert_one_yocto();
+
+        let invalidated = self.salts.rotate_and_invalidate_all().unwrap_or_panic();
+        let current = self.salts.current();
+
+        SaltRotationEvent {
+            current,
+            invalidated,
+        }
+        .emit();
+
+        current
+    }
+
     #[inline]
     fn is_valid_salt(&self, salt: Salt) -> bool {
-        self.salts.is_valid(salt)
+        StateView::is_valid_salt(self, salt)
     }
 
     #[inline]
     fn current_salt(&self) -> Salt {
         self.salts.current()
     }
+
+    fn max_nonce_ttl(&self) -> Option<u32> {
+        self.state.max_nonce_ttl
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    #[payable]
+    fn set_max_nonce_ttl(&mut self, max_nonce_ttl: Option<u32>) {
+        assert_one_yocto();
+        require!(self.state.max_nonce_ttl != max_nonce_ttl, "same");
+        self.storage.state.max_nonce_ttl = max_nonce_ttl;
+    }
+
+    fn require_bounded_deadline(&self) -> bool {
+        self.state.require_bounded_deadline
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    #[payable]
+    fn set_require_bounded_deadline(&mut self, require_bounded_deadline: bool) {
+        assert_one_yocto();
+        require!(
+            self.state.require_bounded_deadline != require_bounded_deadline,
+            "same"
+        );
+        self.storage.state.require_bounded_deadline = require_bounded_deadline;
+    }
+
+    fn reject_legacy_nonces(&self) -> bool {
+        self.state.reject_legacy_nonces
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    #[payable]
+    fn set_reject_legacy_nonces(&mut self, reject_legacy_nonces: bool) {
+        assert_one_yocto();
+        require!(
+            self.state.reject_legacy_nonces != reject_legacy_nonces,
+            "same"
+        );
+        self.storage.state.reject_legacy_nonces = reject_legacy_nonces;
+    }
+
+    fn salt_grace_period(&self) -> Option<u32> {
+        self.state.salt_grace_period
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    #[payable]
+    fn set_salt_grace_period(&mut self, grace_period: Option<u32>) {
+        assert_one_yocto();
+        require!(self.state.salt_grace_period != grace_period, "same");
+        self.storage.state.salt_grace_period = grace_period;
+    }
+
+    fn max_allowed_deadline(&self) -> Deadline {
+        StateView::max_allowed_deadline(self)
+    }
 }
@@ -1,12 +1,37 @@
 mod v0;
+mod v1;
 
-pub use v0::ContractStateV0;
+pub use self::{v0::ContractStateV0, v1::ContractStateV1};
 
-use defuse_core::{SaltRegistry, amounts::Amounts, fees::FeesConfig, token_id::TokenId};
+use crate::{
+    contract::{
+        accounts::WithdrawalCooldown,
+        intents::{
+            gas_report::RecentGasUsage, relayer::RelayerKeyExpiry, stored::StoredIntentEntry,
+        },
+    },
+    intents::{EventVerbosity, RelayerKeyScope},
+    tokens::receipt::DepositReceipt,
+};
+use defuse_core::crypto::PublicKey;
+use defuse_core::{
+    Nonce, NoncePrefix, SaltRegistry,
+    amounts::Amounts,
+    fees::FeesConfig,
+    intents::{account::RevokeBefore, session::Session},
+    payload::multi::SigningStandard,
+    token_id::TokenId,
+};
 use defuse_near_utils::NestPrefix;
+use defuse_nep245::approval::Approval;
+use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
 use near_sdk::{
-    AccountId, BorshStorageKey, IntoStorageKey, borsh::BorshSerialize, near, store::IterableMap,
+    AccountId, BorshStorageKey, Gas, IntoStorageKey,
+    borsh::BorshSerialize,
+    near,
+    store::{IterableMap, IterableSet, LazyOption, LookupMap, LookupSet},
 };
+use std::collections::{BTreeMap, BTreeSet};
 
 pub type TokenBalances = Amounts<IterableMap<TokenId, u128>>;
 
@@ -20,11 +45,169 @@ pub struct ContractState {
     pub fees: FeesConfig,
 
     pub salts: SaltRegistry,
+
+    /// Accounts that are not allowed to send or receive any tokens
+    pub denylist: LookupSet<AccountId>,
+
+    /// Sessions granted by accounts to temporary public keys
+    pub sessions: LookupMap<(AccountId, PublicKey), Session>,
+
+    /// Maximum number of distinct tokens a single account can hold
+    pub max_tokens_per_account: Option<u32>,
+
+    /// Enumerable index of relayer keys
+    pub relayer_keys_index: IterableSet<near_sdk::PublicKey>,
+
+    /// Restrictions of scoped relayer keys
+    pub relayer_key_scopes: LookupMap<near_sdk::PublicKey, RelayerKeyScope>,
+
+    /// Gas burnt by the most recent `execute_intents` calls, kept under
+    /// its own storage key to not bloat the root state
+    pub recent_gas_usage: LazyOption<RecentGasUsage>,
+
+    /// Number of distinct public keys required to sign intents
+    /// on behalf of multisig accounts
+    pub multisig_thresholds: LookupMap<AccountId, u8>,
+
+    /// Number of nonce bitmap pages taken by accounts, used to
+    /// estimate their storage usage
+    pub nonce_pages: LookupMap<AccountId, u32>,
+
+    /// Cutoffs set by accounts via `RevokeBefore` intent
+    pub revoked_before: LookupMap<AccountId, RevokeBefore>,
+
+    pub event_verbosity: EventVerbosity,
+
+    /// Cached metadata of NEP-141 tokens, `None` if it's being fetched
+    /// or the token doesn't provide it
+    pub ft_metadata: LookupMap<AccountId, Option<FungibleTokenMetadata>>,
+
+    /// Account which funds storage of accounts registered on their
+    /// first deposit
+    pub registration_reserve: Option<AccountId>,
+
+    /// Maximum time-to-live of nonces in seconds, i.e. how far in the
+    /// future deadlines of signed intents can be
+    pub max_nonce_ttl: Option<u32>,
+
+    /// Deadlines after which relayer keys can no longer be used
+    pub relayer_key_expiries: LookupMap<near_sdk::PublicKey, RelayerKeyExpiry>,
+
+    /// Signing standards which intents of accounts can't be signed with
+    pub disabled_signing_standards: LookupMap<AccountId, BTreeSet<SigningStandard>>,
+
+    /// Amounts of `token_in` filled so far by partially fillable
+    /// `TokenDiff` intents which nonces are not committed yet
+    pub partially_filled: LookupMap<(AccountId, Nonce), u128>,
+
+    /// Block timestamps in nanoseconds at which nonces that never
+    /// expire on their own, i.e. legacy nonces and nonces with
+    /// [`Deadline::MAX`](defuse_core::Deadline::MAX), were last committed
+    /// to a nonce page. Only recorded while their cleanup is enabled,
+    /// and kept per page, so that it's removed along with the page.
+    pub nonce_committed_at: LookupMap<(AccountId, NoncePrefix), u64>,
+
+    /// Age in seconds after which nonces that never expire on their
+    /// own can be cleaned up, `None` means they are never cleaned up
+    pub unexpirable_nonce_max_age: Option<u32>,
+
+    /// NEP-245 approvals of spenders per owner and token
+    pub mt_approvals: LookupMap<(AccountId, TokenId), BTreeMap<AccountId, Approval>>,
+
+    /// Id to be assigned to the next NEP-245 approval
+    pub next_mt_approval_id: u64,
+
+    /// Withdrawal cooldowns configured for accounts
+    pub withdrawal_cooldowns: LookupMap<AccountId, WithdrawalCooldown>,
+
+    /// Public keys trusted to attest EIP-1271 signatures
+    pub eip1271_attesters: IterableSet<PublicKey>,
+
+    /// Chain which wallet contracts are allowed to sign via EIP-1271,
+    /// `None` means EIP-1271 signatures are rejected
+    pub eip1271_chain_id: Option<u64>,
+
+    /// Whether intents with [`Deadline::MAX`](defuse_core::Deadline::MAX)
+    /// are rejected
+    pub require_bounded_deadline: bool,
+
+    /// Deposits made with `mint_receipt` by ids of their receipts,
+    /// which haven't been redeemed yet
+    pub deposit_receipts: LookupMap<String, DepositReceipt>,
+
+    /// Id to be assigned to the next deposit receipt
+    pub next_deposit_receipt_id: u64,
+
+    /// Prefixes of nonce pages cleaned up by garbage collector, so that
+    /// cleaned nonces can be told apart from never used ones
+    pub cleaned_nonce_prefixes: LookupSet<(AccountId, NoncePrefix)>,
+
+    /// Time in seconds after rotation during which previous salts
+    /// remain valid, `None` means until they are invalidated
+    pub salt_grace_period: Option<u32>,
+
+    /// `min_gas` set by accounts via `SetDefaultMinGas` intent
+    pub default_min_gas: LookupMap<AccountId, Gas>,
+
+    /// Fees deposited to `fee_collector` over the contract's lifetime
+    pub collected_fees: TokenBalances,
+
+    /// Signed intents stored for later execution by ids
+    pub stored_intents: LookupMap<u64, StoredIntentEntry>,
+
+    /// Id to be assigned to the next stored intent
+    pub next_stored_intent_id: u64,
+
+    /// Oracle queried for prices by conditional intents
+    pub price_oracle: Option<AccountId>,
+
+    /// Minimum amounts of tokens allowed to be transferred within the
+    /// contract, so that dust transfers can't bloat event logs
+    pub min_transfer_amounts: LookupMap<TokenId, u128>,
+
+    /// Gas attached to `mt_on_transfer` notifications which don't
+    /// specify `min_gas`, `None` means the hardcoded default
+    pub mt_on_transfer_default_gas: Option<Gas>,
+
+    /// Whether `TokenDiff` intents with referrals which are neither
+    /// registered accounts nor on `referral_allowlist` are rejected
+    pub strict_referrals: bool,
+
+    /// Accounts allowed to be specified as referrals regardless of
+    /// whether they are registered
+    pub referral_allowlist: LookupSet<AccountId>,
+
+    /// Whether legacy nonces without a salt are rejected
+    pub reject_legacy_nonces: bool,
+
+    /// Maximum number of public keys of an account iterated while
+    /// verifying its intents, `None` means the hardcoded default
+    pub max_keys_to_check: Option<u32>,
+
+    /// Display nicknames set by accounts via `SetNickname` intent
+    pub nicknames: LookupMap<AccountId, String>,
+
+    /// Receivers of deposits allowed to be locked by the next deposit
+    /// with [`DepositMessage::lock`](crate::tokens::DepositMessage::lock)
+    pub lock_on_deposit_allowed: LookupSet<AccountId>,
+
+    /// EIP-1271 attesters which accounts opted in to, kept apart from
+    /// public keys of accounts so that attesters can't sign anything
+    /// else on their behalf
+    pub eip1271_opt_ins: LookupSet<(AccountId, PublicKey)>,
 }
 
 impl ContractState {
     #[inline]
-    pub fn new<S>(prefix: S, wnear_id: AccountId, fees: FeesConfig) -> Self
+    pub fn new<S>(
+        prefix: S,
+        wnear_id: AccountId,
+        fees: FeesConfig,
+        max_tokens_per_account: Option<u32>,
+        event_verbosity: EventVerbosity,
+        registration_reserve: Option<AccountId>,
+        max_nonce_ttl: Option<u32>,
+    ) -> Self
     where
         S: IntoStorageKey,
     {
@@ -37,6 +220,62 @@ impl ContractState {
             wnear_id,
             fees,
             salts: SaltRegistry::new(prefix.as_slice().nest(Prefix::Salts)),
+            denylist: LookupSet::new(prefix.as_slice().nest(Prefix::Denylist)),
+            sessions: LookupMap::new(prefix.as_slice().nest(Prefix::Sessions)),
+            max_tokens_per_account,
+            relayer_keys_index: IterableSet::new(prefix.as_slice().nest(Prefix::RelayerKeysIndex)),
+            relayer_key_scopes: LookupMap::new(prefix.as_slice().nest(Prefix::RelayerKeyScopes)),
+            recent_gas_usage: LazyOption::new(prefix.as_slice().nest(Prefix::RecentGasUsage), None),
+            multisig_thresholds: LookupMap::new(prefix.as_slice().nest(Prefix::MultisigThresholds)),
+            nonce_pages: LookupMap::new(prefix.as_slice().nest(Prefix::NoncePages)),
+            revoked_before: LookupMap::new(prefix.as_slice().nest(Prefix::RevokedBefore)),
+            event_verbosity,
+            ft_metadata: LookupMap::new(prefix.as_slice().nest(Prefix::FtMetadata)),
+            registration_reserve,
+            max_nonce_ttl,
+            relayer_key_expiries: LookupMap::new(
+                prefix.as_slice().nest(Prefix::RelayerKeyExpiries),
+            ),
+            disabled_signing_standards: LookupMap::new(
+                prefix.as_slice().nest(Prefix::DisabledSigningStandards),
+            ),
+            partially_filled: LookupMap::new(prefix.as_slice().nest(Prefix::PartiallyFilled)),
+            nonce_committed_at: LookupMap::new(prefix.as_slice().nest(Prefix::NonceCommittedAt)),
+            unexpirable_nonce_max_age: None,
+            mt_approvals: LookupMap::new(prefix.as_slice().nest(Prefix::MtApprovals)),
+            next_mt_approval_id: 0,
+            withdrawal_cooldowns: LookupMap::new(
+                prefix.as_slice().nest(Prefix::WithdrawalCooldowns),
+            ),
+            eip1271_attesters: IterableSet::new(prefix.as_slice().nest(Prefix::Eip1271Attesters)),
+            eip1271_chain_id: None,
+            require_bounded_deadline: false,
+            deposit_receipts: LookupMap::new(prefix.as_slice().nest(Prefix::DepositReceipts)),
+            next_deposit_receipt_id: 0,
+            cleaned_nonce_prefixes: LookupSet::new(
+                prefix.as_slice().nest(Prefix::CleanedNoncePrefixes),
+            ),
+            salt_grace_period: None,
+            default_min_gas: LookupMap::new(prefix.as_slice().nest(Prefix::DefaultMinGas)),
+            collected_fees: TokenBalances::new(IterableMap::new(
+                prefix.as_slice().nest(Prefix::CollectedFees),
+            )),
+            stored_intents: LookupMap::new(prefix.as_slice().nest(Prefix::StoredIntents)),
+            next_stored_intent_id: 0,
+            price_oracle: None,
+            min_transfer_amounts: LookupMap::new(
+                prefix.as_slice().nest(Prefix::MinTransferAmounts),
+            ),
+            mt_on_transfer_default_gas: None,
+            strict_referrals: false,
+            referral_allowlist: LookupSet::new(prefix.as_slice().nest(Prefix::ReferralAllowlist)),
+            reject_legacy_nonces: false,
+            max_keys_to_check: None,
+            nicknames: LookupMap::new(prefix.as_slice().nest(Prefix::Nicknames)),
+            lock_on_deposit_allowed: LookupSet::new(
+                prefix.as_slice().nest(Prefix::LockOnDepositAllowed),
+            ),
+            eip1271_opt_ins: LookupSet::new(prefix.as_slice().nest(Prefix::Eip1271OptIns)),
         }
     }
 }
@@ -46,4 +285,30 @@ impl ContractState {
 enum Prefix {
     TotalSupplies,
     Salts,
+    Denylist,
+    Sessions,
+    RelayerKeysIndex,
+    RelayerKeyScopes,
+    MultisigThresholds,
+    NoncePages,
+    RevokedBefore,
+    FtMetadata,
+    RelayerKeyExpiries,
+    DisabledSigningStandards,
+    PartiallyFilled,
+    NonceCommittedAt,
+    MtApprovals,
+    WithdrawalCooldowns,
+    Eip1271Attesters,
+    DepositReceipts,
+    CleanedNoncePrefixes,
+    DefaultMinGas,
+    CollectedFees,
+    StoredIntents,
+    MinTransferAmounts,
+    ReferralAllowlist,
+    Nicknames,
+    RecentGasUsage,
+    LockOnDepositAllowed,
+    Eip1271OptIns,
 }
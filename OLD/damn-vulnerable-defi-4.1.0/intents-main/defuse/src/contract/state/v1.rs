@@ -0,0 +1,107 @@
+use defuse_core::{SaltRegistry, fees::FeesConfig};
+use defuse_near_utils::NestPrefix;
+use near_sdk::{
+    AccountId, IntoStorageKey, near,
+    store::{IterableMap, IterableSet, LazyOption, LookupMap, LookupSet},
+};
+
+use crate::{
+    contract::{
+        MigrateStorageWithPrefix,
+        state::{ContractState, Prefix, TokenBalances},
+    },
+    intents::EventVerbosity,
+};
+
+/// Legacy: V1 of [`ContractState`]
+#[near(serializers = [borsh])]
+#[derive(Debug)]
+pub struct ContractStateV1 {
+    pub total_supplies: TokenBalances,
+
+    pub wnear_id: AccountId,
+
+    pub fees: FeesConfig,
+
+    pub salts: SaltRegistry,
+}
+
+impl MigrateStorageWithPrefix<ContractStateV1> for ContractState {
+    fn migrate<S>(
+        ContractStateV1 {
+            total_supplies,
+            wnear_id,
+            fees,
+            salts,
+        }: ContractStateV1,
+        prefix: S,
+    ) -> Self
+    where
+        S: IntoStorageKey,
+    {
+        let prefix = prefix.into_storage_key();
+
+        Self {
+            total_supplies,
+            wnear_id,
+            fees,
+            salts,
+            denylist: LookupSet::new(prefix.as_slice().nest(Prefix::Denylist)),
+            sessions: LookupMap::new(prefix.as_slice().nest(Prefix::Sessions)),
+            max_tokens_per_account: None,
+            relayer_keys_index: IterableSet::new(prefix.as_slice().nest(Prefix::RelayerKeysIndex)),
+            relayer_key_scopes: LookupMap::new(prefix.as_slice().nest(Prefix::RelayerKeyScopes)),
+            recent_gas_usage: LazyOption::new(prefix.as_slice().nest(Prefix::RecentGasUsage), None),
+            multisig_thresholds: LookupMap::new(prefix.as_slice().nest(Prefix::MultisigThresholds)),
+            nonce_pages: LookupMap::new(prefix.as_slice().nest(Prefix::NoncePages)),
+            revoked_before: LookupMap::new(prefix.as_slice().nest(Prefix::RevokedBefore)),
+            event_verbosity: EventVerbosity::default(),
+            ft_metadata: LookupMap::new(prefix.as_slice().nest(Prefix::FtMetadata)),
+            registration_reserve: None,
+            max_nonce_ttl: None,
+            relayer_key_expiries: LookupMap::new(
+                prefix.as_slice().nest(Prefix::RelayerKeyExpiries),
+            ),
+            disabled_signing_standards: LookupMap::new(
+                prefix.as_slice().nest(Prefix::DisabledSigningStandards),
+            ),
+            partially_filled: LookupMap::new(prefix.as_slice().nest(Prefix::PartiallyFilled)),
+            nonce_committed_at: LookupMap::new(prefix.as_slice().nest(Prefix::NonceCommittedAt)),
+            unexpirable_nonce_max_age: None,
+            mt_approvals: LookupMap::new(prefix.as_slice().nest(Prefix::MtApprovals)),
+            next_mt_approval_id: 0,
+            withdrawal_cooldowns: LookupMap::new(
+                prefix.as_slice().nest(Prefix::WithdrawalCooldowns),
+            ),
+            eip1271_attesters: IterableSet::new(prefix.as_slice().nest(Prefix::Eip1271Attesters)),
+            eip1271_chain_id: None,
+            require_bounded_deadline: false,
+            deposit_receipts: LookupMap::new(prefix.as_slice().nest(Prefix::DepositReceipts)),
+            next_deposit_receipt_id: 0,
+            cleaned_nonce_prefixes: LookupSet::new(
+                prefix.as_slice().nest(Prefix::CleanedNoncePrefixes),
+            ),
+            salt_grace_period: None,
+            default_min_gas: LookupMap::new(prefix.as_slice().nest(Prefix::DefaultMinGas)),
+            collected_fees: TokenBalances::new(IterableMap::new(
+                prefix.as_slice().nest(Prefix::CollectedFees),
+            )),
+            stored_intents: LookupMap::new(prefix.as_slice().nest(Prefix::StoredIntents)),
+            next_stored_intent_id: 0,
+            price_oracle: None,
+            min_transfer_amounts: LookupMap::new(
+                prefix.as_slice().nest(Prefix::MinTransferAmounts),
+            ),
+            mt_on_transfer_default_gas: None,
+            strict_referrals: false,
+            referral_allowlist: LookupSet::new(prefix.as_slice().nest(Prefix::ReferralAllowlist)),
+            reject_legacy_nonces: false,
+            max_keys_to_check: None,
+            nicknames: LookupMap::new(prefix.as_slice().nest(Prefix::Nicknames)),
+            lock_on_deposit_allowed: LookupSet::new(
+                prefix.as_slice().nest(Prefix::LockOnDepositAllowed),
+            ),
+            eip1271_opt_ins: LookupSet::new(prefix.as_slice().nest(Prefix::Eip1271OptIns)),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use defuse_core::token_id::TokenId;
+use near_plugins::{AccessControllable, access_control_any};
+use near_sdk::{assert_one_yocto, json_types::U128, near, require};
+
+use crate::{
+    contract::{Contract, ContractExt, Role},
+    tokens::min_transfer_amount::MinTransferAmount,
+};
+
+#[near]
+impl MinTransferAmount for Contract {
+    fn min_transfer_amount(&self, token_id: TokenId) -> Option<U128> {
+        self.state
+            .min_transfer_amounts
+            .get(&token_id)
+            .copied()
+            .map(U128)
+    }
+
+    #[access_control_any(roles(Role::DAO, Role::RiskManager))]
+    #[payable]
+    fn set_min_transfer_amount(&mut self, token_id: TokenId, amount: Option<U128>) {
+        assert_one_yocto();
+        require!(
+            MinTransferAmount::min_transfer_amount(self, token_id.clone()) != amount,
+            "same"
+        );
+
+        if let Some(U128(amount)) = amount {
+            self.state.min_transfer_amounts.insert(token_id, amount);
+        } else {
+            self.state.min_transfer_amounts.remove(&token_id);
+        }
+    }
+}
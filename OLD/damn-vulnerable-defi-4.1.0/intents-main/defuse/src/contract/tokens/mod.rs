@@ -1,6 +1,8 @@
+mod min_transfer_amount;
 mod nep141;
 mod nep171;
 mod nep245;
+mod receipt;
 
 use super::Contract;
 use defuse_core::{DefuseError, Result, token_id::TokenId};
@@ -17,6 +19,15 @@ impl Contract {
         tokens: impl IntoIterator<Item = (TokenId, u128)>,
         memo: Option<&str>,
     ) -> Result<()> {
+        let max_tokens = self.storage.state.max_tokens_per_account;
+        // storage of accounts registered on their first deposit is
+        // funded by the registration reserve, if any
+        let registered = self
+            .storage
+            .accounts
+            .get(&owner_id)
+            .is_none()
+            .then(|| owner_id.clone());
         let owner = self
             .storage
             .accounts
@@ -36,6 +47,8 @@ impl Contract {
                 return Err(DefuseError::InvalidIntent);
             }
 
+            owner.ensure_can_hold(&mint_event.owner_id, &token_id, max_tokens)?;
+
             mint_event.token_ids.to_mut().push(token_id.to_string());
             mint_event.amounts.to_mut().push(U128(amount));
 
@@ -64,6 +77,10 @@ impl Contract {
             MtEvent::MtMint([mint_event].as_slice().into()).emit();
         }
 
+        if let Some(account_id) = registered {
+            self.fund_registration(&account_id);
+        }
+
         Ok(())
     }
 
@@ -74,6 +91,12 @@ impl Contract {
         memo: Option<impl Into<String>>,
         force: bool,
     ) -> Result<()> {
+        if !force {
+            // blocked accounts can still be withdrawn from forcefully
+            self.ensure_not_blocked(owner_id)?;
+            self.start_withdrawal_cooldown(owner_id)?;
+        }
+
         let owner = self
             .storage
             .accounts
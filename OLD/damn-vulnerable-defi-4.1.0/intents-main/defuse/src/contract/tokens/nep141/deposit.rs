@@ -27,21 +27,27 @@ impl FungibleTokenReceiver for Contract {
     ) -> PromiseOrValue<U128> {
         require!(amount.0 > 0, "zero amount");
 
+        self.ensure_not_blocked(&sender_id).unwrap_or_panic();
+
         let msg = if msg.is_empty() {
             DepositMessage::new(sender_id)
         } else {
             msg.parse().unwrap_or_panic_display()
         };
 
-        self.deposit(
-            msg.receiver_id,
+        self.ensure_not_blocked(&msg.receiver_id).unwrap_or_panic();
+
+        self.deposit_by_message(
+            &msg,
             [(
                 Nep141TokenId::new(PREDECESSOR_ACCOUNT_ID.clone()).into(),
                 amount.0,
             )],
-            Some("deposit"),
         )
         .unwrap_or_panic();
+        self.lock_on_deposit(&msg);
+
+        self.cache_ft_metadata_once(PREDECESSOR_ACCOUNT_ID.clone());
 
         if !msg.execute_intents.is_empty() {
             if msg.refund_if_fails {
@@ -0,0 +1,73 @@
+use defuse_near_utils::CURRENT_ACCOUNT_ID;
+use near_contract_standards::fungible_token::metadata::{FungibleTokenMetadata, ext_ft_metadata};
+use near_plugins::{AccessControllable, access_control_any};
+use near_sdk::{AccountId, Gas, Promise, PromiseResult, assert_one_yocto, env, near, serde_json};
+
+use crate::{
+    contract::{Contract, ContractExt, Role},
+    tokens::nep141::FungibleTokenMetadataCache,
+};
+
+#[near]
+impl FungibleTokenMetadataCache for Contract {
+    fn token_metadata(&self, token: AccountId) -> Option<FungibleTokenMetadata> {
+        self.state.ft_metadata.get(&token).cloned().flatten()
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    #[payable]
+    fn refresh_token_metadata(&mut self, token: AccountId) -> Promise {
+        assert_one_yocto();
+        self.state.ft_metadata.insert(token.clone(), None);
+        Self::fetch_ft_metadata(token)
+    }
+}
+
+#[near]
+impl Contract {
+    const FT_METADATA_GAS: Gas = Gas::from_tgas(5);
+    const DO_CACHE_FT_METADATA_GAS: Gas = Gas::from_tgas(5);
+
+    /// Fetches metadata of `token` unless it was already requested
+    pub(crate) fn cache_ft_metadata_once(&mut self, token: AccountId) {
+        if self.state.ft_metadata.contains_key(&token) {
+            return;
+        }
+        self.state.ft_metadata.insert(token.clone(), None);
+
+        // detach promise
+        let _ = Self::fetch_ft_metadata(token);
+    }
+
+    fn fetch_ft_metadata(token: AccountId) -> Promise {
+        ext_ft_metadata::ext(token.clone())
+            .with_static_gas(Self::FT_METADATA_GAS)
+            // do not distribute remaining gas here
+            .with_unused_gas_weight(0)
+            .ft_metadata()
+            .then(
+                Self::ext(CURRENT_ACCOUNT_ID.clone())
+                    .with_static_gas(Self::DO_CACHE_FT_METADATA_GAS)
+                    .with_unused_gas_weight(0)
+                    .do_cache_ft_metadata(token),
+            )
+    }
+
+    #[private]
+    pub fn do_cache_ft_metadata(&mut self, token: AccountId) -> bool {
+        let metadata = match env::promise_result(0) {
+            PromiseResult::Successful(value) => {
+                serde_json::from_slice::<FungibleTokenMetadata>(&value).ok()
+            }
+            PromiseResult::Failed => None,
+        };
+        let Some(mut metadata) = metadata else {
+            return false;
+        };
+
+        // icons are usually embedded as data URLs, which can be arbitrarily large
+        metadata.icon = None;
+        self.state.ft_metadata.insert(token, Some(metadata));
+        true
+    }
+}
@@ -1,4 +1,6 @@
 mod deposit;
+mod metadata;
 mod native;
 mod storage_deposit;
 mod withdraw;
+mod withdraw_readiness;
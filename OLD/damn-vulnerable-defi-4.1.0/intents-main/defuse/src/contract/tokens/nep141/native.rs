@@ -1,5 +1,5 @@
-use defuse_core::intents::tokens::NativeWithdraw;
-use near_sdk::{Gas, Promise, PromiseResult, env, near, require};
+use defuse_core::{DefuseError, Result, intents::tokens::NativeWithdraw};
+use near_sdk::{Gas, NearToken, Promise, PromiseResult, env, near, require};
 
 use crate::contract::{Contract, ContractExt};
 
@@ -7,6 +7,9 @@ use crate::contract::{Contract, ContractExt};
 impl Contract {
     pub(crate) const DO_NATIVE_WITHDRAW_GAS: Gas = Gas::from_tgas(10);
 
+    /// NEAR kept on top of the contract's storage staking requirement
+    pub(crate) const CONTRACT_RESERVE_BUFFER: NearToken = NearToken::from_millinear(100);
+
     #[must_use]
     #[private]
     pub fn do_native_withdraw(withdraw: NativeWithdraw) -> Promise {
@@ -18,3 +21,21 @@ impl Contract {
         Promise::new(withdraw.receiver_id).transfer(withdraw.amount)
     }
 }
+
+impl Contract {
+    /// Rejects withdrawals of NEAR while the contract's balance is below
+    /// its storage staking requirement plus [`Self::CONTRACT_RESERVE_BUFFER`].
+    ///
+    /// NOTE: withdrawn NEAR is unwrapped from wNEAR before being paid
+    /// out, so it doesn't reduce the contract's own balance by itself,
+    /// but any shortfall of unwrapping would be covered from the reserve
+    pub(crate) fn check_contract_reserve() -> Result<()> {
+        let reserve = env::storage_byte_cost()
+            .saturating_mul(env::storage_usage().into())
+            .saturating_add(Self::CONTRACT_RESERVE_BUFFER);
+        if env::account_balance() < reserve {
+            return Err(DefuseError::InsufficientContractReserve);
+        }
+        Ok(())
+    }
+}
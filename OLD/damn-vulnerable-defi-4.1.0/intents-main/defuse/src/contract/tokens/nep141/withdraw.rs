@@ -43,6 +43,7 @@ impl FungibleTokenWithdrawer for Contract {
                 memo,
                 msg,
                 storage_deposit: None,
+                auto_storage_deposit: false,
                 min_gas: None,
             },
             false,
@@ -228,6 +229,7 @@ impl FungibleTokenForceWithdrawer for Contract {
                 memo,
                 msg,
                 storage_deposit: None,
+                auto_storage_deposit: false,
                 min_gas: None,
             },
             true,
@@ -0,0 +1,88 @@
+use defuse_near_utils::CURRENT_ACCOUNT_ID;
+use near_contract_standards::storage_management::{
+    StorageBalance, StorageBalanceBounds, ext_storage_management,
+};
+use near_sdk::{
+    AccountId, Gas, Promise, PromiseResult, env, json_types::U128, near, require,
+    serde::de::DeserializeOwned, serde_json,
+};
+
+use crate::{
+    contract::{Contract, ContractExt},
+    tokens::nep141::{FungibleTokenWithdrawReadiness, MAX_WITHDRAWAL_PLAN_LEN, WithdrawReadiness},
+};
+
+#[near]
+impl FungibleTokenWithdrawReadiness for Contract {
+    fn check_withdrawal_readiness(&mut self, plan: Vec<(AccountId, AccountId)>) -> Promise {
+        require!(!plan.is_empty(), "empty plan");
+        require!(plan.len() <= MAX_WITHDRAWAL_PLAN_LEN, "plan is too long");
+
+        plan.iter()
+            .flat_map(|(token, receiver_id)| {
+                let storage_management = || {
+                    ext_storage_management::ext(token.clone())
+                        .with_static_gas(Self::STORAGE_VIEW_GAS)
+                        // do not distribute remaining gas here
+                        .with_unused_gas_weight(0)
+                };
+                [
+                    storage_management().storage_balance_of(receiver_id.clone()),
+                    storage_management().storage_balance_bounds(),
+                ]
+            })
+            .reduce(Promise::and)
+            .unwrap_or_else(|| unreachable!())
+            .then(
+                Self::ext(CURRENT_ACCOUNT_ID.clone())
+                    .with_static_gas(Self::DO_CHECK_WITHDRAWAL_READINESS_GAS)
+                    .do_check_withdrawal_readiness(plan),
+            )
+    }
+}
+
+#[near]
+impl Contract {
+    const STORAGE_VIEW_GAS: Gas = Gas::from_tgas(3);
+    const DO_CHECK_WITHDRAWAL_READINESS_GAS: Gas = Gas::from_tgas(10);
+
+    /// Combines `storage_balance_of` and `storage_balance_bounds`
+    /// returned by tokens for each entry of the `plan`
+    #[private]
+    pub fn do_check_withdrawal_readiness(
+        plan: Vec<(AccountId, AccountId)>,
+    ) -> Vec<WithdrawReadiness> {
+        (0..)
+            .step_by(2)
+            .zip(plan)
+            .map(|(result_idx, (token, receiver_id))| {
+                let (registered, storage_deposit) =
+                    match Self::promise_result_json::<Option<StorageBalance>>(result_idx) {
+                        Some(Some(_)) => (true, 0),
+                        Some(None) => (
+                            false,
+                            Self::promise_result_json::<StorageBalanceBounds>(result_idx + 1)
+                                .map_or(0, |bounds| bounds.min.as_yoctonear()),
+                        ),
+                        None => (false, 0),
+                    };
+                WithdrawReadiness {
+                    token,
+                    receiver_id,
+                    registered,
+                    storage_deposit: U128(storage_deposit),
+                }
+            })
+            .collect()
+    }
+
+    fn promise_result_json<T>(result_idx: u64) -> Option<T>
+    where
+        T: DeserializeOwned,
+    {
+        match env::promise_result(result_idx) {
+            PromiseResult::Successful(value) => serde_json::from_slice(&value).ok(),
+            PromiseResult::Failed => None,
+        }
+    }
+}
@@ -2,14 +2,23 @@ use defuse_core::token_id::nep171::Nep171TokenId;
 use defuse_near_utils::{
     CURRENT_ACCOUNT_ID, PREDECESSOR_ACCOUNT_ID, UnwrapOrPanic, UnwrapOrPanicError,
 };
-use near_contract_standards::non_fungible_token::core::NonFungibleTokenReceiver;
+use near_contract_standards::non_fungible_token::{
+    self, Token,
+    core::{NonFungibleTokenReceiver, ext_nft_core},
+};
 use near_plugins::{Pausable, pause};
-use near_sdk::{AccountId, PromiseOrValue, near};
+use near_sdk::{
+    AccountId, Gas, NearToken, Promise, PromiseOrValue, PromiseResult, assert_one_yocto, env, near,
+    require, serde_json,
+};
 
 use crate::{
     contract::{Contract, ContractExt},
     intents::{Intents, ext_intents},
-    tokens::DepositMessage,
+    tokens::{
+        DepositMessage,
+        nep171::{NonFungibleTokenApprovedDepositResolver, NonFungibleTokenApprovedDepositor},
+    },
 };
 
 #[near]
@@ -28,18 +37,23 @@ impl NonFungibleTokenReceiver for Contract {
     ) -> PromiseOrValue<bool> {
         #[allow(clippy::no_effect_underscore_binding)]
         let _previous_owner_id = previous_owner_id;
+        self.ensure_not_blocked(&sender_id).unwrap_or_panic();
+
         let msg = if msg.is_empty() {
             DepositMessage::new(sender_id)
         } else {
             msg.parse().unwrap_or_panic_display()
         };
 
+        self.ensure_not_blocked(&msg.receiver_id).unwrap_or_panic();
+
         let token_id = Nep171TokenId::new(PREDECESSOR_ACCOUNT_ID.clone(), token_id)
             .unwrap_or_panic_display()
             .into();
 
-        self.deposit(msg.receiver_id, [(token_id, 1)], Some("deposit"))
+        self.deposit_by_message(&msg, [(token_id, 1)])
             .unwrap_or_panic();
+        self.lock_on_deposit(&msg);
 
         if !msg.execute_intents.is_empty() {
             if msg.refund_if_fails {
@@ -54,3 +68,169 @@ impl NonFungibleTokenReceiver for Contract {
         PromiseOrValue::Value(false)
     }
 }
+
+#[near]
+impl NonFungibleTokenApprovedDepositor for Contract {
+    #[pause]
+    #[payable]
+    fn nft_deposit_approved(
+        &mut self,
+        token: AccountId,
+        token_id: non_fungible_token::TokenId,
+        approval_id: u64,
+        msg: Option<String>,
+    ) -> Promise {
+        assert_one_yocto();
+        let sender_id = PREDECESSOR_ACCOUNT_ID.clone();
+        self.ensure_not_blocked(&sender_id).unwrap_or_panic();
+
+        let msg = msg
+            .filter(|msg| !msg.is_empty())
+            .map_or_else(
+                || Ok(DepositMessage::new(sender_id.clone())),
+                |msg| msg.parse(),
+            )
+            .unwrap_or_panic_display();
+        require!(
+            !msg.refund_if_fails,
+            "refund_if_fails is not supported for approved deposits"
+        );
+        // check permission before the token is transferred to us, it's
+        // consumed once the deposit is made
+        require!(
+            !msg.lock
+                || self
+                    .state
+                    .lock_on_deposit_allowed
+                    .contains(&msg.receiver_id),
+            "lock on deposit is not allowed"
+        );
+
+        self.ensure_not_blocked(&msg.receiver_id).unwrap_or_panic();
+
+        // validate token_id before making any calls
+        Nep171TokenId::new(token.clone(), token_id.clone()).unwrap_or_panic_display();
+
+        ext_nft_core::ext(token.clone())
+            .with_static_gas(Self::NFT_TOKEN_GAS)
+            // do not distribute remaining gas here
+            .with_unused_gas_weight(0)
+            .nft_token(token_id.clone())
+            .then(
+                Self::ext(CURRENT_ACCOUNT_ID.clone())
+                    .with_static_gas(Self::DO_NFT_DEPOSIT_APPROVED_GAS)
+                    .do_nft_deposit_approved(sender_id, token, token_id, approval_id, msg),
+            )
+    }
+}
+
+#[near]
+impl Contract {
+    const NFT_TOKEN_GAS: Gas = Gas::from_tgas(5);
+    const NFT_TRANSFER_GAS: Gas = Gas::from_tgas(15);
+    const NFT_RESOLVE_DEPOSIT_APPROVED_GAS: Gas = Gas::from_tgas(5)
+        // refund with nft_transfer() in case of failed deposit
+        .saturating_add(Self::NFT_TRANSFER_GAS);
+    const DO_NFT_DEPOSIT_APPROVED_GAS: Gas = Gas::from_tgas(5)
+        .saturating_add(Self::NFT_TRANSFER_GAS)
+        .saturating_add(Self::NFT_RESOLVE_DEPOSIT_APPROVED_GAS);
+
+    #[must_use]
+    #[private]
+    pub fn do_nft_deposit_approved(
+        sender_id: AccountId,
+        token: AccountId,
+        token_id: non_fungible_token::TokenId,
+        approval_id: u64,
+        msg: DepositMessage,
+    ) -> Promise {
+        let nft = match env::promise_result(0) {
+            PromiseResult::Successful(value) => {
+                serde_json::from_slice::<Option<Token>>(&value).unwrap_or_default()
+            }
+            PromiseResult::Failed => None,
+        };
+        let Some(Token {
+            owner_id,
+            approved_account_ids,
+            ..
+        }) = nft
+        else {
+            env::panic_str("token not found");
+        };
+
+        require!(
+            owner_id == sender_id,
+            "sender is not the owner of the token"
+        );
+        require!(
+            approved_account_ids.and_then(|approved| approved.get(&*CURRENT_ACCOUNT_ID).copied())
+                == Some(approval_id),
+            "invalid approval_id",
+        );
+
+        ext_nft_core::ext(token.clone())
+            .with_attached_deposit(NearToken::from_yoctonear(1))
+            .with_static_gas(Self::NFT_TRANSFER_GAS)
+            // do not distribute remaining gas here
+            .with_unused_gas_weight(0)
+            .nft_transfer(
+                CURRENT_ACCOUNT_ID.clone(),
+                token_id.clone(),
+                Some(approval_id),
+                Some("deposit".to_string()),
+            )
+            .then(
+                Self::ext(CURRENT_ACCOUNT_ID.clone())
+                    .with_static_gas(Self::NFT_RESOLVE_DEPOSIT_APPROVED_GAS)
+                    .nft_resolve_deposit_approved(token, sender_id, token_id, msg),
+            )
+    }
+}
+
+#[near]
+impl NonFungibleTokenApprovedDepositResolver for Contract {
+    #[private]
+    fn nft_resolve_deposit_approved(
+        &mut self,
+        token: AccountId,
+        sender_id: AccountId,
+        token_id: non_fungible_token::TokenId,
+        msg: DepositMessage,
+    ) -> bool {
+        // `nft_transfer` returns empty result on success
+        if !matches!(env::promise_result(0), PromiseResult::Successful(value) if value.is_empty()) {
+            // nothing was transferred
+            return false;
+        }
+
+        let nft = Nep171TokenId::new(token.clone(), token_id.clone())
+            .unwrap_or_panic_display()
+            .into();
+
+        if self
+            .ensure_not_blocked(&msg.receiver_id)
+            .and_then(|()| self.deposit_by_message(&msg, [(nft, 1)]))
+            .is_err()
+        {
+            // refund, since the token was already transferred to us
+            let _ = ext_nft_core::ext(token)
+                .with_attached_deposit(NearToken::from_yoctonear(1))
+                .with_static_gas(Self::NFT_TRANSFER_GAS)
+                .nft_transfer(sender_id, token_id, None, Some("refund".to_string()));
+            return false;
+        }
+
+        if msg.lock {
+            self.internal_lock_account(msg.receiver_id, None);
+        }
+
+        if !msg.execute_intents.is_empty() {
+            // detach promise
+            let _ =
+                ext_intents::ext(CURRENT_ACCOUNT_ID.clone()).execute_intents(msg.execute_intents);
+        }
+
+        true
+    }
+}
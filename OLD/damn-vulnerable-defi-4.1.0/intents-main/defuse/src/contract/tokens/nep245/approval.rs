@@ -0,0 +1,194 @@
+use defuse_core::{DefuseError, Result, token_id::TokenId};
+use defuse_near_utils::{UnwrapOrPanic, UnwrapOrPanicError};
+use defuse_nep245::approval::{Approval, MultiTokenApproval};
+use near_sdk::{
+    AccountId, AccountIdRef, NearToken, Promise, assert_one_yocto, env, json_types::U128, near,
+    require,
+};
+
+use crate::contract::{Contract, ContractExt};
+
+#[near]
+impl MultiTokenApproval for Contract {
+    #[payable]
+    fn mt_approve(
+        &mut self,
+        token_ids: Vec<defuse_nep245::TokenId>,
+        amounts: Vec<U128>,
+        account_id: AccountId,
+        msg: Option<String>,
+    ) {
+        require!(
+            env::attached_deposit() >= NearToken::from_yoctonear(1),
+            "requires attached deposit of at least 1 yoctoNEAR",
+        );
+        require!(msg.is_none(), "msg is not supported");
+        require!(
+            !token_ids.is_empty() && token_ids.len() == amounts.len(),
+            "invalid args"
+        );
+        let owner_id = self.ensure_auth_predecessor_id();
+        require!(*owner_id != account_id, "self-approval");
+
+        let initial_storage_usage = env::storage_usage();
+        for (token_id, amount) in token_ids.into_iter().zip(amounts) {
+            let token_id: TokenId = token_id.parse().unwrap_or_panic_display();
+            let approval_id = self.state.next_mt_approval_id;
+            self.state.next_mt_approval_id = approval_id.checked_add(1).unwrap_or_panic();
+
+            self.state
+                .mt_approvals
+                .entry((owner_id.clone(), token_id))
+                .or_default()
+                .insert(
+                    account_id.clone(),
+                    Approval {
+                        approval_id,
+                        amount,
+                    },
+                );
+        }
+        // storage is written on flush only
+        self.state.mt_approvals.flush();
+        let storage_deposit = env::storage_byte_cost().saturating_mul(
+            env::storage_usage()
+                .saturating_sub(initial_storage_usage)
+                .into(),
+        );
+        let refund = env::attached_deposit()
+            .checked_sub(storage_deposit)
+            .unwrap_or_else(|| env::panic_str("insufficient deposit for storage"));
+        if !refund.is_zero() {
+            Promise::new(owner_id.clone()).transfer(refund);
+        }
+    }
+
+    #[payable]
+    fn mt_revoke(&mut self, token_ids: Vec<defuse_nep245::TokenId>, account_id: AccountId) {
+        assert_one_yocto();
+        let owner_id = self.ensure_auth_predecessor_id();
+
+        for token_id in token_ids {
+            let key = (owner_id.clone(), token_id.parse().unwrap_or_panic_display());
+            let Some(approvals) = self.state.mt_approvals.get_mut(&key) else {
+                continue;
+            };
+            approvals.remove(&account_id);
+            if approvals.is_empty() {
+                self.state.mt_approvals.remove(&key);
+            }
+        }
+    }
+
+    #[payable]
+    fn mt_revoke_all(&mut self, token_ids: Vec<defuse_nep245::TokenId>) {
+        assert_one_yocto();
+        let owner_id = self.ensure_auth_predecessor_id();
+
+        for token_id in token_ids {
+            self.state
+                .mt_approvals
+                .remove(&(owner_id.clone(), token_id.parse().unwrap_or_panic_display()));
+        }
+    }
+
+    fn mt_is_approved(
+        &self,
+        owner_id: AccountId,
+        token_ids: Vec<defuse_nep245::TokenId>,
+        approved_account_id: AccountId,
+        amounts: Vec<U128>,
+        approval_ids: Option<Vec<u64>>,
+    ) -> bool {
+        require!(
+            token_ids.len() == amounts.len()
+                && approval_ids
+                    .as_ref()
+                    .is_none_or(|approval_ids| approval_ids.len() == token_ids.len()),
+            "invalid args"
+        );
+
+        token_ids
+            .into_iter()
+            .zip(amounts)
+            .enumerate()
+            .all(|(i, (token_id, amount))| {
+                self.mt_approval(owner_id.clone(), token_id, approved_account_id.clone())
+                    .is_some_and(|approval| {
+                        approval.amount.0 >= amount.0
+                            && approval_ids
+                                .as_ref()
+                                .is_none_or(|approval_ids| approval_ids[i] == approval.approval_id)
+                    })
+            })
+    }
+
+    fn mt_approval(
+        &self,
+        owner_id: AccountId,
+        token_id: defuse_nep245::TokenId,
+        account_id: AccountId,
+    ) -> Option<Approval> {
+        self.state
+            .mt_approvals
+            .get(&(owner_id, token_id.parse().ok()?))?
+            .get(&account_id)
+            .copied()
+    }
+}
+
+impl Contract {
+    /// Spends approvals given to `spender_id` for transferring `amounts`
+    /// of `token_ids`, returns the owner they were approved by
+    pub(crate) fn internal_mt_spend_approvals(
+        &mut self,
+        spender_id: &AccountIdRef,
+        token_ids: &[defuse_nep245::TokenId],
+        amounts: &[U128],
+        approvals: Vec<Option<(AccountId, u64)>>,
+    ) -> Result<AccountId> {
+        if approvals.len() != token_ids.len() || amounts.len() != token_ids.len() {
+            return Err(DefuseError::InvalidIntent);
+        }
+
+        let mut owner_id: Option<AccountId> = None;
+        for ((token_id, amount), approval) in token_ids.iter().zip(amounts).zip(approvals) {
+            // tokens can be transferred from a single owner at once
+            let Some((approval_owner_id, approval_id)) = approval else {
+                return Err(DefuseError::InvalidIntent);
+            };
+            if owner_id
+                .as_ref()
+                .is_some_and(|owner_id| *owner_id != approval_owner_id)
+            {
+                return Err(DefuseError::InvalidIntent);
+            }
+
+            let token_id: TokenId = token_id.parse()?;
+            let key = (approval_owner_id, token_id);
+            let approvals = self
+                .mt_approvals
+                .get_mut(&key)
+                .ok_or_else(|| DefuseError::NotApproved(key.0.clone(), key.1.clone()))?;
+            let approval = approvals
+                .get_mut(spender_id)
+                .filter(|approval| approval.approval_id == approval_id)
+                .ok_or_else(|| DefuseError::NotApproved(key.0.clone(), key.1.clone()))?;
+            approval.amount.0 = approval
+                .amount
+                .0
+                .checked_sub(amount.0)
+                .ok_or_else(|| DefuseError::NotApproved(key.0.clone(), key.1.clone()))?;
+            if approval.amount.0 == 0 {
+                approvals.remove(spender_id);
+                if approvals.is_empty() {
+                    self.state.mt_approvals.remove(&key);
+                }
+            }
+
+            owner_id = Some(key.0);
+        }
+
+        owner_id.ok_or(DefuseError::InvalidIntent)
+    }
+}
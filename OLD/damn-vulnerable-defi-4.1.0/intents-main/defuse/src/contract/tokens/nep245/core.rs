@@ -39,10 +39,18 @@ impl MultiTokenCore for Contract {
         memo: Option<String>,
     ) {
         assert_one_yocto();
-        require!(approvals.is_none(), "approvals are not supported");
+        let predecessor_id = self.ensure_auth_predecessor_id();
+        // transfer on behalf of the owner who approved it, if any
+        let sender_id = approvals.map_or_else(
+            || predecessor_id.clone(),
+            |approvals| {
+                self.internal_mt_spend_approvals(predecessor_id, &token_ids, &amounts, approvals)
+                    .unwrap_or_panic()
+            },
+        );
 
         self.internal_mt_batch_transfer(
-            self.ensure_auth_predecessor_id(),
+            &sender_id,
             &receiver_id,
             &token_ids,
             &amounts,
@@ -85,6 +93,8 @@ impl MultiTokenCore for Contract {
         msg: String,
     ) -> PromiseOrValue<Vec<U128>> {
         assert_one_yocto();
+        // refunds from the receiver can't restore spent approvals, so
+        // approved transfers are only allowed without a notification
         require!(approvals.is_none(), "approvals are not supported");
 
         self.internal_mt_batch_transfer_call(
@@ -171,11 +181,23 @@ impl Contract {
             return Err(DefuseError::InvalidIntent);
         }
 
+        if !force {
+            self.ensure_not_blocked(sender_id)?;
+        }
+        self.ensure_not_blocked(receiver_id)?;
+
+        let max_tokens = self.max_tokens_per_account;
         for (token_id, amount) in token_ids.iter().zip(amounts.iter().map(|a| a.0)) {
             if amount == 0 {
                 return Err(DefuseError::InvalidIntent);
             }
             let token_id: TokenId = token_id.parse()?;
+            if !force {
+                let min_amount = self.min_transfer_amount(&token_id);
+                if amount < min_amount {
+                    return Err(DefuseError::AmountTooSmall(token_id, min_amount));
+                }
+            }
 
             self.accounts
                 .get_mut(sender_id)
@@ -185,10 +207,13 @@ impl Contract {
                 .token_balances
                 .sub(token_id.clone(), amount)
                 .ok_or(DefuseError::BalanceOverflow)?;
-            self.accounts
+            let receiver = self
+                .accounts
                 .get_or_create(receiver_id.to_owned())
                 // locked accounts are allowed to receive incoming transfers
-                .as_inner_unchecked_mut()
+                .as_inner_unchecked_mut();
+            receiver.ensure_can_hold(receiver_id, &token_id, max_tokens)?;
+            receiver
                 .token_balances
                 .add(token_id, amount)
                 .ok_or(DefuseError::BalanceOverflow)?;
@@ -39,25 +39,29 @@ impl MultiTokenReceiver for Contract {
             "self-wrapping is not allowed"
         );
 
+        self.ensure_not_blocked(&sender_id).unwrap_or_panic();
+
         let msg = if msg.is_empty() {
             DepositMessage::new(sender_id)
         } else {
             msg.parse().unwrap_or_panic_display()
         };
 
+        self.ensure_not_blocked(&msg.receiver_id).unwrap_or_panic();
+
         let n = amounts.len();
 
-        self.deposit(
-            msg.receiver_id,
+        self.deposit_by_message(
+            &msg,
             token_ids
                 .into_iter()
                 .map(|token_id| Nep245TokenId::new(token.clone(), token_id))
                 .map(UnwrapOrPanicError::unwrap_or_panic_display)
                 .map(Into::into)
                 .zip(amounts.into_iter().map(|a| a.0)),
-            Some("deposit"),
         )
         .unwrap_or_panic();
+        self.lock_on_deposit(&msg);
 
         if !msg.execute_intents.is_empty() {
             if msg.refund_if_fails {
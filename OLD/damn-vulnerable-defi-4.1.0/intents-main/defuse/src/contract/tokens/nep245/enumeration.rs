@@ -4,6 +4,13 @@ use defuse_near_utils::UnwrapOrPanicError;
 use defuse_nep245::{Token, enumeration::MultiTokenEnumeration};
 use near_sdk::{AccountId, json_types::U128, near};
 
+/// Tokens are enumerated in the order they were first minted (or
+/// deposited, for `mt_tokens_for_owner`), which is stable across calls,
+/// so `from_index` can be used for pagination.
+///
+/// NOTE: when a token is fully burned (or withdrawn), it's removed by
+/// moving the last token into its position, so the order of remaining
+/// tokens is still deterministic, but no longer matches insertion order.
 #[near]
 impl MultiTokenEnumeration for Contract {
     fn mt_tokens(&self, from_index: Option<U128>, limit: Option<u32>) -> Vec<Token> {
@@ -1,6 +1,8 @@
+mod approval;
 mod core;
 mod deposit;
 mod enumeration;
 mod force;
+mod on_transfer_gas;
 mod resolver;
 mod withdraw;
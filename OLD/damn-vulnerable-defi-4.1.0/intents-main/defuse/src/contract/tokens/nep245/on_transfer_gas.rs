@@ -0,0 +1,29 @@
+use defuse_core::intents::tokens::NotifyOnTransfer;
+use near_plugins::{AccessControllable, access_control_any};
+use near_sdk::{Gas, assert_one_yocto, near, require};
+
+use crate::{
+    contract::{Contract, ContractExt, Role},
+    tokens::nep245::MultiTokenOnTransferGas,
+};
+
+#[near]
+impl MultiTokenOnTransferGas for Contract {
+    fn mt_on_transfer_default_gas(&self) -> Gas {
+        self.state
+            .mt_on_transfer_default_gas
+            .unwrap_or(NotifyOnTransfer::MT_ON_TRANSFER_GAS_DEFAULT)
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    #[payable]
+    fn set_mt_on_transfer_default_gas(&mut self, gas: Option<Gas>) {
+        assert_one_yocto();
+        require!(
+            gas.is_none_or(|gas| gas >= NotifyOnTransfer::MT_ON_TRANSFER_GAS_MIN),
+            "gas is too low"
+        );
+        require!(self.state.mt_on_transfer_default_gas != gas, "same");
+        self.state.mt_on_transfer_default_gas = gas;
+    }
+}
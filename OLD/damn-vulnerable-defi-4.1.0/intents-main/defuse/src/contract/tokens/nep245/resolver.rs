@@ -1,5 +1,11 @@
 use std::borrow::Cow;
 
+use defuse_core::{
+    DefuseError, Result,
+    accounts::AccountEvent,
+    events::DefuseEvent,
+    intents::tokens::{NotificationOutcome, NotificationResolvedEvent},
+};
 use defuse_near_utils::{Lock, UnwrapOrPanic, UnwrapOrPanicError};
 use defuse_nep245::{
     ClearedApproval, MtEventEmit, MtTransferEvent, TokenId, resolver::MultiTokenResolver,
@@ -27,16 +33,27 @@ impl MultiTokenResolver for Contract {
             "invalid args"
         );
 
-        let mut refunds = match env::promise_result(0) {
-            PromiseResult::Successful(value) => serde_json::from_slice::<Vec<U128>>(&value)
-                .ok()
-                .filter(|refund| refund.len() == amounts.len())
-                .unwrap_or_else(|| amounts.clone()),
-            PromiseResult::Failed => amounts.clone(),
-        };
+        let refunds = Self::mt_on_transfer_refunds(env::promise_result(0), amounts.len());
+        let outcome = Self::mt_on_transfer_outcome(refunds.as_deref(), &amounts);
+        let mut refunds = refunds.unwrap_or_else(|err| {
+            // refund in full
+            env::log_str(&err.to_string());
+            amounts.clone()
+        });
 
         let sender_id = previous_owner_ids.first().cloned().unwrap_or_panic();
 
+        let event = DefuseEvent::NotificationResolved(AccountEvent::new(
+            Cow::Borrowed(sender_id.as_ref()),
+            NotificationResolvedEvent {
+                receiver_id: Cow::Borrowed(receiver_id.as_ref()),
+                outcome,
+            },
+        ));
+        if self.state.event_verbosity.allows(&event) {
+            event.emit();
+        }
+
         for ((token_id, previous_owner_id), (amount, refund)) in token_ids
             .iter()
             .map(|token_id| token_id.parse().unwrap_or_panic_display())
@@ -120,3 +137,106 @@ impl MultiTokenResolver for Contract {
         amounts
     }
 }
+
+impl Contract {
+    /// Parses refunds returned by `mt_on_transfer()` for `len` tokens.
+    ///
+    /// Return values over the runtime limit (4MB) fail the receiver's
+    /// receipt, so they end up here as [`PromiseResult::Failed`] just
+    /// like any other failure of the receiver.
+    fn mt_on_transfer_refunds(result: PromiseResult, len: usize) -> Result<Vec<U128>> {
+        let PromiseResult::Successful(value) = result else {
+            return Err(DefuseError::MtOnTransferFailed);
+        };
+        serde_json::from_slice::<Vec<U128>>(&value)
+            .ok()
+            .filter(|refunds| refunds.len() == len)
+            .ok_or(DefuseError::MtOnTransferMalformedRefunds)
+    }
+
+    /// Classifies how the receiver handled `mt_on_transfer` given
+    /// refunds it requested for transferred `amounts`
+    fn mt_on_transfer_outcome(
+        refunds: Result<&[U128], &DefuseError>,
+        amounts: &[U128],
+    ) -> NotificationOutcome {
+        let refunds = match refunds {
+            Ok(refunds) => refunds,
+            Err(DefuseError::MtOnTransferFailed) => return NotificationOutcome::Failed,
+            Err(_) => return NotificationOutcome::MalformedRefunds,
+        };
+        if refunds
+            .iter()
+            .zip(amounts)
+            .any(|(refund, amount)| refund.0 > amount.0)
+        {
+            NotificationOutcome::OverRefunded
+        } else if refunds.iter().any(|refund| refund.0 > 0) {
+            NotificationOutcome::Refunded
+        } else {
+            NotificationOutcome::Accepted
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mt_on_transfer_refunds() {
+        assert_eq!(
+            Contract::mt_on_transfer_refunds(
+                PromiseResult::Successful(b"[\"1\",\"0\"]".to_vec()),
+                2
+            )
+            .unwrap(),
+            [U128(1), U128(0)]
+        );
+
+        assert!(matches!(
+            Contract::mt_on_transfer_refunds(PromiseResult::Failed, 2),
+            Err(DefuseError::MtOnTransferFailed)
+        ));
+
+        for malformed in [
+            // dropped return value
+            b"".as_slice(),
+            b"[\"1\"]",
+            b"[\"1\",\"0\",\"0\"]",
+            b"{}",
+        ] {
+            assert!(matches!(
+                Contract::mt_on_transfer_refunds(PromiseResult::Successful(malformed.to_vec()), 2),
+                Err(DefuseError::MtOnTransferMalformedRefunds)
+            ));
+        }
+    }
+
+    #[test]
+    fn mt_on_transfer_outcome() {
+        let amounts = [U128(100), U128(200)];
+        for (refunds, expected) in [
+            (Ok([U128(0), U128(0)]), NotificationOutcome::Accepted),
+            (Ok([U128(30), U128(0)]), NotificationOutcome::Refunded),
+            (Ok([U128(100), U128(200)]), NotificationOutcome::Refunded),
+            (Ok([U128(0), U128(201)]), NotificationOutcome::OverRefunded),
+            (
+                Err(DefuseError::MtOnTransferFailed),
+                NotificationOutcome::Failed,
+            ),
+            (
+                Err(DefuseError::MtOnTransferMalformedRefunds),
+                NotificationOutcome::MalformedRefunds,
+            ),
+        ] {
+            assert_eq!(
+                Contract::mt_on_transfer_outcome(
+                    refunds.as_ref().map(<[U128; 2]>::as_slice),
+                    &amounts
+                ),
+                expected
+            );
+        }
+    }
+}
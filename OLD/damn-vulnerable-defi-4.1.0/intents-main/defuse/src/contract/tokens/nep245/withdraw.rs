@@ -36,6 +36,7 @@ impl MultiTokenWithdrawer for Contract {
         amounts: Vec<U128>,
         memo: Option<String>,
         msg: Option<String>,
+        receiver_ids: Option<Vec<Option<AccountId>>>,
     ) -> PromiseOrValue<Vec<U128>> {
         assert_one_yocto();
         self.internal_mt_withdraw(
@@ -45,6 +46,7 @@ impl MultiTokenWithdrawer for Contract {
                 receiver_id,
                 token_ids,
                 amounts,
+                receiver_ids,
                 memo,
                 msg,
                 storage_deposit: None,
@@ -63,6 +65,51 @@ impl Contract {
         withdraw: MtWithdraw,
         force: bool,
     ) -> Result<PromiseOrValue<Vec<U128>>> {
+        if withdraw.receiver_ids.is_none() {
+            return self
+                .internal_mt_withdraw_to(owner_id, withdraw, force)
+                .map(Into::into);
+        }
+
+        let token_count = withdraw.token_ids.len();
+        let mut withdrawals = withdraw
+            .split_by_receiver()
+            .ok_or(DefuseError::InvalidIntent)?;
+        if withdrawals.len() == 1 {
+            let (withdraw, _positions) = withdrawals.remove(0);
+            return self
+                .internal_mt_withdraw_to(owner_id, withdraw, force)
+                .map(Into::into);
+        }
+
+        let mut positions = Vec::with_capacity(withdrawals.len());
+        let mut promises = Vec::with_capacity(withdrawals.len());
+        for (withdraw, withdraw_positions) in withdrawals {
+            promises.push(self.internal_mt_withdraw_to(owner_id.clone(), withdraw, force)?);
+            positions.push(withdraw_positions);
+        }
+
+        Ok(promises
+            .into_iter()
+            .reduce(Promise::and)
+            .ok_or(DefuseError::InvalidIntent)?
+            .then(
+                Self::ext(CURRENT_ACCOUNT_ID.clone())
+                    .with_static_gas(Self::MT_JOIN_WITHDRAWN_GAS)
+                    // do not distribute remaining gas here
+                    .with_unused_gas_weight(0)
+                    .mt_join_withdrawn(token_count, positions),
+            )
+            .into())
+    }
+
+    /// Withdraws all tokens to a single `receiver_id`
+    fn internal_mt_withdraw_to(
+        &mut self,
+        owner_id: AccountId,
+        withdraw: MtWithdraw,
+        force: bool,
+    ) -> Result<Promise> {
         if withdraw.token_ids.len() != withdraw.amounts.len() || withdraw.token_ids.is_empty() {
             return Err(DefuseError::InvalidIntent);
         }
@@ -122,8 +169,7 @@ impl Contract {
                     withdraw.amounts,
                     is_call,
                 ),
-        )
-        .into())
+        ))
     }
 
     #[must_use]
@@ -151,6 +197,30 @@ impl Contract {
         // only with storage_deposit
         .saturating_add(STORAGE_DEPOSIT_GAS);
 
+    const MT_JOIN_WITHDRAWN_GAS: Gas = Gas::from_tgas(5);
+
+    /// Joins amounts withdrawn to distinct receivers in order of
+    /// original `token_ids`
+    #[must_use]
+    #[private]
+    pub fn mt_join_withdrawn(token_count: usize, positions: Vec<Vec<usize>>) -> Vec<U128> {
+        let mut used = vec![U128(0); token_count];
+        for (result_index, positions) in (0u64..).zip(positions) {
+            let PromiseResult::Successful(value) = env::promise_result(result_index) else {
+                continue;
+            };
+            let Ok(amounts) = serde_json::from_slice::<Vec<U128>>(&value) else {
+                continue;
+            };
+            for (position, amount) in positions.into_iter().zip(amounts) {
+                if let Some(used) = used.get_mut(position) {
+                    *used = amount;
+                }
+            }
+        }
+        used
+    }
+
     #[must_use]
     #[private]
     pub fn do_mt_withdraw(withdraw: MtWithdraw) -> Promise {
@@ -283,6 +353,7 @@ impl MultiTokenForcedWithdrawer for Contract {
                 receiver_id,
                 token_ids,
                 amounts,
+                receiver_ids: None,
                 memo,
                 msg,
                 storage_deposit: None,
@@ -0,0 +1,99 @@
+use defuse_core::{
+    Result,
+    token_id::{TokenId, nep171::Nep171TokenId},
+};
+use defuse_near_utils::{
+    CURRENT_ACCOUNT_ID, PREDECESSOR_ACCOUNT_ID, UnwrapOrPanic, UnwrapOrPanicError,
+};
+use near_plugins::{Pausable, pause};
+use near_sdk::{assert_one_yocto, env, json_types::U128, near};
+
+use crate::{
+    contract::{Contract, ContractExt},
+    tokens::{
+        DepositMessage,
+        receipt::{DepositReceipt, DepositReceiptManager},
+    },
+};
+
+#[near]
+impl DepositReceiptManager for Contract {
+    fn deposit_receipt(&self, receipt_id: String) -> Option<DepositReceipt> {
+        self.state.deposit_receipts.get(&receipt_id).cloned()
+    }
+
+    #[pause]
+    #[payable]
+    fn redeem_deposit_receipt(&mut self, receipt_id: String) {
+        assert_one_yocto();
+        let holder_id = PREDECESSOR_ACCOUNT_ID.clone();
+        self.ensure_not_blocked(&holder_id).unwrap_or_panic();
+
+        let DepositReceipt { token_id, amount } = self
+            .state
+            .deposit_receipts
+            .remove(&receipt_id)
+            .unwrap_or_else(|| env::panic_str("receipt not found"));
+
+        // redeeming doesn't take tokens out of the contract, so it's
+        // not subject to withdrawal cooldown
+        self.withdraw(
+            &holder_id,
+            [(Self::receipt_token_id(receipt_id), 1)],
+            Some("redeem"),
+            true,
+        )
+        .unwrap_or_panic();
+        self.internal_mt_batch_transfer(
+            &CURRENT_ACCOUNT_ID,
+            &holder_id,
+            &[token_id.to_string()],
+            &[amount],
+            Some("redeem"),
+            true,
+        )
+        .unwrap_or_panic();
+    }
+}
+
+impl Contract {
+    /// Deposits `tokens` to the receiver of `msg`. If it asks to mint
+    /// receipts, tokens are deposited to the contract itself, while the
+    /// receiver gets a receipt for each of them.
+    pub(crate) fn deposit_by_message(
+        &mut self,
+        msg: &DepositMessage,
+        tokens: impl IntoIterator<Item = (TokenId, u128)>,
+    ) -> Result<()> {
+        if !msg.mint_receipt {
+            return self.deposit(msg.receiver_id.clone(), tokens, Some("deposit"));
+        }
+
+        let tokens: Vec<_> = tokens.into_iter().collect();
+        self.deposit(CURRENT_ACCOUNT_ID.clone(), tokens.clone(), Some("deposit"))?;
+
+        let receipts: Vec<_> = tokens
+            .into_iter()
+            .map(|(token_id, amount)| {
+                let receipt_id = self.state.next_deposit_receipt_id;
+                self.state.next_deposit_receipt_id = receipt_id.checked_add(1).unwrap_or_panic();
+                let receipt_id = receipt_id.to_string();
+                self.state.deposit_receipts.insert(
+                    receipt_id.clone(),
+                    DepositReceipt {
+                        token_id,
+                        amount: U128(amount),
+                    },
+                );
+                (Self::receipt_token_id(receipt_id), 1)
+            })
+            .collect();
+        self.deposit(msg.receiver_id.clone(), receipts, Some("receipt"))
+    }
+
+    fn receipt_token_id(receipt_id: String) -> TokenId {
+        Nep171TokenId::new(CURRENT_ACCOUNT_ID.clone(), receipt_id)
+            .unwrap_or_panic_display()
+            .into()
+    }
+}
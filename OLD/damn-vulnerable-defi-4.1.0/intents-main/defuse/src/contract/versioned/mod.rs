@@ -1,4 +1,5 @@
 mod v0;
+mod v1;
 
 use std::{
     borrow::Cow,
@@ -14,18 +15,21 @@ use near_sdk::{
 
 use super::ContractStorage;
 use v0::ContractStorageV0;
+use v1::ContractStorageV1;
 
 /// Versioned [Contract] state for de/serialization.
 #[derive(Debug)]
 #[near(serializers = [borsh])]
 enum VersionedContractStorage<'a> {
     V0(Cow<'a, PanicOnClone<ContractStorageV0>>),
+    V1(Cow<'a, PanicOnClone<ContractStorageV1>>),
     // When upgrading to a new version, given current version `N`:
     // 1. Copy current `ContractStorage` struct definition and name it `ContractStorageVN`
     // 2. Add variant `VN(Cow<'a, PanicOnClone<ContractStorageVN>>)` before `Latest`
     // 3. Handle new variant in `match` expessions below
     // 4. Add tests for `VN -> Latest` migration
-    Latest(Cow<'a, PanicOnClone<ContractStorage>>),
+    // boxed, since the latest state outgrew the legacy ones by far
+    Latest(Box<Cow<'a, PanicOnClone<ContractStorage>>>),
 }
 
 impl From<VersionedContractStorage<'_>> for ContractStorage {
@@ -34,7 +38,8 @@ impl From<VersionedContractStorage<'_>> for ContractStorage {
         // safe to call `Cow::<PanicOnClone<_>>::into_owned()` here.
         match versioned {
             VersionedContractStorage::V0(contract) => contract.into_owned().into_inner().into(),
-            VersionedContractStorage::Latest(contract) => contract.into_owned().into_inner(),
+            VersionedContractStorage::V1(contract) => contract.into_owned().into_inner().into(),
+            VersionedContractStorage::Latest(contract) => (*contract).into_owned().into_inner(),
         }
     }
 }
@@ -43,7 +48,7 @@ impl From<VersionedContractStorage<'_>> for ContractStorage {
 impl<'a> From<&'a ContractStorage> for VersionedContractStorage<'a> {
     fn from(value: &'a ContractStorage) -> Self {
         // always serialize as latest version
-        Self::Latest(Cow::Borrowed(PanicOnClone::from_ref(value)))
+        Self::Latest(Box::new(Cow::Borrowed(PanicOnClone::from_ref(value))))
     }
 }
 
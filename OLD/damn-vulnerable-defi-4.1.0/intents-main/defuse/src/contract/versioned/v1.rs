@@ -0,0 +1,36 @@
+use impl_tools::autoimpl;
+use near_sdk::{near, store::LookupSet};
+
+use crate::contract::{
+    ContractStorage, MigrateStorageWithPrefix, Prefix,
+    accounts::Accounts,
+    state::{ContractState, ContractStateV1},
+};
+
+#[derive(Debug)]
+#[autoimpl(Deref using self.state)]
+#[autoimpl(DerefMut using self.state)]
+#[near(serializers = [borsh])]
+pub struct ContractStorageV1 {
+    accounts: Accounts,
+
+    state: ContractStateV1,
+
+    relayer_keys: LookupSet<near_sdk::PublicKey>,
+}
+
+impl From<ContractStorageV1> for ContractStorage {
+    fn from(
+        ContractStorageV1 {
+            accounts,
+            state,
+            relayer_keys,
+        }: ContractStorageV1,
+    ) -> Self {
+        Self {
+            accounts,
+            state: ContractState::migrate(state, Prefix::State),
+            relayer_keys,
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use near_plugins::{AccessControllable, access_control_any};
+use near_sdk::{AccountId, assert_one_yocto, near, require};
+
+use crate::wnear::WNearManager;
+
+use super::{Contract, ContractExt, Role};
+
+#[near]
+impl WNearManager for Contract {
+    fn wnear_id(&self) -> AccountId {
+        self.state.wnear_id.clone()
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    #[payable]
+    fn set_wnear_id(&mut self, wnear_id: AccountId) {
+        assert_one_yocto();
+        require!(self.state.wnear_id != wnear_id, "same");
+        self.storage.state.wnear_id = wnear_id;
+    }
+}
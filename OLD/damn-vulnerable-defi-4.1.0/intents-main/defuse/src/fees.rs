@@ -1,6 +1,13 @@
-use defuse_core::fees::Pips;
+use defuse_core::{fees::Pips, token_id::TokenId};
 use near_plugins::AccessControllable;
-use near_sdk::{AccountId, ext_contract};
+use near_sdk::{
+    AccountId, ext_contract,
+    json_types::{I128, U128},
+};
+
+/// Maximum number of tokens returned at once by
+/// [`FeesManager::collected_fees`]
+pub const MAX_COLLECTED_FEES_PER_QUERY: usize = 256;
 
 #[ext_contract(ext_fees_manager)]
 #[allow(clippy::module_name_repetitions)]
@@ -11,4 +18,43 @@ pub trait FeesManager: AccessControllable {
 
     fn set_fee_collector(&mut self, fee_collector: AccountId);
     fn fee_collector(&self) -> &AccountId;
+
+    /// Returns up to `limit` tokens with total fees deposited to
+    /// `fee_collector` over the contract's lifetime, regardless of
+    /// changes of the collector. Starts right after `from` or from the
+    /// first token if it's `None`. The last returned token can be passed
+    /// as `from` to get the next page.
+    ///
+    /// NOTE: at most [`MAX_COLLECTED_FEES_PER_QUERY`] tokens can be
+    /// queried at once.
+    fn collected_fees(&self, from: Option<TokenId>, limit: u32) -> Vec<(TokenId, U128)>;
+
+    /// Returns delta on `token_out` the user should request in `TokenDiff`
+    /// when giving `amount_in` of `token_in` to a counterparty filling at
+    /// parity, given the current fee.
+    /// See [`TokenDiff::quote`](defuse_core::intents::token_diff::TokenDiff::quote).
+    fn quote_token_diff(
+        &self,
+        token_in: TokenId,
+        amount_in: U128,
+        token_out: TokenId,
+        referral: Option<AccountId>,
+    ) -> I128;
+
+    /// Sets whether `TokenDiff` intents are rejected if their referral
+    /// is neither a registered account nor on the referral allowlist.
+    /// Attached deposit of 1yN is required for security purposes.
+    fn set_strict_referrals(&mut self, strict: bool);
+    fn strict_referrals(&self) -> bool;
+
+    /// Adds accounts to the referral allowlist, so that they can be
+    /// specified as referrals even when they are not registered.
+    /// Attached deposit of 1yN is required for security purposes.
+    fn add_allowed_referrals(&mut self, referrals: Vec<AccountId>);
+
+    /// Removes accounts from the referral allowlist.
+    /// Attached deposit of 1yN is required for security purposes.
+    fn remove_allowed_referrals(&mut self, referrals: Vec<AccountId>);
+
+    fn is_referral_allowlisted(&self, referral: AccountId) -> bool;
 }
@@ -9,4 +9,16 @@ pub trait GarbageCollector {
     /// Omitting any errors, e.g. if account doesn't exist or nonces are not expired.
     /// NOTE: MUST attach 1 yⓃ for security purposes.
     fn cleanup_nonces(&mut self, nonces: Vec<(AccountId, Vec<AsBase64<Nonce>>)>);
+
+    /// Returns age in seconds after which nonces that never expire on
+    /// their own, i.e. legacy nonces and nonces with `Deadline::MAX`,
+    /// can be cleaned up, if any
+    fn unexpirable_nonce_max_age(&self) -> Option<u32>;
+
+    /// Sets age in seconds after which nonces that never expire on
+    /// their own can be cleaned up since they were committed.
+    /// `None` means they are never cleaned up, and only nonces committed
+    /// while it's set can be cleaned up at all.
+    /// NOTE: MUST attach 1 yⓃ for security purposes.
+    fn set_unexpirable_nonce_max_age(&mut self, max_age: Option<u32>);
 }
@@ -1,17 +1,169 @@
-use defuse_core::payload::multi::MultiPayload;
+use std::collections::BTreeSet;
+
+use defuse_core::{
+    Deadline, crypto,
+    engine::deltas::InvariantViolated,
+    events::DefuseEvent,
+    intents::{DefuseIntents, IntentKind},
+    payload::{DefusePayload, multi::MultiPayload},
+    token_id::TokenId,
+};
 
 use near_plugins::AccessControllable;
-use near_sdk::{Promise, PublicKey, ext_contract};
+use near_sdk::{
+    AccountId, AccountIdRef, Gas, NearToken, Promise, PublicKey, ext_contract, json_types::U128,
+    near,
+};
 
 use crate::{fees::FeesManager, salts::SaltManager};
 
-pub use crate::simulation_output::{SimulationOutput, StateOutput};
+pub use crate::simulation_output::{DetailedSimulationOutput, SimulationOutput, StateOutput};
+
+/// Maximum number of stored intents scanned at once by
+/// [`StoredIntents::stored_order_book`]
+pub const MAX_STORED_INTENTS_PER_QUERY: u32 = 256;
+
+/// Gas required by `execute_intents` regardless of the number of
+/// signed payloads
+pub const EXECUTE_INTENTS_BASE_GAS: Gas = Gas::from_tgas(5);
+
+/// Gas required by `execute_intents` per signed payload
+pub const EXECUTE_INTENTS_GAS_PER_PAYLOAD: Gas = Gas::from_tgas(2);
+
+/// Returns minimum gas to be attached to `execute_intents` with given
+/// signed payloads, so that it doesn't fail in the middle of execution.
+/// Each of aggregated payloads counts separately.
+pub fn execute_intents_min_gas(signed: &[MultiPayload]) -> Gas {
+    let payloads = signed
+        .iter()
+        .map(|payload| match payload {
+            MultiPayload::BlsAggregate(aggregate) => aggregate.payloads.len(),
+            _ => 1,
+        })
+        .fold(0u64, |total, n| {
+            total.saturating_add(u64::try_from(n).unwrap_or(u64::MAX))
+        });
+    EXECUTE_INTENTS_BASE_GAS
+        .saturating_add(EXECUTE_INTENTS_GAS_PER_PAYLOAD.saturating_mul(payloads))
+}
 
 #[ext_contract(ext_intents)]
 pub trait Intents: FeesManager + SaltManager {
     fn execute_intents(&mut self, signed: Vec<MultiPayload>);
 
+    /// Same as [`Self::execute_intents`], but takes borsh-encoded
+    /// `signed` payloads, which are more compact than JSON ones, so
+    /// that relayers pay less for submitting large batches
+    fn execute_intents_borsh(&mut self, #[serializer(borsh)] signed: Vec<MultiPayload>);
+
     fn simulate_intents(&self, signed: Vec<MultiPayload>) -> SimulationOutput;
+
+    /// Simulates the batch skipping signed intents which fail, so that
+    /// clients can find out which ones fail and drop them.
+    /// Returned simulation of the batch excludes failed intents.
+    ///
+    /// NOTE: `TokenDiff` intents which are not matched by the solver
+    /// they are bound to are reported as failed, while they are still
+    /// included in the returned simulation
+    fn simulate_intents_detailed(&self, signed: Vec<MultiPayload>) -> DetailedSimulationOutput;
+
+    /// Nets deltas of `TokenDiff` intents in the batch without executing
+    /// it, so that solvers can cheaply check whether the batch is
+    /// balanced before submitting it. Returns the same `invariant_violated`
+    /// as [`Self::simulate_intents`] would, if any.
+    ///
+    /// NOTE: signatures, nonces and balances are not checked, while
+    /// partially fillable `TokenDiff` intents are skipped, since they
+    /// are filled with whatever is left unmatched
+    fn check_batch_consistency(&self, signed: Vec<MultiPayload>) -> Option<InvariantViolated>;
+
+    /// Extracts human-readable payload from the signed one, so that
+    /// clients and explorers don't need to implement each of signing
+    /// standards to show what was signed.
+    ///
+    /// NOTE: neither signature nor nonce is verified
+    fn decode_payload(&self, signed: MultiPayload) -> DefusePayload<DefuseIntents>;
+
+    /// Returns aggregated gas burnt by the most recent `execute_intents`
+    /// calls, so that relayers can size their gas budgets
+    fn execute_intents_gas_report(&self) -> GasReport;
+
+    /// Returns verbosity of events emitted by `execute_intents`
+    fn event_verbosity(&self) -> EventVerbosity;
+
+    /// Sets verbosity of events emitted by `execute_intents`.
+    /// NOTE: requires 1yN for security purposes
+    fn set_event_verbosity(&mut self, verbosity: EventVerbosity);
+}
+
+/// Verbosity of events emitted while executing intents, see
+/// [`EventVerbosity::allows`].
+///
+/// NOTE: NEP-245 events are always emitted, since indexers rely on
+/// them to track balances.
+#[near(serializers = [borsh, json])]
+#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EventVerbosity {
+    /// Emit an event for each executed intent along with the summary
+    #[default]
+    Full,
+    /// Emit only `intents_executed` summary
+    Minimal,
+    /// Suppress all non-essential events
+    None,
+}
+
+impl EventVerbosity {
+    #[inline]
+    pub const fn is_full(&self) -> bool {
+        matches!(self, Self::Full)
+    }
+
+    /// Returns whether `event` should be emitted. Events changing
+    /// authorization of accounts, such as key management and account
+    /// locks, as well as governance ones are never suppressed.
+    pub const fn allows(&self, event: &DefuseEvent<'_>) -> bool {
+        match event {
+            DefuseEvent::PublicKeyAdded(_)
+            | DefuseEvent::PublicKeyRemoved(_)
+            | DefuseEvent::SetAuthByPredecessorId(_)
+            | DefuseEvent::SessionGranted(_)
+            | DefuseEvent::SetMultisigThreshold(_)
+            | DefuseEvent::RevokeBefore(_)
+            | DefuseEvent::AccountLocked(_)
+            | DefuseEvent::AccountUnlocked(_)
+            | DefuseEvent::AccountBlocked(_)
+            | DefuseEvent::AccountUnblocked(_)
+            | DefuseEvent::FeeChanged(_)
+            | DefuseEvent::FeeCollectorChanged(_)
+            | DefuseEvent::SaltRotation(_)
+            | DefuseEvent::RoleGranted(_)
+            | DefuseEvent::RoleRevoked(_)
+            | DefuseEvent::RegistrationReserveLow(_) => true,
+            DefuseEvent::IntentsExecuted(_) => !matches!(self, Self::None),
+            DefuseEvent::Transfer(_)
+            | DefuseEvent::Donation(_)
+            | DefuseEvent::TokenDiff(_)
+            | DefuseEvent::FtWithdraw(_)
+            | DefuseEvent::NftWithdraw(_)
+            | DefuseEvent::MtWithdraw(_)
+            | DefuseEvent::NativeWithdraw(_)
+            | DefuseEvent::StorageDeposit(_)
+            | DefuseEvent::SetDefaultMinGas(_)
+            | DefuseEvent::SetNickname(_)
+            | DefuseEvent::NotificationResolved(_) => self.is_full(),
+        }
+    }
+}
+
+#[near(serializers = [json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasReport {
+    /// Number of aggregated calls
+    pub calls: u32,
+    /// Total gas burnt by these calls
+    pub total_gas: Gas,
 }
 
 #[ext_contract(ext_relayer_keys)]
@@ -25,4 +177,211 @@ pub trait RelayerKeys: AccessControllable {
 
     /// NOTE: requires 1yN for security purposes
     fn delete_relayer_key(&mut self, public_key: PublicKey) -> Promise;
+
+    /// Returns list of authorized relayer keys.
+    ///
+    /// NOTE: keys added before relayer keys started being indexed
+    /// are only listed here once backfilled via [`index_relayer_keys`](RelayerKeys::index_relayer_keys).
+    fn relayer_keys(&self) -> Vec<PublicKey>;
+
+    /// Backfills index of [`relayer_keys`](RelayerKeys::relayer_keys) with
+    /// keys added before it was introduced. Each key must already be
+    /// an authorized relayer key.
+    /// NOTE: requires 1yN for security purposes
+    fn index_relayer_keys(&mut self, public_keys: Vec<PublicKey>);
+
+    /// Restricts relayer key to given scope, or lifts the restriction
+    /// when `scope` is `None`.
+    /// NOTE: requires 1yN for security purposes
+    fn set_relayer_key_scope(&mut self, public_key: PublicKey, scope: Option<RelayerKeyScope>);
+
+    /// Returns scope of given relayer key, `None` means unrestricted
+    fn relayer_key_scope(&self, public_key: PublicKey) -> Option<RelayerKeyScope>;
+
+    /// Sets deadline after which the relayer key can no longer be used
+    /// to execute intents, or makes it valid indefinitely when
+    /// `expires_at` is `None`.
+    /// NOTE: requires 1yN for security purposes
+    fn set_relayer_key_expiry(&mut self, public_key: PublicKey, expires_at: Option<Deadline>);
+
+    /// Returns expiry of given relayer key, `None` means it never expires
+    fn relayer_key_expiry(&self, public_key: PublicKey) -> Option<Deadline>;
+}
+
+#[ext_contract(ext_eip1271_attesters)]
+pub trait Eip1271Attesters: AccessControllable {
+    /// Trusts given key to attest EIP-1271 signatures of smart-contract
+    /// wallets, i.e. that `isValidSignature()` returned the magic value.
+    /// Wallets still have to opt in to the attester, see
+    /// [`Self::opt_in_eip1271_attester`].
+    /// NOTE: requires 1yN for security purposes
+    fn add_eip1271_attester(&mut self, public_key: crypto::PublicKey);
+
+    /// NOTE: requires 1yN for security purposes
+    fn remove_eip1271_attester(&mut self, public_key: crypto::PublicKey);
+
+    /// Returns list of trusted EIP-1271 attesters
+    fn eip1271_attesters(&self) -> Vec<crypto::PublicKey>;
+
+    /// Sets chain which wallet contracts are allowed to sign via
+    /// EIP-1271, `None` rejects EIP-1271 signatures altogether.
+    /// NOTE: requires 1yN for security purposes
+    fn set_eip1271_chain_id(&mut self, chain_id: Option<u64>);
+
+    fn eip1271_chain_id(&self) -> Option<u64>;
+
+    /// Opts the predecessor in to given trusted attester vouching for
+    /// its EIP-1271 signatures. The attester doesn't become a public key
+    /// of the account, so it can't sign anything else on its behalf.
+    /// NOTE: requires 1yN for security purposes
+    fn opt_in_eip1271_attester(&mut self, public_key: crypto::PublicKey);
+
+    /// NOTE: requires 1yN for security purposes
+    fn opt_out_eip1271_attester(&mut self, public_key: crypto::PublicKey);
+
+    /// Records opt-in of wallet contract to given trusted attester, or
+    /// opt-out if `opted_in` is false. Wallet contracts can't call this
+    /// contract themselves, so their opt-ins are recorded on request
+    /// once the wallet was verified on its chain.
+    /// NOTE: requires 1yN for security purposes
+    fn set_eip1271_opt_in(
+        &mut self,
+        account_id: AccountId,
+        public_key: crypto::PublicKey,
+        opted_in: bool,
+    );
+
+    fn is_eip1271_opted_in(&self, account_id: AccountId, public_key: crypto::PublicKey) -> bool;
+}
+
+#[ext_contract(ext_stored_intents)]
+pub trait StoredIntents {
+    /// Stores signed intent on-chain, so that any relayer can execute it
+    /// later with [`Self::execute_stored_intent`], e.g. once conditions
+    /// it relies on are met. Only the signature, deadline and nonce are
+    /// checked upfront, everything else is checked on execution.
+    ///
+    /// Attached deposit pays for storage and the excess is refunded.
+    /// Storage deposit is refunded to the caller once the intent is
+    /// executed or cancelled. Returns id of the stored intent.
+    fn store_signed_intent(&mut self, signed: MultiPayload) -> u64;
+
+    /// Returns stored intent, if it wasn't executed or cancelled yet
+    fn stored_intent(&self, id: u64) -> Option<StoredIntent>;
+
+    /// Executes stored intent, can be called by anyone
+    fn execute_stored_intent(&mut self, id: u64);
+
+    /// Cancels stored intent and commits its nonce, so that it can't be
+    /// executed via `execute_intents` either. Can only be called by the
+    /// signer, unless the nonce was already used. So signers which can't
+    /// call the contract directly, e.g. multisig or implicit accounts,
+    /// cancel it by executing any signed intent with the same nonce,
+    /// after which anyone can remove it and refund the storage deposit.
+    ///
+    /// NOTE: MUST attach 1 yⓃ for security purposes.
+    fn cancel_stored_intent(&mut self, id: u64);
+
+    /// Returns open orders among stored intents with ids in
+    /// `from_id..from_id + limit` grouped by token pair, so that solvers
+    /// can discover orders to match. Orders are stored intents with a
+    /// single `TokenDiff` on exactly two tokens, which are neither
+    /// expired nor executed or filled completely yet.
+    ///
+    /// NOTE: at most [`MAX_STORED_INTENTS_PER_QUERY`] stored intents
+    /// can be scanned at once.
+    fn stored_order_book(&self, from_id: u64, limit: u32) -> Vec<OrderBookPair>;
+}
+
+#[ext_contract(ext_conditional_intents)]
+pub trait ConditionalIntents: AccessControllable {
+    /// Reads prices required by `conditional` intents from the price
+    /// oracle and executes signed intents once they are read. If any
+    /// of conditions is not met, the whole batch fails and nonces stay
+    /// unused, so that it can be retried later.
+    fn execute_conditional_intents(&mut self, signed: Vec<MultiPayload>) -> Promise;
+
+    /// Returns oracle queried for prices by conditional intents
+    fn price_oracle(&self) -> Option<AccountId>;
+
+    /// Sets oracle queried for prices by conditional intents, `None`
+    /// disables them.
+    /// NOTE: requires 1yN for security purposes
+    fn set_price_oracle(&mut self, oracle_id: Option<AccountId>);
+}
+
+/// Interface of price oracles queried by conditional intents
+#[ext_contract(ext_price_oracle)]
+pub trait PriceOracle {
+    /// Returns current price of given asset, if known
+    fn get_price(&self, asset: String) -> Option<U128>;
+}
+
+/// Signed intent stored with [`StoredIntents::store_signed_intent`]
+#[near(serializers = [json])]
+#[derive(Debug, Clone)]
+pub struct StoredIntent {
+    pub signed: MultiPayload,
+    pub signer_id: AccountId,
+    /// Account which paid for storage
+    pub storage_payer: AccountId,
+    pub storage_deposit: NearToken,
+}
+
+/// Open orders of a single token pair returned by
+/// [`StoredIntents::stored_order_book`]
+#[near(serializers = [json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderBookPair {
+    /// Token given by the signers
+    pub token_in: TokenId,
+    /// Token received by the signers
+    pub token_out: TokenId,
+    /// Orders in the order of their ids
+    pub orders: Vec<StoredOrder>,
+}
+
+/// Stored `TokenDiff` intent trading `token_in` for `token_out` at the
+/// price of `amount_out / amount_in` given by its deltas
+#[near(serializers = [json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredOrder {
+    /// Id of the stored intent
+    pub id: u64,
+    pub signer_id: AccountId,
+    pub amount_in: U128,
+    pub amount_out: U128,
+    /// Amount of `token_in` left to be filled, less than `amount_in`
+    /// only for partially filled orders
+    pub remaining_in: U128,
+    pub deadline: Deadline,
+}
+
+/// Restrictions applied to intents relayed in transactions
+/// signed by a relayer key
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RelayerKeyScope {
+    /// Kinds of intents allowed to be relayed, any if `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub intents: Option<BTreeSet<IntentKind>>,
+
+    /// Signers whose intents are allowed to be relayed, any if `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accounts: Option<BTreeSet<AccountId>>,
+}
+
+impl RelayerKeyScope {
+    /// Whether intents signed by `signer_id` can be relayed by the key
+    pub fn allows(&self, signer_id: &AccountIdRef, intents: &DefuseIntents) -> bool {
+        self.accounts
+            .as_ref()
+            .is_none_or(|accounts| accounts.contains(signer_id))
+            && self.intents.as_ref().is_none_or(|kinds| {
+                intents
+                    .intents
+                    .iter()
+                    .all(|intent| kinds.contains(&IntentKind::from(intent.unconditional())))
+            })
+    }
 }
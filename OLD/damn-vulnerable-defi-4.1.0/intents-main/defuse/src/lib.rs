@@ -6,9 +6,12 @@ pub mod accounts;
 pub mod fees;
 pub mod garbage_collector;
 pub mod intents;
+pub mod pause;
+pub mod roles;
 pub mod salts;
 pub mod simulation_output;
 pub mod tokens;
+pub mod wnear;
 
 pub use defuse_core as core;
 pub use defuse_nep245 as nep245;
@@ -16,7 +19,8 @@ pub use defuse_nep245 as nep245;
 use defuse_admin_utils::full_access_keys::FullAccessKeys;
 use defuse_controller::ControllerUpgradable;
 use defuse_nep245::{
-    MultiTokenCore, enumeration::MultiTokenEnumeration, receiver::MultiTokenReceiver,
+    MultiTokenCore, approval::MultiTokenApproval, enumeration::MultiTokenEnumeration,
+    receiver::MultiTokenReceiver,
 };
 use near_contract_standards::{
     fungible_token::receiver::FungibleTokenReceiver,
@@ -25,42 +29,72 @@ use near_contract_standards::{
 use near_plugins::{AccessControllable, Pausable};
 use near_sdk::ext_contract;
 
-use crate::{accounts::ForceAccountManager, tokens::nep245::MultiTokenForcedCore};
+use crate::{
+    accounts::{
+        AccountDenylist, AccountKeysLimit, AccountRegistrationReserve, AccountTokensLimit,
+        AccountWithdrawalCooldown, ForceAccountManager,
+    },
+    tokens::nep245::MultiTokenForcedCore,
+};
 
 use self::{
     accounts::AccountManager,
-    intents::{Intents, RelayerKeys},
+    intents::{ConditionalIntents, Eip1271Attesters, Intents, RelayerKeys, StoredIntents},
+    pause::PauseStateView,
+    roles::RoleManager,
     tokens::{
+        min_transfer_amount::MinTransferAmount,
         nep141::{FungibleTokenForceWithdrawer, FungibleTokenWithdrawer},
-        nep171::{NonFungibleTokenForceWithdrawer, NonFungibleTokenWithdrawer},
-        nep245::{MultiTokenForcedWithdrawer, MultiTokenWithdrawer},
+        nep171::{
+            NonFungibleTokenApprovedDepositor, NonFungibleTokenForceWithdrawer,
+            NonFungibleTokenWithdrawer,
+        },
+        nep245::{MultiTokenForcedWithdrawer, MultiTokenOnTransferGas, MultiTokenWithdrawer},
+        receipt::DepositReceiptManager,
     },
+    wnear::WNearManager,
 };
 
 #[ext_contract(ext_defuse)]
 pub trait Defuse:
     Intents
     + RelayerKeys
+    + Eip1271Attesters
+    + StoredIntents
+    + ConditionalIntents
     + AccountManager
     + MultiTokenCore
+    + MultiTokenApproval
     // NEP-141 deposits/withdrawals
     + FungibleTokenReceiver
     + FungibleTokenWithdrawer
     // NEP-171 deposits/withdrawals
     + NonFungibleTokenReceiver
     + NonFungibleTokenWithdrawer
+    + NonFungibleTokenApprovedDepositor
     // NEP-245 deposits/withdrawals
     + MultiTokenReceiver
     + MultiTokenWithdrawer
     + MultiTokenEnumeration
+    + DepositReceiptManager
     // Governance
     + AccessControllable
+    + RoleManager
     + MultiTokenForcedCore
     + FungibleTokenForceWithdrawer
     + NonFungibleTokenForceWithdrawer
     + MultiTokenForcedWithdrawer
     + ForceAccountManager
+    + AccountDenylist
+    + AccountTokensLimit
+    + AccountKeysLimit
+    + AccountWithdrawalCooldown
+    + MinTransferAmount
+    + MultiTokenOnTransferGas
+    + AccountRegistrationReserve
+    + WNearManager
     + Pausable
+    + PauseStateView
     + ControllerUpgradable
     + FullAccessKeys
 {
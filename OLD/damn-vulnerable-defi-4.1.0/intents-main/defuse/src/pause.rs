@@ -0,0 +1,62 @@
+use near_plugins::Pausable;
+use near_sdk::{ext_contract, near};
+
+/// Key pausing all features at once
+pub const PAUSE_ALL: &str = "ALL";
+
+/// Keys pausing execution of signed intents
+pub const INTENTS_PAUSE_KEYS: &[&str] = &["intents"];
+
+/// Keys pausing `mt_transfer*` within the contract
+pub const TRANSFERS_PAUSE_KEYS: &[&str] = &["mt_transfer"];
+
+/// Keys pausing deposits of NEP-141, NEP-171 and NEP-245 tokens
+pub const DEPOSITS_PAUSE_KEYS: &[&str] = &[
+    "ft_on_transfer",
+    "nft_on_transfer",
+    "nft_deposit_approved",
+    "mt_on_transfer",
+];
+
+/// Keys pausing withdrawals of NEP-141, NEP-171 and NEP-245 tokens
+pub const WITHDRAWALS_PAUSE_KEYS: &[&str] = &["ft_withdraw", "nft_withdraw", "mt_withdraw"];
+
+/// Categories of features which are paused. A category is reported as
+/// paused if at least one of its keys is paused, see
+/// [`Pausable::pa_all_paused`] for exact keys.
+#[near(serializers = [json])]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct PauseState {
+    /// Whether all features are paused at once
+    pub all: bool,
+    /// Execution of signed intents, including withdrawals via intents
+    pub intents: bool,
+    /// `mt_transfer*` within the contract
+    pub transfers: bool,
+    /// `ft_on_transfer`, `nft_on_transfer` and `mt_on_transfer` deposits
+    pub deposits: bool,
+    /// `ft_withdraw`, `nft_withdraw` and `mt_withdraw`
+    pub withdrawals: bool,
+}
+
+impl PauseState {
+    pub fn new(is_paused: impl Fn(&str) -> bool) -> Self {
+        let all = is_paused(PAUSE_ALL);
+        let any_paused = |keys: &[&str]| all || keys.iter().any(|key| is_paused(key));
+        Self {
+            all,
+            intents: any_paused(INTENTS_PAUSE_KEYS),
+            transfers: any_paused(TRANSFERS_PAUSE_KEYS),
+            deposits: any_paused(DEPOSITS_PAUSE_KEYS),
+            withdrawals: any_paused(WITHDRAWALS_PAUSE_KEYS),
+        }
+    }
+}
+
+#[ext_contract(ext_pause_state)]
+pub trait PauseStateView: Pausable {
+    /// Returns which categories of features are currently paused, so
+    /// that clients can check whether their calls are executable
+    fn pause_state(&self) -> PauseState;
+}
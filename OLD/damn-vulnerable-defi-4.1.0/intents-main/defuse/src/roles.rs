@@ -0,0 +1,19 @@
+use near_plugins::AccessControllable;
+use near_sdk::{AccountId, ext_contract};
+
+#[ext_contract(ext_role_manager)]
+pub trait RoleManager: AccessControllable {
+    /// Grants `role` to `account_id` and emits `role_granted` event.
+    /// Requires the same permissions as `acl_grant_role` and returns
+    /// whether the role was newly granted.
+    ///
+    /// Attached deposit of 1yN is required for security purposes.
+    fn grant_role(&mut self, role: String, account_id: AccountId) -> bool;
+
+    /// Revokes `role` from `account_id` and emits `role_revoked` event.
+    /// Requires the same permissions as `acl_revoke_role` and returns
+    /// whether the account had the role.
+    ///
+    /// Attached deposit of 1yN is required for security purposes.
+    fn revoke_role(&mut self, role: String, account_id: AccountId) -> bool;
+}
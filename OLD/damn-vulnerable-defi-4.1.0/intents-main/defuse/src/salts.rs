@@ -1,4 +1,4 @@
-use defuse_core::Salt;
+use defuse_core::{Deadline, Salt};
 use near_sdk::ext_contract;
 
 #[ext_contract(ext_salt_manager)]
@@ -13,9 +13,56 @@ pub trait SaltManager {
     /// Returns the current salt.
     fn invalidate_salts(&mut self, salts: Vec<Salt>) -> Salt;
 
-    /// Returns whether the provided salt is valid
+    /// Sets the current salt to a new one and invalidates all of the
+    /// previous salts, so that nonces with any of them are rejected.
+    /// Returns the new current salt.
+    fn rotate_and_invalidate_all_salts(&mut self) -> Salt;
+
+    /// Returns whether the provided salt is valid, i.e. it's current or
+    /// a previous one which is neither invalidated nor past the grace period
     fn is_valid_salt(&self, salt: Salt) -> bool;
 
     /// Returns the current salt
     fn current_salt(&self) -> Salt;
+
+    /// Returns maximum time-to-live of nonces in seconds, if any
+    fn max_nonce_ttl(&self) -> Option<u32>;
+
+    /// Sets maximum time-to-live of nonces in seconds.
+    /// `None` means unlimited.
+    ///
+    /// Attached deposit of 1yN is required for security purposes.
+    fn set_max_nonce_ttl(&mut self, max_nonce_ttl: Option<u32>);
+
+    /// Returns whether intents with `Deadline::MAX` are rejected
+    fn require_bounded_deadline(&self) -> bool;
+
+    /// Sets whether intents with `Deadline::MAX` are rejected.
+    ///
+    /// Attached deposit of 1yN is required for security purposes.
+    fn set_require_bounded_deadline(&mut self, require_bounded_deadline: bool);
+
+    /// Returns whether legacy nonces without a salt are rejected
+    fn reject_legacy_nonces(&self) -> bool;
+
+    /// Sets whether legacy nonces without a salt are rejected, so that
+    /// only salted nonces are accepted.
+    ///
+    /// Attached deposit of 1yN is required for security purposes.
+    fn set_reject_legacy_nonces(&mut self, reject_legacy_nonces: bool);
+
+    /// Returns time in seconds after rotation during which previous
+    /// salts remain valid, if any
+    fn salt_grace_period(&self) -> Option<u32>;
+
+    /// Sets time in seconds after rotation during which previous salts
+    /// remain valid, after which they are invalid without an explicit
+    /// `invalidate_salts` call. `None` means until invalidated.
+    ///
+    /// Attached deposit of 1yN is required for security purposes.
+    fn set_salt_grace_period(&mut self, grace_period: Option<u32>);
+
+    /// Returns the furthest deadline intents can be signed with at
+    /// current block time, i.e. `now + max_nonce_ttl`
+    fn max_allowed_deadline(&self) -> Deadline;
 }
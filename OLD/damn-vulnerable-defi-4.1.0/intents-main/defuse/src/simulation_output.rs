@@ -10,7 +10,7 @@ use defuse_core::{
 //     all(feature = "abi", not(target_arch = "wasm32")),
 //     serde_as(schemars = true)
 // )]
-use near_sdk::near;
+use near_sdk::{Gas, near};
 // use serde_with::serde_as;
 
 #[near(serializers = [json])]
@@ -19,6 +19,11 @@ pub struct SimulationReport {
     pub intents_executed: Vec<IntentEvent<AccountEvent<'static, NonceEvent>>>,
     pub logs: Vec<String>,
     pub min_deadline: Deadline,
+
+    /// Estimated gas of each of `intents_executed`, i.e. gas burnt while
+    /// simulating it plus minimum gas of promises it would create
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gas_estimates: Vec<Gas>,
 }
 
 #[near(serializers = [json])]
@@ -45,6 +50,28 @@ impl SimulationOutput {
     }
 }
 
+#[near(serializers = [json])]
+#[derive(Debug, Clone)]
+pub struct DetailedSimulationOutput {
+    /// Outcomes of signed intents in the same order as they were
+    /// passed: `Ok` or the error the intent failed with
+    pub outcomes: Vec<Result<(), String>>,
+
+    /// Simulation of the batch with failed intents excluded
+    #[serde(flatten)]
+    pub output: SimulationOutput,
+}
+
+impl DetailedSimulationOutput {
+    /// Returns indices of signed intents which failed
+    pub fn failed(&self) -> impl Iterator<Item = usize> + '_ {
+        self.outcomes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, outcome)| outcome.is_err().then_some(i))
+    }
+}
+
 #[near(serializers = [json])]
 #[derive(Debug, Clone)]
 pub struct StateOutput {
@@ -0,0 +1,20 @@
+use defuse_core::token_id::TokenId;
+use near_plugins::AccessControllable;
+use near_sdk::{ext_contract, json_types::U128};
+
+#[ext_contract(ext_min_transfer_amount)]
+pub trait MinTransferAmount: AccessControllable {
+    /// Returns minimum amount of the token allowed to be transferred
+    /// within the contract, if any
+    fn min_transfer_amount(&self, token_id: TokenId) -> Option<U128>;
+
+    /// Sets minimum amount of the token allowed to be transferred
+    /// within the contract by `Transfer` intents and `mt_transfer*`,
+    /// so that dust transfers can't bloat event logs. `None` means no
+    /// minimum.
+    ///
+    /// NOTE: deposits, withdrawals and forced transfers are not affected.
+    ///
+    /// Attached deposit of 1yN is required for security purposes.
+    fn set_min_transfer_amount(&mut self, token_id: TokenId, amount: Option<U128>);
+}
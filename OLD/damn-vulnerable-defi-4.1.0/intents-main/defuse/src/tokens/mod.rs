@@ -1,6 +1,8 @@
+pub mod min_transfer_amount;
 pub mod nep141;
 pub mod nep171;
 pub mod nep245;
+pub mod receipt;
 
 use core::{
     fmt::{self, Debug, Display},
@@ -23,9 +25,28 @@ pub struct DepositMessage {
 
     #[serde(default, skip_serializing_if = "::core::ops::Not::not")]
     pub refund_if_fails: bool,
+
+    /// Lock `receiver_id` right after the deposit, so that deposited
+    /// funds stay in escrow until the account is force-unlocked.
+    /// Requires the lock to be allowed beforehand via
+    /// [`ForceAccountManager::force_allow_lock_on_deposit`](crate::accounts::ForceAccountManager::force_allow_lock_on_deposit).
+    #[serde(default, skip_serializing_if = "::core::ops::Not::not")]
+    pub lock: bool,
+
+    /// Keep deposited tokens on the contract's own account and mint
+    /// a receipt to `receiver_id` for each of them instead, see
+    /// [`DepositReceiptManager`](receipt::DepositReceiptManager)
+    #[serde(default, skip_serializing_if = "::core::ops::Not::not")]
+    pub mint_receipt: bool,
 }
 
 impl DepositMessage {
+    /// Maximum number of signed payloads in `execute_intents`, so that
+    /// their execution fits into the gas left after the deposit.
+    /// Messages exceeding this limit fail to parse, so the deposit is
+    /// refunded regardless of `refund_if_fails`.
+    pub const MAX_EXECUTE_INTENTS: usize = 16;
+
     #[must_use]
     #[inline]
     pub const fn new(receiver_id: AccountId) -> Self {
@@ -33,9 +54,37 @@ impl DepositMessage {
             receiver_id,
             execute_intents: Vec::new(),
             refund_if_fails: false,
+            lock: false,
+            mint_receipt: false,
         }
     }
 
+    /// Deposit to `receiver_id` and immediately execute `swap`, i.e. signed
+    /// [`TokenDiff`](defuse_core::intents::token_diff::TokenDiff) of the
+    /// receiver along with counterparties' ones.
+    ///
+    /// Deposit is credited before any of the intents are executed, so the
+    /// swap can spend just deposited tokens. If the swap fails, the whole
+    /// deposit is refunded.
+    #[must_use]
+    #[inline]
+    pub fn deposit_then_swap(
+        receiver_id: AccountId,
+        swap: impl IntoIterator<Item = MultiPayload>,
+    ) -> Self {
+        Self::new(receiver_id)
+            .with_execute_intents(swap)
+            .with_refund_if_fails()
+    }
+
+    /// Deposit to `receiver_id` and lock it in the same transaction, so
+    /// that deposited funds can't be moved until the account is unlocked.
+    #[must_use]
+    #[inline]
+    pub const fn deposit_then_lock(receiver_id: AccountId) -> Self {
+        Self::new(receiver_id).with_lock()
+    }
+
     #[must_use]
     #[inline]
     pub fn with_execute_intents(mut self, intents: impl IntoIterator<Item = MultiPayload>) -> Self {
@@ -49,12 +98,26 @@ impl DepositMessage {
         self.refund_if_fails = true;
         self
     }
+
+    #[must_use]
+    #[inline]
+    pub const fn with_lock(mut self) -> Self {
+        self.lock = true;
+        self
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn with_mint_receipt(mut self) -> Self {
+        self.mint_receipt = true;
+        self
+    }
 }
 
 impl Display for DepositMessage {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.execute_intents.is_empty() {
+        if self.execute_intents.is_empty() && !self.lock && !self.mint_receipt {
             f.write_str(self.receiver_id.as_str())
         } else {
             f.write_str(&serde_json::to_string(self).unwrap_or_panic_display())
@@ -68,7 +131,19 @@ impl FromStr for DepositMessage {
     #[inline]
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if s.starts_with('{') {
-            serde_json::from_str(s).map_err(Into::into)
+            let msg: Self = serde_json::from_str(s)?;
+            if msg.execute_intents.len() > Self::MAX_EXECUTE_INTENTS {
+                return Err(ParseDepositMessageError::TooManyIntents(
+                    msg.execute_intents.len(),
+                ));
+            }
+            if msg.lock && !msg.execute_intents.is_empty() {
+                return Err(ParseDepositMessageError::LockWithIntents);
+            }
+            if msg.mint_receipt && !msg.execute_intents.is_empty() {
+                return Err(ParseDepositMessageError::ReceiptWithIntents);
+            }
+            Ok(msg)
         } else {
             s.parse().map(Self::new).map_err(Into::into)
         }
@@ -81,4 +156,13 @@ pub enum ParseDepositMessageError {
     Account(#[from] ParseAccountError),
     #[error("JSON: {0}")]
     JSON(#[from] serde_json::Error),
+    #[error(
+        "too many intents to execute: {0}, max: {max}",
+        max = DepositMessage::MAX_EXECUTE_INTENTS
+    )]
+    TooManyIntents(usize),
+    #[error("intents can't be executed on deposit to an account being locked")]
+    LockWithIntents,
+    #[error("intents can't be executed on deposit minting a receipt")]
+    ReceiptWithIntents,
 }
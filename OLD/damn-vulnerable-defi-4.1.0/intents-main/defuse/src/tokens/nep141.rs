@@ -1,6 +1,8 @@
-use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
+use near_contract_standards::fungible_token::{
+    metadata::FungibleTokenMetadata, receiver::FungibleTokenReceiver,
+};
 use near_plugins::AccessControllable;
-use near_sdk::{AccountId, PromiseOrValue, ext_contract, json_types::U128};
+use near_sdk::{AccountId, Promise, PromiseOrValue, ext_contract, json_types::U128, near};
 
 #[ext_contract(ext_ft_withdraw)]
 pub trait FungibleTokenWithdrawer: FungibleTokenReceiver + FungibleTokenWithdrawResolver {
@@ -43,3 +45,48 @@ pub trait FungibleTokenForceWithdrawer: FungibleTokenWithdrawer + AccessControll
         msg: Option<String>,
     ) -> PromiseOrValue<U128>;
 }
+
+#[ext_contract(ext_ft_metadata_cache)]
+pub trait FungibleTokenMetadataCache: AccessControllable {
+    /// Returns cached metadata of NEP-141 `token`, if any.
+    ///
+    /// Metadata is fetched from the token contract on its first deposit.
+    /// NOTE: `icon` is not cached to keep storage usage bounded.
+    fn token_metadata(&self, token: AccountId) -> Option<FungibleTokenMetadata>;
+
+    /// Drops cached metadata of `token` and fetches it again.
+    ///
+    /// NOTE: MUST attach 1 yⓃ for security purposes.
+    fn refresh_token_metadata(&mut self, token: AccountId) -> Promise;
+}
+
+/// Maximum number of entries in a plan checked at once by
+/// [`FungibleTokenWithdrawReadiness::check_withdrawal_readiness`]
+pub const MAX_WITHDRAWAL_PLAN_LEN: usize = 16;
+
+#[ext_contract(ext_ft_withdraw_readiness)]
+pub trait FungibleTokenWithdrawReadiness {
+    /// Checks whether receivers of planned withdrawals of NEP-141 tokens
+    /// are registered on them and how much `storage_deposit` would be
+    /// needed otherwise, so that frontends can validate multi-withdrawals
+    /// before the user signs them. `plan` consists of `(token, receiver_id)`
+    /// pairs, readiness of which is returned in the same order.
+    ///
+    /// NOTE: it's not a view method, since token contracts need to be
+    /// queried. At most [`MAX_WITHDRAWAL_PLAN_LEN`] entries can be
+    /// checked at once.
+    fn check_withdrawal_readiness(&mut self, plan: Vec<(AccountId, AccountId)>) -> Promise;
+}
+
+#[near(serializers = [json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WithdrawReadiness {
+    pub token: AccountId,
+    pub receiver_id: AccountId,
+    /// Whether `receiver_id` is registered on `token`.
+    /// Tokens which don't implement NEP-145 are reported as unregistered.
+    pub registered: bool,
+    /// Minimum storage deposit required for `receiver_id` to be
+    /// registered on `token`, `0` if it's already registered
+    pub storage_deposit: U128,
+}
@@ -1,6 +1,8 @@
 use near_contract_standards::non_fungible_token::{TokenId, core::NonFungibleTokenReceiver};
 use near_plugins::AccessControllable;
-use near_sdk::{AccountId, PromiseOrValue, ext_contract};
+use near_sdk::{AccountId, Promise, PromiseOrValue, ext_contract};
+
+use super::DepositMessage;
 
 #[ext_contract(ext_nft_withdraw)]
 pub trait NonFungibleTokenWithdrawer:
@@ -22,6 +24,41 @@ pub trait NonFungibleTokenWithdrawer:
     ) -> PromiseOrValue<bool>;
 }
 
+#[ext_contract(ext_nft_approved_deposit)]
+pub trait NonFungibleTokenApprovedDepositor: NonFungibleTokenApprovedDepositResolver {
+    /// Deposit NFT that was previously approved for this contract via
+    /// `nft_approve()` on `token`. The NFT is pulled from the caller with
+    /// `nft_transfer()` using given `approval_id`.
+    ///
+    /// `msg` has the same format as in `nft_on_transfer()`, i.e. it
+    /// contains [`AccountId`] of the internal recipient or serialized
+    /// [`DepositMessage`]. Intents from the
+    /// message are executed in a detached promise, so `refund_if_fails`
+    /// is not supported.
+    ///
+    /// Returns whether the NFT was deposited.
+    ///
+    /// NOTE: MUST attach 1 yⓃ for security purposes.
+    fn nft_deposit_approved(
+        &mut self,
+        token: AccountId,
+        token_id: TokenId,
+        approval_id: u64,
+        msg: Option<String>,
+    ) -> Promise;
+}
+
+#[ext_contract(ext_nft_approved_deposit_resolver)]
+pub trait NonFungibleTokenApprovedDepositResolver {
+    fn nft_resolve_deposit_approved(
+        &mut self,
+        token: AccountId,
+        sender_id: AccountId,
+        token_id: TokenId,
+        msg: DepositMessage,
+    ) -> bool;
+}
+
 #[ext_contract(ext_nft_withdraw_resolver)]
 pub trait NonFungibleTokenWithdrawResolver {
     fn nft_resolve_withdraw(
@@ -2,7 +2,7 @@
 
 use defuse_nep245::{MultiTokenCore, TokenId, receiver::MultiTokenReceiver};
 use near_plugins::AccessControllable;
-use near_sdk::{AccountId, PromiseOrValue, ext_contract, json_types::U128};
+use near_sdk::{AccountId, Gas, PromiseOrValue, ext_contract, json_types::U128};
 
 #[ext_contract(ext_mt_withdraw)]
 pub trait MultiTokenWithdrawer: MultiTokenReceiver + MultiTokenWithdrawResolver {
@@ -11,6 +11,10 @@ pub trait MultiTokenWithdrawer: MultiTokenReceiver + MultiTokenWithdrawResolver
     /// Optionally can specify `storage_deposit` for `receiver_id` on `token`.
     /// The amount will be subtracted from user's NEP-141 `wNEAR` balance.
     ///
+    /// Optional `receiver_ids` are parallel to `token_ids` and allow to
+    /// withdraw different tokens to different receivers, while `None`
+    /// entries fall back to `receiver_id`.
+    ///
     /// NOTE: MUST attach 1 yⓃ for security purposes.
     fn mt_withdraw(
         &mut self,
@@ -20,6 +24,7 @@ pub trait MultiTokenWithdrawer: MultiTokenReceiver + MultiTokenWithdrawResolver
         amounts: Vec<U128>,
         memo: Option<String>,
         msg: Option<String>,
+        receiver_ids: Option<Vec<Option<AccountId>>>,
     ) -> PromiseOrValue<Vec<U128>>;
 }
 
@@ -95,3 +100,20 @@ pub trait MultiTokenForcedWithdrawer: MultiTokenWithdrawer + AccessControllable
         msg: Option<String>,
     ) -> PromiseOrValue<Vec<U128>>;
 }
+
+#[ext_contract(ext_mt_on_transfer_gas)]
+pub trait MultiTokenOnTransferGas: AccessControllable {
+    /// Returns gas attached to `mt_on_transfer` notifications of
+    /// transfers which don't specify `min_gas`
+    fn mt_on_transfer_default_gas(&self) -> Gas;
+
+    /// Sets gas attached to `mt_on_transfer` notifications of transfers
+    /// which don't specify `min_gas`. `None` resets it to
+    /// [`NotifyOnTransfer::MT_ON_TRANSFER_GAS_DEFAULT`](defuse_core::intents::tokens::NotifyOnTransfer::MT_ON_TRANSFER_GAS_DEFAULT).
+    ///
+    /// NOTE: can't be lower than
+    /// [`NotifyOnTransfer::MT_ON_TRANSFER_GAS_MIN`](defuse_core::intents::tokens::NotifyOnTransfer::MT_ON_TRANSFER_GAS_MIN).
+    ///
+    /// Attached deposit of 1yN is required for security purposes.
+    fn set_mt_on_transfer_default_gas(&mut self, gas: Option<Gas>);
+}
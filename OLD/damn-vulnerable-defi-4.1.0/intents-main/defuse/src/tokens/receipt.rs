@@ -0,0 +1,28 @@
+use defuse_core::token_id::TokenId;
+use near_sdk::{ext_contract, json_types::U128, near};
+
+/// Record of a deposit made with
+/// [`DepositMessage::mint_receipt`](super::DepositMessage::mint_receipt).
+///
+/// Receipts are NEP-171 tokens issued by this contract itself, i.e.
+/// `nep171:<this contract>:<receipt_id>`, which can be transferred
+/// within the contract like any other token.
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepositReceipt {
+    pub token_id: TokenId,
+    pub amount: U128,
+}
+
+#[ext_contract(ext_deposit_receipt_manager)]
+pub trait DepositReceiptManager {
+    /// Returns the deposit recorded by given receipt, if it wasn't
+    /// redeemed yet
+    fn deposit_receipt(&self, receipt_id: String) -> Option<DepositReceipt>;
+
+    /// Burns the receipt held by the caller and credits them with the
+    /// deposited tokens.
+    ///
+    /// NOTE: MUST attach 1 yⓃ for security purposes.
+    fn redeem_deposit_receipt(&mut self, receipt_id: String);
+}
@@ -0,0 +1,19 @@
+use near_plugins::AccessControllable;
+use near_sdk::{AccountId, ext_contract};
+
+#[ext_contract(ext_wnear_manager)]
+pub trait WNearManager: AccessControllable {
+    /// Returns id of wNEAR contract used for native withdrawals
+    /// and storage deposits
+    fn wnear_id(&self) -> AccountId;
+
+    /// Sets id of wNEAR contract used for native withdrawals and
+    /// storage deposits.
+    ///
+    /// NOTE: balances of the previous wNEAR are not remapped, since it
+    /// would require the underlying tokens to be migrated as well.
+    /// They remain withdrawable via `ft_withdraw`.
+    ///
+    /// Attached deposit of 1yN is required for security purposes.
+    fn set_wnear_id(&mut self, wnear_id: AccountId);
+}
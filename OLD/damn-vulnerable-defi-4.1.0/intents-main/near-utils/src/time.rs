@@ -1,10 +1,46 @@
 use chrono::{DateTime, Utc};
 use near_sdk::env;
 
+/// Storage key of the offset added to block timestamp by
+/// [`advance_mock_time`]
+#[cfg(feature = "mock_time")]
+const MOCK_TIME_OFFSET_KEY: &[u8] = b"mock_time_offset";
+
 pub fn now() -> DateTime<Utc> {
     DateTime::from_timestamp_nanos(
-        env::block_timestamp()
+        block_timestamp()
             .try_into()
             .unwrap_or_else(|_| unreachable!()),
     )
 }
+
+/// Same as [`env::block_timestamp()`], but shifted forward by
+/// [`advance_mock_time`] when `mock_time` feature is enabled
+#[cfg(not(feature = "mock_time"))]
+#[inline]
+pub fn block_timestamp() -> u64 {
+    env::block_timestamp()
+}
+
+/// Same as [`env::block_timestamp()`], but shifted forward by
+/// [`advance_mock_time`] when `mock_time` feature is enabled
+#[cfg(feature = "mock_time")]
+#[inline]
+pub fn block_timestamp() -> u64 {
+    env::block_timestamp().saturating_add(mock_time_offset())
+}
+
+#[cfg(feature = "mock_time")]
+fn mock_time_offset() -> u64 {
+    env::storage_read(MOCK_TIME_OFFSET_KEY)
+        .and_then(|offset| offset.try_into().ok())
+        .map_or(0, u64::from_le_bytes)
+}
+
+/// Moves the clock observed by the contract forward, so that tests can
+/// expire deadlines without waiting for them in real time
+#[cfg(feature = "mock_time")]
+pub fn advance_mock_time(by: core::time::Duration) {
+    let offset = mock_time_offset().saturating_add(by.as_nanos().try_into().unwrap_or(u64::MAX));
+    env::storage_write(MOCK_TIME_OFFSET_KEY, &offset.to_le_bytes());
+}
@@ -0,0 +1,63 @@
+use near_sdk::{AccountId, ext_contract, json_types::U128, near};
+
+use crate::TokenId;
+
+/// Amount of a token an account is allowed to transfer on behalf of
+/// its owner
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Approval {
+    pub approval_id: u64,
+    pub amount: U128,
+}
+
+/// A trait representing the [multi-token approval management standard](https://nomicon.io/Standards/Tokens/MultiToken/ApprovalManagement#interface).
+#[ext_contract(ext_mt_approval)]
+pub trait MultiTokenApproval {
+    /// Allows `account_id` to transfer up to `amounts` of `token_ids`
+    /// on behalf of the predecessor, overriding previous approvals.
+    /// `msg` is not supported.
+    ///
+    /// Approved transfers can only be made with `mt_transfer` and
+    /// `mt_batch_transfer`, `*_call` variants reject approvals.
+    ///
+    /// NOTE: MUST attach at least 1 yⓃ and enough to cover storage of
+    /// new approvals, the excess is refunded.
+    fn mt_approve(
+        &mut self,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<U128>,
+        account_id: AccountId,
+        msg: Option<String>,
+    );
+
+    /// Revokes approvals of `account_id` for `token_ids`
+    ///
+    /// NOTE: MUST attach 1 yⓃ for security purposes.
+    fn mt_revoke(&mut self, token_ids: Vec<TokenId>, account_id: AccountId);
+
+    /// Revokes approvals of all accounts for `token_ids`
+    ///
+    /// NOTE: MUST attach 1 yⓃ for security purposes.
+    fn mt_revoke_all(&mut self, token_ids: Vec<TokenId>);
+
+    /// Returns whether `approved_account_id` is allowed to transfer
+    /// `amounts` of `token_ids` on behalf of `owner_id`, optionally
+    /// checking given `approval_ids` as well
+    fn mt_is_approved(
+        &self,
+        owner_id: AccountId,
+        token_ids: Vec<TokenId>,
+        approved_account_id: AccountId,
+        amounts: Vec<U128>,
+        approval_ids: Option<Vec<u64>>,
+    ) -> bool;
+
+    /// Returns approval of `account_id` for `token_id` owned by `owner_id`
+    fn mt_approval(
+        &self,
+        owner_id: AccountId,
+        token_id: TokenId,
+        account_id: AccountId,
+    ) -> Option<Approval>;
+}
@@ -1,3 +1,4 @@
+pub mod approval;
 mod core;
 pub mod enumeration;
 mod events;
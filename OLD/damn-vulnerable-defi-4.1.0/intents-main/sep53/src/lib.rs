@@ -4,7 +4,7 @@ use near_sdk::{env, near};
 use serde_with::serde_as;
 
 /// See [SEP-53](https://github.com/stellar/stellar-protocol/blob/master/ecosystem/sep-0053.md)
-#[near(serializers = [json])]
+#[near(serializers = [borsh, json])]
 #[serde(rename_all = "snake_case")]
 #[derive(Debug, Clone)]
 pub struct Sep53Payload {
@@ -38,7 +38,7 @@ impl Payload for Sep53Payload {
     not(all(feature = "abi", not(target_arch = "wasm32"))),
     serde_as(schemars = false)
 )]
-#[near(serializers = [json])]
+#[near(serializers = [borsh, json])]
 #[autoimpl(Deref using self.payload)]
 #[derive(Debug, Clone)]
 pub struct SignedSep53Payload {
@@ -26,7 +26,9 @@ where
     where
         D: Deserializer<'de>,
     {
-        let s = <&str as Deserialize>::deserialize(deserializer)?;
+        // owned rather than borrowed, so that buffered input (i.e. `serde_json::Value`
+        // or `#[serde(flatten)]`) can be deserialized, too
+        let s = String::deserialize(deserializer)?;
 
         let bytes = bs58::decode(s).into_vec().map_err(de::Error::custom)?;
 
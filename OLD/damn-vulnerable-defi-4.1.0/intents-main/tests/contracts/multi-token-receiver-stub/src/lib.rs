@@ -1,5 +1,8 @@
-use defuse_nep245::{TokenId, receiver::MultiTokenReceiver};
-use near_sdk::{AccountId, PromiseOrValue, json_types::U128, near, serde_json};
+use defuse_nep245::{TokenId, ext_mt_core, receiver::MultiTokenReceiver};
+use near_sdk::{
+    AccountId, Gas, GasWeight, NearToken, Promise, PromiseOrValue, env, json_types::U128, near,
+    serde_json,
+};
 
 /// Minimal stub contract used for integration tests.
 #[derive(Default)]
@@ -14,6 +17,16 @@ pub enum MTReceiverMode {
     ReturnValue(U128),
     Panic,
     LargeReturn,
+    /// Calls back into the token contract to transfer received tokens
+    /// to given account before the transfer is resolved
+    Reenter(AccountId),
+    /// Calls given method of the token contract with given arguments
+    /// and deposit before the transfer is resolved
+    ReenterCall {
+        method_name: String,
+        args: serde_json::Value,
+        deposit: NearToken,
+    },
 }
 
 #[near]
@@ -37,6 +50,23 @@ impl MultiTokenReceiver for Contract {
             }
             // 16 * 250_000 = 4 MB, which is the limit for a contract return value
             MTReceiverMode::LargeReturn => PromiseOrValue::Value(vec![U128(u128::MAX); 250_000]),
+            MTReceiverMode::Reenter(receiver_id) => ext_mt_core::ext(env::predecessor_account_id())
+                .with_attached_deposit(NearToken::from_yoctonear(1))
+                .mt_batch_transfer(receiver_id, token_ids, amounts, None, None)
+                .into(),
+            MTReceiverMode::ReenterCall {
+                method_name,
+                args,
+                deposit,
+            } => Promise::new(env::predecessor_account_id())
+                .function_call_weight(
+                    method_name,
+                    serde_json::to_vec(&args).unwrap(),
+                    deposit,
+                    Gas::from_gas(0),
+                    GasWeight(1),
+                )
+                .into(),
         }
     }
 }
@@ -117,4 +147,23 @@ mod tests {
 
         assert_eq!(result, vec![U128(u128::MAX); 250000]);
     }
+
+    #[test]
+    fn mt_on_transfer_reenter() {
+        let mut contract = Contract;
+        let message = serde_json::to_string(&MTReceiverMode::Reenter(
+            AccountId::from_str("attacker.testnet").unwrap(),
+        ))
+        .unwrap();
+
+        let result = contract.mt_on_transfer(
+            AccountId::from_str("sender.testnet").unwrap(),
+            vec![],
+            vec!["token".to_string()],
+            vec![U128(1)],
+            message,
+        );
+
+        assert!(matches!(result, PromiseOrValue::Promise(_)));
+    }
 }
@@ -0,0 +1,25 @@
+use std::collections::HashMap;
+
+use near_sdk::{json_types::U128, near};
+
+/// Minimal price oracle used for testing conditional intents.
+#[derive(Default)]
+#[near(contract_state)]
+pub struct Contract {
+    prices: HashMap<String, U128>,
+}
+
+#[near]
+impl Contract {
+    pub fn set_price(&mut self, asset: String, price: Option<U128>) {
+        if let Some(price) = price {
+            self.prices.insert(asset, price);
+        } else {
+            self.prices.remove(&asset);
+        }
+    }
+
+    pub fn get_price(&self, asset: String) -> Option<U128> {
+        self.prices.get(&asset).copied()
+    }
+}
@@ -0,0 +1,290 @@
+use defuse::{
+    contract::Role,
+    core::{
+        DefuseError,
+        amounts::Amounts,
+        fees::Pips,
+        intents::{
+            token_diff::{TokenDeltas, TokenDiff},
+            tokens::Transfer,
+        },
+        token_id::{TokenId, nep141::Nep141TokenId},
+    },
+};
+use defuse_test_utils::asserts::ResultAssertsExt;
+use near_sdk::AccountId;
+use rstest::rstest;
+
+use crate::{
+    tests::defuse::{
+        DefuseSignerExt, accounts::traits::AccountDenylistExt, env::Env,
+        intents::ExecuteIntentsExt, tokens::nep141::traits::DefuseFtWithdrawer,
+    },
+    utils::{acl::AclExt, mt::MtExt},
+};
+
+#[tokio::test]
+#[rstest]
+async fn test_denylist() {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (blocked_account, other_account, dao, ft) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_token()
+    );
+
+    env.initial_ft_storage_deposit(vec![blocked_account.id(), other_account.id()], vec![&ft])
+        .await;
+
+    let ft1: TokenId = Nep141TokenId::new(ft.clone()).into();
+    env.defuse_ft_deposit_to(&ft, 1000, blocked_account.id())
+        .await
+        .unwrap();
+    env.defuse_ft_deposit_to(&ft, 1000, other_account.id())
+        .await
+        .unwrap();
+
+    // block account
+    {
+        dao.block_accounts(env.defuse.id(), [blocked_account.id().clone()])
+            .await
+            .expect_err("doesn't have DAO role yet");
+        assert!(
+            !env.is_account_blocked(env.defuse.id(), blocked_account.id())
+                .await
+                .unwrap()
+        );
+
+        env.acl_grant_role(env.defuse.id(), Role::DAO, dao.id())
+            .await
+            .unwrap();
+
+        dao.block_accounts(env.defuse.id(), [blocked_account.id().clone()])
+            .await
+            .unwrap();
+        assert!(
+            env.is_account_blocked(env.defuse.id(), blocked_account.id())
+                .await
+                .unwrap()
+        );
+    }
+
+    // incoming deposit
+    env.defuse_ft_deposit_to(&ft, 100, blocked_account.id())
+        .await
+        .expect_err("deposits to blocked account should be refunded");
+
+    // incoming mt_transfer
+    other_account
+        .mt_transfer(
+            env.defuse.id(),
+            blocked_account.id(),
+            &ft1.to_string(),
+            100,
+            None,
+            None,
+        )
+        .await
+        .assert_err_contains(DefuseError::AccountBlocked(blocked_account.id().clone()).to_string());
+
+    // incoming transfer intent
+    {
+        let payload = other_account
+            .sign_defuse_payload_default(
+                env.defuse.id(),
+                [Transfer {
+                    receiver_id: blocked_account.id().clone(),
+                    tokens: Amounts::new([(ft1.clone(), 100)].into()),
+                    memo: None,
+                    notification: None,
+                }],
+            )
+            .await
+            .unwrap();
+
+        env.defuse
+            .execute_intents(env.defuse.id(), [payload])
+            .await
+            .assert_err_contains(
+                DefuseError::AccountBlocked(blocked_account.id().clone()).to_string(),
+            );
+    }
+
+    // outgoing transfer intent
+    {
+        let payload = blocked_account
+            .sign_defuse_payload_default(
+                env.defuse.id(),
+                [Transfer {
+                    receiver_id: other_account.id().clone(),
+                    tokens: Amounts::new([(ft1.clone(), 100)].into()),
+                    memo: None,
+                    notification: None,
+                }],
+            )
+            .await
+            .unwrap();
+
+        env.defuse
+            .execute_intents(env.defuse.id(), [payload])
+            .await
+            .assert_err_contains(
+                DefuseError::AccountBlocked(blocked_account.id().clone()).to_string(),
+            );
+    }
+
+    // outgoing mt_transfer
+    blocked_account
+        .mt_transfer(
+            env.defuse.id(),
+            other_account.id(),
+            &ft1.to_string(),
+            100,
+            None,
+            None,
+        )
+        .await
+        .assert_err_contains(DefuseError::AccountBlocked(blocked_account.id().clone()).to_string());
+
+    // outgoing withdrawal
+    blocked_account
+        .defuse_ft_withdraw(env.defuse.id(), &ft, other_account.id(), 100, None, None)
+        .await
+        .assert_err_contains(DefuseError::AccountBlocked(blocked_account.id().clone()).to_string());
+
+    for account in [&blocked_account, &other_account] {
+        assert_eq!(
+            env.defuse
+                .mt_balance_of(account.id(), &ft1.to_string())
+                .await
+                .unwrap(),
+            1000,
+            "nothing should be transferred to/from blocked account"
+        );
+    }
+
+    // unblock account
+    dao.unblock_accounts(env.defuse.id(), [blocked_account.id().clone()])
+        .await
+        .unwrap();
+    assert!(
+        !env.is_account_blocked(env.defuse.id(), blocked_account.id())
+            .await
+            .unwrap()
+    );
+
+    blocked_account
+        .mt_transfer(
+            env.defuse.id(),
+            other_account.id(),
+            &ft1.to_string(),
+            100,
+            None,
+            None,
+        )
+        .await
+        .expect("unblocked account should be able to transfer");
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(other_account.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        1100
+    );
+}
+
+#[tokio::test]
+#[rstest]
+async fn blocked_fee_collector_still_receives_fees() {
+    let fee = Pips::ONE_PERCENT;
+    let fee_collector: AccountId = "treasury.near".parse().unwrap();
+    let env = Env::builder()
+        .deployer_as_super_admin()
+        .fee(fee)
+        .fee_collector(fee_collector.clone())
+        .build()
+        .await;
+
+    let (user1, user2, dao, ft1, ft2) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_token(),
+        env.create_token()
+    );
+
+    env.initial_ft_storage_deposit(vec![user1.id(), user2.id()], vec![&ft1, &ft2])
+        .await;
+
+    let ft1_token_id = TokenId::from(Nep141TokenId::new(ft1.clone()));
+    let ft2_token_id = TokenId::from(Nep141TokenId::new(ft2.clone()));
+    env.defuse_ft_deposit_to(&ft1, 100, user1.id())
+        .await
+        .unwrap();
+    env.defuse_ft_deposit_to(&ft2, 200, user2.id())
+        .await
+        .unwrap();
+
+    env.acl_grant_role(env.defuse.id(), Role::DAO, dao.id())
+        .await
+        .unwrap();
+    dao.block_accounts(env.defuse.id(), [fee_collector.clone()])
+        .await
+        .unwrap();
+
+    let token_diff = |deltas: [(&TokenId, i128); 2]| TokenDiff {
+        diff: TokenDeltas::default()
+            .with_apply_deltas(
+                deltas
+                    .into_iter()
+                    .map(|(token_id, delta)| (token_id.clone(), delta)),
+            )
+            .unwrap(),
+        memo: None,
+        referral: None,
+        max_fee: None,
+        solver: None,
+        partial_fill: false,
+    };
+    let user1_delta_out = TokenDiff::closure_delta(&ft2_token_id, -200, fee).unwrap();
+    let user2_delta_out = TokenDiff::closure_delta(&ft1_token_id, -100, fee).unwrap();
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [
+                user1
+                    .sign_defuse_payload_default(
+                        env.defuse.id(),
+                        [token_diff([
+                            (&ft1_token_id, -100),
+                            (&ft2_token_id, user1_delta_out),
+                        ])],
+                    )
+                    .await
+                    .unwrap(),
+                user2
+                    .sign_defuse_payload_default(
+                        env.defuse.id(),
+                        [token_diff([
+                            (&ft1_token_id, user2_delta_out),
+                            (&ft2_token_id, -200),
+                        ])],
+                    )
+                    .await
+                    .unwrap(),
+            ],
+        )
+        .await
+        .expect("swaps shouldn't be blocked by fee collector being blocked");
+
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(&fee_collector, &ft1_token_id.to_string())
+            .await
+            .unwrap(),
+        100 - user2_delta_out.unsigned_abs()
+    );
+}
@@ -1,23 +1,33 @@
 use crate::{tests::defuse::DefuseSignerExt, utils::fixtures::public_key};
 use defuse::{
+    accounts::{MAX_ACCOUNTS_PER_FORCE_LOCK, MAX_ACCOUNTS_PER_QUERY},
     contract::Role,
     core::{
-        DefuseError,
+        Deadline, DefuseError,
         crypto::PublicKey,
-        intents::Intent,
+        intents::{DefuseIntents, Intent, account::AddPublicKey},
+        payload::multi::{MultiPayload, SigningStandard},
         token_id::{TokenId, nep141::Nep141TokenId},
     },
+    tokens::DepositMessage,
 };
 
 use defuse_test_utils::asserts::ResultAssertsExt;
+use multi_token_receiver_stub::MTReceiverMode;
+use near_sdk::{AccountId, NearToken};
+use near_workspaces::{
+    Account,
+    types::{KeyType, SecretKey},
+};
 use rstest::rstest;
 
 use crate::{
     tests::defuse::{
+        DefuseSigner,
         accounts::{AccountManagerExt, traits::ForceAccountManagerExt},
         env::Env,
         intents::ExecuteIntentsExt,
-        tokens::nep141::traits::DefuseFtWithdrawer,
+        tokens::nep141::traits::{DefuseFtReceiver, DefuseFtWithdrawer},
     },
     utils::{acl::AclExt, mt::MtExt, payload::ExtractNonceExt},
 };
@@ -498,3 +508,477 @@ async fn test_force_set_auth_by_predecessor_id(public_key: PublicKey) {
         );
     }
 }
+
+#[tokio::test]
+async fn test_are_auth_by_predecessor_id_enabled() {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (user1, user2, user3, account_locker) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_user()
+    );
+
+    env.acl_grant_role(
+        env.defuse.id(),
+        Role::UnrestrictedAccountLocker,
+        account_locker.id(),
+    )
+    .await
+    .unwrap();
+
+    account_locker
+        .force_disable_auth_by_predecessor_ids(
+            env.defuse.id(),
+            [user1.id().clone(), user3.id().clone()],
+        )
+        .await
+        .unwrap();
+
+    // accounts which have never interacted with the contract are enabled too
+    let unknown: AccountId = "unknown.near".parse().unwrap();
+    assert_eq!(
+        env.defuse
+            .are_auth_by_predecessor_id_enabled(&[
+                user3.id().clone(),
+                user2.id().clone(),
+                unknown,
+                user1.id().clone(),
+                user3.id().clone(),
+            ])
+            .await
+            .unwrap(),
+        [false, true, true, false, false]
+    );
+
+    assert!(
+        env.defuse
+            .are_auth_by_predecessor_id_enabled(&[])
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    env.defuse
+        .are_auth_by_predecessor_id_enabled(&vec![user1.id().clone(); MAX_ACCOUNTS_PER_QUERY + 1])
+        .await
+        .unwrap_err();
+}
+
+#[tokio::test]
+async fn test_deposit_then_lock() {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (escrow, receiver, ft) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+
+    env.initial_ft_storage_deposit(vec![escrow.id(), receiver.id()], vec![&ft])
+        .await;
+
+    let ft1: TokenId = Nep141TokenId::new(ft.clone()).into();
+
+    // no permission: deposit is refunded
+    {
+        assert_eq!(
+            env.defuse_ft_deposit(
+                env.defuse.id(),
+                &ft,
+                1000,
+                DepositMessage::deposit_then_lock(escrow.id().clone()),
+            )
+            .await
+            .unwrap(),
+            0,
+        );
+        assert!(
+            !env.is_account_locked(env.defuse.id(), escrow.id())
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            env.defuse
+                .mt_balance_of(escrow.id(), &ft1.to_string())
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    for role in [
+        Role::UnrestrictedAccountLocker,
+        Role::UnrestrictedAccountUnlocker,
+    ] {
+        env.acl_grant_role(env.defuse.id(), role, env.id())
+            .await
+            .unwrap();
+    }
+
+    // having the role is not enough, the lock has to be allowed beforehand
+    assert_eq!(
+        env.defuse_ft_deposit(
+            env.defuse.id(),
+            &ft,
+            1000,
+            DepositMessage::deposit_then_lock(escrow.id().clone()),
+        )
+        .await
+        .unwrap(),
+        0,
+    );
+    assert!(
+        env.force_allow_lock_on_deposit(env.defuse.id(), escrow.id())
+            .await
+            .unwrap()
+    );
+
+    // funds are present, but the account is locked
+    {
+        assert_eq!(
+            env.defuse_ft_deposit(
+                env.defuse.id(),
+                &ft,
+                1000,
+                DepositMessage::deposit_then_lock(escrow.id().clone()),
+            )
+            .await
+            .unwrap(),
+            1000,
+        );
+        assert!(
+            env.is_account_locked(env.defuse.id(), escrow.id())
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            env.defuse
+                .mt_balance_of(escrow.id(), &ft1.to_string())
+                .await
+                .unwrap(),
+            1000
+        );
+
+        escrow
+            .mt_transfer(
+                env.defuse.id(),
+                receiver.id(),
+                &ft1.to_string(),
+                100,
+                None,
+                None,
+            )
+            .await
+            .expect_err("locked account shouldn't be able to transfer");
+        escrow
+            .defuse_ft_withdraw(env.defuse.id(), &ft, escrow.id(), 100, None, None)
+            .await
+            .expect_err("locked account shouldn't be able to withdraw");
+    }
+
+    // release the escrow
+    {
+        assert!(
+            env.force_unlock_account(env.defuse.id(), escrow.id())
+                .await
+                .unwrap()
+        );
+
+        escrow
+            .mt_transfer(
+                env.defuse.id(),
+                receiver.id(),
+                &ft1.to_string(),
+                1000,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            env.defuse
+                .mt_balance_of(receiver.id(), &ft1.to_string())
+                .await
+                .unwrap(),
+            1000
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_intermediary_cant_lock_on_deposit() {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (victim, ft, intermediary) = futures::join!(
+        env.create_user(),
+        env.create_token(),
+        env.deploy_mt_receiver_stub()
+    );
+
+    env.initial_ft_storage_deposit(vec![env.id()], vec![&ft])
+        .await;
+    env.defuse_ft_deposit_to(&ft, 1000, env.id()).await.unwrap();
+    env.acl_grant_role(env.defuse.id(), Role::UnrestrictedAccountLocker, env.id())
+        .await
+        .unwrap();
+
+    // account holding the role calls an untrusted contract, which tries
+    // to lock the victim by pretending to be a token being deposited
+    let ft1: TokenId = Nep141TokenId::new(ft.clone()).into();
+    env.mt_transfer_call(
+        env.defuse.id(),
+        intermediary.id(),
+        &ft1.to_string(),
+        100,
+        None,
+        None,
+        serde_json::to_string(&MTReceiverMode::ReenterCall {
+            method_name: "ft_on_transfer".to_string(),
+            args: serde_json::json!({
+                "sender_id": intermediary.id(),
+                "amount": "1",
+                "msg": DepositMessage::deposit_then_lock(victim.id().clone()).to_string(),
+            }),
+            deposit: NearToken::from_yoctonear(0),
+        })
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert!(
+        !env.is_account_locked(env.defuse.id(), victim.id())
+            .await
+            .unwrap()
+    );
+}
+
+async fn sign_empty_intents(
+    env: &Env,
+    signer: &Account,
+    standard: crate::tests::defuse::SigningStandard,
+) -> MultiPayload {
+    let deadline = Deadline::timeout(std::time::Duration::from_secs(120));
+    let nonce = signer
+        .unique_nonce(env.defuse.id(), Some(deadline))
+        .await
+        .unwrap();
+    signer.sign_defuse_message(
+        standard,
+        env.defuse.id(),
+        nonce,
+        deadline,
+        DefuseIntents { intents: [].into() },
+    )
+}
+
+#[tokio::test]
+async fn test_force_disable_signing_standards() {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (account_locker, account_unlocker) = futures::join!(env.create_user(), env.create_user());
+
+    // ERC-191 payloads are signed by secp256k1 key registered by default
+    // for implicit Ethereum account, while NEP-413 ones by an ed25519 key
+    // added to the same account
+    let secret_key = SecretKey::from_random(KeyType::SECP256K1);
+    let eth_user = Account::from_secret_key(
+        PublicKey::Secp256k1(secret_key.public_key().key_data().try_into().unwrap())
+            .to_implicit_account_id(),
+        secret_key,
+        env.sandbox().worker(),
+    );
+    let secret_key = SecretKey::from_random(KeyType::ED25519);
+    let near_key_user = Account::from_secret_key(
+        eth_user.id().clone(),
+        secret_key.clone(),
+        env.sandbox().worker(),
+    );
+    {
+        let deadline = Deadline::timeout(std::time::Duration::from_secs(120));
+        let nonce = eth_user
+            .unique_nonce(env.defuse.id(), Some(deadline))
+            .await
+            .unwrap();
+        env.defuse
+            .execute_intents(
+                env.defuse.id(),
+                [eth_user.sign_defuse_message(
+                    crate::tests::defuse::SigningStandard::Erc191,
+                    env.defuse.id(),
+                    nonce,
+                    deadline,
+                    DefuseIntents {
+                        intents: [AddPublicKey {
+                            public_key: PublicKey::Ed25519(
+                                secret_key.public_key().key_data().try_into().unwrap(),
+                            ),
+                        }
+                        .into()]
+                        .into(),
+                    },
+                )],
+            )
+            .await
+            .unwrap();
+    }
+
+    // no permission
+    account_locker
+        .force_disable_signing_standards(env.defuse.id(), eth_user.id(), [SigningStandard::Erc191])
+        .await
+        .unwrap_err();
+
+    env.acl_grant_role(
+        env.defuse.id(),
+        Role::UnrestrictedAccountLocker,
+        account_locker.id(),
+    )
+    .await
+    .unwrap();
+    env.acl_grant_role(
+        env.defuse.id(),
+        Role::UnrestrictedAccountUnlocker,
+        account_unlocker.id(),
+    )
+    .await
+    .unwrap();
+
+    // disable ERC-191
+    account_locker
+        .force_disable_signing_standards(env.defuse.id(), eth_user.id(), [SigningStandard::Erc191])
+        .await
+        .unwrap();
+    assert_eq!(
+        account_locker
+            .disabled_signing_standards(env.defuse.id(), eth_user.id())
+            .await
+            .unwrap(),
+        [SigningStandard::Erc191].into()
+    );
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [sign_empty_intents(
+                &env,
+                &eth_user,
+                crate::tests::defuse::SigningStandard::Erc191,
+            )
+            .await],
+        )
+        .await
+        .assert_err_contains(
+            DefuseError::SigningStandardDisabled(eth_user.id().clone(), SigningStandard::Erc191)
+                .to_string(),
+        );
+
+    // other standards are still allowed
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [sign_empty_intents(
+                &env,
+                &near_key_user,
+                crate::tests::defuse::SigningStandard::Nep413,
+            )
+            .await],
+        )
+        .await
+        .unwrap();
+
+    // re-enable ERC-191
+    account_unlocker
+        .force_enable_signing_standards(env.defuse.id(), eth_user.id(), [SigningStandard::Erc191])
+        .await
+        .unwrap();
+    assert!(
+        account_locker
+            .disabled_signing_standards(env.defuse.id(), eth_user.id())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [sign_empty_intents(
+                &env,
+                &eth_user,
+                crate::tests::defuse::SigningStandard::Erc191,
+            )
+            .await],
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_force_lock_accounts() {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (account_locker, user1, user2, user3, untouched) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_user()
+    );
+    let account_ids = [user1.id().clone(), user2.id().clone(), user3.id().clone()];
+
+    // no permission
+    account_locker
+        .force_lock_accounts(env.defuse.id(), account_ids.clone(), Some("incident"))
+        .await
+        .expect_err("doesn't have UnrestrictedAccountLocker role yet");
+
+    env.acl_grant_role(
+        env.defuse.id(),
+        Role::UnrestrictedAccountLocker,
+        account_locker.id(),
+    )
+    .await
+    .unwrap();
+
+    // input length is bounded
+    account_locker
+        .force_lock_accounts(
+            env.defuse.id(),
+            (0..=MAX_ACCOUNTS_PER_FORCE_LOCK)
+                .map(|i| format!("user{i}.near").parse::<AccountId>().unwrap()),
+            None,
+        )
+        .await
+        .assert_err_contains("too many accounts");
+
+    assert!(
+        account_locker
+            .force_lock_account(env.defuse.id(), user1.id())
+            .await
+            .unwrap()
+    );
+
+    // already locked account is reported as not newly locked
+    assert_eq!(
+        account_locker
+            .force_lock_accounts(env.defuse.id(), account_ids.clone(), Some("incident"))
+            .await
+            .unwrap(),
+        [false, true, true]
+    );
+
+    for account_id in &account_ids {
+        assert!(
+            env.is_account_locked(env.defuse.id(), account_id)
+                .await
+                .unwrap(),
+            "{account_id} should be locked",
+        );
+    }
+    assert!(
+        !env.is_account_locked(env.defuse.id(), untouched.id())
+            .await
+            .unwrap(),
+        "account not in the batch shouldn't be locked",
+    );
+}
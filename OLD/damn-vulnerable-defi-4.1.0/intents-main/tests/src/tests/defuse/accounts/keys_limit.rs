@@ -0,0 +1,140 @@
+use defuse::{
+    accounts::DEFAULT_MAX_KEYS_TO_CHECK,
+    contract::Role,
+    core::{
+        DefuseError,
+        amounts::Amounts,
+        intents::{account::SetMultisigThreshold, tokens::Transfer},
+        token_id::{TokenId, nep141::Nep141TokenId},
+    },
+};
+use defuse_test_utils::asserts::ResultAssertsExt;
+use near_workspaces::types::{KeyType, SecretKey};
+use rstest::rstest;
+
+use crate::{
+    tests::defuse::{
+        DefuseSignerExt,
+        accounts::{AccountManagerExt, traits::AccountKeysLimitExt},
+        env::Env,
+        intents::ExecuteIntentsExt,
+    },
+    utils::{acl::AclExt, mt::MtExt},
+};
+
+const MAX_KEYS_TO_CHECK: u32 = 8;
+
+#[tokio::test]
+#[rstest]
+async fn max_keys_to_check() {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (user, other_user, dao, ft) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_token()
+    );
+
+    env.initial_ft_storage_deposit(vec![user.id(), other_user.id()], vec![&ft])
+        .await;
+
+    let ft1: TokenId = Nep141TokenId::new(ft.clone()).into();
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        env.max_keys_to_check(env.defuse.id()).await.unwrap(),
+        DEFAULT_MAX_KEYS_TO_CHECK
+    );
+
+    // set the limit
+    {
+        dao.set_max_keys_to_check(env.defuse.id(), Some(MAX_KEYS_TO_CHECK))
+            .await
+            .expect_err("doesn't have DAO role yet");
+
+        env.acl_grant_role(env.defuse.id(), Role::DAO, dao.id())
+            .await
+            .unwrap();
+
+        dao.set_max_keys_to_check(env.defuse.id(), Some(MAX_KEYS_TO_CHECK))
+            .await
+            .unwrap();
+        assert_eq!(
+            env.max_keys_to_check(env.defuse.id()).await.unwrap(),
+            MAX_KEYS_TO_CHECK
+        );
+    }
+
+    // user already has its own key registered, so it ends up holding
+    // more keys than the limit
+    for _ in 0..MAX_KEYS_TO_CHECK {
+        let public_key = SecretKey::from_random(KeyType::ED25519).public_key();
+        user.add_public_key(env.defuse.id(), public_key.to_string().parse().unwrap())
+            .await
+            .unwrap();
+    }
+    let n_keys = u8::try_from(MAX_KEYS_TO_CHECK + 1).unwrap();
+
+    // signatures are still verified without iterating keys
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(
+                    env.defuse.id(),
+                    [Transfer {
+                        receiver_id: other_user.id().clone(),
+                        tokens: Amounts::new([(ft1.clone(), 100)].into()),
+                        memo: None,
+                        notification: None,
+                    }],
+                )
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(other_user.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        100
+    );
+
+    // counting keys gives up past the limit
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(
+                    env.defuse.id(),
+                    [SetMultisigThreshold { m: 2, n_keys }],
+                )
+                .await
+                .unwrap()],
+        )
+        .await
+        .assert_err_contains(DefuseError::TooManyKeysToCheck.to_string());
+
+    // resetting to the default lifts the restriction
+    dao.set_max_keys_to_check(env.defuse.id(), None)
+        .await
+        .unwrap();
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(
+                    env.defuse.id(),
+                    [SetMultisigThreshold { m: 2, n_keys }],
+                )
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+}
@@ -11,7 +11,10 @@ use rstest::rstest;
 use crate::{
     assert_eq_event_logs,
     tests::defuse::{accounts::AccountManagerExt, env::Env},
-    utils::{fixtures::public_key, test_log::TestLog},
+    utils::{
+        fixtures::{ed25519_pk, p256_pk, public_key, secp256k1_pk},
+        test_log::TestLog,
+    },
 };
 
 #[tokio::test]
@@ -115,3 +118,67 @@ async fn test_add_and_remove_public_key(public_key: PublicKey) {
             .unwrap()
     );
 }
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn test_account_public_keys_paging(
+    ed25519_pk: PublicKey,
+    secp256k1_pk: PublicKey,
+    p256_pk: PublicKey,
+) {
+    let env = Env::builder().build().await;
+
+    let user = env.create_user().await;
+
+    let public_keys = [ed25519_pk, secp256k1_pk, p256_pk];
+    for public_key in public_keys {
+        user.add_public_key(env.defuse.id(), public_key)
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(
+        env.defuse
+            .account_public_keys(user.id(), None, 10)
+            .await
+            .unwrap(),
+        public_keys,
+    );
+
+    let first = env
+        .defuse
+        .account_public_keys(user.id(), None, 2)
+        .await
+        .unwrap();
+    assert_eq!(first, public_keys[..2]);
+
+    let second = env
+        .defuse
+        .account_public_keys(user.id(), first.last(), 2)
+        .await
+        .unwrap();
+    assert_eq!(second, public_keys[2..]);
+
+    assert!(
+        env.defuse
+            .account_public_keys(user.id(), second.last(), 2)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // pages are the same when queried again
+    assert_eq!(
+        env.defuse
+            .account_public_keys(user.id(), first.last(), 2)
+            .await
+            .unwrap(),
+        second,
+    );
+
+    env.defuse
+        .account_public_keys(user.id(), None, 257)
+        .await
+        .unwrap_err();
+}
@@ -1,10 +1,19 @@
 mod auth_by_predecessor_id;
+mod denylist;
 mod force;
+mod keys_limit;
 mod manage_public_keys;
 mod nonces;
+mod registration_reserve;
+mod storage_usage;
+mod tokens_limit;
 mod traits;
+mod withdrawal_cooldown;
 
-use defuse::core::{Nonce, crypto::PublicKey};
+use defuse::{
+    accounts::NonceStatus,
+    core::{Nonce, crypto::PublicKey},
+};
 use defuse_serde_utils::base64::AsBase64;
 use near_sdk::{AccountId, AccountIdRef, Gas, NearToken};
 use serde_json::json;
@@ -35,13 +44,37 @@ pub trait AccountManagerExt {
         public_key: &PublicKey,
     ) -> anyhow::Result<bool>;
 
+    async fn account_public_keys(
+        &self,
+        account_id: &AccountId,
+        from: Option<&PublicKey>,
+        limit: u32,
+    ) -> anyhow::Result<Vec<PublicKey>>;
+
     async fn is_nonce_used(&self, account_id: &AccountId, nonce: &Nonce) -> anyhow::Result<bool>;
 
+    async fn are_nonces_used(
+        &self,
+        account_id: &AccountId,
+        nonces: &[Nonce],
+    ) -> anyhow::Result<Vec<bool>>;
+
+    async fn nonce_status(
+        &self,
+        account_id: &AccountId,
+        nonce: &Nonce,
+    ) -> anyhow::Result<NonceStatus>;
+
     async fn is_auth_by_predecessor_id_enabled(
         &self,
         account_id: &AccountIdRef,
     ) -> anyhow::Result<bool>;
 
+    async fn are_auth_by_predecessor_id_enabled(
+        &self,
+        account_ids: &[AccountId],
+    ) -> anyhow::Result<Vec<bool>>;
+
     async fn disable_auth_by_predecessor_id(
         &self,
         defuse_contract_id: &AccountId,
@@ -108,6 +141,23 @@ impl AccountManagerExt for near_workspaces::Account {
             .await
     }
 
+    async fn account_public_keys(
+        &self,
+        account_id: &AccountId,
+        from: Option<&PublicKey>,
+        limit: u32,
+    ) -> anyhow::Result<Vec<PublicKey>> {
+        self.view(self.id(), "account_public_keys")
+            .args_json(json!({
+                "account_id": account_id,
+                "from": from,
+                "limit": limit,
+            }))
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
     async fn is_nonce_used(&self, account_id: &AccountId, nonce: &Nonce) -> anyhow::Result<bool> {
         self.view(self.id(), "is_nonce_used")
             .args_json(json!({
@@ -119,6 +169,36 @@ impl AccountManagerExt for near_workspaces::Account {
             .map_err(Into::into)
     }
 
+    async fn are_nonces_used(
+        &self,
+        account_id: &AccountId,
+        nonces: &[Nonce],
+    ) -> anyhow::Result<Vec<bool>> {
+        self.view(self.id(), "are_nonces_used")
+            .args_json(json!({
+                "account_id": account_id,
+                "nonces": nonces.iter().map(AsBase64).collect::<Vec<_>>(),
+            }))
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn nonce_status(
+        &self,
+        account_id: &AccountId,
+        nonce: &Nonce,
+    ) -> anyhow::Result<NonceStatus> {
+        self.view(self.id(), "nonce_status")
+            .args_json(json!({
+                "account_id": account_id,
+                "nonce": AsBase64(nonce),
+            }))
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
     async fn is_auth_by_predecessor_id_enabled(
         &self,
         account_id: &AccountIdRef,
@@ -132,6 +212,19 @@ impl AccountManagerExt for near_workspaces::Account {
             .map_err(Into::into)
     }
 
+    async fn are_auth_by_predecessor_id_enabled(
+        &self,
+        account_ids: &[AccountId],
+    ) -> anyhow::Result<Vec<bool>> {
+        self.view(self.id(), "are_auth_by_predecessor_id_enabled")
+            .args_json(json!({
+                "account_ids": account_ids,
+            }))
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
     async fn disable_auth_by_predecessor_id(
         &self,
         defuse_contract_id: &AccountId,
@@ -188,10 +281,37 @@ impl AccountManagerExt for near_workspaces::Contract {
             .await
     }
 
+    async fn account_public_keys(
+        &self,
+        account_id: &AccountId,
+        from: Option<&PublicKey>,
+        limit: u32,
+    ) -> anyhow::Result<Vec<PublicKey>> {
+        self.as_account()
+            .account_public_keys(account_id, from, limit)
+            .await
+    }
+
     async fn is_nonce_used(&self, account_id: &AccountId, nonce: &Nonce) -> anyhow::Result<bool> {
         self.as_account().is_nonce_used(account_id, nonce).await
     }
 
+    async fn are_nonces_used(
+        &self,
+        account_id: &AccountId,
+        nonces: &[Nonce],
+    ) -> anyhow::Result<Vec<bool>> {
+        self.as_account().are_nonces_used(account_id, nonces).await
+    }
+
+    async fn nonce_status(
+        &self,
+        account_id: &AccountId,
+        nonce: &Nonce,
+    ) -> anyhow::Result<NonceStatus> {
+        self.as_account().nonce_status(account_id, nonce).await
+    }
+
     async fn is_auth_by_predecessor_id_enabled(
         &self,
         account_id: &AccountIdRef,
@@ -201,6 +321,15 @@ impl AccountManagerExt for near_workspaces::Contract {
             .await
     }
 
+    async fn are_auth_by_predecessor_id_enabled(
+        &self,
+        account_ids: &[AccountId],
+    ) -> anyhow::Result<Vec<bool>> {
+        self.as_account()
+            .are_auth_by_predecessor_id_enabled(account_ids)
+            .await
+    }
+
     async fn disable_auth_by_predecessor_id(
         &self,
         defuse_contract_id: &AccountId,
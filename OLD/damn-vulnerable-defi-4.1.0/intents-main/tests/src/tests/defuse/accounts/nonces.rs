@@ -1,6 +1,7 @@
 use arbitrary::{Arbitrary, Unstructured};
 use chrono::{TimeDelta, Utc};
 use defuse::{
+    accounts::{MAX_NONCES_PER_QUERY, NonceStatus},
     contract::Role,
     core::{Deadline, Nonce, Salt, intents::DefuseIntents},
 };
@@ -18,9 +19,9 @@ use rstest::rstest;
 
 use crate::{
     tests::defuse::{
-        DefuseSigner, SigningStandard,
+        DefuseSigner, DefuseSignerExt, SigningStandard,
         accounts::AccountManagerExt,
-        env::{Env, create_random_salted_nonce},
+        env::{Env, create_namespaced_nonce, create_random_salted_nonce},
         garbage_collector::GarbageCollectorExt,
         intents::ExecuteIntentsExt,
         state::SaltManagerExt,
@@ -375,6 +376,181 @@ async fn test_cleanup_nonces(#[notrace] mut rng: impl Rng) {
     }
 }
 
+#[tokio::test]
+#[rstest]
+async fn nonce_status(#[notrace] mut rng: impl Rng) {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+    let user = env.create_user().await;
+
+    let current_salt = env.defuse.current_salt(env.defuse.id()).await.unwrap();
+    let deadline = Deadline::new(
+        Utc::now()
+            .checked_add_signed(TimeDelta::seconds(1))
+            .unwrap(),
+    );
+
+    let unused_nonce = create_random_salted_nonce(current_salt, deadline, &mut rng);
+    let committed_nonce = create_random_salted_nonce(current_salt, deadline, &mut rng);
+
+    assert_eq!(
+        env.defuse
+            .nonce_status(user.id(), &committed_nonce)
+            .await
+            .unwrap(),
+        NonceStatus::Unused,
+    );
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user.sign_defuse_message(
+                SigningStandard::default(),
+                env.defuse.id(),
+                committed_nonce,
+                deadline,
+                DefuseIntents { intents: [].into() },
+            )],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        env.defuse
+            .nonce_status(user.id(), &committed_nonce)
+            .await
+            .unwrap(),
+        NonceStatus::Committed,
+    );
+
+    sleep(Duration::from_secs(3)).await;
+
+    env.acl_grant_role(env.defuse.id(), Role::GarbageCollector, user.id())
+        .await
+        .expect("failed to grant role");
+    user.cleanup_nonces(
+        env.defuse.id(),
+        vec![(user.id().clone(), vec![committed_nonce])],
+    )
+    .await
+    .unwrap();
+
+    // cleaned nonce is not reported as used anymore, but isn't
+    // mistaken for a never used one either
+    assert!(
+        !env.defuse
+            .is_nonce_used(user.id(), &committed_nonce)
+            .await
+            .unwrap(),
+    );
+    assert_eq!(
+        env.defuse
+            .nonce_status(user.id(), &committed_nonce)
+            .await
+            .unwrap(),
+        NonceStatus::CleanedExpired,
+    );
+    assert_eq!(
+        env.defuse
+            .nonce_status(user.id(), &unused_nonce)
+            .await
+            .unwrap(),
+        NonceStatus::Unused,
+    );
+}
+
+#[tokio::test]
+#[rstest]
+async fn test_cleanup_unexpirable_nonces(#[notrace] mut rng: impl Rng) {
+    const MAX_AGE: u32 = 1;
+
+    let env = Env::builder().deployer_as_super_admin().build().await;
+    let user = env.create_user().await;
+    let current_salt = env.defuse.current_salt(env.defuse.id()).await.unwrap();
+
+    let mut unexpirable_nonces = || {
+        let legacy_nonce: Nonce = rng.random();
+        let max_deadline_nonce = create_random_salted_nonce(current_salt, Deadline::MAX, &mut rng);
+        [legacy_nonce, max_deadline_nonce]
+    };
+    let commit = |nonces: [Nonce; 2]| {
+        env.defuse.execute_intents(
+            env.defuse.id(),
+            nonces.map(|nonce| {
+                user.sign_defuse_message(
+                    SigningStandard::default(),
+                    env.defuse.id(),
+                    nonce,
+                    Deadline::MAX,
+                    DefuseIntents { intents: [].into() },
+                )
+            }),
+        )
+    };
+
+    let untracked_nonces = unexpirable_nonces();
+    commit(untracked_nonces).await.unwrap();
+
+    env.acl_grant_role(env.defuse.id(), Role::GarbageCollector, user.id())
+        .await
+        .expect("failed to grant role");
+
+    // never cleaned up by default
+    {
+        user.cleanup_nonces(
+            env.defuse.id(),
+            vec![(user.id().clone(), untracked_nonces.to_vec())],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            env.defuse
+                .are_nonces_used(user.id(), &untracked_nonces)
+                .await
+                .unwrap(),
+            [true, true]
+        );
+    }
+
+    // cleaned up once older than max age, if committed while
+    // cleanup was enabled
+    {
+        user.set_unexpirable_nonce_max_age(env.defuse.id(), Some(MAX_AGE))
+            .await
+            .unwrap();
+
+        let tracked_nonces = unexpirable_nonces();
+        commit(tracked_nonces).await.unwrap();
+
+        sleep(Duration::from_secs(u64::from(MAX_AGE) + 2)).await;
+
+        user.cleanup_nonces(
+            env.defuse.id(),
+            vec![(
+                user.id().clone(),
+                [untracked_nonces, tracked_nonces].concat(),
+            )],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            env.defuse
+                .are_nonces_used(user.id(), &tracked_nonces)
+                .await
+                .unwrap(),
+            [false, false]
+        );
+        assert_eq!(
+            env.defuse
+                .are_nonces_used(user.id(), &untracked_nonces)
+                .await
+                .unwrap(),
+            [true, true]
+        );
+    }
+}
+
 #[tokio::test]
 #[rstest]
 async fn cleanup_multiple_nonces(
@@ -448,3 +624,137 @@ async fn cleanup_multiple_nonces(
         gas_used.total_gas_burnt(),
     );
 }
+
+#[tokio::test]
+#[rstest]
+async fn are_nonces_used() {
+    let env = Env::new().await;
+    let user = env.create_user().await;
+    let deadline = Deadline::timeout(Duration::from_secs(120));
+
+    let mut nonces = Vec::new();
+    for _ in 0..5 {
+        nonces.push(
+            user.unique_nonce(env.defuse.id(), Some(deadline))
+                .await
+                .unwrap(),
+        );
+    }
+
+    // commit every other nonce
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            nonces.iter().step_by(2).map(|nonce| {
+                user.sign_defuse_message(
+                    SigningStandard::Nep413,
+                    env.defuse.id(),
+                    *nonce,
+                    deadline,
+                    DefuseIntents { intents: [].into() },
+                )
+            }),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        env.defuse
+            .are_nonces_used(user.id(), &nonces)
+            .await
+            .unwrap(),
+        [true, false, true, false, true]
+    );
+
+    env.defuse
+        .are_nonces_used(user.id(), &vec![nonces[0]; MAX_NONCES_PER_QUERY + 1])
+        .await
+        .unwrap_err();
+}
+
+#[tokio::test]
+#[rstest]
+async fn namespaced_nonces_dont_collide(#[notrace] mut rng: impl Rng) {
+    let env = Env::new().await;
+    let user = env.create_user().await;
+    let deadline = Deadline::timeout(Duration::from_secs(120));
+    let salt = env.defuse.current_salt(env.defuse.id()).await.unwrap();
+
+    // same random bytes in different namespaces
+    let nonce_bytes: [u8; 14] = rng.random();
+    let nonces = (0..4)
+        .map(|namespace| create_namespaced_nonce(salt, namespace, deadline, nonce_bytes))
+        .collect::<Vec<_>>();
+
+    // each lane is relayed independently
+    futures::future::try_join_all(nonces.iter().map(|nonce| {
+        env.defuse.execute_intents(
+            env.defuse.id(),
+            [user.sign_defuse_message(
+                SigningStandard::default(),
+                env.defuse.id(),
+                *nonce,
+                deadline,
+                DefuseIntents { intents: [].into() },
+            )],
+        )
+    }))
+    .await
+    .unwrap();
+
+    assert_eq!(
+        env.defuse
+            .are_nonces_used(user.id(), &nonces)
+            .await
+            .unwrap(),
+        [true; 4]
+    );
+}
+
+#[tokio::test]
+#[rstest]
+async fn expire_nonce_with_mock_time(#[notrace] mut rng: impl Rng) {
+    let env = Env::builder().mock_time().build().await;
+    let user = env.create_user().await;
+
+    let current_salt = env.defuse.current_salt(env.defuse.id()).await.unwrap();
+    let deadline = Deadline::new(Utc::now().checked_add_signed(TimeDelta::hours(1)).unwrap());
+
+    let [executed_nonce, expired_nonce] =
+        std::array::from_fn(|_| create_random_salted_nonce(current_salt, deadline, &mut rng));
+    let sign = |nonce| {
+        user.sign_defuse_message(
+            SigningStandard::default(),
+            env.defuse.id(),
+            nonce,
+            deadline,
+            DefuseIntents { intents: [].into() },
+        )
+    };
+
+    env.defuse
+        .execute_intents(env.defuse.id(), [sign(executed_nonce)])
+        .await
+        .unwrap();
+
+    // no need to wait for an hour
+    env.advance_time(Duration::from_secs(2 * 60 * 60)).await;
+
+    env.defuse
+        .execute_intents(env.defuse.id(), [sign(expired_nonce)])
+        .await
+        .assert_err_contains("deadline has expired");
+
+    assert!(
+        env.defuse
+            .is_nonce_used(user.id(), &executed_nonce)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !env.defuse
+            .is_nonce_used(user.id(), &expired_nonce)
+            .await
+            .unwrap()
+    );
+}
@@ -0,0 +1,158 @@
+use defuse::{
+    contract::Role,
+    core::{
+        accounts::{AccountEvent, RegistrationReserveLowEvent},
+        events::DefuseEvent,
+        token_id::{TokenId, nep141::Nep141TokenId},
+    },
+    tokens::DepositMessage,
+};
+use defuse_near_utils::NearSdkLog;
+use near_sdk::{AccountId, NearToken, env::storage_byte_cost, json_types::U128};
+use rstest::rstest;
+use serde_json::json;
+
+use crate::{
+    tests::defuse::{
+        accounts::traits::{AccountRegistrationReserveExt, AccountStorageUsageExt},
+        env::Env,
+        tokens::nep141::traits::DefuseFtWithdrawer,
+    },
+    utils::{acl::AclExt, mt::MtExt},
+};
+
+#[tokio::test]
+#[rstest]
+async fn registration_reserve_funds_new_accounts() {
+    const RESERVE: NearToken = NearToken::from_near(2);
+
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (reserve, dao, ft) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+
+    env.initial_ft_storage_deposit(vec![reserve.id()], vec![&ft])
+        .await;
+
+    let wnear = TokenId::from(Nep141TokenId::new(env.wnear.id().clone())).to_string();
+    env.defuse_ft_deposit_to(env.wnear.id(), RESERVE.as_yoctonear(), reserve.id())
+        .await
+        .unwrap();
+
+    // set the reserve
+    {
+        assert_eq!(
+            env.registration_reserve(env.defuse.id()).await.unwrap(),
+            None
+        );
+
+        dao.set_registration_reserve(env.defuse.id(), Some(reserve.id()))
+            .await
+            .expect_err("doesn't have DAO role yet");
+
+        env.acl_grant_role(env.defuse.id(), Role::DAO, dao.id())
+            .await
+            .unwrap();
+
+        dao.set_registration_reserve(env.defuse.id(), Some(reserve.id()))
+            .await
+            .unwrap();
+        assert_eq!(
+            env.registration_reserve(env.defuse.id()).await.unwrap(),
+            Some(reserve.id().clone())
+        );
+    }
+
+    let storage_cost = async |account_id: &AccountId| {
+        storage_byte_cost()
+            .saturating_mul(
+                env.account_storage_usage(env.defuse.id(), account_id)
+                    .await
+                    .unwrap()
+                    .into(),
+            )
+            .as_yoctonear()
+    };
+
+    // first deposit registers the account at the cost of the reserve
+    let new_user: AccountId = "new-user.near".parse().unwrap();
+    env.defuse_ft_deposit_to(&ft, 1000, &new_user)
+        .await
+        .unwrap();
+
+    let cost = storage_cost(&new_user).await;
+    assert!(cost > 0);
+    let balance = env
+        .defuse
+        .mt_balance_of(reserve.id(), &wnear)
+        .await
+        .unwrap();
+    assert_eq!(balance, RESERVE.as_yoctonear() - cost);
+
+    // subsequent deposits are not charged
+    env.defuse_ft_deposit_to(&ft, 1000, &new_user)
+        .await
+        .unwrap();
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(reserve.id(), &wnear)
+            .await
+            .unwrap(),
+        balance
+    );
+
+    // drain the reserve below the threshold
+    let remaining = NearToken::from_millinear(500).as_yoctonear();
+    reserve
+        .defuse_ft_withdraw(
+            env.defuse.id(),
+            env.wnear.id(),
+            reserve.id(),
+            balance - remaining,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let other_user: AccountId = "other-new-user.near".parse().unwrap();
+    let logs = env
+        .call(&ft, "ft_transfer_call")
+        .args_json(json!({
+            "receiver_id": env.defuse.id(),
+            "amount": U128(1000),
+            "msg": DepositMessage::new(other_user.clone()).to_string(),
+        }))
+        .deposit(NearToken::from_yoctonear(1))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap()
+        .logs()
+        .into_iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    let balance = remaining - storage_cost(&other_user).await;
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(reserve.id(), &wnear)
+            .await
+            .unwrap(),
+        balance
+    );
+
+    let low_reserve_log = DefuseEvent::RegistrationReserveLow(AccountEvent::new(
+        reserve.id(),
+        RegistrationReserveLowEvent {
+            balance: U128(balance),
+        },
+    ))
+    .to_near_sdk_log();
+    assert!(
+        logs.contains(&low_reserve_log),
+        "missing {low_reserve_log} in {logs:?}"
+    );
+}
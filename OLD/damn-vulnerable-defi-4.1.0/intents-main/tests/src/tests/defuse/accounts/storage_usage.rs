@@ -0,0 +1,49 @@
+use defuse::core::{crypto::PublicKey, intents::Intent};
+use rstest::rstest;
+
+use crate::tests::defuse::{
+    DefuseSignerExt,
+    accounts::{AccountManagerExt, traits::AccountStorageUsageExt},
+    env::Env,
+    intents::ExecuteIntentsExt,
+};
+
+#[tokio::test]
+#[rstest]
+async fn account_storage_usage() {
+    let env = Env::new().await;
+
+    let user = env.create_user().await;
+
+    let initial = user
+        .account_storage_usage(env.defuse.id(), user.id())
+        .await
+        .unwrap();
+
+    // adding a public key takes more storage
+    user.add_public_key(env.defuse.id(), PublicKey::Ed25519([1; 32]))
+        .await
+        .unwrap();
+    let with_key = user
+        .account_storage_usage(env.defuse.id(), user.id())
+        .await
+        .unwrap();
+    assert!(with_key > initial);
+
+    // committing a nonce takes more storage
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(env.defuse.id(), Vec::<Intent>::new())
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+    let with_nonce = user
+        .account_storage_usage(env.defuse.id(), user.id())
+        .await
+        .unwrap();
+    assert!(with_nonce > with_key);
+}
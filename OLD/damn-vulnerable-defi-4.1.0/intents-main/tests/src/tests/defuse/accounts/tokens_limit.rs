@@ -0,0 +1,236 @@
+use defuse::{
+    contract::Role,
+    core::{
+        DefuseError,
+        fees::Pips,
+        intents::token_diff::{TokenDeltas, TokenDiff},
+        token_id::{TokenId, nep141::Nep141TokenId},
+    },
+};
+use defuse_test_utils::asserts::ResultAssertsExt;
+use near_sdk::AccountId;
+use rstest::rstest;
+
+use crate::{
+    tests::defuse::{
+        DefuseSignerExt, accounts::traits::AccountTokensLimitExt, env::Env,
+        intents::ExecuteIntentsExt, tokens::nep141::traits::DefuseFtWithdrawer,
+    },
+    utils::{acl::AclExt, mt::MtExt},
+};
+
+#[tokio::test]
+#[rstest]
+async fn test_max_tokens_per_account() {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (user, other_user, dao, ft1, ft2, ft3) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_token(),
+        env.create_token(),
+        env.create_token()
+    );
+
+    env.initial_ft_storage_deposit(vec![user.id(), other_user.id()], vec![&ft1, &ft2, &ft3])
+        .await;
+
+    let [token1, token2, token3]: [TokenId; 3] =
+        [&ft1, &ft2, &ft3].map(|ft| Nep141TokenId::new(ft.clone()).into());
+
+    // set the limit
+    {
+        dao.set_max_tokens_per_account(env.defuse.id(), Some(2))
+            .await
+            .expect_err("doesn't have DAO role yet");
+
+        env.acl_grant_role(env.defuse.id(), Role::DAO, dao.id())
+            .await
+            .unwrap();
+
+        dao.set_max_tokens_per_account(env.defuse.id(), Some(2))
+            .await
+            .unwrap();
+        assert_eq!(
+            env.max_tokens_per_account(env.defuse.id()).await.unwrap(),
+            Some(2)
+        );
+    }
+
+    env.defuse_ft_deposit_to(&ft1, 1000, user.id())
+        .await
+        .unwrap();
+    env.defuse_ft_deposit_to(&ft2, 1000, user.id())
+        .await
+        .unwrap();
+    env.defuse_ft_deposit_to(&ft3, 1000, other_user.id())
+        .await
+        .unwrap();
+
+    // hitting the cap
+    {
+        env.defuse_ft_deposit_to(&ft3, 100, user.id())
+            .await
+            .expect_err("deposit of a new token beyond the cap should be refunded");
+
+        other_user
+            .mt_transfer(
+                env.defuse.id(),
+                user.id(),
+                &token3.to_string(),
+                100,
+                None,
+                None,
+            )
+            .await
+            .assert_err_contains(DefuseError::TooManyTokensHeld(user.id().clone(), 2).to_string());
+
+        // existing tokens can still be topped up
+        env.defuse_ft_deposit_to(&ft1, 100, user.id())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            env.defuse
+                .mt_balance_of(user.id(), &token3.to_string())
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    // withdrawing to zero frees a slot
+    user.defuse_ft_withdraw(env.defuse.id(), &ft1, user.id(), 1100, None, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(user.id(), &token1.to_string())
+            .await
+            .unwrap(),
+        0
+    );
+
+    other_user
+        .mt_transfer(
+            env.defuse.id(),
+            user.id(),
+            &token3.to_string(),
+            100,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    for (token, amount) in [(&token2, 1000), (&token3, 100)] {
+        assert_eq!(
+            env.defuse
+                .mt_balance_of(user.id(), &token.to_string())
+                .await
+                .unwrap(),
+            amount
+        );
+    }
+}
+
+#[tokio::test]
+#[rstest]
+async fn capped_fee_collector_still_receives_fees() {
+    let fee = Pips::ONE_PERCENT;
+    let fee_collector: AccountId = "treasury.near".parse().unwrap();
+    let env = Env::builder()
+        .deployer_as_super_admin()
+        .fee(fee)
+        .fee_collector(fee_collector.clone())
+        .build()
+        .await;
+
+    let (user1, user2, dao, ft1, ft2) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_token(),
+        env.create_token()
+    );
+
+    env.initial_ft_storage_deposit(vec![user1.id(), user2.id()], vec![&ft1, &ft2])
+        .await;
+
+    let ft1_token_id = TokenId::from(Nep141TokenId::new(ft1.clone()));
+    let ft2_token_id = TokenId::from(Nep141TokenId::new(ft2.clone()));
+    env.defuse_ft_deposit_to(&ft1, 100, user1.id())
+        .await
+        .unwrap();
+    env.defuse_ft_deposit_to(&ft2, 200, user2.id())
+        .await
+        .unwrap();
+
+    // fees are collected in both tokens, which is more than the cap
+    env.acl_grant_role(env.defuse.id(), Role::DAO, dao.id())
+        .await
+        .unwrap();
+    dao.set_max_tokens_per_account(env.defuse.id(), Some(1))
+        .await
+        .unwrap();
+
+    let token_diff = |deltas: [(&TokenId, i128); 2]| TokenDiff {
+        diff: TokenDeltas::default()
+            .with_apply_deltas(
+                deltas
+                    .into_iter()
+                    .map(|(token_id, delta)| (token_id.clone(), delta)),
+            )
+            .unwrap(),
+        memo: None,
+        referral: None,
+        max_fee: None,
+        solver: None,
+        partial_fill: false,
+    };
+    let user1_delta_out = TokenDiff::closure_delta(&ft2_token_id, -200, fee).unwrap();
+    let user2_delta_out = TokenDiff::closure_delta(&ft1_token_id, -100, fee).unwrap();
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [
+                user1
+                    .sign_defuse_payload_default(
+                        env.defuse.id(),
+                        [token_diff([
+                            (&ft1_token_id, -100),
+                            (&ft2_token_id, user1_delta_out),
+                        ])],
+                    )
+                    .await
+                    .unwrap(),
+                user2
+                    .sign_defuse_payload_default(
+                        env.defuse.id(),
+                        [token_diff([
+                            (&ft1_token_id, user2_delta_out),
+                            (&ft2_token_id, -200),
+                        ])],
+                    )
+                    .await
+                    .unwrap(),
+            ],
+        )
+        .await
+        .expect("swaps shouldn't be blocked by the cap of fee collector");
+
+    for (token_id, expected) in [
+        (&ft1_token_id, 100 - user2_delta_out.unsigned_abs()),
+        (&ft2_token_id, 200 - user1_delta_out.unsigned_abs()),
+    ] {
+        assert_eq!(
+            env.defuse
+                .mt_balance_of(&fee_collector, &token_id.to_string())
+                .await
+                .unwrap(),
+            expected
+        );
+    }
+}
@@ -1,3 +1,6 @@
+use std::collections::BTreeSet;
+
+use defuse::core::payload::multi::SigningStandard;
 use near_sdk::{AccountId, AccountIdRef, NearToken};
 use serde_json::json;
 
@@ -14,12 +17,25 @@ pub trait ForceAccountManagerExt {
         account_id: &AccountIdRef,
     ) -> anyhow::Result<bool>;
 
+    async fn force_lock_accounts(
+        &self,
+        contract_id: &AccountId,
+        account_ids: impl IntoIterator<Item = AccountId>,
+        reason: Option<&str>,
+    ) -> anyhow::Result<Vec<bool>>;
+
     async fn force_unlock_account(
         &self,
         contract_id: &AccountId,
         account_id: &AccountIdRef,
     ) -> anyhow::Result<bool>;
 
+    async fn force_allow_lock_on_deposit(
+        &self,
+        contract_id: &AccountId,
+        account_id: &AccountIdRef,
+    ) -> anyhow::Result<bool>;
+
     async fn force_disable_auth_by_predecessor_ids(
         &self,
         contract_id: &AccountId,
@@ -31,6 +47,26 @@ pub trait ForceAccountManagerExt {
         contract_id: &AccountId,
         account_ids: impl IntoIterator<Item = AccountId>,
     ) -> anyhow::Result<()>;
+
+    async fn disabled_signing_standards(
+        &self,
+        contract_id: &AccountId,
+        account_id: &AccountIdRef,
+    ) -> anyhow::Result<BTreeSet<SigningStandard>>;
+
+    async fn force_disable_signing_standards(
+        &self,
+        contract_id: &AccountId,
+        account_id: &AccountIdRef,
+        standards: impl IntoIterator<Item = SigningStandard>,
+    ) -> anyhow::Result<()>;
+
+    async fn force_enable_signing_standards(
+        &self,
+        contract_id: &AccountId,
+        account_id: &AccountIdRef,
+        standards: impl IntoIterator<Item = SigningStandard>,
+    ) -> anyhow::Result<()>;
 }
 
 impl ForceAccountManagerExt for near_workspaces::Account {
@@ -66,6 +102,26 @@ impl ForceAccountManagerExt for near_workspaces::Account {
             .map_err(Into::into)
     }
 
+    async fn force_lock_accounts(
+        &self,
+        contract_id: &AccountId,
+        account_ids: impl IntoIterator<Item = AccountId>,
+        reason: Option<&str>,
+    ) -> anyhow::Result<Vec<bool>> {
+        self.call(contract_id, "force_lock_accounts")
+            .args_json(json!({
+                "account_ids": account_ids.into_iter().collect::<Vec<_>>(),
+                "reason": reason,
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?
+            .json()
+            .map_err(Into::into)
+    }
+
     async fn force_unlock_account(
         &self,
         contract_id: &AccountId,
@@ -84,6 +140,24 @@ impl ForceAccountManagerExt for near_workspaces::Account {
             .map_err(Into::into)
     }
 
+    async fn force_allow_lock_on_deposit(
+        &self,
+        contract_id: &AccountId,
+        account_id: &AccountIdRef,
+    ) -> anyhow::Result<bool> {
+        self.call(contract_id, "force_allow_lock_on_deposit")
+            .args_json(json!({
+                "account_id": account_id,
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?
+            .json()
+            .map_err(Into::into)
+    }
+
     async fn force_disable_auth_by_predecessor_ids(
         &self,
         contract_id: &AccountId,
@@ -117,4 +191,294 @@ impl ForceAccountManagerExt for near_workspaces::Account {
             .into_result()?;
         Ok(())
     }
+
+    async fn disabled_signing_standards(
+        &self,
+        contract_id: &AccountId,
+        account_id: &AccountIdRef,
+    ) -> anyhow::Result<BTreeSet<SigningStandard>> {
+        self.view(contract_id, "disabled_signing_standards")
+            .args_json(json!({
+                "account_id": account_id,
+            }))
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn force_disable_signing_standards(
+        &self,
+        contract_id: &AccountId,
+        account_id: &AccountIdRef,
+        standards: impl IntoIterator<Item = SigningStandard>,
+    ) -> anyhow::Result<()> {
+        self.call(contract_id, "force_disable_signing_standards")
+            .args_json(json!({
+                "account_id": account_id,
+                "standards": standards.into_iter().collect::<Vec<_>>(),
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    async fn force_enable_signing_standards(
+        &self,
+        contract_id: &AccountId,
+        account_id: &AccountIdRef,
+        standards: impl IntoIterator<Item = SigningStandard>,
+    ) -> anyhow::Result<()> {
+        self.call(contract_id, "force_enable_signing_standards")
+            .args_json(json!({
+                "account_id": account_id,
+                "standards": standards.into_iter().collect::<Vec<_>>(),
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+}
+
+pub trait AccountDenylistExt {
+    async fn is_account_blocked(
+        &self,
+        contract_id: &AccountId,
+        account_id: &AccountIdRef,
+    ) -> anyhow::Result<bool>;
+
+    async fn block_accounts(
+        &self,
+        contract_id: &AccountId,
+        account_ids: impl IntoIterator<Item = AccountId>,
+    ) -> anyhow::Result<()>;
+
+    async fn unblock_accounts(
+        &self,
+        contract_id: &AccountId,
+        account_ids: impl IntoIterator<Item = AccountId>,
+    ) -> anyhow::Result<()>;
+}
+
+impl AccountDenylistExt for near_workspaces::Account {
+    async fn is_account_blocked(
+        &self,
+        contract_id: &AccountId,
+        account_id: &AccountIdRef,
+    ) -> anyhow::Result<bool> {
+        self.view(contract_id, "is_account_blocked")
+            .args_json(json!({
+                "account_id": account_id,
+            }))
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn block_accounts(
+        &self,
+        contract_id: &AccountId,
+        account_ids: impl IntoIterator<Item = AccountId>,
+    ) -> anyhow::Result<()> {
+        self.call(contract_id, "block_accounts")
+            .args_json(json!({
+                "account_ids": account_ids.into_iter().collect::<Vec<_>>(),
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    async fn unblock_accounts(
+        &self,
+        contract_id: &AccountId,
+        account_ids: impl IntoIterator<Item = AccountId>,
+    ) -> anyhow::Result<()> {
+        self.call(contract_id, "unblock_accounts")
+            .args_json(json!({
+                "account_ids": account_ids.into_iter().collect::<Vec<_>>(),
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+}
+
+pub trait AccountTokensLimitExt {
+    async fn max_tokens_per_account(&self, contract_id: &AccountId) -> anyhow::Result<Option<u32>>;
+
+    async fn set_max_tokens_per_account(
+        &self,
+        contract_id: &AccountId,
+        max_tokens_per_account: Option<u32>,
+    ) -> anyhow::Result<()>;
+}
+
+impl AccountTokensLimitExt for near_workspaces::Account {
+    async fn max_tokens_per_account(&self, contract_id: &AccountId) -> anyhow::Result<Option<u32>> {
+        self.view(contract_id, "max_tokens_per_account")
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn set_max_tokens_per_account(
+        &self,
+        contract_id: &AccountId,
+        max_tokens_per_account: Option<u32>,
+    ) -> anyhow::Result<()> {
+        self.call(contract_id, "set_max_tokens_per_account")
+            .args_json(json!({
+                "max_tokens_per_account": max_tokens_per_account,
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+}
+
+pub trait AccountKeysLimitExt {
+    async fn max_keys_to_check(&self, contract_id: &AccountId) -> anyhow::Result<u32>;
+
+    async fn set_max_keys_to_check(
+        &self,
+        contract_id: &AccountId,
+        max_keys_to_check: Option<u32>,
+    ) -> anyhow::Result<()>;
+}
+
+impl AccountKeysLimitExt for near_workspaces::Account {
+    async fn max_keys_to_check(&self, contract_id: &AccountId) -> anyhow::Result<u32> {
+        self.view(contract_id, "max_keys_to_check")
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn set_max_keys_to_check(
+        &self,
+        contract_id: &AccountId,
+        max_keys_to_check: Option<u32>,
+    ) -> anyhow::Result<()> {
+        self.call(contract_id, "set_max_keys_to_check")
+            .args_json(json!({
+                "max_keys_to_check": max_keys_to_check,
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+}
+
+pub trait AccountWithdrawalCooldownExt {
+    async fn set_withdrawal_cooldown(
+        &self,
+        contract_id: &AccountId,
+        account_id: &AccountId,
+        cooldown: Option<u32>,
+    ) -> anyhow::Result<()>;
+}
+
+impl AccountWithdrawalCooldownExt for near_workspaces::Account {
+    async fn set_withdrawal_cooldown(
+        &self,
+        contract_id: &AccountId,
+        account_id: &AccountId,
+        cooldown: Option<u32>,
+    ) -> anyhow::Result<()> {
+        self.call(contract_id, "set_withdrawal_cooldown")
+            .args_json(json!({
+                "account_id": account_id,
+                "cooldown": cooldown,
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+}
+
+pub trait AccountStorageUsageExt {
+    async fn account_storage_usage(
+        &self,
+        contract_id: &AccountId,
+        account_id: &AccountIdRef,
+    ) -> anyhow::Result<u64>;
+}
+
+impl AccountStorageUsageExt for near_workspaces::Account {
+    async fn account_storage_usage(
+        &self,
+        contract_id: &AccountId,
+        account_id: &AccountIdRef,
+    ) -> anyhow::Result<u64> {
+        self.view(contract_id, "account_storage_usage")
+            .args_json(json!({
+                "account_id": account_id,
+            }))
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+}
+
+pub trait AccountRegistrationReserveExt {
+    async fn registration_reserve(
+        &self,
+        contract_id: &AccountId,
+    ) -> anyhow::Result<Option<AccountId>>;
+
+    async fn set_registration_reserve(
+        &self,
+        contract_id: &AccountId,
+        reserve_id: Option<&AccountIdRef>,
+    ) -> anyhow::Result<()>;
+}
+
+impl AccountRegistrationReserveExt for near_workspaces::Account {
+    async fn registration_reserve(
+        &self,
+        contract_id: &AccountId,
+    ) -> anyhow::Result<Option<AccountId>> {
+        self.view(contract_id, "registration_reserve")
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn set_registration_reserve(
+        &self,
+        contract_id: &AccountId,
+        reserve_id: Option<&AccountIdRef>,
+    ) -> anyhow::Result<()> {
+        self.call(contract_id, "set_registration_reserve")
+            .args_json(json!({
+                "reserve_id": reserve_id,
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
 }
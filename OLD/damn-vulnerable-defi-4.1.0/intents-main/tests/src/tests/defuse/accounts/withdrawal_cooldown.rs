@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use defuse::{
+    contract::Role,
+    core::token_id::{TokenId, nep141::Nep141TokenId},
+};
+use defuse_test_utils::asserts::ResultAssertsExt;
+use rstest::rstest;
+use tokio::time::sleep;
+
+use crate::{
+    tests::defuse::{
+        accounts::traits::AccountWithdrawalCooldownExt, env::Env,
+        tokens::nep141::traits::DefuseFtWithdrawer,
+    },
+    utils::{acl::AclExt, mt::MtExt},
+};
+
+#[tokio::test]
+#[rstest]
+async fn test_withdrawal_cooldown() {
+    const COOLDOWN: u32 = 3;
+
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (user, other_user, risk_manager, ft) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_token()
+    );
+
+    env.initial_ft_storage_deposit(vec![user.id(), other_user.id()], vec![&ft])
+        .await;
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    let token_id: TokenId = Nep141TokenId::new(ft.clone()).into();
+
+    // set the cooldown
+    {
+        risk_manager
+            .set_withdrawal_cooldown(env.defuse.id(), user.id(), Some(COOLDOWN))
+            .await
+            .expect_err("doesn't have RiskManager role yet");
+
+        env.acl_grant_role(env.defuse.id(), Role::RiskManager, risk_manager.id())
+            .await
+            .unwrap();
+
+        risk_manager
+            .set_withdrawal_cooldown(env.defuse.id(), user.id(), Some(COOLDOWN))
+            .await
+            .unwrap();
+    }
+
+    // first withdrawal starts the cooldown
+    assert_eq!(
+        user.defuse_ft_withdraw(env.defuse.id(), &ft, user.id(), 100, None, None)
+            .await
+            .unwrap(),
+        100
+    );
+
+    // subsequent withdrawal is blocked
+    user.defuse_ft_withdraw(env.defuse.id(), &ft, user.id(), 100, None, None)
+        .await
+        .assert_err_contains("withdrawals are on cooldown");
+
+    // transfers are not affected
+    user.mt_transfer(
+        env.defuse.id(),
+        other_user.id(),
+        &token_id.to_string(),
+        100,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    sleep(Duration::from_secs(COOLDOWN.into())).await;
+
+    assert_eq!(
+        user.defuse_ft_withdraw(env.defuse.id(), &ft, user.id(), 100, None, None)
+            .await
+            .unwrap(),
+        100
+    );
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), user.id(), &token_id.to_string())
+            .await
+            .unwrap(),
+        700
+    );
+}
@@ -11,6 +11,7 @@ use defuse::{
         config::{DefuseConfig, RolesConfig},
     },
     core::fees::{FeesConfig, Pips},
+    intents::EventVerbosity,
 };
 use defuse_poa_factory::contract::Role as POAFactoryRole;
 use defuse_test_utils::random::Seed;
@@ -31,6 +32,9 @@ pub struct EnvBuilder {
     deployer_as_super_admin: bool,
     disable_ft_storage_deposit: bool,
     disable_registration: bool,
+    require_bounded_deadline: bool,
+    reject_legacy_nonces: bool,
+    mock_time: bool,
 
     // Create only unique users (no reusing from persistent state)
     create_unique_users: bool,
@@ -77,11 +81,28 @@ impl EnvBuilder {
         self
     }
 
+    pub const fn require_bounded_deadline(mut self) -> Self {
+        self.require_bounded_deadline = true;
+        self
+    }
+
+    pub const fn reject_legacy_nonces(mut self) -> Self {
+        self.reject_legacy_nonces = true;
+        self
+    }
+
     pub const fn no_registration(mut self, no_reg_value: bool) -> Self {
         self.disable_registration = no_reg_value;
         self
     }
 
+    /// Deploys the contract built with `mock_time` feature, so that
+    /// its clock can be moved with [`Env::advance_time`]
+    pub const fn mock_time(mut self) -> Self {
+        self.mock_time = true;
+        self
+    }
+
     pub const fn create_unique_users(mut self) -> Self {
         self.create_unique_users = true;
         self
@@ -100,8 +121,17 @@ impl EnvBuilder {
                     .clone(),
             },
             roles: self.roles.clone(),
+            max_tokens_per_account: None,
+            event_verbosity: EventVerbosity::default(),
+            registration_reserve: None,
+            max_nonce_ttl: None,
+            require_bounded_deadline: self.require_bounded_deadline,
+            reject_legacy_nonces: self.reject_legacy_nonces,
         };
 
+        if self.mock_time {
+            return root.deploy_defuse_mock_time(id, cfg).await.unwrap();
+        }
         root.deploy_defuse(id, cfg, legacy).await.unwrap()
     }
 
@@ -15,7 +15,10 @@ use crate::{
 use anyhow::{Ok, Result, anyhow};
 use arbitrary::Unstructured;
 use defuse::{
-    core::{Deadline, ExpirableNonce, Nonce, Salt, SaltedNonce, VersionedNonce},
+    core::{
+        Deadline, ExpirableNonce, NamespacedNonce, Nonce, Salt, SaltedNonce, VersionedNonce,
+        intents::tokens::NotificationOutcome,
+    },
     tokens::DepositMessage,
 };
 use defuse_near_utils::arbitrary::ArbitraryNamedAccountId;
@@ -36,6 +39,7 @@ use std::{
         LazyLock,
         atomic::{AtomicUsize, Ordering},
     },
+    time::Duration,
 };
 
 pub static POA_TOKEN_WASM_NO_REGISTRATION: LazyLock<Vec<u8>> =
@@ -44,6 +48,9 @@ pub static POA_TOKEN_WASM_NO_REGISTRATION: LazyLock<Vec<u8>> =
 pub static MT_RECEIVER_STUB_WASM: LazyLock<Vec<u8>> =
     LazyLock::new(|| read_wasm("res/multi-token-receiver-stub/multi_token_receiver_stub"));
 
+pub static PRICE_ORACLE_STUB_WASM: LazyLock<Vec<u8>> =
+    LazyLock::new(|| read_wasm("res/price-oracle-stub/price_oracle_stub"));
+
 pub struct Env {
     sandbox: Sandbox,
 
@@ -271,13 +278,40 @@ impl Env {
     }
 
     pub async fn deploy_mt_receiver_stub(&self) -> Contract {
+        self.deploy_named_mt_receiver_stub("mt_receiver_stub").await
+    }
+
+    pub async fn deploy_named_mt_receiver_stub(&self, name: &str) -> Contract {
+        self.sandbox()
+            .root_account()
+            .deploy_contract(name, &MT_RECEIVER_STUB_WASM)
+            .await
+            .unwrap()
+    }
+
+    pub async fn deploy_price_oracle_stub(&self) -> Contract {
         self.sandbox()
             .root_account()
-            .deploy_contract("mt_receiver_stub", &MT_RECEIVER_STUB_WASM)
+            .deploy_contract("price_oracle_stub", &PRICE_ORACLE_STUB_WASM)
             .await
             .unwrap()
     }
 
+    /// Moves the contract's clock forward, requires the contract to be
+    /// deployed with [`EnvBuilder::mock_time`]
+    pub async fn advance_time(&self, by: Duration) {
+        self.defuse
+            .call("mock_advance_time")
+            .args_json(json!({
+                "seconds": by.as_secs(),
+            }))
+            .transact()
+            .await
+            .unwrap()
+            .into_result()
+            .unwrap();
+    }
+
     pub async fn near_balance(&self, account_id: &AccountId) -> NearToken {
         self.sandbox
             .worker()
@@ -343,6 +377,7 @@ pub struct TransferCallExpectation {
     pub intent_transfer_amount: Option<u128>,
     pub expected_sender_balance: u128,
     pub expected_receiver_balance: u128,
+    pub expected_outcome: NotificationOutcome,
 }
 
 pub fn get_account_public_key(account: &Account) -> defuse::core::crypto::PublicKey {
@@ -365,6 +400,19 @@ pub fn create_random_salted_nonce(salt: Salt, deadline: Deadline, mut rng: impl
     .into()
 }
 
+pub fn create_namespaced_nonce(
+    salt: Salt,
+    namespace: u8,
+    deadline: Deadline,
+    nonce: [u8; 14],
+) -> Nonce {
+    VersionedNonce::V2(SaltedNonce::new(
+        salt,
+        NamespacedNonce::new(namespace, ExpirableNonce::new(deadline, nonce)),
+    ))
+    .into()
+}
+
 fn generate_random_account_id(parent_id: &AccountId, prefix: Option<&str>) -> Result<AccountId> {
     let mut rng = make_true_rng();
     ArbitraryNamedAccountId::arbitrary_subaccount(
@@ -11,6 +11,12 @@ pub trait GarbageCollectorExt {
         defuse_contract_id: &AccountId,
         data: impl IntoIterator<Item = (AccountId, impl IntoIterator<Item = Nonce>)>,
     ) -> anyhow::Result<TestLog>;
+
+    async fn set_unexpirable_nonce_max_age(
+        &self,
+        defuse_contract_id: &AccountId,
+        max_age: Option<u32>,
+    ) -> anyhow::Result<()>;
 }
 
 impl GarbageCollectorExt for near_workspaces::Account {
@@ -42,6 +48,24 @@ impl GarbageCollectorExt for near_workspaces::Account {
 
         Ok(res)
     }
+
+    async fn set_unexpirable_nonce_max_age(
+        &self,
+        defuse_contract_id: &AccountId,
+        max_age: Option<u32>,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "set_unexpirable_nonce_max_age")
+            .deposit(NearToken::from_yoctonear(1))
+            .args_json(json!({
+                "max_age": max_age,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        Ok(())
+    }
 }
 
 impl GarbageCollectorExt for near_workspaces::Contract {
@@ -54,4 +78,14 @@ impl GarbageCollectorExt for near_workspaces::Contract {
             .cleanup_nonces(defuse_contract_id, data)
             .await
     }
+
+    async fn set_unexpirable_nonce_max_age(
+        &self,
+        defuse_contract_id: &AccountId,
+        max_age: Option<u32>,
+    ) -> anyhow::Result<()> {
+        self.as_account()
+            .set_unexpirable_nonce_max_age(defuse_contract_id, max_age)
+            .await
+    }
 }
@@ -0,0 +1,108 @@
+use defuse::core::{
+    Deadline, DefuseError,
+    accounts::{AccountEvent, NonceEvent},
+    amounts::Amounts,
+    crypto::Payload,
+    events::DefuseEvent,
+    intents::{Intent, IntentEvent, tokens::Transfer},
+    payload::MAX_CLIENT_REF_LEN,
+    token_id::{TokenId, nep141::Nep141TokenId},
+};
+use defuse_near_utils::NearSdkLog;
+use defuse_test_utils::asserts::ResultAssertsExt;
+use rstest::rstest;
+use serde_json::json;
+
+use crate::{
+    tests::defuse::{
+        DefuseSigner, DefuseSignerExt, SigningStandard, env::Env, intents::ExecuteIntentsExt,
+    },
+    utils::mt::MtExt,
+};
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn client_ref_is_echoed_in_event() {
+    let env = Env::builder().build().await;
+
+    let (user1, user2, ft) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+
+    env.initial_ft_storage_deposit(vec![user1.id(), user2.id()], vec![&ft])
+        .await;
+
+    let ft1: TokenId = Nep141TokenId::new(ft.clone()).into();
+    env.defuse_ft_deposit_to(&ft, 1000, user1.id())
+        .await
+        .unwrap();
+
+    let sign = async |client_ref: String| {
+        let deadline = Deadline::timeout(std::time::Duration::from_secs(120));
+        let nonce = user1
+            .unique_nonce(env.defuse.id(), Some(deadline))
+            .await
+            .unwrap();
+        let intent: Intent = Transfer {
+            receiver_id: user2.id().clone(),
+            tokens: Amounts::new([(ft1.clone(), 100)].into()),
+            memo: None,
+            notification: None,
+        }
+        .into();
+        let payload = user1.sign_defuse_message(
+            SigningStandard::default(),
+            env.defuse.id(),
+            nonce,
+            deadline,
+            // flattened into the signed payload
+            json!({
+                "client_ref": client_ref,
+                "intents": [intent],
+            }),
+        );
+        (nonce, payload)
+    };
+
+    // too long
+    {
+        let (_, payload) = sign("x".repeat(MAX_CLIENT_REF_LEN + 1)).await;
+        env.defuse_execute_intents(env.defuse.id(), [payload])
+            .await
+            .assert_err_contains(DefuseError::ClientRefTooLong.to_string());
+    }
+
+    let client_ref = "relayer-job-42".to_string();
+    let (nonce, payload) = sign(client_ref.clone()).await;
+
+    let result = env
+        .defuse
+        .execute_intents(env.defuse.id(), [payload.clone()])
+        .await
+        .unwrap();
+
+    let intents_executed_log = DefuseEvent::IntentsExecuted(
+        vec![IntentEvent::new(
+            AccountEvent::new(
+                user1.id().clone(),
+                NonceEvent::new(nonce).with_client_ref(client_ref),
+            ),
+            payload.hash(),
+        )]
+        .into(),
+    )
+    .to_near_sdk_log();
+    assert!(
+        result.logs().contains(&intents_executed_log),
+        "missing {intents_executed_log} in {:?}",
+        result.logs(),
+    );
+
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(user2.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        100
+    );
+}
@@ -0,0 +1,112 @@
+use defuse::core::{
+    Deadline,
+    amounts::Amounts,
+    crypto::Payload,
+    intents::{DefuseIntents, tokens::Transfer},
+    payload::multi::MultiPayload,
+    token_id::{TokenId, nep141::Nep141TokenId},
+};
+use near_sdk::borsh::{self, BorshDeserialize};
+use rstest::rstest;
+
+use crate::{
+    tests::defuse::{
+        DefuseSigner, DefuseSignerExt, SigningStandard,
+        env::Env,
+        intents::{AccountNonceIntentEvent, ExecuteIntentsExt},
+    },
+    utils::mt::MtExt,
+};
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn execute_intents_borsh_matches_json(
+    #[values(
+        SigningStandard::Nep413,
+        SigningStandard::TonConnect,
+        SigningStandard::Sep53
+    )]
+    signing_standard: SigningStandard,
+) {
+    let env = Env::builder().build().await;
+
+    let (user1, user2, ft) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+
+    env.initial_ft_storage_deposit(vec![user1.id(), user2.id()], vec![&ft])
+        .await;
+
+    let ft1: TokenId = Nep141TokenId::new(ft.clone()).into();
+    env.defuse_ft_deposit_to(&ft, 1000, user1.id())
+        .await
+        .unwrap();
+
+    let sign = async || {
+        let deadline = Deadline::timeout(std::time::Duration::from_secs(120));
+        let nonce = user1
+            .unique_nonce(env.defuse.id(), Some(deadline))
+            .await
+            .unwrap();
+        let payload = user1.sign_defuse_message(
+            signing_standard,
+            env.defuse.id(),
+            nonce,
+            deadline,
+            DefuseIntents {
+                intents: [Transfer {
+                    receiver_id: user2.id().clone(),
+                    tokens: Amounts::new([(ft1.clone(), 100)].into()),
+                    memo: None,
+                    notification: None,
+                }
+                .into()]
+                .into(),
+            },
+        );
+        (nonce, payload)
+    };
+
+    let (json_nonce, json_payload) = sign().await;
+    let (borsh_nonce, borsh_payload) = sign().await;
+
+    // borsh round-trip preserves both the payload and its hash
+    let decoded = MultiPayload::try_from_slice(&borsh::to_vec(&borsh_payload).unwrap()).unwrap();
+    assert_eq!(decoded.hash(), borsh_payload.hash());
+    assert_eq!(
+        serde_json::to_value(&decoded).unwrap(),
+        serde_json::to_value(&borsh_payload).unwrap(),
+    );
+
+    let json_result = env
+        .defuse
+        .execute_intents(env.defuse.id(), [json_payload.clone()])
+        .await
+        .unwrap();
+    let borsh_result = env
+        .defuse
+        .execute_intents_borsh(env.defuse.id(), [borsh_payload.clone()])
+        .await
+        .unwrap();
+
+    for (result, nonce, payload) in [
+        (json_result, json_nonce, &json_payload),
+        (borsh_result, borsh_nonce, &borsh_payload),
+    ] {
+        let intents_executed_log =
+            AccountNonceIntentEvent::new(user1.id(), nonce, payload).into_event_log();
+        assert!(
+            result.logs().contains(&intents_executed_log),
+            "missing {intents_executed_log} in {:?}",
+            result.logs(),
+        );
+    }
+
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(user2.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        200
+    );
+}
@@ -0,0 +1,211 @@
+use defuse::{
+    contract::Role,
+    core::{
+        amounts::Amounts,
+        intents::{
+            conditional::{ConditionalIntent, PriceComparison, PriceCondition},
+            tokens::Transfer,
+        },
+        payload::multi::MultiPayload,
+        token_id::{TokenId, nep141::Nep141TokenId},
+    },
+};
+use defuse_test_utils::asserts::ResultAssertsExt;
+use near_sdk::{AccountId, NearToken, json_types::U128};
+use rstest::rstest;
+use serde_json::json;
+
+use crate::{
+    tests::defuse::{DefuseSignerExt, env::Env, intents::ExecuteIntentsExt},
+    utils::{acl::AclExt, mt::MtExt},
+};
+
+#[tokio::test]
+#[rstest]
+async fn conditional_intent() {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (dao, user, other_user, relayer, ft) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_token()
+    );
+    let oracle = env.deploy_price_oracle_stub().await;
+
+    env.initial_ft_storage_deposit(vec![user.id(), other_user.id()], vec![&ft])
+        .await;
+
+    let ft_id = TokenId::from(Nep141TokenId::new(ft.clone()));
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    let set_price = async |price: u128| {
+        oracle
+            .call("set_price")
+            .args_json(json!({
+                "asset": "near",
+                "price": U128(price),
+            }))
+            .transact()
+            .await
+            .unwrap()
+            .into_result()
+            .unwrap();
+    };
+    set_price(90).await;
+
+    let signed = user
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            [ConditionalIntent {
+                condition: PriceCondition {
+                    asset: "near".to_string(),
+                    comparison: PriceComparison::AtLeast,
+                    price: U128(100),
+                },
+                inner: Box::new(
+                    Transfer {
+                        receiver_id: other_user.id().clone(),
+                        tokens: Amounts::new([(ft_id.clone(), 100)].into()),
+                        memo: None,
+                        notification: None,
+                    }
+                    .into(),
+                ),
+            }],
+        )
+        .await
+        .unwrap();
+
+    relayer
+        .execute_conditional_intents(env.defuse.id(), [signed.clone()])
+        .await
+        .assert_err_contains("price oracle is not set");
+
+    // only DAO can set the oracle
+    user.set_price_oracle(env.defuse.id(), Some(oracle.id()))
+        .await
+        .assert_err_contains("Insufficient permissions for method");
+    env.acl_grant_role(env.defuse.id(), Role::DAO, dao.id())
+        .await
+        .expect("failed to grant role");
+    dao.set_price_oracle(env.defuse.id(), Some(oracle.id()))
+        .await
+        .unwrap();
+
+    // prices are only read by `execute_conditional_intents`
+    env.defuse
+        .execute_intents(env.defuse.id(), [signed.clone()])
+        .await
+        .assert_err_contains("price of 'near' is not available from the oracle");
+
+    // price is below the threshold
+    relayer
+        .execute_conditional_intents(env.defuse.id(), [signed.clone()])
+        .await
+        .assert_err_contains("price condition on 'near' is not met");
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), user.id(), &ft_id.to_string())
+            .await
+            .unwrap(),
+        1000
+    );
+
+    // nonce wasn't committed, so the same intent can be retried
+    set_price(100).await;
+    relayer
+        .execute_conditional_intents(env.defuse.id(), [signed.clone()])
+        .await
+        .unwrap();
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), user.id(), &ft_id.to_string())
+            .await
+            .unwrap(),
+        900
+    );
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), other_user.id(), &ft_id.to_string())
+            .await
+            .unwrap(),
+        100
+    );
+
+    relayer
+        .execute_conditional_intents(env.defuse.id(), [signed])
+        .await
+        .assert_err_contains("nonce was already used");
+}
+
+pub trait ConditionalIntentsExt {
+    async fn execute_conditional_intents(
+        &self,
+        defuse_contract_id: &AccountId,
+        signed: impl IntoIterator<Item = MultiPayload>,
+    ) -> anyhow::Result<()>;
+
+    async fn set_price_oracle(
+        &self,
+        defuse_contract_id: &AccountId,
+        oracle_id: Option<&AccountId>,
+    ) -> anyhow::Result<()>;
+}
+
+impl ConditionalIntentsExt for near_workspaces::Account {
+    async fn execute_conditional_intents(
+        &self,
+        defuse_contract_id: &AccountId,
+        signed: impl IntoIterator<Item = MultiPayload>,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "execute_conditional_intents")
+            .args_json(json!({
+                "signed": signed.into_iter().collect::<Vec<_>>(),
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    async fn set_price_oracle(
+        &self,
+        defuse_contract_id: &AccountId,
+        oracle_id: Option<&AccountId>,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "set_price_oracle")
+            .deposit(NearToken::from_yoctonear(1))
+            .args_json(json!({
+                "oracle_id": oracle_id,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+}
+
+impl ConditionalIntentsExt for near_workspaces::Contract {
+    async fn execute_conditional_intents(
+        &self,
+        defuse_contract_id: &AccountId,
+        signed: impl IntoIterator<Item = MultiPayload>,
+    ) -> anyhow::Result<()> {
+        self.as_account()
+            .execute_conditional_intents(defuse_contract_id, signed)
+            .await
+    }
+
+    async fn set_price_oracle(
+        &self,
+        defuse_contract_id: &AccountId,
+        oracle_id: Option<&AccountId>,
+    ) -> anyhow::Result<()> {
+        self.as_account()
+            .set_price_oracle(defuse_contract_id, oracle_id)
+            .await
+    }
+}
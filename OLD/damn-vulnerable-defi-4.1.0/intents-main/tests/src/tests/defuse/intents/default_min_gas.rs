@@ -0,0 +1,104 @@
+use defuse::core::{
+    intents::{account::SetDefaultMinGas, tokens::FtWithdraw},
+    token_id::{TokenId, nep141::Nep141TokenId},
+};
+use defuse_test_utils::asserts::ResultAssertsExt;
+use near_sdk::Gas;
+use rstest::rstest;
+
+use crate::{
+    tests::defuse::{DefuseSignerExt, env::Env, intents::ExecuteIntentsExt},
+    utils::{ft::FtExt, mt::MtExt},
+};
+
+#[tokio::test]
+#[rstest]
+async fn default_min_gas() {
+    let env = Env::new().await;
+
+    let (user, other_user, ft) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+
+    env.initial_ft_storage_deposit(vec![user.id(), other_user.id()], vec![&ft])
+        .await;
+
+    let token_id = TokenId::from(Nep141TokenId::new(ft.clone()));
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    let withdraw = |min_gas| FtWithdraw {
+        token: ft.clone(),
+        receiver_id: other_user.id().clone(),
+        amount: 100.into(),
+        memo: None,
+        msg: None,
+        storage_deposit: None,
+        auto_storage_deposit: false,
+        min_gas,
+    };
+    let set_default_min_gas = async |gas| {
+        env.defuse
+            .execute_intents(
+                env.defuse.id(),
+                [user
+                    .sign_defuse_payload_default(env.defuse.id(), [SetDefaultMinGas { gas }])
+                    .await
+                    .unwrap()],
+            )
+            .await
+            .unwrap();
+    };
+
+    // more than what's left after executing intents
+    set_default_min_gas(Gas::from_tgas(290)).await;
+
+    // default is applied when `min_gas` is omitted
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(env.defuse.id(), [withdraw(None)])
+                .await
+                .unwrap()],
+        )
+        .await
+        .assert_err_contains("Exceeded the prepaid gas");
+
+    // explicit `min_gas` takes precedence
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(env.defuse.id(), [withdraw(Some(Gas::from_tgas(15)))])
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+
+    // zero unsets the default
+    set_default_min_gas(Gas::from_gas(0)).await;
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(env.defuse.id(), [withdraw(None)])
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        env.ft_token_balance_of(&ft, other_user.id()).await.unwrap(),
+        200
+    );
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), user.id(), &token_id.to_string())
+            .await
+            .unwrap(),
+        800
+    );
+}
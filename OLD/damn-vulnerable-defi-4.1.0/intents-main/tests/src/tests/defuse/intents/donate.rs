@@ -0,0 +1,69 @@
+use defuse::core::{
+    amounts::Amounts,
+    intents::tokens::Donate,
+    token_id::{TokenId, nep141::Nep141TokenId},
+};
+use near_sdk::AccountId;
+use rstest::rstest;
+
+use crate::{
+    tests::defuse::{DefuseSignerExt, env::Env, intents::ExecuteIntentsExt},
+    utils::mt::MtExt,
+};
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn donate_intent() {
+    let fee_collector: AccountId = "treasury.near".parse().unwrap();
+    let env = Env::builder()
+        .fee_collector(fee_collector.clone())
+        .build()
+        .await;
+
+    let (user, ft) = futures::join!(env.create_user(), env.create_token());
+    let token_id = TokenId::from(Nep141TokenId::new(ft.clone()));
+
+    env.initial_ft_storage_deposit(vec![user.id()], vec![&ft])
+        .await;
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    let result = env
+        .defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(
+                    env.defuse.id(),
+                    [Donate {
+                        tokens: Amounts::new([(token_id.clone(), 300)].into()),
+                    }],
+                )
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+    assert!(
+        result
+            .logs()
+            .iter()
+            .any(|log| log.contains(r#""event":"donation""#)),
+        "donation event should be emitted"
+    );
+
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), user.id(), &token_id.to_string())
+            .await
+            .unwrap(),
+        700
+    );
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), &fee_collector, &token_id.to_string())
+            .await
+            .unwrap(),
+        300
+    );
+}
@@ -0,0 +1,731 @@
+use defuse::{
+    contract::Role,
+    core::{
+        Deadline, DefuseError,
+        crypto::PublicKey,
+        erc191::Erc191Payload,
+        intents::{
+            DefuseIntents,
+            account::{AddPublicKey, SetMultisigThreshold},
+        },
+        payload::{DefusePayload, eip1271::SignedEip1271Payload, multi::MultiPayload},
+    },
+};
+use defuse_randomness::Rng;
+use defuse_test_utils::{asserts::ResultAssertsExt, random::rng};
+use near_sdk::{AccountId, NearToken};
+use near_workspaces::{
+    Account,
+    types::{KeyType, SecretKey},
+};
+use rstest::rstest;
+use serde_json::json;
+
+use crate::{
+    tests::defuse::{
+        DefuseSigner, DefuseSignerExt, SigningStandard,
+        accounts::AccountManagerExt,
+        env::{Env, get_account_public_key},
+        intents::ExecuteIntentsExt,
+    },
+    utils::{acl::AclExt, crypto::Signer},
+};
+
+/// Wallet which is an implicit Ethereum account, so that it can manage
+/// its public keys with ERC-191 signed intents
+fn eth_wallet(env: &Env) -> Account {
+    let secret_key = SecretKey::from_random(KeyType::SECP256K1);
+    Account::from_secret_key(
+        PublicKey::Secp256k1(secret_key.public_key().key_data().try_into().unwrap())
+            .to_implicit_account_id(),
+        secret_key,
+        env.sandbox().worker(),
+    )
+}
+
+/// Executes `intents` signed by the `wallet` with its ERC-191 key
+async fn execute_erc191(env: &Env, wallet: &Account, intents: DefuseIntents) {
+    let deadline = Deadline::timeout(std::time::Duration::from_secs(120));
+    let nonce = wallet
+        .unique_nonce(env.defuse.id(), Some(deadline))
+        .await
+        .unwrap();
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [wallet.sign_defuse_message(
+                SigningStandard::Erc191,
+                env.defuse.id(),
+                nonce,
+                deadline,
+                intents,
+            )],
+        )
+        .await
+        .unwrap();
+}
+
+/// Attests EIP-1271 `signature` of the `wallet` on `chain_id` with
+/// the key of `attester`
+fn sign_eip1271(
+    attester: &Account,
+    wallet: &AccountId,
+    defuse_contract_id: &AccountId,
+    chain_id: u64,
+    nonce: [u8; 32],
+    signature: Vec<u8>,
+) -> SignedEip1271Payload {
+    let mut signed = SignedEip1271Payload {
+        payload: Erc191Payload(
+            serde_json::to_string(&DefusePayload {
+                signer_id: wallet.clone(),
+                verifying_contract: defuse_contract_id.clone(),
+                deadline: Deadline::MAX,
+                nonce,
+                client_ref: None,
+                require_prepaid_gas: None,
+                priority: None,
+                signed_at: None,
+                message: DefuseIntents { intents: [].into() },
+            })
+            .unwrap(),
+        ),
+        chain_id,
+        signature,
+        attester_public_key: [0; 32],
+        attestation: [0; 64],
+    };
+
+    let secret_key = Signer::secret_key(attester);
+    match (
+        secret_key.sign(&signed.attestation_hash()),
+        secret_key.public_key(),
+    ) {
+        (near_crypto::Signature::ED25519(sig), near_crypto::PublicKey::ED25519(pk)) => {
+            signed.attester_public_key = pk.0;
+            signed.attestation = sig.to_bytes();
+        }
+        _ => unreachable!(),
+    }
+    signed
+}
+
+#[tokio::test]
+#[rstest]
+async fn eip1271_attested_signature(mut rng: impl Rng) {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (dao, attester) = futures::join!(env.create_user(), env.create_user());
+
+    let wallet = eth_wallet(&env);
+    let signature: Vec<u8> = rng.random::<[u8; 65]>().into();
+    let nonce = rng.random();
+
+    let signed = sign_eip1271(
+        &attester,
+        wallet.id(),
+        env.defuse.id(),
+        1,
+        nonce,
+        signature.clone(),
+    );
+    let attester_public_key = PublicKey::Ed25519(signed.attester_public_key);
+
+    // EIP-1271 signatures are rejected until the chain is configured
+    env.defuse
+        .execute_intents(env.defuse.id(), [MultiPayload::Eip1271(signed.clone())])
+        .await
+        .assert_err_contains(DefuseError::WrongEip1271ChainId(1).to_string());
+
+    env.acl_grant_role(env.defuse.id(), Role::DAO, dao.id())
+        .await
+        .unwrap();
+    dao.set_eip1271_chain_id(env.defuse.id(), Some(1))
+        .await
+        .unwrap();
+    assert_eq!(
+        env.defuse.eip1271_chain_id(env.defuse.id()).await.unwrap(),
+        Some(1),
+    );
+
+    // attestations from other chains are rejected
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [MultiPayload::Eip1271(sign_eip1271(
+                &attester,
+                wallet.id(),
+                env.defuse.id(),
+                2,
+                rng.random(),
+                signature.clone(),
+            ))],
+        )
+        .await
+        .assert_err_contains(DefuseError::WrongEip1271ChainId(2).to_string());
+
+    // attester is not trusted yet
+    env.defuse
+        .execute_intents(env.defuse.id(), [MultiPayload::Eip1271(signed.clone())])
+        .await
+        .assert_err_contains(
+            DefuseError::PublicKeyNotExist(wallet.id().clone(), attester_public_key).to_string(),
+        );
+
+    {
+        attester
+            .add_eip1271_attester(env.defuse.id(), &attester_public_key)
+            .await
+            .expect_err("doesn't have DAO role");
+
+        dao.add_eip1271_attester(env.defuse.id(), &attester_public_key)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            env.defuse.eip1271_attesters(env.defuse.id()).await.unwrap(),
+            [attester_public_key],
+        );
+    }
+
+    // wallet hasn't opted in to the attester yet
+    env.defuse
+        .execute_intents(env.defuse.id(), [MultiPayload::Eip1271(signed.clone())])
+        .await
+        .assert_err_contains(
+            DefuseError::PublicKeyNotExist(wallet.id().clone(), attester_public_key).to_string(),
+        );
+
+    // wallet contracts can't call the contract, so the opt-in is
+    // recorded on their behalf
+    {
+        attester
+            .set_eip1271_opt_in(env.defuse.id(), wallet.id(), &attester_public_key, true)
+            .await
+            .expect_err("doesn't have DAO role");
+
+        dao.set_eip1271_opt_in(env.defuse.id(), wallet.id(), &attester_public_key, true)
+            .await
+            .unwrap();
+
+        assert!(
+            env.defuse
+                .is_eip1271_opted_in(env.defuse.id(), wallet.id(), &attester_public_key)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !env.defuse
+                .has_public_key(wallet.id(), &attester_public_key)
+                .await
+                .unwrap()
+        );
+    }
+
+    // tampered signature doesn't match the attestation
+    {
+        let mut tampered = signed.clone();
+        tampered.signature[0] ^= 1;
+
+        env.defuse
+            .execute_intents(env.defuse.id(), [MultiPayload::Eip1271(tampered)])
+            .await
+            .assert_err_contains(DefuseError::InvalidSignature.to_string());
+    }
+
+    env.defuse
+        .execute_intents(env.defuse.id(), [MultiPayload::Eip1271(signed)])
+        .await
+        .unwrap();
+
+    assert!(env.defuse.is_nonce_used(wallet.id(), &nonce).await.unwrap());
+
+    // attestations are no longer accepted once the attester is removed
+    {
+        dao.remove_eip1271_attester(env.defuse.id(), &attester_public_key)
+            .await
+            .unwrap();
+
+        env.defuse
+            .execute_intents(
+                env.defuse.id(),
+                [MultiPayload::Eip1271(sign_eip1271(
+                    &attester,
+                    wallet.id(),
+                    env.defuse.id(),
+                    1,
+                    rng.random(),
+                    signature,
+                ))],
+            )
+            .await
+            .assert_err_contains(
+                DefuseError::PublicKeyNotExist(wallet.id().clone(), attester_public_key)
+                    .to_string(),
+            );
+    }
+}
+
+#[tokio::test]
+#[rstest]
+async fn eip1271_attester_cant_approve_multisig(mut rng: impl Rng) {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (dao, attester) = futures::join!(env.create_user(), env.create_user());
+    let wallet = eth_wallet(&env);
+
+    let signed = sign_eip1271(
+        &attester,
+        wallet.id(),
+        env.defuse.id(),
+        1,
+        rng.random(),
+        rng.random::<[u8; 65]>().into(),
+    );
+    let attester_public_key = PublicKey::Ed25519(signed.attester_public_key);
+
+    env.acl_grant_role(env.defuse.id(), Role::DAO, dao.id())
+        .await
+        .unwrap();
+    dao.set_eip1271_chain_id(env.defuse.id(), Some(1))
+        .await
+        .unwrap();
+    dao.add_eip1271_attester(env.defuse.id(), &attester_public_key)
+        .await
+        .unwrap();
+
+    let cosigner = Account::from_secret_key(
+        wallet.id().clone(),
+        SecretKey::from_random(KeyType::ED25519),
+        env.sandbox().worker(),
+    );
+    dao.set_eip1271_opt_in(env.defuse.id(), wallet.id(), &attester_public_key, true)
+        .await
+        .unwrap();
+    execute_erc191(
+        &env,
+        &wallet,
+        DefuseIntents {
+            intents: [
+                AddPublicKey {
+                    public_key: get_account_public_key(&cosigner),
+                }
+                .into(),
+                SetMultisigThreshold { m: 2, n_keys: 2 }.into(),
+            ]
+            .into(),
+        },
+    )
+    .await;
+
+    env.defuse
+        .execute_intents(env.defuse.id(), [MultiPayload::Eip1271(signed)])
+        .await
+        .assert_err_contains(
+            DefuseError::MultisigApprovalRequired(wallet.id().clone()).to_string(),
+        );
+}
+
+#[tokio::test]
+#[rstest]
+async fn eip1271_attester_cant_sign_other_standards(mut rng: impl Rng) {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (dao, attester) = futures::join!(env.create_user(), env.create_user());
+    let wallet = eth_wallet(&env);
+
+    let attester_public_key = get_account_public_key(&attester);
+    env.acl_grant_role(env.defuse.id(), Role::DAO, dao.id())
+        .await
+        .unwrap();
+    dao.set_eip1271_chain_id(env.defuse.id(), Some(1))
+        .await
+        .unwrap();
+    dao.add_eip1271_attester(env.defuse.id(), &attester_public_key)
+        .await
+        .unwrap();
+    dao.set_eip1271_opt_in(env.defuse.id(), wallet.id(), &attester_public_key, true)
+        .await
+        .unwrap();
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [MultiPayload::Eip1271(sign_eip1271(
+                &attester,
+                wallet.id(),
+                env.defuse.id(),
+                1,
+                rng.random(),
+                rng.random::<[u8; 65]>().into(),
+            ))],
+        )
+        .await
+        .unwrap();
+
+    // the attester key signing as the wallet on its own
+    let impersonator = Account::from_secret_key(
+        wallet.id().clone(),
+        attester.secret_key().clone(),
+        env.sandbox().worker(),
+    );
+    for standard in [SigningStandard::Nep413, SigningStandard::Sep53] {
+        let deadline = Deadline::timeout(std::time::Duration::from_secs(120));
+        let nonce = impersonator
+            .unique_nonce(env.defuse.id(), Some(deadline))
+            .await
+            .unwrap();
+        env.defuse
+            .execute_intents(
+                env.defuse.id(),
+                [impersonator.sign_defuse_message(
+                    standard,
+                    env.defuse.id(),
+                    nonce,
+                    deadline,
+                    DefuseIntents { intents: [].into() },
+                )],
+            )
+            .await
+            .assert_err_contains(
+                DefuseError::PublicKeyNotExist(wallet.id().clone(), attester_public_key)
+                    .to_string(),
+            );
+    }
+}
+
+#[tokio::test]
+#[rstest]
+async fn eip1271_opt_in_by_predecessor() {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (dao, user) = futures::join!(env.create_user(), env.create_user());
+    let attester_public_key = get_account_public_key(&dao);
+
+    user.opt_in_eip1271_attester(env.defuse.id(), &attester_public_key)
+        .await
+        .assert_err_contains("attester not found");
+
+    env.acl_grant_role(env.defuse.id(), Role::DAO, dao.id())
+        .await
+        .unwrap();
+    dao.add_eip1271_attester(env.defuse.id(), &attester_public_key)
+        .await
+        .unwrap();
+
+    user.opt_in_eip1271_attester(env.defuse.id(), &attester_public_key)
+        .await
+        .unwrap();
+    assert!(
+        env.defuse
+            .is_eip1271_opted_in(env.defuse.id(), user.id(), &attester_public_key)
+            .await
+            .unwrap()
+    );
+    user.opt_in_eip1271_attester(env.defuse.id(), &attester_public_key)
+        .await
+        .assert_err_contains("already opted in");
+
+    user.opt_out_eip1271_attester(env.defuse.id(), &attester_public_key)
+        .await
+        .unwrap();
+    assert!(
+        !env.defuse
+            .is_eip1271_opted_in(env.defuse.id(), user.id(), &attester_public_key)
+            .await
+            .unwrap()
+    );
+    user.opt_out_eip1271_attester(env.defuse.id(), &attester_public_key)
+        .await
+        .assert_err_contains("not opted in");
+}
+
+pub trait Eip1271AttestersExt {
+    async fn add_eip1271_attester(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> anyhow::Result<()>;
+
+    async fn remove_eip1271_attester(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> anyhow::Result<()>;
+
+    async fn eip1271_attesters(
+        &self,
+        defuse_contract_id: &AccountId,
+    ) -> anyhow::Result<Vec<PublicKey>>;
+
+    async fn set_eip1271_chain_id(
+        &self,
+        defuse_contract_id: &AccountId,
+        chain_id: Option<u64>,
+    ) -> anyhow::Result<()>;
+
+    async fn eip1271_chain_id(&self, defuse_contract_id: &AccountId)
+    -> anyhow::Result<Option<u64>>;
+
+    async fn opt_in_eip1271_attester(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> anyhow::Result<()>;
+
+    async fn opt_out_eip1271_attester(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> anyhow::Result<()>;
+
+    async fn set_eip1271_opt_in(
+        &self,
+        defuse_contract_id: &AccountId,
+        account_id: &AccountId,
+        public_key: &PublicKey,
+        opted_in: bool,
+    ) -> anyhow::Result<()>;
+
+    async fn is_eip1271_opted_in(
+        &self,
+        defuse_contract_id: &AccountId,
+        account_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> anyhow::Result<bool>;
+}
+
+impl Eip1271AttestersExt for Account {
+    async fn add_eip1271_attester(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "add_eip1271_attester")
+            .deposit(NearToken::from_yoctonear(1))
+            .args_json(json!({
+                "public_key": public_key,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    async fn remove_eip1271_attester(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "remove_eip1271_attester")
+            .deposit(NearToken::from_yoctonear(1))
+            .args_json(json!({
+                "public_key": public_key,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    async fn eip1271_attesters(
+        &self,
+        defuse_contract_id: &AccountId,
+    ) -> anyhow::Result<Vec<PublicKey>> {
+        self.view(defuse_contract_id, "eip1271_attesters")
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn set_eip1271_chain_id(
+        &self,
+        defuse_contract_id: &AccountId,
+        chain_id: Option<u64>,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "set_eip1271_chain_id")
+            .deposit(NearToken::from_yoctonear(1))
+            .args_json(json!({
+                "chain_id": chain_id,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    async fn eip1271_chain_id(
+        &self,
+        defuse_contract_id: &AccountId,
+    ) -> anyhow::Result<Option<u64>> {
+        self.view(defuse_contract_id, "eip1271_chain_id")
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn opt_in_eip1271_attester(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "opt_in_eip1271_attester")
+            .deposit(NearToken::from_yoctonear(1))
+            .args_json(json!({
+                "public_key": public_key,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    async fn opt_out_eip1271_attester(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "opt_out_eip1271_attester")
+            .deposit(NearToken::from_yoctonear(1))
+            .args_json(json!({
+                "public_key": public_key,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    async fn set_eip1271_opt_in(
+        &self,
+        defuse_contract_id: &AccountId,
+        account_id: &AccountId,
+        public_key: &PublicKey,
+        opted_in: bool,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "set_eip1271_opt_in")
+            .deposit(NearToken::from_yoctonear(1))
+            .args_json(json!({
+                "account_id": account_id,
+                "public_key": public_key,
+                "opted_in": opted_in,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    async fn is_eip1271_opted_in(
+        &self,
+        defuse_contract_id: &AccountId,
+        account_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> anyhow::Result<bool> {
+        self.view(defuse_contract_id, "is_eip1271_opted_in")
+            .args_json(json!({
+                "account_id": account_id,
+                "public_key": public_key,
+            }))
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+}
+
+impl Eip1271AttestersExt for near_workspaces::Contract {
+    async fn add_eip1271_attester(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> anyhow::Result<()> {
+        self.as_account()
+            .add_eip1271_attester(defuse_contract_id, public_key)
+            .await
+    }
+
+    async fn remove_eip1271_attester(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> anyhow::Result<()> {
+        self.as_account()
+            .remove_eip1271_attester(defuse_contract_id, public_key)
+            .await
+    }
+
+    async fn eip1271_attesters(
+        &self,
+        defuse_contract_id: &AccountId,
+    ) -> anyhow::Result<Vec<PublicKey>> {
+        self.as_account()
+            .eip1271_attesters(defuse_contract_id)
+            .await
+    }
+
+    async fn set_eip1271_chain_id(
+        &self,
+        defuse_contract_id: &AccountId,
+        chain_id: Option<u64>,
+    ) -> anyhow::Result<()> {
+        self.as_account()
+            .set_eip1271_chain_id(defuse_contract_id, chain_id)
+            .await
+    }
+
+    async fn eip1271_chain_id(
+        &self,
+        defuse_contract_id: &AccountId,
+    ) -> anyhow::Result<Option<u64>> {
+        self.as_account().eip1271_chain_id(defuse_contract_id).await
+    }
+
+    async fn opt_in_eip1271_attester(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> anyhow::Result<()> {
+        self.as_account()
+            .opt_in_eip1271_attester(defuse_contract_id, public_key)
+            .await
+    }
+
+    async fn opt_out_eip1271_attester(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> anyhow::Result<()> {
+        self.as_account()
+            .opt_out_eip1271_attester(defuse_contract_id, public_key)
+            .await
+    }
+
+    async fn set_eip1271_opt_in(
+        &self,
+        defuse_contract_id: &AccountId,
+        account_id: &AccountId,
+        public_key: &PublicKey,
+        opted_in: bool,
+    ) -> anyhow::Result<()> {
+        self.as_account()
+            .set_eip1271_opt_in(defuse_contract_id, account_id, public_key, opted_in)
+            .await
+    }
+
+    async fn is_eip1271_opted_in(
+        &self,
+        defuse_contract_id: &AccountId,
+        account_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> anyhow::Result<bool> {
+        self.as_account()
+            .is_eip1271_opted_in(defuse_contract_id, account_id, public_key)
+            .await
+    }
+}
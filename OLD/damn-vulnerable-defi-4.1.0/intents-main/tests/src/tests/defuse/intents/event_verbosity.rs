@@ -0,0 +1,218 @@
+use std::borrow::Cow;
+
+use defuse::{
+    contract::Role,
+    core::{
+        accounts::{AccountEvent, PublicKeyEvent, TransferEvent},
+        amounts::Amounts,
+        crypto::{Payload, PublicKey},
+        events::DefuseEvent,
+        intents::{IntentEvent, account::AddPublicKey, tokens::Transfer},
+        token_id::{TokenId, nep141::Nep141TokenId},
+    },
+    intents::EventVerbosity,
+};
+use defuse_near_utils::NearSdkLog;
+use near_sdk::{AccountId, NearToken};
+use near_workspaces::Account;
+use rstest::rstest;
+use serde_json::json;
+
+use crate::{
+    assert_eq_event_logs,
+    tests::defuse::{
+        DefuseSignerExt,
+        env::Env,
+        intents::{AccountNonceIntentEvent, ExecuteIntentsExt},
+    },
+    utils::{acl::AclExt, fixtures::public_key, mt::MtExt, payload::ExtractNonceExt},
+};
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn event_verbosity(
+    #[values(EventVerbosity::Full, EventVerbosity::Minimal, EventVerbosity::None)]
+    verbosity: EventVerbosity,
+) {
+    let env = Env::builder()
+        .deployer_as_super_admin()
+        .no_registration(true)
+        .build()
+        .await;
+
+    let (user1, user2, dao, ft) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_token()
+    );
+
+    env.initial_ft_storage_deposit(vec![user1.id(), user2.id()], vec![&ft])
+        .await;
+
+    let ft1: TokenId = Nep141TokenId::new(ft.clone()).into();
+    env.defuse_ft_deposit_to(&ft, 1000, user1.id())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        dao.event_verbosity(env.defuse.id()).await.unwrap(),
+        EventVerbosity::Full
+    );
+
+    if verbosity != EventVerbosity::Full {
+        dao.set_event_verbosity(env.defuse.id(), verbosity)
+            .await
+            .expect_err("doesn't have DAO role yet");
+
+        env.acl_grant_role(env.defuse.id(), Role::DAO, dao.id())
+            .await
+            .unwrap();
+
+        dao.set_event_verbosity(env.defuse.id(), verbosity)
+            .await
+            .unwrap();
+    }
+    assert_eq!(
+        dao.event_verbosity(env.defuse.id()).await.unwrap(),
+        verbosity
+    );
+
+    let transfer = Transfer {
+        receiver_id: user2.id().clone(),
+        tokens: Amounts::new([(ft1.clone(), 1000)].into()),
+        memo: None,
+        notification: None,
+    };
+    let payload = user1
+        .sign_defuse_payload_default(env.defuse.id(), [transfer.clone()])
+        .await
+        .unwrap();
+    let nonce = payload.extract_nonce().unwrap();
+
+    let result = env
+        .defuse
+        .execute_intents(env.defuse.id(), [payload.clone()])
+        .await
+        .unwrap();
+
+    // NEP-245 events are emitted regardless of verbosity
+    let (nep245_logs, defuse_logs): (Vec<_>, Vec<_>) = result
+        .logs()
+        .iter()
+        .cloned()
+        .partition(|log| log.contains(r#""standard":"nep245""#));
+    assert_eq!(nep245_logs.len(), 1);
+
+    let transfer_log = DefuseEvent::Transfer(
+        vec![IntentEvent {
+            intent_hash: payload.hash(),
+            event: AccountEvent {
+                account_id: user1.id().clone().into(),
+                event: TransferEvent {
+                    receiver_id: Cow::Borrowed(&transfer.receiver_id),
+                    tokens: Cow::Borrowed(&transfer.tokens),
+                    memo: Cow::Borrowed(&transfer.memo),
+                },
+            },
+        }]
+        .into(),
+    )
+    .to_near_sdk_log();
+    let intents_executed_log =
+        AccountNonceIntentEvent::new(&user1.id(), nonce, &payload).into_event_log();
+
+    let expected: Vec<String> = match verbosity {
+        EventVerbosity::Full => vec![transfer_log, intents_executed_log],
+        EventVerbosity::Minimal => vec![intents_executed_log],
+        EventVerbosity::None => vec![],
+    };
+    assert_eq_event_logs!(defuse_logs, expected);
+
+    // execution semantics are unchanged
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(user2.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        1000
+    );
+}
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn key_management_events_are_never_suppressed(
+    #[values(EventVerbosity::Minimal, EventVerbosity::None)] verbosity: EventVerbosity,
+    public_key: PublicKey,
+) {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+    let (user, dao) = futures::join!(env.create_user(), env.create_user());
+
+    env.acl_grant_role(env.defuse.id(), Role::DAO, dao.id())
+        .await
+        .unwrap();
+    dao.set_event_verbosity(env.defuse.id(), verbosity)
+        .await
+        .unwrap();
+
+    let payload = user
+        .sign_defuse_payload_default(env.defuse.id(), [AddPublicKey { public_key }])
+        .await
+        .unwrap();
+    let result = env
+        .defuse
+        .execute_intents(env.defuse.id(), [payload])
+        .await
+        .unwrap();
+
+    let public_key_added_log = DefuseEvent::PublicKeyAdded(AccountEvent::new(
+        user.id(),
+        PublicKeyEvent {
+            public_key: Cow::Borrowed(&public_key),
+        },
+    ))
+    .to_near_sdk_log();
+    assert!(
+        result.logs().contains(&public_key_added_log),
+        "{:?}",
+        result.logs()
+    );
+}
+
+pub trait EventVerbosityExt {
+    async fn event_verbosity(&self, defuse_id: &AccountId) -> anyhow::Result<EventVerbosity>;
+
+    async fn set_event_verbosity(
+        &self,
+        defuse_id: &AccountId,
+        verbosity: EventVerbosity,
+    ) -> anyhow::Result<()>;
+}
+
+impl EventVerbosityExt for Account {
+    async fn event_verbosity(&self, defuse_id: &AccountId) -> anyhow::Result<EventVerbosity> {
+        self.view(defuse_id, "event_verbosity")
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn set_event_verbosity(
+        &self,
+        defuse_id: &AccountId,
+        verbosity: EventVerbosity,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_id, "set_event_verbosity")
+            .args_json(json!({
+                "verbosity": verbosity,
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+}
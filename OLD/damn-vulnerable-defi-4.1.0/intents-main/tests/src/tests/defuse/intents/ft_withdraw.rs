@@ -10,6 +10,7 @@ use defuse::core::token_id::{TokenId, nep141::Nep141TokenId};
 use defuse::{
     contract::config::{DefuseConfig, RolesConfig},
     core::fees::{FeesConfig, Pips},
+    intents::EventVerbosity,
 };
 use defuse_test_utils::asserts::ResultAssertsExt;
 use near_sdk::{AccountId, Gas, NearToken};
@@ -60,6 +61,7 @@ async fn ft_withdraw_intent(#[values(false, true)] no_registration: bool) {
                 memo: None,
                 msg: None,
                 storage_deposit: None,
+                auto_storage_deposit: false,
                 min_gas: None,
             }],
         )
@@ -94,6 +96,7 @@ async fn ft_withdraw_intent(#[values(false, true)] no_registration: bool) {
                 msg: None,
                 // user has no wnear yet
                 storage_deposit: Some(STORAGE_DEPOSIT),
+                auto_storage_deposit: false,
                 min_gas: None,
             }],
         )
@@ -155,15 +158,17 @@ async fn ft_withdraw_intent(#[values(false, true)] no_registration: bool) {
                 memo: None,
                 msg: None,
                 storage_deposit,
+                auto_storage_deposit: false,
                 min_gas: Some(Gas::from_tgas(300)),
             }],
         )
         .await
         .unwrap();
 
+    // rejected before execution rather than by exceeding the prepaid gas
     env.defuse_execute_intents(env.defuse.id(), [too_large_min_gas_payload])
         .await
-        .assert_err_contains("Exceeded the prepaid gas.");
+        .assert_err_contains("min_gas requested by intents 300.0 Tgas exceeds available gas");
 
     let valid_payload = user
         .sign_defuse_payload_default(
@@ -175,6 +180,7 @@ async fn ft_withdraw_intent(#[values(false, true)] no_registration: bool) {
                 memo: None,
                 msg: None,
                 storage_deposit,
+                auto_storage_deposit: false,
                 min_gas: None,
             }],
         )
@@ -247,6 +253,12 @@ async fn ft_withdraw_intent_msg(#[values(false, true)] no_registration: bool) {
                     fee_collector: env.id().clone(),
                 },
                 roles: RolesConfig::default(),
+                max_tokens_per_account: None,
+                event_verbosity: EventVerbosity::default(),
+                registration_reserve: None,
+                max_nonce_ttl: None,
+                require_bounded_deadline: false,
+                reject_legacy_nonces: false,
             },
             false,
         )
@@ -274,6 +286,7 @@ async fn ft_withdraw_intent_msg(#[values(false, true)] no_registration: bool) {
                     memo: Some("defuse-to-defuse".to_string()),
                     msg: Some(other_user_id.to_string()),
                     storage_deposit: None,
+                    auto_storage_deposit: false,
                     // too small, but minimum of 30TGas will be used
                     min_gas: Some(Gas::from_tgas(1)),
                 }],
@@ -319,6 +332,7 @@ async fn ft_withdraw_intent_msg(#[values(false, true)] no_registration: bool) {
                 memo: Some("defuse-to-defuse".to_string()),
                 msg: Some(other_user_id.to_string()),
                 storage_deposit: None,
+                auto_storage_deposit: false,
                 min_gas: None,
             }],
         )
@@ -352,3 +366,94 @@ async fn ft_withdraw_intent_msg(#[values(false, true)] no_registration: bool) {
         1000
     );
 }
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn ft_withdraw_to_implicit_account_prepays_storage_deposit(
+    #[values(false, true)] auto: bool,
+) {
+    use crate::tests::defuse::DefuseSignerExt;
+
+    const WNEAR_AMOUNT: NearToken = NearToken::from_near(1);
+
+    let env = Env::builder().build().await;
+
+    let (user, ft) = futures::join!(env.create_user(), env.create_token());
+
+    let token_id = TokenId::from(Nep141TokenId::new(ft.clone()));
+    let wnear_token_id = TokenId::from(Nep141TokenId::new(env.wnear.id().clone()));
+    // never seen before, so not registered on `ft`
+    let implicit_receiver_id: AccountId = "ab".repeat(32).parse().unwrap();
+
+    env.initial_ft_storage_deposit(vec![user.id()], vec![&ft])
+        .await;
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    env.transfer_near(user.id(), WNEAR_AMOUNT)
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+    user.near_deposit(env.wnear.id(), WNEAR_AMOUNT)
+        .await
+        .unwrap();
+    user.defuse_ft_deposit(
+        env.defuse.id(),
+        env.wnear.id(),
+        WNEAR_AMOUNT.as_yoctonear(),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let payload = user
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            [FtWithdraw {
+                token: ft.clone(),
+                receiver_id: implicit_receiver_id.clone(),
+                amount: 1000.into(),
+                memo: None,
+                msg: None,
+                storage_deposit: None,
+                // storage is prepaid only if the signer opted in
+                auto_storage_deposit: auto,
+                min_gas: None,
+            }],
+        )
+        .await
+        .unwrap();
+
+    env.defuse
+        .execute_intents(env.defuse.id(), [payload])
+        .await
+        .unwrap();
+
+    // otherwise, the transfer fails and the tokens are refunded
+    assert_eq!(
+        env.ft_token_balance_of(&ft, &implicit_receiver_id)
+            .await
+            .unwrap(),
+        if auto { 1000 } else { 0 }
+    );
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), user.id(), &token_id.to_string())
+            .await
+            .unwrap(),
+        if auto { 0 } else { 1000 }
+    );
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), user.id(), &wnear_token_id.to_string())
+            .await
+            .unwrap(),
+        if auto {
+            WNEAR_AMOUNT.saturating_sub(FtWithdraw::IMPLICIT_RECEIVER_STORAGE_DEPOSIT)
+        } else {
+            WNEAR_AMOUNT
+        }
+        .as_yoctonear()
+    );
+}
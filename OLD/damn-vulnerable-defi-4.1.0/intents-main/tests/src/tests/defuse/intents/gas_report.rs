@@ -0,0 +1,44 @@
+use defuse::intents::GasReport;
+use near_sdk::AccountId;
+use near_workspaces::Account;
+use rstest::rstest;
+
+use crate::tests::defuse::{env::Env, intents::ExecuteIntentsExt};
+
+#[tokio::test]
+#[rstest]
+async fn execute_intents_gas_report() {
+    let env = Env::new().await;
+
+    let user = env.create_user().await;
+
+    let before = user
+        .execute_intents_gas_report(env.defuse.id())
+        .await
+        .unwrap();
+
+    for _ in 0..3 {
+        user.execute_intents(env.defuse.id(), []).await.unwrap();
+    }
+
+    let after = user
+        .execute_intents_gas_report(env.defuse.id())
+        .await
+        .unwrap();
+
+    assert_eq!(after.calls, before.calls + 3);
+    assert!(after.total_gas > before.total_gas);
+}
+
+pub trait GasReportExt {
+    async fn execute_intents_gas_report(&self, defuse_id: &AccountId) -> anyhow::Result<GasReport>;
+}
+
+impl GasReportExt for Account {
+    async fn execute_intents_gas_report(&self, defuse_id: &AccountId) -> anyhow::Result<GasReport> {
+        self.view(defuse_id, "execute_intents_gas_report")
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+}
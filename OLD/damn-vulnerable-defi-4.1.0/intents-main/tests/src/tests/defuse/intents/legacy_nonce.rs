@@ -1,14 +1,16 @@
 use crate::{
-    tests::defuse::{DefuseSigner, SigningStandard, env::Env, intents::ExecuteIntentsExt},
+    tests::defuse::{
+        DefuseSigner, DefuseSignerExt, SigningStandard, env::Env, intents::ExecuteIntentsExt,
+    },
     utils::mt::MtExt,
 };
 use defuse::core::{
-    Deadline, Nonce,
+    Deadline, DefuseError, Nonce,
     amounts::Amounts,
-    intents::{DefuseIntents, tokens::Transfer},
+    intents::{DefuseIntents, Intent, tokens::Transfer},
     token_id::{TokenId, nep141::Nep141TokenId},
 };
-use defuse_test_utils::random::make_arbitrary;
+use defuse_test_utils::{asserts::ResultAssertsExt, random::make_arbitrary};
 use rstest::rstest;
 
 #[tokio::test]
@@ -83,3 +85,46 @@ async fn execute_intent_with_legacy_nonce(#[from(make_arbitrary)] legacy_nonce:
         1000
     );
 }
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn reject_legacy_nonces(
+    #[from(make_arbitrary)] legacy_nonce: Nonce,
+    #[values(false, true)] reject: bool,
+) {
+    let mut builder = Env::builder();
+    if reject {
+        builder = builder.reject_legacy_nonces();
+    }
+    let env = builder.build().await;
+
+    let user = env.create_user().await;
+
+    let legacy_payload = user.sign_defuse_message(
+        SigningStandard::default(),
+        env.defuse.id(),
+        legacy_nonce,
+        Deadline::MAX,
+        DefuseIntents { intents: [].into() },
+    );
+    let result = env
+        .defuse
+        .execute_intents(env.defuse.id(), [legacy_payload])
+        .await;
+    if reject {
+        result.assert_err_contains(DefuseError::LegacyNonceRejected.to_string());
+    } else {
+        result.unwrap();
+    }
+
+    // salted nonces are accepted either way
+    let salted_payload = user
+        .sign_defuse_payload_default(env.defuse.id(), Vec::<Intent>::new())
+        .await
+        .unwrap();
+    env.defuse
+        .execute_intents(env.defuse.id(), [salted_payload])
+        .await
+        .unwrap();
+}
@@ -1,6 +1,11 @@
 use super::{DefuseSigner, accounts::AccountManagerExt, env::Env};
 use crate::tests::defuse::SigningStandard;
-use crate::utils::{crypto::Signer, mt::MtExt, test_log::TestLog};
+use crate::utils::{
+    crypto::Signer,
+    mt::MtExt,
+    test_log::{TestLog, assert_simulated_logs_match},
+    webauthn::WebAuthnSigner,
+};
 use arbitrary::{Arbitrary, Unstructured};
 use defuse::core::token_id::TokenId;
 use defuse::core::token_id::nep141::Nep141TokenId;
@@ -10,7 +15,8 @@ use defuse::{
         Deadline, Nonce,
         accounts::{AccountEvent, NonceEvent},
         amounts::Amounts,
-        crypto::Payload,
+        crypto::{Payload, SignedPayload},
+        engine::deltas::InvariantViolated,
         events::DefuseEvent,
         intents::{
             DefuseIntents, IntentEvent,
@@ -18,7 +24,7 @@ use defuse::{
         },
         payload::{DefusePayload, ExtractDefusePayload, multi::MultiPayload},
     },
-    intents::SimulationOutput,
+    intents::{DetailedSimulationOutput, SimulationOutput},
 };
 use defuse_near_utils::NearSdkLog;
 use defuse_randomness::Rng;
@@ -52,12 +58,29 @@ impl AccountNonceIntentEvent {
     }
 }
 
+mod client_ref;
+mod compact_encoding;
+mod conditional;
+mod default_min_gas;
+mod donate;
+mod eip1271;
+mod event_verbosity;
 mod ft_withdraw;
+mod gas_report;
 mod legacy_nonce;
+mod multisig;
 mod native_withdraw;
+mod nickname;
+mod prepaid_gas;
+mod priority;
 mod public_key;
+mod recover_signer;
 mod relayers;
+mod revoke_before;
+mod session;
 mod simulate;
+mod stored;
+mod template;
 mod token_diff;
 mod transfer;
 
@@ -66,6 +89,25 @@ pub const DUMMY_MSG_ADDRESS: MsgAddress = MsgAddress {
     address: [12u8; 32],
 };
 
+/// Simulates and then executes `intents`, asserting that the simulation
+/// recorded the same events as the execution emitted
+pub async fn assert_simulation_matches_execution(
+    env: &Env,
+    intents: impl IntoIterator<Item = MultiPayload>,
+) -> TestLog {
+    let intents: Vec<_> = intents.into_iter().collect();
+
+    let simulated = env.defuse.simulate_intents(intents.clone()).await.unwrap();
+    let executed = env
+        .defuse
+        .execute_intents_without_simulation(intents)
+        .await
+        .unwrap();
+
+    assert_simulated_logs_match(&simulated.report.logs, executed.logs());
+    executed
+}
+
 pub trait ExecuteIntentsExt: AccountManagerExt {
     async fn defuse_execute_intents(
         &self,
@@ -94,6 +136,12 @@ pub trait ExecuteIntentsExt: AccountManagerExt {
         intents: impl IntoIterator<Item = MultiPayload>,
     ) -> anyhow::Result<TestLog>;
 
+    async fn execute_intents_borsh(
+        &self,
+        defuse_id: &AccountId,
+        intents: impl IntoIterator<Item = MultiPayload>,
+    ) -> anyhow::Result<TestLog>;
+
     async fn defuse_simulate_intents(
         &self,
         defuse_id: &AccountId,
@@ -104,6 +152,21 @@ pub trait ExecuteIntentsExt: AccountManagerExt {
         &self,
         intents: impl IntoIterator<Item = MultiPayload>,
     ) -> anyhow::Result<SimulationOutput>;
+
+    async fn simulate_intents_detailed(
+        &self,
+        intents: impl IntoIterator<Item = MultiPayload>,
+    ) -> anyhow::Result<DetailedSimulationOutput>;
+
+    async fn check_batch_consistency(
+        &self,
+        intents: impl IntoIterator<Item = MultiPayload>,
+    ) -> anyhow::Result<Option<InvariantViolated>>;
+
+    async fn decode_payload(
+        &self,
+        signed: MultiPayload,
+    ) -> anyhow::Result<DefusePayload<DefuseIntents>>;
 }
 
 impl ExecuteIntentsExt for near_workspaces::Account {
@@ -141,6 +204,24 @@ impl ExecuteIntentsExt for near_workspaces::Account {
         self.defuse_execute_intents(self.id(), intents).await
     }
 
+    async fn execute_intents_borsh(
+        &self,
+        defuse_id: &AccountId,
+        intents: impl IntoIterator<Item = MultiPayload>,
+    ) -> anyhow::Result<TestLog> {
+        self.call(defuse_id, "execute_intents_borsh")
+            .args_borsh(intents.into_iter().collect::<Vec<_>>())
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()
+            .inspect(|outcome| {
+                println!("execute_intents_borsh: {outcome:#?}");
+            })
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
     async fn defuse_simulate_intents(
         &self,
         defuse_id: &AccountId,
@@ -165,6 +246,45 @@ impl ExecuteIntentsExt for near_workspaces::Account {
     ) -> anyhow::Result<SimulationOutput> {
         self.defuse_simulate_intents(self.id(), intents).await
     }
+
+    async fn simulate_intents_detailed(
+        &self,
+        intents: impl IntoIterator<Item = MultiPayload>,
+    ) -> anyhow::Result<DetailedSimulationOutput> {
+        self.view(self.id(), "simulate_intents_detailed")
+            .args_json(json!({
+                "signed": intents.into_iter().collect::<Vec<_>>(),
+            }))
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn check_batch_consistency(
+        &self,
+        intents: impl IntoIterator<Item = MultiPayload>,
+    ) -> anyhow::Result<Option<InvariantViolated>> {
+        self.view(self.id(), "check_batch_consistency")
+            .args_json(json!({
+                "signed": intents.into_iter().collect::<Vec<_>>(),
+            }))
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn decode_payload(
+        &self,
+        signed: MultiPayload,
+    ) -> anyhow::Result<DefusePayload<DefuseIntents>> {
+        self.view(self.id(), "decode_payload")
+            .args_json(json!({
+                "signed": signed,
+            }))
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
 }
 
 impl ExecuteIntentsExt for near_workspaces::Contract {
@@ -194,6 +314,16 @@ impl ExecuteIntentsExt for near_workspaces::Contract {
             .await
     }
 
+    async fn execute_intents_borsh(
+        &self,
+        defuse_id: &AccountId,
+        intents: impl IntoIterator<Item = MultiPayload>,
+    ) -> anyhow::Result<TestLog> {
+        self.as_account()
+            .execute_intents_borsh(defuse_id, intents)
+            .await
+    }
+
     async fn defuse_simulate_intents(
         &self,
         defuse_id: &AccountId,
@@ -209,6 +339,27 @@ impl ExecuteIntentsExt for near_workspaces::Contract {
     ) -> anyhow::Result<SimulationOutput> {
         self.as_account().simulate_intents(intents).await
     }
+
+    async fn simulate_intents_detailed(
+        &self,
+        intents: impl IntoIterator<Item = MultiPayload>,
+    ) -> anyhow::Result<DetailedSimulationOutput> {
+        self.as_account().simulate_intents_detailed(intents).await
+    }
+
+    async fn check_batch_consistency(
+        &self,
+        intents: impl IntoIterator<Item = MultiPayload>,
+    ) -> anyhow::Result<Option<InvariantViolated>> {
+        self.as_account().check_batch_consistency(intents).await
+    }
+
+    async fn decode_payload(
+        &self,
+        signed: MultiPayload,
+    ) -> anyhow::Result<DefusePayload<DefuseIntents>> {
+        self.as_account().decode_payload(signed).await
+    }
 }
 
 #[tokio::test]
@@ -309,6 +460,146 @@ async fn simulate_is_view_method(
     );
 }
 
+#[tokio::test]
+async fn insufficient_gas() {
+    use defuse::intents::execute_intents_min_gas;
+    use defuse_test_utils::asserts::ResultAssertsExt;
+    use near_sdk::Gas;
+
+    use crate::{tests::defuse::DefuseSignerExt, utils::payload::ExtractNonceExt};
+
+    let env = Env::new().await;
+
+    let (user, other_user, ft) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+    let ft_id = TokenId::from(Nep141TokenId::new(ft.clone()));
+
+    env.initial_ft_storage_deposit(vec![user.id(), other_user.id()], vec![&ft])
+        .await;
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    let mut payloads = Vec::new();
+    for _ in 0..8 {
+        payloads.push(
+            user.sign_defuse_payload_default(
+                env.defuse.id(),
+                [Transfer {
+                    receiver_id: other_user.id().clone(),
+                    tokens: Amounts::new(std::iter::once((ft_id.clone(), 100)).collect()),
+                    memo: None,
+                    notification: None,
+                }],
+            )
+            .await
+            .unwrap(),
+        );
+    }
+    let required = execute_intents_min_gas(&payloads);
+
+    env.call(env.defuse.id(), "execute_intents")
+        .args_json(json!({
+            "signed": payloads,
+        }))
+        .gas(required.saturating_sub(Gas::from_tgas(1)))
+        .transact()
+        .await
+        .unwrap()
+        .into_result()
+        .map_err(anyhow::Error::from)
+        .assert_err_contains(format!("insufficient gas attached, required: {required}"));
+
+    // nothing was executed
+    for payload in &payloads {
+        assert!(
+            !env.defuse
+                .is_nonce_used(user.id(), &payload.extract_nonce().unwrap())
+                .await
+                .unwrap()
+        );
+    }
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(other_user.id(), &ft_id.to_string())
+            .await
+            .unwrap(),
+        0
+    );
+
+    env.defuse
+        .execute_intents(env.defuse.id(), payloads)
+        .await
+        .unwrap();
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(other_user.id(), &ft_id.to_string())
+            .await
+            .unwrap(),
+        800
+    );
+}
+
+#[tokio::test]
+async fn duplicate_intent() {
+    use defuse::core::DefuseError;
+    use defuse_test_utils::asserts::ResultAssertsExt;
+
+    use crate::{tests::defuse::DefuseSignerExt, utils::payload::ExtractNonceExt};
+
+    let env = Env::new().await;
+
+    let (user, other_user, ft) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+    let ft_id = TokenId::from(Nep141TokenId::new(ft.clone()));
+
+    env.initial_ft_storage_deposit(vec![user.id(), other_user.id()], vec![&ft])
+        .await;
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    let payload = user
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            [Transfer {
+                receiver_id: other_user.id().clone(),
+                tokens: Amounts::new(std::iter::once((ft_id.clone(), 100)).collect()),
+                memo: None,
+                notification: None,
+            }],
+        )
+        .await
+        .unwrap();
+
+    env.defuse
+        .execute_intents(env.defuse.id(), [payload.clone(), payload.clone()])
+        .await
+        .assert_err_contains(DefuseError::DuplicateIntent.to_string());
+
+    // nothing was executed
+    assert!(
+        !env.defuse
+            .is_nonce_used(user.id(), &payload.extract_nonce().unwrap())
+            .await
+            .unwrap()
+    );
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(user.id(), &ft_id.to_string())
+            .await
+            .unwrap(),
+        1000
+    );
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(other_user.id(), &ft_id.to_string())
+            .await
+            .unwrap(),
+        0
+    );
+}
+
 #[tokio::test]
 #[rstest]
 async fn webauthn(#[values(false, true)] no_registration: bool) {
@@ -370,6 +661,124 @@ async fn webauthn(#[values(false, true)] no_registration: bool) {
     );
 }
 
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn webauthn_signer(#[values(false, true)] p256: bool, #[notrace] mut rng: impl Rng) {
+    let env = Env::builder().build().await;
+
+    let (user, ft) = futures::join!(env.create_user(), env.create_token());
+    let ft_id = TokenId::from(Nep141TokenId::new(ft.clone()));
+
+    let passkey = if p256 {
+        WebAuthnSigner::random_p256(&mut rng)
+    } else {
+        WebAuthnSigner::random_ed25519()
+    };
+
+    env.initial_ft_storage_deposit(vec![user.id()], vec![&ft])
+        .await;
+    env.defuse_ft_deposit_to(&ft, 1000, &passkey.account_id())
+        .await
+        .unwrap();
+
+    let signed = passkey.sign_defuse_message(
+        env.defuse.id(),
+        rng.random(),
+        Deadline::timeout(std::time::Duration::from_secs(120)),
+        DefuseIntents {
+            intents: [Transfer {
+                receiver_id: user.id().clone(),
+                tokens: Amounts::new([(ft_id.clone(), 1000)].into()),
+                memo: None,
+                notification: None,
+            }
+            .into()]
+            .into(),
+        },
+    );
+    assert_eq!(signed.verify(), Some(passkey.public_key()));
+
+    env.defuse
+        .execute_intents(env.defuse.id(), [signed])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(user.id(), &ft_id.to_string())
+            .await
+            .unwrap(),
+        1000
+    );
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(&passkey.account_id(), &ft_id.to_string())
+            .await
+            .unwrap(),
+        0
+    );
+}
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn decode_payload(#[notrace] mut rng: impl Rng) {
+    let env = Env::builder().build().await;
+
+    let user = env.create_user().await;
+    let passkey = WebAuthnSigner::random_ed25519();
+
+    let deadline = Deadline::timeout(std::time::Duration::from_secs(120));
+    let intents = DefuseIntents {
+        intents: [Transfer {
+            receiver_id: "bob.near".parse().unwrap(),
+            tokens: Amounts::new(
+                [(
+                    TokenId::from(Nep141TokenId::new("ft.near".parse().unwrap())),
+                    1000,
+                )]
+                .into(),
+            ),
+            memo: Some("memo".to_string()),
+            notification: None,
+        }
+        .into()]
+        .into(),
+    };
+
+    let nonce: Nonce = rng.random();
+    let signed = [
+        SigningStandard::Nep413,
+        SigningStandard::TonConnect,
+        SigningStandard::Sep53,
+    ]
+    .into_iter()
+    .map(|standard| {
+        (
+            user.id().clone(),
+            user.sign_defuse_message(standard, env.defuse.id(), nonce, deadline, &intents),
+        )
+    })
+    .chain([(
+        passkey.account_id(),
+        passkey.sign_defuse_message(env.defuse.id(), nonce, deadline, &intents),
+    )]);
+
+    for (signer_id, signed) in signed {
+        let decoded = env.defuse.decode_payload(signed).await.unwrap();
+
+        assert_eq!(decoded.signer_id, signer_id);
+        assert_eq!(decoded.verifying_contract, *env.defuse.id());
+        assert_eq!(decoded.deadline, deadline);
+        assert_eq!(decoded.nonce, nonce);
+        assert_eq!(
+            serde_json::to_value(&decoded.message).unwrap(),
+            serde_json::to_value(&intents).unwrap(),
+        );
+    }
+}
+
 #[tokio::test]
 #[rstest]
 #[trace]
@@ -386,6 +795,7 @@ async fn ton_connect_sign_intent_example() {
             memo: None,
             msg: None,
             storage_deposit: None,
+            auto_storage_deposit: false,
             min_gas: None,
         }
         .into()]
@@ -403,6 +813,10 @@ async fn ton_connect_sign_intent_example() {
                 verifying_contract: "intent.near".parse().unwrap(),
                 deadline: Deadline::timeout(std::time::Duration::from_secs(120)),
                 nonce,
+                client_ref: None,
+                require_prepaid_gas: None,
+                priority: None,
+                signed_at: None,
                 message: intents,
             })
             .unwrap(),
@@ -0,0 +1,249 @@
+use std::{collections::BTreeSet, time::Duration};
+
+use defuse::core::{
+    Deadline, DefuseError,
+    amounts::Amounts,
+    intents::{
+        DefuseIntents, account::SetMultisigThreshold, session::GrantSession, tokens::Transfer,
+    },
+    token_id::{TokenId, nep141::Nep141TokenId},
+};
+use defuse_test_utils::asserts::ResultAssertsExt;
+use near_workspaces::{Account, types::SecretKey};
+use rstest::rstest;
+
+use crate::{
+    tests::defuse::{
+        DefuseSigner, DefuseSignerExt, SigningStandard,
+        accounts::AccountManagerExt,
+        env::{Env, get_account_public_key},
+        intents::ExecuteIntentsExt,
+        tokens::nep141::traits::DefuseFtWithdrawer,
+    },
+    utils::mt::MtExt,
+};
+
+#[tokio::test]
+#[rstest]
+async fn multisig_threshold() {
+    let env = Env::new().await;
+
+    let (user, other_user, ft) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+
+    env.initial_ft_storage_deposit(vec![user.id(), other_user.id()], vec![&ft])
+        .await;
+
+    let ft1: TokenId = Nep141TokenId::new(ft.clone()).into();
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    // all keys generated by near-workspaces are the same, so we need random ones
+    let [cosigner1, cosigner2] = std::array::from_fn(|_| {
+        Account::from_secret_key(
+            user.id().clone(),
+            SecretKey::from_random(near_workspaces::types::KeyType::ED25519),
+            env.sandbox().worker(),
+        )
+    });
+    for cosigner in [&cosigner1, &cosigner2] {
+        user.add_public_key(env.defuse.id(), get_account_public_key(cosigner))
+            .await
+            .unwrap();
+    }
+
+    // number of keys doesn't match registered ones
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(
+                    env.defuse.id(),
+                    [SetMultisigThreshold { m: 2, n_keys: 2 }],
+                )
+                .await
+                .unwrap()],
+        )
+        .await
+        .assert_err_contains(DefuseError::InvalidMultisigThreshold.to_string());
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(
+                    env.defuse.id(),
+                    [SetMultisigThreshold { m: 2, n_keys: 3 }],
+                )
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+
+    let deadline = Deadline::timeout(Duration::from_secs(120));
+    let nonce = user
+        .unique_nonce(env.defuse.id(), Some(deadline))
+        .await
+        .unwrap();
+    let intents = DefuseIntents {
+        intents: [Transfer {
+            receiver_id: other_user.id().clone(),
+            tokens: Amounts::new([(ft1.clone(), 100)].into()),
+            memo: None,
+            notification: None,
+        }
+        .into()]
+        .into(),
+    };
+    let sign = |signer: &Account| {
+        signer.sign_defuse_message(
+            SigningStandard::Nep413,
+            env.defuse.id(),
+            nonce,
+            deadline,
+            intents.clone(),
+        )
+    };
+
+    // single signature is not enough
+    env.defuse
+        .execute_intents(env.defuse.id(), [sign(&user)])
+        .await
+        .assert_err_contains(DefuseError::InsufficientSignatures.to_string());
+
+    // the same key can't approve twice
+    env.defuse
+        .execute_intents(env.defuse.id(), [sign(&user), sign(&user)])
+        .await
+        .assert_err_contains(DefuseError::InsufficientSignatures.to_string());
+
+    assert!(!env.defuse.is_nonce_used(user.id(), &nonce).await.unwrap());
+
+    // threshold is met
+    env.defuse
+        .execute_intents(env.defuse.id(), [sign(&user), sign(&cosigner2)])
+        .await
+        .unwrap();
+
+    assert!(env.defuse.is_nonce_used(user.id(), &nonce).await.unwrap());
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(other_user.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        100
+    );
+
+    // signatures can't be replayed
+    env.defuse
+        .execute_intents(env.defuse.id(), [sign(&cosigner1), sign(&cosigner2)])
+        .await
+        .assert_err_contains(DefuseError::NonceUsed.to_string());
+}
+
+#[tokio::test]
+#[rstest]
+async fn multisig_threshold_cant_be_bypassed() {
+    let env = Env::new().await;
+
+    let (user, other_user, ft) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+
+    env.initial_ft_storage_deposit(vec![user.id(), other_user.id()], vec![&ft])
+        .await;
+
+    let ft1: TokenId = Nep141TokenId::new(ft.clone()).into();
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    let [cosigner, session_account] = std::array::from_fn(|_| {
+        Account::from_secret_key(
+            user.id().clone(),
+            SecretKey::from_random(near_workspaces::types::KeyType::ED25519),
+            env.sandbox().worker(),
+        )
+    });
+    user.add_public_key(env.defuse.id(), get_account_public_key(&cosigner))
+        .await
+        .unwrap();
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(
+                    env.defuse.id(),
+                    [GrantSession {
+                        key: get_account_public_key(&session_account),
+                        limits: Amounts::new([(ft1.clone(), 500)].into()),
+                        expiry: Deadline::timeout(Duration::from_secs(3600)),
+                        auth_calls: BTreeSet::new(),
+                    }],
+                )
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(
+                    env.defuse.id(),
+                    [SetMultisigThreshold { m: 2, n_keys: 2 }],
+                )
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+
+    // sessions granted before are not enough to authorize intents
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [session_account
+                .sign_defuse_payload_default(
+                    env.defuse.id(),
+                    [Transfer {
+                        receiver_id: other_user.id().clone(),
+                        tokens: Amounts::new([(ft1.clone(), 100)].into()),
+                        memo: None,
+                        notification: None,
+                    }],
+                )
+                .await
+                .unwrap()],
+        )
+        .await
+        .assert_err_contains(DefuseError::MultisigApprovalRequired(user.id().clone()).to_string());
+
+    // neither is authentication by predecessor
+    user.mt_transfer(
+        env.defuse.id(),
+        other_user.id(),
+        &ft1.to_string(),
+        100,
+        None,
+        None,
+    )
+    .await
+    .assert_err_contains(DefuseError::MultisigApprovalRequired(user.id().clone()).to_string());
+
+    user.defuse_ft_withdraw(env.defuse.id(), &ft, other_user.id(), 100, None, None)
+        .await
+        .assert_err_contains(DefuseError::MultisigApprovalRequired(user.id().clone()).to_string());
+
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(user.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        1000
+    );
+}
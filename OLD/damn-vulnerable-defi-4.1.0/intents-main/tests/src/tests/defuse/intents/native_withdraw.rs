@@ -2,19 +2,23 @@ use crate::tests::defuse::DefuseSignerExt;
 use crate::utils::fixtures::{ed25519_pk, secp256k1_pk};
 use crate::{
     tests::defuse::{
-        env::Env, intents::ExecuteIntentsExt, tokens::nep141::traits::DefuseFtReceiver,
+        env::Env, intents::ExecuteIntentsExt, state::WNearManagerExt,
+        tokens::nep141::traits::DefuseFtReceiver,
     },
-    utils::{mt::MtExt, wnear::WNearExt},
+    utils::{ft::FtExt, mt::MtExt, wnear::WNearExt},
 };
 use defuse::{
     core::{
+        DefuseError,
         crypto::PublicKey,
         intents::tokens::NativeWithdraw,
         token_id::{TokenId, nep141::Nep141TokenId},
     },
     tokens::DepositMessage,
 };
+use defuse_test_utils::asserts::ResultAssertsExt;
 use near_sdk::NearToken;
+use near_workspaces::AccountDetailsPatch;
 use rstest::rstest;
 
 #[tokio::test]
@@ -126,3 +130,154 @@ async fn native_withdraw_intent(ed25519_pk: PublicKey, secp256k1_pk: PublicKey)
         );
     }
 }
+
+#[tokio::test]
+#[rstest]
+async fn native_withdraw_after_wnear_id_update(ed25519_pk: PublicKey) {
+    const AMOUNT: NearToken = NearToken::from_near(10);
+
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (user, new_wnear) = futures::join!(env.create_user(), async {
+        env.deploy_wrap_near("wnear2").await.unwrap()
+    });
+
+    assert_eq!(
+        env.defuse.wnear_id(env.defuse.id()).await.unwrap(),
+        *env.wnear.id()
+    );
+
+    // only DAO can update wNEAR
+    user.set_wnear_id(env.defuse.id(), new_wnear.id())
+        .await
+        .assert_err_contains("Insufficient permissions for method");
+    env.set_wnear_id(env.defuse.id(), new_wnear.id())
+        .await
+        .unwrap();
+    assert_eq!(
+        env.defuse.wnear_id(env.defuse.id()).await.unwrap(),
+        *new_wnear.id()
+    );
+
+    env.poa_factory
+        .ft_storage_deposit_many(new_wnear.id(), &[env.defuse.id(), env.id()])
+        .await
+        .unwrap();
+    env.near_deposit(new_wnear.id(), AMOUNT).await.unwrap();
+    env.defuse_ft_deposit(
+        env.defuse.id(),
+        new_wnear.id(),
+        AMOUNT.as_yoctonear(),
+        DepositMessage::new(user.id().clone()),
+    )
+    .await
+    .unwrap();
+
+    // native withdrawal unwraps the new wNEAR
+    let receiver_id = ed25519_pk.to_implicit_account_id();
+    env.defuse_execute_intents(
+        env.defuse.id(),
+        [user
+            .sign_defuse_payload_default(
+                env.defuse.id(),
+                [NativeWithdraw {
+                    receiver_id: receiver_id.clone(),
+                    amount: AMOUNT,
+                }],
+            )
+            .await
+            .unwrap()],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(
+                user.id(),
+                &TokenId::Nep141(Nep141TokenId::new(new_wnear.id().clone())).to_string()
+            )
+            .await
+            .unwrap(),
+        0,
+    );
+    assert_eq!(
+        env.sandbox()
+            .worker()
+            .view_account(&receiver_id)
+            .await
+            .unwrap()
+            .balance,
+        AMOUNT
+    );
+}
+
+#[tokio::test]
+#[rstest]
+async fn native_withdraw_below_contract_reserve(ed25519_pk: PublicKey) {
+    const AMOUNT: NearToken = NearToken::from_near(10);
+
+    let env = Env::new().await;
+
+    let user = env.create_user().await;
+
+    env.initial_ft_storage_deposit(vec![user.id()], &[]).await;
+    env.near_deposit(env.wnear.id(), AMOUNT).await.unwrap();
+    env.defuse_ft_deposit(
+        env.defuse.id(),
+        env.wnear.id(),
+        AMOUNT.as_yoctonear(),
+        DepositMessage::new(user.id().clone()),
+    )
+    .await
+    .unwrap();
+
+    // leave the contract with just enough NEAR to cover its storage
+    env.sandbox()
+        .worker()
+        .patch(env.defuse.id())
+        .account_from_current(|account| {
+            AccountDetailsPatch::default().balance(
+                NearToken::from_yoctonear(10u128.pow(19))
+                    .saturating_mul(account.storage_usage.into())
+                    .saturating_add(NearToken::from_millinear(50)),
+            )
+        })
+        .transact()
+        .await
+        .unwrap();
+
+    let wnear_id = TokenId::Nep141(Nep141TokenId::new(env.wnear.id().clone())).to_string();
+    let receiver_id = ed25519_pk.to_implicit_account_id();
+    env.defuse_execute_intents(
+        env.defuse.id(),
+        [user
+            .sign_defuse_payload_default(
+                env.defuse.id(),
+                [NativeWithdraw {
+                    receiver_id: receiver_id.clone(),
+                    amount: AMOUNT,
+                }],
+            )
+            .await
+            .unwrap()],
+    )
+    .await
+    .assert_err_contains(DefuseError::InsufficientContractReserve.to_string());
+
+    // nothing was withdrawn
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(user.id(), &wnear_id)
+            .await
+            .unwrap(),
+        AMOUNT.as_yoctonear()
+    );
+    assert!(
+        env.sandbox()
+            .worker()
+            .view_account(&receiver_id)
+            .await
+            .is_err()
+    );
+}
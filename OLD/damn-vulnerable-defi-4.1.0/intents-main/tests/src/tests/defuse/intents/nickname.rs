@@ -0,0 +1,88 @@
+use defuse::core::{
+    DefuseError,
+    intents::account::{MAX_NICKNAME_LEN, SetNickname},
+};
+use defuse_test_utils::asserts::ResultAssertsExt;
+use near_sdk::AccountId;
+use rstest::rstest;
+use serde_json::json;
+
+use crate::tests::defuse::{DefuseSignerExt, env::Env, intents::ExecuteIntentsExt};
+
+pub trait NicknameExt {
+    async fn nickname(&self, account_id: &AccountId) -> anyhow::Result<Option<String>>;
+}
+
+impl NicknameExt for near_workspaces::Account {
+    async fn nickname(&self, account_id: &AccountId) -> anyhow::Result<Option<String>> {
+        self.view(self.id(), "nickname")
+            .args_json(json!({
+                "account_id": account_id,
+            }))
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+}
+
+impl NicknameExt for near_workspaces::Contract {
+    async fn nickname(&self, account_id: &AccountId) -> anyhow::Result<Option<String>> {
+        self.as_account().nickname(account_id).await
+    }
+}
+
+#[tokio::test]
+#[rstest]
+async fn nickname() {
+    let env = Env::new().await;
+
+    let (user, other_user) = futures::join!(env.create_user(), env.create_user());
+
+    let set_nickname = async |nickname: Option<&str>| {
+        env.defuse
+            .execute_intents(
+                env.defuse.id(),
+                [user
+                    .sign_defuse_payload_default(
+                        env.defuse.id(),
+                        [SetNickname {
+                            nickname: nickname.map(ToString::to_string),
+                        }],
+                    )
+                    .await
+                    .unwrap()],
+            )
+            .await
+    };
+
+    assert_eq!(env.defuse.nickname(user.id()).await.unwrap(), None);
+
+    set_nickname(Some("alice")).await.unwrap();
+    assert_eq!(
+        env.defuse.nickname(user.id()).await.unwrap().as_deref(),
+        Some("alice")
+    );
+    // nicknames are per account
+    assert_eq!(env.defuse.nickname(other_user.id()).await.unwrap(), None);
+
+    // over-long nickname is rejected and the previous one is kept
+    set_nickname(Some(&"a".repeat(MAX_NICKNAME_LEN + 1)))
+        .await
+        .assert_err_contains(DefuseError::NicknameTooLong.to_string());
+    assert_eq!(
+        env.defuse.nickname(user.id()).await.unwrap().as_deref(),
+        Some("alice")
+    );
+
+    let longest = "a".repeat(MAX_NICKNAME_LEN);
+    set_nickname(Some(&longest)).await.unwrap();
+    assert_eq!(env.defuse.nickname(user.id()).await.unwrap(), Some(longest));
+
+    set_nickname(None).await.unwrap();
+    assert_eq!(env.defuse.nickname(user.id()).await.unwrap(), None);
+
+    // empty nickname clears it as well
+    set_nickname(Some("bob")).await.unwrap();
+    set_nickname(Some("")).await.unwrap();
+    assert_eq!(env.defuse.nickname(user.id()).await.unwrap(), None);
+}
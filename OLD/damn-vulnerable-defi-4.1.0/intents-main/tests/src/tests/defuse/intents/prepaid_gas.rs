@@ -0,0 +1,133 @@
+use defuse::core::{
+    Deadline, DefuseError,
+    amounts::Amounts,
+    intents::{
+        Intent,
+        tokens::{FtWithdraw, Transfer},
+    },
+    token_id::{TokenId, nep141::Nep141TokenId},
+};
+use defuse_test_utils::asserts::ResultAssertsExt;
+use near_sdk::Gas;
+use rstest::rstest;
+use serde_json::json;
+
+use crate::{
+    tests::defuse::{DefuseSigner, DefuseSignerExt, SigningStandard, env::Env},
+    utils::mt::MtExt,
+};
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn require_prepaid_gas() {
+    const REQUIRED: Gas = Gas::from_tgas(200);
+
+    let env = Env::builder().build().await;
+
+    let (user1, user2, ft) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+    let ft1 = TokenId::from(Nep141TokenId::new(ft.clone()));
+
+    env.initial_ft_storage_deposit(vec![user1.id(), user2.id()], vec![&ft])
+        .await;
+    env.defuse_ft_deposit_to(&ft, 1000, user1.id())
+        .await
+        .unwrap();
+
+    let deadline = Deadline::timeout(std::time::Duration::from_secs(120));
+    let nonce = user1
+        .unique_nonce(env.defuse.id(), Some(deadline))
+        .await
+        .unwrap();
+    let intent: Intent = Transfer {
+        receiver_id: user2.id().clone(),
+        tokens: Amounts::new([(ft1.clone(), 100)].into()),
+        memo: None,
+        notification: None,
+    }
+    .into();
+    let payload = user1.sign_defuse_message(
+        SigningStandard::default(),
+        env.defuse.id(),
+        nonce,
+        deadline,
+        // flattened into the signed payload
+        json!({
+            "require_prepaid_gas": REQUIRED,
+            "intents": [intent],
+        }),
+    );
+
+    let execute = async |gas: Gas| -> anyhow::Result<()> {
+        env.defuse
+            .call("execute_intents")
+            .args_json(json!({
+                "signed": [&payload],
+            }))
+            .gas(gas)
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    };
+
+    execute(Gas::from_tgas(100))
+        .await
+        .assert_err_contains(DefuseError::InsufficientGas { required: REQUIRED }.to_string());
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), user2.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        0
+    );
+
+    execute(REQUIRED).await.unwrap();
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), user2.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        100
+    );
+}
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn min_gas_exceeds_prepaid() {
+    let env = Env::builder().build().await;
+
+    let (user, ft) = futures::join!(env.create_user(), env.create_token());
+
+    let withdraw = |min_gas| FtWithdraw {
+        token: ft.clone(),
+        receiver_id: user.id().clone(),
+        amount: 100.into(),
+        memo: None,
+        msg: None,
+        storage_deposit: None,
+        auto_storage_deposit: false,
+        min_gas: Some(min_gas),
+    };
+
+    // each of them fits into the prepaid gas, but not together
+    let payload = user
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            [withdraw(Gas::from_tgas(100)), withdraw(Gas::from_tgas(100))],
+        )
+        .await
+        .unwrap();
+
+    env.defuse
+        .call("execute_intents")
+        .args_json(json!({
+            "signed": [payload],
+        }))
+        .gas(Gas::from_tgas(150))
+        .transact()
+        .await
+        .unwrap()
+        .into_result()
+        .assert_err_contains("min_gas requested by intents 200.0 Tgas exceeds available gas");
+}
@@ -0,0 +1,103 @@
+use defuse::core::{
+    Deadline, DefuseError,
+    amounts::Amounts,
+    intents::{Intent, tokens::Transfer},
+    payload::multi::MultiPayload,
+    token_id::{TokenId, nep141::Nep141TokenId},
+};
+use defuse_test_utils::asserts::ResultAssertsExt;
+use near_sdk::AccountId;
+use rstest::rstest;
+use serde_json::json;
+
+use crate::{
+    tests::defuse::{
+        DefuseSigner, DefuseSignerExt, SigningStandard, env::Env, intents::ExecuteIntentsExt,
+    },
+    utils::mt::MtExt,
+};
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn priority() {
+    let env = Env::builder().build().await;
+
+    let (user1, user2, user3, ft) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_token()
+    );
+    let ft1 = TokenId::from(Nep141TokenId::new(ft.clone()));
+
+    env.initial_ft_storage_deposit(vec![user1.id(), user2.id(), user3.id()], vec![&ft])
+        .await;
+    env.defuse_ft_deposit_to(&ft, 1000, user1.id())
+        .await
+        .unwrap();
+
+    let transfer = |receiver_id: &AccountId| -> Intent {
+        Transfer {
+            receiver_id: receiver_id.clone(),
+            tokens: Amounts::new([(ft1.clone(), 100)].into()),
+            memo: None,
+            notification: None,
+        }
+        .into()
+    };
+
+    let deadline = Deadline::timeout(std::time::Duration::from_secs(120));
+
+    // user2 spends tokens which are only funded by the payload below
+    let spend = user2.sign_defuse_message(
+        SigningStandard::default(),
+        env.defuse.id(),
+        user2
+            .unique_nonce(env.defuse.id(), Some(deadline))
+            .await
+            .unwrap(),
+        deadline,
+        json!({
+            "intents": [transfer(user3.id())],
+        }),
+    );
+
+    let fund = async |priority: Option<u8>| -> MultiPayload {
+        user1.sign_defuse_message(
+            SigningStandard::default(),
+            env.defuse.id(),
+            user1
+                .unique_nonce(env.defuse.id(), Some(deadline))
+                .await
+                .unwrap(),
+            deadline,
+            // flattened into the signed payload
+            json!({
+                "priority": priority,
+                "intents": [transfer(user2.id())],
+            }),
+        )
+    };
+
+    // executed in the order of the batch
+    env.defuse
+        .execute_intents(env.defuse.id(), [spend.clone(), fund(None).await])
+        .await
+        .assert_err_contains(DefuseError::BalanceOverflow.to_string());
+
+    // funding is listed later, but executed first
+    env.defuse
+        .execute_intents(env.defuse.id(), [spend, fund(Some(1)).await])
+        .await
+        .unwrap();
+
+    for (account_id, expected) in [(user1.id(), 900), (user2.id(), 0), (user3.id(), 100)] {
+        assert_eq!(
+            env.mt_contract_balance_of(env.defuse.id(), account_id, &ft1.to_string())
+                .await
+                .unwrap(),
+            expected
+        );
+    }
+}
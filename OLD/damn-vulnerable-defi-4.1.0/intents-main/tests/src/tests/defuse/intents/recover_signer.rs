@@ -0,0 +1,101 @@
+use defuse::core::{
+    Deadline,
+    crypto::{PublicKey, SignedPayload},
+    intents::DefuseIntents,
+};
+use defuse_randomness::Rng;
+use defuse_test_utils::random::rng;
+use near_workspaces::{
+    Account,
+    types::{KeyType, SecretKey},
+};
+use rstest::rstest;
+
+use crate::{
+    tests::defuse::{DefuseSigner, DefuseSignerExt, SigningStandard, env::Env},
+    utils::webauthn::WebAuthnSigner,
+};
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn recover_signer(
+    #[values(
+        SigningStandard::Nep413,
+        SigningStandard::TonConnect,
+        SigningStandard::Sep53
+    )]
+    standard: SigningStandard,
+) {
+    let env = Env::new().await;
+    let user = env.create_user().await;
+
+    let deadline = Deadline::timeout(std::time::Duration::from_secs(120));
+    let nonce = user
+        .unique_nonce(env.defuse.id(), Some(deadline))
+        .await
+        .unwrap();
+    let payload = user.sign_defuse_message(
+        standard,
+        env.defuse.id(),
+        nonce,
+        deadline,
+        DefuseIntents { intents: [].into() },
+    );
+
+    assert_eq!(payload.recover_signer().unwrap(), *user.id());
+}
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn recover_erc191_signer() {
+    let env = Env::new().await;
+
+    // ERC-191 payloads are signed on behalf of implicit Ethereum accounts
+    // of secp256k1 keys
+    let secret_key = SecretKey::from_random(KeyType::SECP256K1);
+    let eth_account_id =
+        PublicKey::Secp256k1(secret_key.public_key().key_data().try_into().unwrap())
+            .to_implicit_account_id();
+    let eth_user =
+        Account::from_secret_key(eth_account_id.clone(), secret_key, env.sandbox().worker());
+
+    let deadline = Deadline::timeout(std::time::Duration::from_secs(120));
+    let nonce = eth_user
+        .unique_nonce(env.defuse.id(), Some(deadline))
+        .await
+        .unwrap();
+    let payload = eth_user.sign_defuse_message(
+        SigningStandard::Erc191,
+        env.defuse.id(),
+        nonce,
+        deadline,
+        DefuseIntents { intents: [].into() },
+    );
+
+    assert_eq!(
+        payload.verify().unwrap().to_implicit_account_id(),
+        eth_account_id
+    );
+    assert_eq!(payload.recover_signer().unwrap(), eth_account_id);
+}
+
+#[rstest]
+#[test]
+fn recover_webauthn_signer(#[values(false, true)] p256: bool, #[notrace] mut rng: impl Rng) {
+    let signer = if p256 {
+        WebAuthnSigner::random_p256(&mut rng)
+    } else {
+        WebAuthnSigner::random_ed25519()
+    };
+
+    let payload = signer.sign_defuse_message(
+        &"intents.near".parse().unwrap(),
+        rng.random(),
+        Deadline::MAX,
+        DefuseIntents { intents: [].into() },
+    );
+
+    assert_eq!(payload.recover_signer().unwrap(), signer.account_id());
+}
@@ -1,20 +1,32 @@
-use defuse::contract::Role;
+use std::{collections::BTreeSet, time::Duration};
+
+use defuse::{
+    contract::Role,
+    core::{
+        Deadline, DefuseError,
+        amounts::Amounts,
+        intents::{
+            IntentKind,
+            tokens::{FtWithdraw, Transfer},
+        },
+        token_id::{TokenId, nep141::Nep141TokenId},
+    },
+    intents::RelayerKeyScope,
+};
 use defuse_test_utils::asserts::ResultAssertsExt;
-use near_sdk::{AccountId, NearToken, PublicKey};
-use near_workspaces::{Account, types::SecretKey};
+use near_sdk::{AccountId, NearToken, PublicKey, json_types::U128};
+use near_workspaces::{Account, Contract, types::SecretKey};
 use rstest::rstest;
 use serde_json::json;
 
 use crate::{
-    tests::defuse::{env::Env, intents::ExecuteIntentsExt},
-    utils::acl::AclExt,
+    tests::defuse::{DefuseSignerExt, env::Env, intents::ExecuteIntentsExt},
+    utils::{acl::AclExt, mt::MtExt},
 };
 
 #[tokio::test]
 #[rstest]
 async fn relayer_keys(#[values(false, true)] no_registration: bool) {
-    use near_workspaces::Contract;
-
     let env = Env::builder()
         .deployer_as_super_admin()
         .no_registration(no_registration)
@@ -93,6 +105,355 @@ async fn relayer_keys(#[values(false, true)] no_registration: bool) {
     assert!(!access_keys.is_empty());
 }
 
+#[tokio::test]
+#[rstest]
+async fn relayer_keys_enumeration() {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let user = env.create_user().await;
+
+    env.acl_grant_role(env.defuse.id(), Role::RelayerKeysManager, user.id())
+        .await
+        .unwrap();
+
+    let [key1, key2]: [PublicKey; 2] = std::array::from_fn(|_| {
+        SecretKey::from_random(near_workspaces::types::KeyType::ED25519)
+            .public_key()
+            .to_string()
+            .parse()
+            .unwrap()
+    });
+
+    let initial_keys = user.relayer_keys(env.defuse.id()).await.unwrap();
+
+    user.add_relayer_key(env.defuse.id(), &key1).await.unwrap();
+    user.add_relayer_key(env.defuse.id(), &key2).await.unwrap();
+
+    let keys = user.relayer_keys(env.defuse.id()).await.unwrap();
+    assert_eq!(keys.len(), initial_keys.len() + 2);
+    assert!(keys.contains(&key1));
+    assert!(keys.contains(&key2));
+
+    user.delete_relayer_key(env.defuse.id(), &key1)
+        .await
+        .unwrap();
+
+    let keys = user.relayer_keys(env.defuse.id()).await.unwrap();
+    assert_eq!(keys.len(), initial_keys.len() + 1);
+    assert!(!keys.contains(&key1));
+    assert!(keys.contains(&key2));
+}
+
+#[tokio::test]
+#[rstest]
+async fn index_relayer_keys() {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (manager, dao) = futures::join!(env.create_user(), env.create_user());
+
+    env.acl_grant_role(env.defuse.id(), Role::RelayerKeysManager, manager.id())
+        .await
+        .unwrap();
+    env.acl_grant_role(env.defuse.id(), Role::DAO, dao.id())
+        .await
+        .unwrap();
+
+    let [key1, key2]: [PublicKey; 2] = std::array::from_fn(|_| {
+        SecretKey::from_random(near_workspaces::types::KeyType::ED25519)
+            .public_key()
+            .to_string()
+            .parse()
+            .unwrap()
+    });
+
+    manager
+        .add_relayer_key(env.defuse.id(), &key1)
+        .await
+        .unwrap();
+
+    manager
+        .index_relayer_keys(env.defuse.id(), &[key1.clone()])
+        .await
+        .expect_err("only DAO can backfill the index");
+
+    // only authorized relayer keys can be indexed
+    dao.index_relayer_keys(env.defuse.id(), &[key1.clone(), key2.clone()])
+        .await
+        .assert_err_contains("key not found");
+
+    let initial_keys = dao.relayer_keys(env.defuse.id()).await.unwrap();
+    dao.index_relayer_keys(env.defuse.id(), &[key1.clone()])
+        .await
+        .unwrap();
+
+    let keys = dao.relayer_keys(env.defuse.id()).await.unwrap();
+    assert_eq!(keys, initial_keys, "indexing is idempotent");
+    assert!(keys.contains(&key1));
+    assert!(!keys.contains(&key2));
+}
+
+#[tokio::test]
+#[rstest]
+async fn relayer_key_scope() {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (user, other_user, ft) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+
+    env.initial_ft_storage_deposit(vec![user.id(), other_user.id()], vec![&ft])
+        .await;
+
+    let ft1: TokenId = Nep141TokenId::new(ft.clone()).into();
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    env.acl_grant_role(env.defuse.id(), Role::RelayerKeysManager, user.id())
+        .await
+        .unwrap();
+
+    let relayer_secret_key = SecretKey::from_random(near_workspaces::types::KeyType::ED25519);
+    let relayer_public_key: PublicKey =
+        relayer_secret_key.public_key().to_string().parse().unwrap();
+    let relayer = Contract::from_secret_key(
+        env.defuse.id().clone(),
+        relayer_secret_key,
+        env.sandbox().worker(),
+    );
+
+    user.add_relayer_key(env.defuse.id(), &relayer_public_key)
+        .await
+        .unwrap();
+
+    let scope = RelayerKeyScope {
+        intents: Some(BTreeSet::from([IntentKind::Transfer])),
+        accounts: None,
+    };
+
+    // only relayer keys can be scoped
+    user.set_relayer_key_scope(
+        env.defuse.id(),
+        &SecretKey::from_random(near_workspaces::types::KeyType::ED25519)
+            .public_key()
+            .to_string()
+            .parse()
+            .unwrap(),
+        Some(&scope),
+    )
+    .await
+    .assert_err_contains("key not found");
+
+    // unauthorized user can't change the scope
+    other_user
+        .set_relayer_key_scope(env.defuse.id(), &relayer_public_key, Some(&scope))
+        .await
+        .assert_err_contains("Requires one of these roles:");
+
+    user.set_relayer_key_scope(env.defuse.id(), &relayer_public_key, Some(&scope))
+        .await
+        .unwrap();
+    assert_eq!(
+        user.relayer_key_scope(env.defuse.id(), &relayer_public_key)
+            .await
+            .unwrap(),
+        Some(scope),
+    );
+
+    // transfers are within the scope
+    relayer
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(
+                    env.defuse.id(),
+                    [Transfer {
+                        receiver_id: other_user.id().clone(),
+                        tokens: Amounts::new([(ft1.clone(), 100)].into()),
+                        memo: None,
+                        notification: None,
+                    }],
+                )
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+
+    let withdraw = FtWithdraw {
+        token: ft.clone(),
+        receiver_id: user.id().clone(),
+        amount: U128(100),
+        memo: None,
+        msg: None,
+        storage_deposit: None,
+        auto_storage_deposit: false,
+        min_gas: None,
+    };
+
+    // withdrawals are not
+    relayer
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(env.defuse.id(), [withdraw.clone()])
+                .await
+                .unwrap()],
+        )
+        .await
+        .assert_err_contains(DefuseError::RelayerKeyOutOfScope.to_string());
+
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(user.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        900
+    );
+
+    // lifting the scope allows any intents again
+    user.set_relayer_key_scope(env.defuse.id(), &relayer_public_key, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        user.relayer_key_scope(env.defuse.id(), &relayer_public_key)
+            .await
+            .unwrap(),
+        None,
+    );
+
+    relayer
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(env.defuse.id(), [withdraw])
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(user.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        800
+    );
+}
+
+#[tokio::test]
+#[rstest]
+async fn relayer_key_expiry() {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (user, other_user, ft) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+
+    env.initial_ft_storage_deposit(vec![user.id(), other_user.id()], vec![&ft])
+        .await;
+
+    let ft1: TokenId = Nep141TokenId::new(ft.clone()).into();
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    env.acl_grant_role(env.defuse.id(), Role::RelayerKeysManager, user.id())
+        .await
+        .unwrap();
+
+    let relayer_secret_key = SecretKey::from_random(near_workspaces::types::KeyType::ED25519);
+    let relayer_public_key: PublicKey =
+        relayer_secret_key.public_key().to_string().parse().unwrap();
+    let relayer = Contract::from_secret_key(
+        env.defuse.id().clone(),
+        relayer_secret_key,
+        env.sandbox().worker(),
+    );
+
+    user.add_relayer_key(env.defuse.id(), &relayer_public_key)
+        .await
+        .unwrap();
+    assert_eq!(
+        user.relayer_key_expiry(env.defuse.id(), &relayer_public_key)
+            .await
+            .unwrap(),
+        None,
+    );
+
+    // unauthorized user can't change the expiry
+    let expires_at = Deadline::timeout(Duration::from_secs(10));
+    other_user
+        .set_relayer_key_expiry(env.defuse.id(), &relayer_public_key, Some(expires_at))
+        .await
+        .assert_err_contains("Requires one of these roles:");
+
+    user.set_relayer_key_expiry(env.defuse.id(), &relayer_public_key, Some(expires_at))
+        .await
+        .unwrap();
+    assert_eq!(
+        user.relayer_key_expiry(env.defuse.id(), &relayer_public_key)
+            .await
+            .unwrap(),
+        Some(expires_at),
+    );
+
+    let transfer = Transfer {
+        receiver_id: other_user.id().clone(),
+        tokens: Amounts::new([(ft1.clone(), 100)].into()),
+        memo: None,
+        notification: None,
+    };
+
+    // the key is still valid
+    relayer
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(env.defuse.id(), [transfer.clone()])
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+
+    tokio::time::sleep(Duration::from_secs(15)).await;
+
+    relayer
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(env.defuse.id(), [transfer.clone()])
+                .await
+                .unwrap()],
+        )
+        .await
+        .assert_err_contains(DefuseError::RelayerKeyExpired.to_string());
+
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(user.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        900
+    );
+
+    // lifting the expiry makes the key valid again
+    user.set_relayer_key_expiry(env.defuse.id(), &relayer_public_key, None)
+        .await
+        .unwrap();
+
+    relayer
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(env.defuse.id(), [transfer])
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+}
+
 pub trait RelayerKeysExt {
     async fn add_relayer_key(
         &self,
@@ -105,6 +466,40 @@ pub trait RelayerKeysExt {
         defuse_contract_id: &AccountId,
         public_key: &PublicKey,
     ) -> anyhow::Result<()>;
+
+    async fn relayer_keys(&self, defuse_contract_id: &AccountId) -> anyhow::Result<Vec<PublicKey>>;
+
+    async fn index_relayer_keys(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_keys: &[PublicKey],
+    ) -> anyhow::Result<()>;
+
+    async fn set_relayer_key_scope(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_key: &PublicKey,
+        scope: Option<&RelayerKeyScope>,
+    ) -> anyhow::Result<()>;
+
+    async fn relayer_key_scope(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> anyhow::Result<Option<RelayerKeyScope>>;
+
+    async fn set_relayer_key_expiry(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_key: &PublicKey,
+        expires_at: Option<Deadline>,
+    ) -> anyhow::Result<()>;
+
+    async fn relayer_key_expiry(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> anyhow::Result<Option<Deadline>>;
 }
 
 impl RelayerKeysExt for Account {
@@ -141,4 +536,94 @@ impl RelayerKeysExt for Account {
             .into_result()?;
         Ok(())
     }
+
+    async fn relayer_keys(&self, defuse_contract_id: &AccountId) -> anyhow::Result<Vec<PublicKey>> {
+        self.view(defuse_contract_id, "relayer_keys")
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn index_relayer_keys(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_keys: &[PublicKey],
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "index_relayer_keys")
+            .deposit(NearToken::from_yoctonear(1))
+            .args_json(json!({
+                "public_keys": public_keys,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    async fn set_relayer_key_scope(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_key: &PublicKey,
+        scope: Option<&RelayerKeyScope>,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "set_relayer_key_scope")
+            .deposit(NearToken::from_yoctonear(1))
+            .args_json(json!({
+                "public_key": public_key,
+                "scope": scope,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    async fn relayer_key_scope(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> anyhow::Result<Option<RelayerKeyScope>> {
+        self.view(defuse_contract_id, "relayer_key_scope")
+            .args_json(json!({
+                "public_key": public_key,
+            }))
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn set_relayer_key_expiry(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_key: &PublicKey,
+        expires_at: Option<Deadline>,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "set_relayer_key_expiry")
+            .deposit(NearToken::from_yoctonear(1))
+            .args_json(json!({
+                "public_key": public_key,
+                "expires_at": expires_at,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    async fn relayer_key_expiry(
+        &self,
+        defuse_contract_id: &AccountId,
+        public_key: &PublicKey,
+    ) -> anyhow::Result<Option<Deadline>> {
+        self.view(defuse_contract_id, "relayer_key_expiry")
+            .args_json(json!({
+                "public_key": public_key,
+            }))
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
 }
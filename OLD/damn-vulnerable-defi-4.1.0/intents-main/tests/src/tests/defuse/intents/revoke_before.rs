@@ -0,0 +1,159 @@
+use std::time::Duration;
+
+use defuse::core::{
+    Deadline, DefuseError,
+    amounts::Amounts,
+    intents::{DefuseIntents, account::RevokeBefore, tokens::Transfer},
+    token_id::{TokenId, nep141::Nep141TokenId},
+};
+use defuse_test_utils::asserts::ResultAssertsExt;
+use rstest::rstest;
+
+use crate::{
+    tests::defuse::{
+        DefuseSigner, DefuseSignerExt, SigningStandard, env::Env, intents::ExecuteIntentsExt,
+    },
+    utils::mt::MtExt,
+};
+
+#[tokio::test]
+#[rstest]
+async fn revoke_before() {
+    let env = Env::new().await;
+
+    let (user, other_user, ft) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+
+    env.initial_ft_storage_deposit(vec![user.id(), other_user.id()], vec![&ft])
+        .await;
+
+    let ft1: TokenId = Nep141TokenId::new(ft.clone()).into();
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    let sign_transfer = async |deadline| {
+        let nonce = user
+            .unique_nonce(env.defuse.id(), Some(deadline))
+            .await
+            .unwrap();
+        user.sign_defuse_message(
+            SigningStandard::Nep413,
+            env.defuse.id(),
+            nonce,
+            deadline,
+            DefuseIntents {
+                intents: [Transfer {
+                    receiver_id: other_user.id().clone(),
+                    tokens: Amounts::new([(ft1.clone(), 100)].into()),
+                    memo: None,
+                    notification: None,
+                }
+                .into()]
+                .into(),
+            },
+        )
+    };
+
+    // signed before revocation, but not relayed yet, with deadline far
+    // beyond the cutoff
+    let older = sign_transfer(Deadline::timeout(Duration::from_secs(600))).await;
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(
+                    env.defuse.id(),
+                    [RevokeBefore {
+                        timestamp: Deadline::now(),
+                    }],
+                )
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+
+    env.defuse
+        .execute_intents(env.defuse.id(), [older])
+        .await
+        .assert_err_contains(DefuseError::IntentRevoked.to_string());
+
+    // signed after the cutoff, even with an earlier deadline
+    let newer = sign_transfer(Deadline::timeout(Duration::from_secs(300))).await;
+    env.defuse
+        .execute_intents(env.defuse.id(), [newer])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(other_user.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        100
+    );
+}
+
+#[tokio::test]
+#[rstest]
+async fn revoke_before_future_timestamp_is_clamped() {
+    let env = Env::new().await;
+
+    let (user, other_user, ft) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+
+    env.initial_ft_storage_deposit(vec![user.id(), other_user.id()], vec![&ft])
+        .await;
+
+    let ft1: TokenId = Nep141TokenId::new(ft.clone()).into();
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(
+                    env.defuse.id(),
+                    [RevokeBefore {
+                        timestamp: Deadline::MAX,
+                    }],
+                )
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+
+    // the cutoff is clamped to the block timestamp, so that the account
+    // is still able to sign new intents
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(
+                    env.defuse.id(),
+                    [Transfer {
+                        receiver_id: other_user.id().clone(),
+                        tokens: Amounts::new([(ft1.clone(), 100)].into()),
+                        memo: None,
+                        notification: None,
+                    }],
+                )
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(other_user.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        100
+    );
+}
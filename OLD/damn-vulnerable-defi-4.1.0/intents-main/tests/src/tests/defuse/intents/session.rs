@@ -0,0 +1,277 @@
+use std::{collections::BTreeSet, time::Duration};
+
+use chrono::{TimeDelta, Utc};
+use defuse::core::{
+    Deadline, DefuseError,
+    amounts::Amounts,
+    intents::{auth::AuthCall, session::GrantSession, tokens::Transfer},
+    token_id::{TokenId, nep141::Nep141TokenId},
+};
+use defuse_test_utils::asserts::ResultAssertsExt;
+use near_sdk::NearToken;
+use near_workspaces::{Account, types::SecretKey};
+use rstest::rstest;
+
+use crate::{
+    tests::defuse::{
+        DefuseSignerExt,
+        env::{Env, get_account_public_key},
+        intents::ExecuteIntentsExt,
+    },
+    utils::mt::MtExt,
+};
+
+#[tokio::test]
+#[rstest]
+async fn session_key_spending_limit() {
+    let env = Env::new().await;
+
+    let (user, other_user, ft) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+
+    env.initial_ft_storage_deposit(vec![user.id(), other_user.id()], vec![&ft])
+        .await;
+
+    let ft1: TokenId = Nep141TokenId::new(ft.clone()).into();
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    // all keys generated by near-workspaces are the same, so we need a random one
+    let session_account = Account::from_secret_key(
+        user.id().clone(),
+        SecretKey::from_random(near_workspaces::types::KeyType::ED25519),
+        env.sandbox().worker(),
+    );
+
+    let transfer = |amount| Transfer {
+        receiver_id: other_user.id().clone(),
+        tokens: Amounts::new([(ft1.clone(), amount)].into()),
+        memo: None,
+        notification: None,
+    };
+
+    // session key is not authorized yet
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [session_account
+                .sign_defuse_payload_default(env.defuse.id(), [transfer(300)])
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap_err();
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(
+                    env.defuse.id(),
+                    [GrantSession {
+                        key: get_account_public_key(&session_account),
+                        limits: Amounts::new([(ft1.clone(), 500)].into()),
+                        expiry: Deadline::timeout(Duration::from_secs(3600)),
+                        auth_calls: BTreeSet::new(),
+                    }],
+                )
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+
+    // within the allowance
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [session_account
+                .sign_defuse_payload_default(env.defuse.id(), [transfer(300)])
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+
+    // exceeds remaining allowance
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [session_account
+                .sign_defuse_payload_default(env.defuse.id(), [transfer(300)])
+                .await
+                .unwrap()],
+        )
+        .await
+        .assert_err_contains(DefuseError::SessionLimitExceeded.to_string());
+
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(user.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        700
+    );
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(other_user.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        300
+    );
+}
+
+#[tokio::test]
+#[rstest]
+async fn session_key_expiry() {
+    let env = Env::new().await;
+
+    let (user, other_user, ft) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+
+    env.initial_ft_storage_deposit(vec![user.id(), other_user.id()], vec![&ft])
+        .await;
+
+    let ft1: TokenId = Nep141TokenId::new(ft.clone()).into();
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    let session_account = Account::from_secret_key(
+        user.id().clone(),
+        SecretKey::from_random(near_workspaces::types::KeyType::ED25519),
+        env.sandbox().worker(),
+    );
+    let grant_session = |expiry| GrantSession {
+        key: get_account_public_key(&session_account),
+        limits: Amounts::new([(ft1.clone(), 500)].into()),
+        expiry,
+        auth_calls: BTreeSet::new(),
+    };
+
+    // can't grant already expired session
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(
+                    env.defuse.id(),
+                    [grant_session(Deadline::new(
+                        Utc::now().checked_sub_signed(TimeDelta::hours(1)).unwrap(),
+                    ))],
+                )
+                .await
+                .unwrap()],
+        )
+        .await
+        .assert_err_contains(DefuseError::SessionExpired.to_string());
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(
+                    env.defuse.id(),
+                    [grant_session(Deadline::timeout(Duration::from_secs(5)))],
+                )
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+
+    tokio::time::sleep(Duration::from_secs(10)).await;
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [session_account
+                .sign_defuse_payload_default(
+                    env.defuse.id(),
+                    [Transfer {
+                        receiver_id: other_user.id().clone(),
+                        tokens: Amounts::new([(ft1.clone(), 100)].into()),
+                        memo: None,
+                        notification: None,
+                    }],
+                )
+                .await
+                .unwrap()],
+        )
+        .await
+        .assert_err_contains(DefuseError::SessionExpired.to_string());
+
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(user.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        1000
+    );
+}
+
+#[tokio::test]
+#[rstest]
+async fn session_key_auth_call_allowlist() {
+    let env = Env::new().await;
+
+    let (user, ft) = futures::join!(env.create_user(), env.create_token());
+
+    let session_account = Account::from_secret_key(
+        user.id().clone(),
+        SecretKey::from_random(near_workspaces::types::KeyType::ED25519),
+        env.sandbox().worker(),
+    );
+    let grant_session = |auth_calls| GrantSession {
+        key: get_account_public_key(&session_account),
+        limits: Amounts::default(),
+        expiry: Deadline::timeout(Duration::from_secs(3600)),
+        auth_calls,
+    };
+    let auth_call = AuthCall {
+        contract_id: ft.clone(),
+        msg: String::new(),
+        attached_deposit: NearToken::from_yoctonear(0),
+        min_gas: None,
+    };
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(env.defuse.id(), [grant_session(BTreeSet::new())])
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+
+    // contract is not allowlisted for the session
+    env.defuse
+        .simulate_intents([session_account
+            .sign_defuse_payload_default(env.defuse.id(), [auth_call.clone()])
+            .await
+            .unwrap()])
+        .await
+        .assert_err_contains(DefuseError::SessionAuthCallNotAllowed(ft.clone()).to_string());
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(env.defuse.id(), [grant_session([ft.clone()].into())])
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+
+    env.defuse
+        .simulate_intents([session_account
+            .sign_defuse_payload_default(env.defuse.id(), [auth_call])
+            .await
+            .unwrap()])
+        .await
+        .expect("allowlisted contract can be called by session key");
+}
@@ -1,5 +1,7 @@
 use crate::tests::defuse::DefuseSignerExt;
-use crate::tests::defuse::intents::{AccountNonceIntentEvent, ExecuteIntentsExt, NonceEvent};
+use crate::tests::defuse::intents::{
+    AccountNonceIntentEvent, ExecuteIntentsExt, NonceEvent, assert_simulation_matches_execution,
+};
 use crate::utils::fixtures::public_key;
 use crate::utils::payload::ExtractNonceExt;
 use crate::utils::{ft::FtExt, mt::MtExt, nft::NftExt, wnear::WNearExt};
@@ -7,8 +9,10 @@ use crate::{
     tests::defuse::DefuseExt, tests::defuse::accounts::AccountManagerExt, tests::defuse::env::Env,
 };
 use defuse::contract::config::{DefuseConfig, RolesConfig};
+use defuse::core::DefuseError;
 use defuse::core::accounts::TransferEvent;
 use defuse::core::crypto::Payload;
+use defuse::intents::EventVerbosity;
 
 use defuse::core::crypto::PublicKey;
 use defuse::core::fees::{FeesConfig, Pips};
@@ -99,6 +103,112 @@ async fn simulate_transfer_intent() {
     );
 }
 
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn simulate_transfer_intent_matches_execution() {
+    let env = Env::builder().no_registration(true).build().await;
+
+    let (user1, user2, ft1) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+    let token_id = TokenId::from(Nep141TokenId::new(ft1.clone()));
+
+    env.initial_ft_storage_deposit(vec![user1.id(), user2.id()], vec![&ft1])
+        .await;
+
+    env.defuse_ft_deposit_to(&ft1, 1000, user1.id())
+        .await
+        .unwrap();
+
+    let payload = user1
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            [Transfer {
+                receiver_id: user2.id().clone(),
+                tokens: Amounts::new([(token_id.clone(), 400)].into()),
+                memo: Some("memo".to_string()),
+                notification: None,
+            }],
+        )
+        .await
+        .unwrap();
+
+    assert_simulation_matches_execution(&env, [payload]).await;
+
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), user2.id(), &token_id.to_string())
+            .await
+            .unwrap(),
+        400
+    );
+}
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn simulate_gas_estimates() {
+    let env = Env::builder().no_registration(true).build().await;
+
+    let (user1, user2, ft1) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+
+    env.initial_ft_storage_deposit(vec![user1.id(), user2.id()], vec![&ft1])
+        .await;
+
+    env.defuse_ft_deposit_to(&ft1, 1000, user1.id())
+        .await
+        .unwrap();
+
+    let transfer_payload = user1
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            [Transfer {
+                receiver_id: user2.id().clone(),
+                tokens: Amounts::new(
+                    std::iter::once((TokenId::from(Nep141TokenId::new(ft1.clone())), 500))
+                        .collect(),
+                ),
+                memo: None,
+                notification: None,
+            }],
+        )
+        .await
+        .unwrap();
+    let ft_withdraw_payload = user1
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            [FtWithdraw {
+                token: ft1.clone(),
+                receiver_id: user2.id().clone(),
+                amount: near_sdk::json_types::U128(500),
+                memo: None,
+                msg: None,
+                storage_deposit: None,
+                auto_storage_deposit: false,
+                min_gas: None,
+            }],
+        )
+        .await
+        .unwrap();
+
+    let result = env
+        .defuse
+        .simulate_intents([transfer_payload, ft_withdraw_payload])
+        .await
+        .unwrap();
+
+    let [transfer_gas, ft_withdraw_gas] = result.report.gas_estimates[..] else {
+        panic!(
+            "expected an estimate per intent, got: {:?}",
+            result.report.gas_estimates
+        );
+    };
+    assert!(
+        transfer_gas < ft_withdraw_gas,
+        "transfer: {transfer_gas}, ft_withdraw: {ft_withdraw_gas}"
+    );
+}
+
 #[tokio::test]
 #[rstest]
 #[trace]
@@ -132,6 +242,7 @@ async fn simulate_ft_withdraw_intent() {
         memo: None,
         msg: None,
         storage_deposit: None,
+        auto_storage_deposit: false,
         min_gas: None,
     };
 
@@ -364,6 +475,12 @@ async fn simulate_mt_withdraw_intent() {
                     fee_collector: env.id().clone(),
                 },
                 roles: RolesConfig::default(),
+                max_tokens_per_account: None,
+                event_verbosity: EventVerbosity::default(),
+                registration_reserve: None,
+                max_nonce_ttl: None,
+                require_bounded_deadline: false,
+                reject_legacy_nonces: false,
             },
             false,
         )
@@ -432,6 +549,7 @@ async fn simulate_mt_withdraw_intent() {
         receiver_id: user2.id().clone(), // Withdraw to user2's account in defuse1
         token_ids: vec![ft1_id.to_string()], // The FT token ID within defuse1
         amounts: vec![near_sdk::json_types::U128(200)],
+        receiver_ids: None,
         memo: None,
         msg: None,
         storage_deposit: None,
@@ -598,6 +716,9 @@ async fn simulate_token_diff_intent() {
             .unwrap(),
         memo: None,
         referral: None,
+        max_fee: None,
+        solver: None,
+        partial_fill: false,
     };
 
     // user2: swap -200 ft2 for +100 ft1
@@ -607,6 +728,9 @@ async fn simulate_token_diff_intent() {
             .unwrap(),
         memo: None,
         referral: None,
+        max_fee: None,
+        solver: None,
+        partial_fill: false,
     };
 
     let user1_payload = user1
@@ -894,3 +1018,146 @@ async fn simulation_fails_on_used_nonce() {
 
     assert!(result.is_err());
 }
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn simulate_intents_detailed_pinpoints_failed_intent() {
+    let env = Env::builder().build().await;
+
+    let (user1, user2, ft1) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+
+    env.initial_ft_storage_deposit(vec![user1.id(), user2.id()], vec![&ft1])
+        .await;
+
+    env.defuse_ft_deposit_to(&ft1, 1000, user1.id())
+        .await
+        .unwrap();
+
+    let ft1_token_id = TokenId::from(Nep141TokenId::new(ft1.clone()));
+    let transfer = |amount| Transfer {
+        receiver_id: user2.id().clone(),
+        tokens: Amounts::new(std::iter::once((ft1_token_id.clone(), amount)).collect()),
+        memo: None,
+        notification: None,
+    };
+
+    let mut payloads = Vec::new();
+    // the middle one exceeds the balance
+    for amount in [100, 5000, 200] {
+        payloads.push(
+            user1
+                .sign_defuse_payload_default(env.defuse.id(), [transfer(amount)])
+                .await
+                .unwrap(),
+        );
+    }
+
+    env.defuse
+        .simulate_intents(payloads.clone())
+        .await
+        .unwrap_err();
+
+    let result = env
+        .defuse
+        .simulate_intents_detailed(payloads.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(result.failed().collect::<Vec<_>>(), [1]);
+    assert_eq!(
+        result.outcomes[1],
+        Err(DefuseError::BalanceOverflow.to_string())
+    );
+
+    // failed intent is excluded from the batch simulation
+    assert_eq!(
+        result
+            .output
+            .report
+            .intents_executed
+            .iter()
+            .map(|e| e.intent_hash)
+            .collect::<Vec<_>>(),
+        [payloads[0].hash(), payloads[2].hash()],
+    );
+    assert!(result.output.invariant_violated.is_none());
+}
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn simulate_intents_detailed_reports_unmatched_solver() {
+    let env = Env::builder()
+        .fee(Pips::ZERO)
+        .no_registration(true)
+        .build()
+        .await;
+
+    let (user1, user2, solver, ft1, ft2) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_token(),
+        env.create_token()
+    );
+
+    env.initial_ft_storage_deposit(vec![user1.id(), user2.id()], vec![&ft1, &ft2])
+        .await;
+
+    let ft1_token_id = TokenId::from(Nep141TokenId::new(ft1.clone()));
+    let ft2_token_id = TokenId::from(Nep141TokenId::new(ft2.clone()));
+
+    env.defuse_ft_deposit_to(&ft1, 100, user1.id())
+        .await
+        .unwrap();
+    env.defuse_ft_deposit_to(&ft2, 200, user2.id())
+        .await
+        .unwrap();
+
+    // user1 only trades with the solver, but it's user2 who matches it
+    let token_diff = |deltas, solver| TokenDiff {
+        diff: TokenDeltas::default().with_apply_deltas(deltas).unwrap(),
+        memo: None,
+        referral: None,
+        max_fee: None,
+        solver,
+        partial_fill: false,
+    };
+    let user1_payload = user1
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            [token_diff(
+                [(ft1_token_id.clone(), -100), (ft2_token_id.clone(), 200)],
+                Some(solver.id().clone()),
+            )],
+        )
+        .await
+        .unwrap();
+    let user2_payload = user2
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            [token_diff(
+                [(ft1_token_id.clone(), 100), (ft2_token_id.clone(), -200)],
+                None,
+            )],
+        )
+        .await
+        .unwrap();
+
+    let result = env
+        .defuse
+        .simulate_intents_detailed([user1_payload, user2_payload])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        result.outcomes,
+        [
+            Err(DefuseError::SolverNotMatched(solver.id().clone()).to_string()),
+            Ok(()),
+        ]
+    );
+    assert!(result.output.invariant_violated.is_none());
+}
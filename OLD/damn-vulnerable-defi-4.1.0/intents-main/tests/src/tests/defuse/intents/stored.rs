@@ -0,0 +1,518 @@
+use defuse::{
+    core::{
+        Deadline,
+        amounts::Amounts,
+        intents::{
+            DefuseIntents, Intent,
+            token_diff::{TokenDeltas, TokenDiff},
+            tokens::Transfer,
+        },
+        payload::multi::MultiPayload,
+        token_id::{TokenId, nep141::Nep141TokenId},
+    },
+    intents::{MAX_STORED_INTENTS_PER_QUERY, OrderBookPair, StoredIntent},
+};
+use defuse_test_utils::asserts::ResultAssertsExt;
+use near_sdk::{AccountId, NearToken, json_types::U128};
+use rstest::rstest;
+use serde_json::json;
+
+use crate::{
+    tests::defuse::{
+        DefuseSigner, DefuseSignerExt, SigningStandard, env::Env, intents::ExecuteIntentsExt,
+    },
+    utils::mt::MtExt,
+};
+
+#[tokio::test]
+#[rstest]
+async fn stored_intent() {
+    let env = Env::new().await;
+
+    let (user, other_user, relayer, other_relayer, ft) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_token()
+    );
+
+    env.initial_ft_storage_deposit(vec![user.id(), other_user.id()], vec![&ft])
+        .await;
+
+    let ft_id = TokenId::from(Nep141TokenId::new(ft.clone()));
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    let sign_transfer = async || {
+        user.sign_defuse_payload_default(
+            env.defuse.id(),
+            [Transfer {
+                receiver_id: other_user.id().clone(),
+                tokens: Amounts::new([(ft_id.clone(), 100)].into()),
+                memo: None,
+                notification: None,
+            }],
+        )
+        .await
+        .unwrap()
+    };
+
+    // stored by one relayer, executed later by another one
+    {
+        let id = relayer
+            .store_signed_intent(env.defuse.id(), sign_transfer().await)
+            .await
+            .unwrap();
+
+        let stored = env
+            .defuse
+            .stored_intent(env.defuse.id(), id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.signer_id, *user.id());
+        assert_eq!(stored.storage_payer, *relayer.id());
+        assert!(!stored.storage_deposit.is_zero());
+
+        other_relayer
+            .execute_stored_intent(env.defuse.id(), id)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            env.mt_contract_balance_of(env.defuse.id(), other_user.id(), &ft_id.to_string())
+                .await
+                .unwrap(),
+            100
+        );
+        assert!(
+            env.defuse
+                .stored_intent(env.defuse.id(), id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        other_relayer
+            .execute_stored_intent(env.defuse.id(), id)
+            .await
+            .assert_err_contains("stored intent not found");
+    }
+
+    // cancelled by the signer
+    {
+        let signed = sign_transfer().await;
+        let id = relayer
+            .store_signed_intent(env.defuse.id(), signed.clone())
+            .await
+            .unwrap();
+
+        // only the signer can cancel
+        relayer
+            .cancel_stored_intent(env.defuse.id(), id)
+            .await
+            .assert_err_contains("stored intent not found");
+
+        user.cancel_stored_intent(env.defuse.id(), id)
+            .await
+            .unwrap();
+
+        other_relayer
+            .execute_stored_intent(env.defuse.id(), id)
+            .await
+            .assert_err_contains("stored intent not found");
+
+        // nonce is committed, so it can't be executed directly either
+        env.defuse
+            .execute_intents(env.defuse.id(), [signed])
+            .await
+            .assert_err_contains("nonce was already used");
+
+        assert_eq!(
+            env.mt_contract_balance_of(env.defuse.id(), other_user.id(), &ft_id.to_string())
+                .await
+                .unwrap(),
+            100
+        );
+    }
+
+    // cancelled by a signed intent with the same nonce, so that signers
+    // which can't call the contract directly can cancel it as well
+    {
+        let deadline = Deadline::timeout(std::time::Duration::from_secs(120));
+        let nonce = user
+            .unique_nonce(env.defuse.id(), Some(deadline))
+            .await
+            .unwrap();
+        let sign = |intents: Vec<Intent>| {
+            user.sign_defuse_message(
+                SigningStandard::default(),
+                env.defuse.id(),
+                nonce,
+                deadline,
+                DefuseIntents { intents },
+            )
+        };
+        let id = relayer
+            .store_signed_intent(
+                env.defuse.id(),
+                sign(vec![
+                    Transfer {
+                        receiver_id: other_user.id().clone(),
+                        tokens: Amounts::new([(ft_id.clone(), 100)].into()),
+                        memo: None,
+                        notification: None,
+                    }
+                    .into(),
+                ]),
+            )
+            .await
+            .unwrap();
+
+        env.defuse
+            .execute_intents(env.defuse.id(), [sign(Vec::new())])
+            .await
+            .unwrap();
+
+        // anyone can clean it up once the nonce is used
+        other_relayer
+            .cancel_stored_intent(env.defuse.id(), id)
+            .await
+            .unwrap();
+        assert!(
+            env.defuse
+                .stored_intent(env.defuse.id(), id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(
+            env.mt_contract_balance_of(env.defuse.id(), other_user.id(), &ft_id.to_string())
+                .await
+                .unwrap(),
+            100
+        );
+    }
+}
+
+#[tokio::test]
+#[rstest]
+async fn stored_order_book() {
+    let env = Env::new().await;
+
+    let (user1, user2, user3, relayer) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_user()
+    );
+    let ft1 = TokenId::from(Nep141TokenId::new(env.create_token().await));
+    let ft2 = TokenId::from(Nep141TokenId::new(env.create_token().await));
+    let ft3 = TokenId::from(Nep141TokenId::new(env.create_token().await));
+
+    let store_diff = async |user: &near_workspaces::Account, diff: &[(&TokenId, i128)]| {
+        relayer
+            .store_signed_intent(
+                env.defuse.id(),
+                user.sign_defuse_payload_default(
+                    env.defuse.id(),
+                    [TokenDiff {
+                        diff: TokenDeltas::new(
+                            diff.iter()
+                                .map(|(token_id, delta)| ((*token_id).clone(), *delta))
+                                .collect(),
+                        ),
+                        memo: None,
+                        referral: None,
+                        max_fee: None,
+                        solver: None,
+                        partial_fill: false,
+                    }],
+                )
+                .await
+                .unwrap(),
+            )
+            .await
+            .unwrap()
+    };
+
+    let sell1 = store_diff(&user1, &[(&ft1, -100), (&ft2, 200)]).await;
+    let sell2 = store_diff(&user2, &[(&ft1, -50), (&ft2, 120)]).await;
+    let buy = store_diff(&user3, &[(&ft2, -300), (&ft1, 100)]).await;
+    // neither is an order on a single token pair
+    store_diff(&user1, &[(&ft1, -100), (&ft2, 100), (&ft3, 100)]).await;
+    relayer
+        .store_signed_intent(
+            env.defuse.id(),
+            user2
+                .sign_defuse_payload_default(
+                    env.defuse.id(),
+                    [Transfer {
+                        receiver_id: user3.id().clone(),
+                        tokens: Amounts::new([(ft1.clone(), 100)].into()),
+                        memo: None,
+                        notification: None,
+                    }],
+                )
+                .await
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let order = |id, user: &near_workspaces::Account, amount_in: u128, amount_out: u128| {
+        (
+            id,
+            user.id().clone(),
+            U128(amount_in),
+            U128(amount_out),
+            U128(amount_in),
+        )
+    };
+    let order_book = async |from_id, limit| {
+        let mut pairs: Vec<_> = env
+            .defuse
+            .stored_order_book(env.defuse.id(), from_id, limit)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|pair| {
+                (
+                    pair.token_in,
+                    pair.token_out,
+                    pair.orders
+                        .into_iter()
+                        .map(|order| {
+                            (
+                                order.id,
+                                order.signer_id,
+                                order.amount_in,
+                                order.amount_out,
+                                order.remaining_in,
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+        pairs.sort();
+        pairs
+    };
+
+    let mut expected = vec![
+        (
+            ft1.clone(),
+            ft2.clone(),
+            vec![
+                order(sell1, &user1, 100, 200),
+                order(sell2, &user2, 50, 120),
+            ],
+        ),
+        (ft2.clone(), ft1.clone(), vec![order(buy, &user3, 300, 100)]),
+    ];
+    expected.sort();
+    assert_eq!(order_book(sell1, 10).await, expected);
+
+    // paginated by ids
+    assert_eq!(
+        order_book(sell1, 1).await,
+        [(
+            ft1.clone(),
+            ft2.clone(),
+            vec![order(sell1, &user1, 100, 200)]
+        )]
+    );
+    assert_eq!(
+        order_book(buy, 10).await,
+        [(ft2.clone(), ft1.clone(), vec![order(buy, &user3, 300, 100)])]
+    );
+    env.defuse
+        .stored_order_book(env.defuse.id(), 0, MAX_STORED_INTENTS_PER_QUERY + 1)
+        .await
+        .assert_err_contains("limit is too large");
+
+    // cancelled orders are not open anymore
+    user1
+        .cancel_stored_intent(env.defuse.id(), sell1)
+        .await
+        .unwrap();
+    assert_eq!(
+        order_book(sell1, 2).await,
+        [(
+            ft1.clone(),
+            ft2.clone(),
+            vec![order(sell2, &user2, 50, 120)]
+        )]
+    );
+}
+
+pub trait StoredIntentsExt {
+    async fn store_signed_intent(
+        &self,
+        defuse_contract_id: &AccountId,
+        signed: MultiPayload,
+    ) -> anyhow::Result<u64>;
+
+    async fn stored_intent(
+        &self,
+        defuse_contract_id: &AccountId,
+        id: u64,
+    ) -> anyhow::Result<Option<StoredIntent>>;
+
+    async fn execute_stored_intent(
+        &self,
+        defuse_contract_id: &AccountId,
+        id: u64,
+    ) -> anyhow::Result<()>;
+
+    async fn cancel_stored_intent(
+        &self,
+        defuse_contract_id: &AccountId,
+        id: u64,
+    ) -> anyhow::Result<()>;
+
+    async fn stored_order_book(
+        &self,
+        defuse_contract_id: &AccountId,
+        from_id: u64,
+        limit: u32,
+    ) -> anyhow::Result<Vec<OrderBookPair>>;
+}
+
+impl StoredIntentsExt for near_workspaces::Account {
+    async fn store_signed_intent(
+        &self,
+        defuse_contract_id: &AccountId,
+        signed: MultiPayload,
+    ) -> anyhow::Result<u64> {
+        self.call(defuse_contract_id, "store_signed_intent")
+            .args_json(json!({
+                "signed": signed,
+            }))
+            .deposit(NearToken::from_near(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn stored_intent(
+        &self,
+        defuse_contract_id: &AccountId,
+        id: u64,
+    ) -> anyhow::Result<Option<StoredIntent>> {
+        self.view(defuse_contract_id, "stored_intent")
+            .args_json(json!({
+                "id": id,
+            }))
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn execute_stored_intent(
+        &self,
+        defuse_contract_id: &AccountId,
+        id: u64,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "execute_stored_intent")
+            .args_json(json!({
+                "id": id,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    async fn cancel_stored_intent(
+        &self,
+        defuse_contract_id: &AccountId,
+        id: u64,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "cancel_stored_intent")
+            .args_json(json!({
+                "id": id,
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    async fn stored_order_book(
+        &self,
+        defuse_contract_id: &AccountId,
+        from_id: u64,
+        limit: u32,
+    ) -> anyhow::Result<Vec<OrderBookPair>> {
+        self.view(defuse_contract_id, "stored_order_book")
+            .args_json(json!({
+                "from_id": from_id,
+                "limit": limit,
+            }))
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+}
+
+impl StoredIntentsExt for near_workspaces::Contract {
+    async fn store_signed_intent(
+        &self,
+        defuse_contract_id: &AccountId,
+        signed: MultiPayload,
+    ) -> anyhow::Result<u64> {
+        self.as_account()
+            .store_signed_intent(defuse_contract_id, signed)
+            .await
+    }
+
+    async fn stored_intent(
+        &self,
+        defuse_contract_id: &AccountId,
+        id: u64,
+    ) -> anyhow::Result<Option<StoredIntent>> {
+        self.as_account()
+            .stored_intent(defuse_contract_id, id)
+            .await
+    }
+
+    async fn execute_stored_intent(
+        &self,
+        defuse_contract_id: &AccountId,
+        id: u64,
+    ) -> anyhow::Result<()> {
+        self.as_account()
+            .execute_stored_intent(defuse_contract_id, id)
+            .await
+    }
+
+    async fn cancel_stored_intent(
+        &self,
+        defuse_contract_id: &AccountId,
+        id: u64,
+    ) -> anyhow::Result<()> {
+        self.as_account()
+            .cancel_stored_intent(defuse_contract_id, id)
+            .await
+    }
+
+    async fn stored_order_book(
+        &self,
+        defuse_contract_id: &AccountId,
+        from_id: u64,
+        limit: u32,
+    ) -> anyhow::Result<Vec<OrderBookPair>> {
+        self.as_account()
+            .stored_order_book(defuse_contract_id, from_id, limit)
+            .await
+    }
+}
@@ -0,0 +1,100 @@
+use defuse::core::{
+    DefuseError,
+    amounts::Amounts,
+    intents::template::{TransferTemplate, recipients_root},
+    payload::{multi::MultiPayload, template::SignedTemplatePayload},
+    token_id::{TokenId, nep141::Nep141TokenId},
+};
+use defuse_test_utils::asserts::ResultAssertsExt;
+use near_sdk::AccountId;
+use rstest::rstest;
+
+use super::ExecuteIntentsExt;
+use crate::{
+    tests::defuse::{DefuseSignerExt, env::Env},
+    utils::mt::MtExt,
+};
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn transfer_template_to_multiple_recipients() {
+    let env = Env::builder().build().await;
+
+    let (user, ft) = futures::join!(env.create_user(), env.create_token());
+    let token_id = TokenId::from(Nep141TokenId::new(ft.clone()));
+
+    env.initial_ft_storage_deposit(vec![user.id()], vec![&ft])
+        .await;
+
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    let recipients: Vec<AccountId> = ["alice.near", "bob.near", "carol.near"]
+        .into_iter()
+        .map(|id| id.parse().unwrap())
+        .collect();
+
+    let signed = user
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            [TransferTemplate {
+                recipients_root: recipients_root(&recipients),
+                tokens: Amounts::new([(token_id.clone(), 100)].into()),
+                memo: Some("airdrop".to_string()),
+            }],
+        )
+        .await
+        .unwrap();
+
+    // recipients are not covered by the signature directly, but have
+    // to match the signed Merkle root
+    {
+        let mut tampered = recipients.clone();
+        tampered[1] = "mallory.near".parse().unwrap();
+
+        env.defuse
+            .execute_intents(
+                env.defuse.id(),
+                [MultiPayload::Template(SignedTemplatePayload {
+                    signed: Box::new(signed.clone()),
+                    recipients: tampered,
+                })],
+            )
+            .await
+            .assert_err_contains(DefuseError::InvalidSignature.to_string());
+    }
+
+    // templates can't be executed without being instantiated
+    env.defuse
+        .execute_intents(env.defuse.id(), [signed.clone()])
+        .await
+        .assert_err_contains(DefuseError::InvalidIntent.to_string());
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [MultiPayload::Template(SignedTemplatePayload {
+                signed: Box::new(signed),
+                recipients: recipients.clone(),
+            })],
+        )
+        .await
+        .unwrap();
+
+    for recipient in &recipients {
+        assert_eq!(
+            env.mt_contract_balance_of(env.defuse.id(), recipient, &token_id.to_string())
+                .await
+                .unwrap(),
+            100
+        );
+    }
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), user.id(), &token_id.to_string())
+            .await
+            .unwrap(),
+        700
+    );
+}
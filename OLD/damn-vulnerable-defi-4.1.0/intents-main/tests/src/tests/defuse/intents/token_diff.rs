@@ -1,12 +1,15 @@
 use crate::{
-    tests::defuse::{DefuseSignerExt, env::Env},
-    utils::mt::MtExt,
+    tests::defuse::{DefuseSignerExt, env::Env, state::FeesManagerExt},
+    utils::{acl::AclExt, mt::MtExt},
 };
+use defuse::contract::Role;
 use defuse::core::token_id::{TokenId, nep141::Nep141TokenId};
 use defuse::core::{
+    DefuseError,
     fees::Pips,
     intents::token_diff::{TokenDeltas, TokenDiff},
 };
+use defuse_test_utils::asserts::ResultAssertsExt;
 use near_sdk::AccountId;
 use near_workspaces::Account;
 use rstest::rstest;
@@ -230,6 +233,9 @@ async fn test_ft_diffs(env: &Env, accounts: Vec<AccountFtDiff<'_>>) {
                     diff,
                     memo: None,
                     referral: None,
+                    max_fee: None,
+                    solver: None,
+                    partial_fill: false,
                 }],
             )
         })
@@ -272,6 +278,101 @@ async fn test_ft_diffs(env: &Env, accounts: Vec<AccountFtDiff<'_>>) {
     }
 }
 
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn check_batch_consistency(#[values(Pips::ZERO, Pips::ONE_PERCENT)] fee: Pips) {
+    let env = Env::builder().fee(fee).build().await;
+
+    let (user1, user2, ft1, ft2) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_token(),
+        env.create_token(),
+    );
+
+    let ft1_token_id = TokenId::from(Nep141TokenId::new(ft1.clone()));
+    let ft2_token_id = TokenId::from(Nep141TokenId::new(ft2.clone()));
+
+    let token_diff = |deltas: [(&TokenId, i128); 2]| TokenDiff {
+        diff: TokenDeltas::default()
+            .with_apply_deltas(deltas.map(|(token_id, delta)| (token_id.clone(), delta)))
+            .unwrap(),
+        memo: None,
+        referral: None,
+        max_fee: None,
+        solver: None,
+        partial_fill: false,
+    };
+
+    // balances are not checked, so nothing has to be deposited
+    let signed1 = user1
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            [token_diff([(&ft1_token_id, -1000), (&ft2_token_id, 2000)])],
+        )
+        .await
+        .unwrap();
+
+    // balanced batch
+    {
+        let signed2 = user2
+            .sign_defuse_payload_default(
+                env.defuse.id(),
+                [token_diff([
+                    (
+                        &ft1_token_id,
+                        TokenDiff::closure_delta(&ft1_token_id, -1000, fee).unwrap(),
+                    ),
+                    (
+                        &ft2_token_id,
+                        TokenDiff::closure_delta(&ft2_token_id, 2000, fee).unwrap(),
+                    ),
+                ])],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            env.defuse
+                .check_batch_consistency([signed1.clone(), signed2])
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    // imbalanced batch returns the residual
+    {
+        let signed2 = user2
+            .sign_defuse_payload_default(
+                env.defuse.id(),
+                [token_diff([
+                    (
+                        &ft1_token_id,
+                        TokenDiff::closure_delta(&ft1_token_id, -1000, fee).unwrap() + 1,
+                    ),
+                    (
+                        &ft2_token_id,
+                        TokenDiff::closure_delta(&ft2_token_id, 2000, fee).unwrap(),
+                    ),
+                ])],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            env.defuse
+                .check_batch_consistency([signed1, signed2])
+                .await
+                .unwrap()
+                .unwrap()
+                .into_unmatched_deltas(),
+            Some(TokenDeltas::new([(ft1_token_id, 1)].into()))
+        );
+    }
+}
+
 #[tokio::test]
 #[rstest]
 #[trace]
@@ -313,6 +414,9 @@ async fn invariant_violated(#[values(false, true)] no_registration: bool) {
                     .unwrap(),
                 memo: None,
                 referral: None,
+                max_fee: None,
+                solver: None,
+                partial_fill: false,
             }],
         ),
         user1.sign_defuse_payload_default(
@@ -326,6 +430,9 @@ async fn invariant_violated(#[values(false, true)] no_registration: bool) {
                     .unwrap(),
                 memo: None,
                 referral: None,
+                max_fee: None,
+                solver: None,
+                partial_fill: false,
             }],
         ),
     ])
@@ -435,6 +542,9 @@ async fn solver_user_closure(
                 ),
                 memo: None,
                 referral: None,
+                max_fee: None,
+                solver: None,
+                partial_fill: false,
             }],
         )
         .await
@@ -490,6 +600,9 @@ async fn solver_user_closure(
                 ),
                 memo: None,
                 referral: None,
+                max_fee: None,
+                solver: None,
+                partial_fill: false,
             }],
         )
         .await
@@ -537,3 +650,685 @@ async fn solver_user_closure(
         ]
     );
 }
+
+#[rstest]
+#[tokio::test]
+#[trace]
+async fn quote_token_diff(#[values(Pips::ZERO, Pips::ONE_BIP, Pips::ONE_PERCENT)] fee: Pips) {
+    const USER_DELTA_IN: i128 = -1000;
+    const AMOUNT_IN: u128 = USER_DELTA_IN.unsigned_abs();
+
+    let env = Env::builder().fee(fee).build().await;
+
+    let (user, solver, ft1, ft2) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_token(),
+        env.create_token()
+    );
+
+    env.initial_ft_storage_deposit(vec![user.id(), solver.id()], vec![&ft1, &ft2])
+        .await;
+
+    futures::try_join!(
+        env.defuse_ft_deposit_to(&ft1, AMOUNT_IN, user.id()),
+        env.defuse_ft_deposit_to(&ft2, AMOUNT_IN, solver.id())
+    )
+    .expect("Failed to deposit tokens");
+
+    let token_in = TokenId::from(Nep141TokenId::new(ft1.clone()));
+    let token_out = TokenId::from(Nep141TokenId::new(ft2.clone()));
+
+    let user_delta_out = env
+        .defuse
+        .quote_token_diff(env.defuse.id(), &token_in, AMOUNT_IN, &token_out, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        user_delta_out,
+        TokenDiff::quote(&token_in, AMOUNT_IN, &token_out, fee).unwrap()
+    );
+
+    // solver fills at parity
+    let solver_delta_in = TokenDiff::closure_delta(&token_in, USER_DELTA_IN, fee).unwrap();
+
+    let (user_commitment, solver_commitment) = futures::join!(
+        user.sign_defuse_payload_default(
+            env.defuse.id(),
+            [TokenDiff {
+                diff: TokenDeltas::new(
+                    [
+                        (token_in.clone(), USER_DELTA_IN),
+                        (token_out.clone(), user_delta_out),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+                memo: None,
+                referral: None,
+                max_fee: None,
+                solver: None,
+                partial_fill: false,
+            }],
+        ),
+        solver.sign_defuse_payload_default(
+            env.defuse.id(),
+            [TokenDiff {
+                diff: TokenDeltas::new(
+                    [
+                        (token_in.clone(), solver_delta_in),
+                        (token_out.clone(), -solver_delta_in),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+                memo: None,
+                referral: None,
+                max_fee: None,
+                solver: None,
+                partial_fill: false,
+            }],
+        ),
+    );
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [solver_commitment.unwrap(), user_commitment.unwrap()],
+        )
+        .await
+        .unwrap();
+
+    // realized output matches the quote
+    assert_eq!(
+        env.mt_contract_batch_balance_of(
+            env.defuse.id(),
+            user.id(),
+            [&token_in.to_string(), &token_out.to_string()]
+        )
+        .await
+        .unwrap(),
+        [0, user_delta_out.unsigned_abs()]
+    );
+}
+
+#[rstest]
+#[tokio::test]
+#[trace]
+async fn partial_fill(#[values(Pips::ZERO, Pips::ONE_PERCENT)] fee: Pips) {
+    use crate::{tests::defuse::accounts::AccountManagerExt, utils::payload::ExtractNonceExt};
+
+    let env = Env::builder().fee(fee).build().await;
+
+    let (user, solver, ft1, ft2) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_token(),
+        env.create_token()
+    );
+
+    env.initial_ft_storage_deposit(vec![user.id(), solver.id()], vec![&ft1, &ft2])
+        .await;
+
+    futures::try_join!(
+        env.defuse_ft_deposit_to(&ft1, 1000, user.id()),
+        env.defuse_ft_deposit_to(&ft2, 3000, solver.id())
+    )
+    .expect("Failed to deposit tokens");
+
+    let token_in = TokenId::from(Nep141TokenId::new(ft1.clone()));
+    let token_out = TokenId::from(Nep141TokenId::new(ft2.clone()));
+
+    // user is willing to sell up to 1000 token_in for 2000 token_out
+    let user_commitment = user
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            [TokenDiff {
+                diff: TokenDeltas::new(
+                    [(token_in.clone(), -1000), (token_out.clone(), 2000)]
+                        .into_iter()
+                        .collect(),
+                ),
+                memo: None,
+                referral: None,
+                max_fee: None,
+                solver: None,
+                partial_fill: true,
+            }],
+        )
+        .await
+        .unwrap();
+    let nonce = user_commitment.extract_nonce().unwrap();
+
+    let solver_fill = |amount_in: i128| {
+        let (token_in, token_out) = (token_in.clone(), token_out.clone());
+        let solver = solver.clone();
+        let defuse_id = env.defuse.id().clone();
+        async move {
+            solver
+                .sign_defuse_payload_default(
+                    &defuse_id,
+                    [TokenDiff {
+                        diff: TokenDeltas::new(
+                            [
+                                (
+                                    token_in.clone(),
+                                    TokenDiff::closure_delta(&token_in, -amount_in, fee).unwrap(),
+                                ),
+                                (
+                                    token_out.clone(),
+                                    TokenDiff::closure_delta(&token_out, amount_in * 2, fee)
+                                        .unwrap(),
+                                ),
+                            ]
+                            .into_iter()
+                            .collect(),
+                        ),
+                        memo: None,
+                        referral: None,
+                        max_fee: None,
+                        solver: None,
+                        partial_fill: false,
+                    }],
+                )
+                .await
+                .unwrap()
+        }
+    };
+
+    // smaller counter-intent fills 300 out of 1000
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user_commitment.clone(), solver_fill(300).await],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        env.mt_contract_batch_balance_of(
+            env.defuse.id(),
+            user.id(),
+            [&token_in.to_string(), &token_out.to_string()]
+        )
+        .await
+        .unwrap(),
+        [700, 600]
+    );
+    assert!(
+        !env.defuse.is_nonce_used(user.id(), &nonce).await.unwrap(),
+        "nonce should remain available for the rest"
+    );
+
+    // the rest can't be overfilled
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user_commitment.clone(), solver_fill(701).await],
+        )
+        .await
+        .unwrap_err();
+
+    // fill the rest
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user_commitment.clone(), solver_fill(700).await],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        env.mt_contract_batch_balance_of(
+            env.defuse.id(),
+            user.id(),
+            [&token_in.to_string(), &token_out.to_string()]
+        )
+        .await
+        .unwrap(),
+        [0, 2000]
+    );
+    assert!(env.defuse.is_nonce_used(user.id(), &nonce).await.unwrap());
+
+    env.defuse
+        .execute_intents(env.defuse.id(), [user_commitment, solver_fill(100).await])
+        .await
+        .assert_err_contains("nonce was already used");
+}
+
+#[tokio::test]
+#[rstest]
+async fn max_fee_exceeded() {
+    let env = Env::builder()
+        .fee(Pips::ONE_BIP)
+        .deployer_as_super_admin()
+        .build()
+        .await;
+
+    let (user1, fees_manager, ft1, ft2) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_token(),
+        env.create_token(),
+    );
+
+    let ft1_token_id = TokenId::from(Nep141TokenId::new(ft1.clone()));
+    let ft2_token_id = TokenId::from(Nep141TokenId::new(ft2.clone()));
+
+    env.initial_ft_storage_deposit(vec![user1.id()], vec![&ft1, &ft2])
+        .await;
+
+    env.defuse_ft_deposit_to(&ft1, 1000, user1.id())
+        .await
+        .expect("Failed to deposit tokens");
+
+    // user1 agrees to pay at most the current fee
+    let signed = user1
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            [TokenDiff {
+                diff: TokenDeltas::default()
+                    .with_apply_deltas([
+                        (ft1_token_id.clone(), -1000),
+                        (ft2_token_id.clone(), 2000),
+                    ])
+                    .unwrap(),
+                memo: None,
+                referral: None,
+                max_fee: Some(Pips::ONE_BIP),
+                solver: None,
+                partial_fill: false,
+            }],
+        )
+        .await
+        .unwrap();
+
+    // fee is raised between signing and execution
+    env.acl_grant_role(env.defuse.id(), Role::FeesManager, fees_manager.id())
+        .await
+        .unwrap();
+    fees_manager
+        .set_fee(env.defuse.id(), Pips::ONE_PERCENT)
+        .await
+        .unwrap();
+
+    env.defuse
+        .execute_intents(env.defuse.id(), [signed])
+        .await
+        .assert_err_contains(DefuseError::MaxFeeExceeded.to_string());
+
+    assert_eq!(
+        env.mt_contract_batch_balance_of(
+            env.defuse.id(),
+            user1.id(),
+            [&ft1_token_id.to_string(), &ft2_token_id.to_string()]
+        )
+        .await
+        .unwrap(),
+        [1000, 0]
+    );
+}
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn solver_binding() {
+    let env = Env::builder().build().await;
+
+    let (user, solver, third_party, ft1, ft2) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_token(),
+        env.create_token(),
+    );
+
+    let ft1_token_id = TokenId::from(Nep141TokenId::new(ft1.clone()));
+    let ft2_token_id = TokenId::from(Nep141TokenId::new(ft2.clone()));
+
+    env.initial_ft_storage_deposit(
+        vec![user.id(), solver.id(), third_party.id()],
+        vec![&ft1, &ft2],
+    )
+    .await;
+
+    futures::try_join!(
+        env.defuse_ft_deposit_to(&ft1, 1000, user.id()),
+        env.defuse_ft_deposit_to(&ft2, 2000, solver.id()),
+        env.defuse_ft_deposit_to(&ft2, 2000, third_party.id()),
+    )
+    .expect("Failed to deposit tokens");
+
+    // user binds the swap to the solver
+    let user_commitment = user
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            [TokenDiff {
+                diff: TokenDeltas::default()
+                    .with_apply_deltas([
+                        (ft1_token_id.clone(), -1000),
+                        (ft2_token_id.clone(), 2000),
+                    ])
+                    .unwrap(),
+                memo: None,
+                referral: None,
+                max_fee: None,
+                solver: Some(solver.id().clone()),
+                partial_fill: false,
+            }],
+        )
+        .await
+        .unwrap();
+
+    let counter_commitment = async |account: &Account| {
+        account
+            .sign_defuse_payload_default(
+                env.defuse.id(),
+                [TokenDiff {
+                    diff: TokenDeltas::default()
+                        .with_apply_deltas([
+                            (ft1_token_id.clone(), 1000),
+                            (ft2_token_id.clone(), -2000),
+                        ])
+                        .unwrap(),
+                    memo: None,
+                    referral: None,
+                    max_fee: None,
+                    solver: None,
+                    partial_fill: false,
+                }],
+            )
+            .await
+            .unwrap()
+    };
+
+    // third party can't match user's intent
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [
+                counter_commitment(&third_party).await,
+                user_commitment.clone(),
+            ],
+        )
+        .await
+        .assert_err_contains(DefuseError::SolverNotMatched(solver.id().clone()).to_string());
+
+    // neither can it be matched by the solver signing an unrelated diff
+    // while the third party actually takes the other side
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [
+                counter_commitment(&third_party).await,
+                user_commitment.clone(),
+                solver
+                    .sign_defuse_payload_default(
+                        env.defuse.id(),
+                        [TokenDiff {
+                            diff: TokenDeltas::default()
+                                .with_apply_deltas([(ft2_token_id.clone(), -1)])
+                                .unwrap(),
+                            memo: None,
+                            referral: None,
+                            max_fee: None,
+                            solver: None,
+                            partial_fill: false,
+                        }],
+                    )
+                    .await
+                    .unwrap(),
+            ],
+        )
+        .await
+        .assert_err_contains(DefuseError::SolverNotMatched(solver.id().clone()).to_string());
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [counter_commitment(&solver).await, user_commitment],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        env.mt_contract_batch_balance_of(
+            env.defuse.id(),
+            user.id(),
+            [&ft1_token_id.to_string(), &ft2_token_id.to_string()]
+        )
+        .await
+        .unwrap(),
+        [0, 2000]
+    );
+    assert_eq!(
+        env.mt_contract_batch_balance_of(
+            env.defuse.id(),
+            third_party.id(),
+            [&ft1_token_id.to_string(), &ft2_token_id.to_string()]
+        )
+        .await
+        .unwrap(),
+        [0, 2000]
+    );
+}
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn collected_fees() {
+    const SWAPS: u128 = 3;
+
+    let fee = Pips::ONE_PERCENT;
+    let env = Env::builder().fee(fee).build().await;
+
+    let (user1, user2, ft1, ft2) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_token(),
+        env.create_token(),
+    );
+
+    let ft1_token_id = TokenId::from(Nep141TokenId::new(ft1.clone()));
+    let ft2_token_id = TokenId::from(Nep141TokenId::new(ft2.clone()));
+
+    env.initial_ft_storage_deposit(vec![user1.id(), user2.id()], vec![&ft1, &ft2])
+        .await;
+
+    futures::try_join!(
+        env.defuse_ft_deposit_to(&ft1, 1000, user1.id()),
+        env.defuse_ft_deposit_to(&ft2, 2000, user2.id()),
+    )
+    .expect("Failed to deposit tokens");
+
+    assert!(
+        env.defuse
+            .collected_fees(env.defuse.id(), None, 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let token_diff = |deltas: [(&TokenId, i128); 2]| TokenDiff {
+        diff: TokenDeltas::default()
+            .with_apply_deltas(
+                deltas
+                    .into_iter()
+                    .map(|(token_id, delta)| (token_id.clone(), delta)),
+            )
+            .unwrap(),
+        memo: None,
+        referral: None,
+        max_fee: None,
+        solver: None,
+        partial_fill: false,
+    };
+
+    let user1_delta_out = TokenDiff::closure_delta(&ft2_token_id, -200, fee).unwrap();
+    let user2_delta_out = TokenDiff::closure_delta(&ft1_token_id, -100, fee).unwrap();
+    for _ in 0..SWAPS {
+        let signed1 = user1
+            .sign_defuse_payload_default(
+                env.defuse.id(),
+                [token_diff([
+                    (&ft1_token_id, -100),
+                    (&ft2_token_id, user1_delta_out),
+                ])],
+            )
+            .await
+            .unwrap();
+        let signed2 = user2
+            .sign_defuse_payload_default(
+                env.defuse.id(),
+                [token_diff([
+                    (&ft1_token_id, user2_delta_out),
+                    (&ft2_token_id, -200),
+                ])],
+            )
+            .await
+            .unwrap();
+
+        env.defuse
+            .execute_intents(env.defuse.id(), [signed1, signed2])
+            .await
+            .unwrap();
+    }
+
+    // whatever wasn't received by the counterparty went to fee collector
+    let expected: BTreeMap<_, _> = [
+        (
+            ft1_token_id.clone(),
+            SWAPS * (100 - user2_delta_out.unsigned_abs()),
+        ),
+        (
+            ft2_token_id.clone(),
+            SWAPS * (200 - user1_delta_out.unsigned_abs()),
+        ),
+    ]
+    .into();
+    assert!(expected.values().all(|fee| *fee > 0));
+
+    // paginated
+    let first_page = env
+        .defuse
+        .collected_fees(env.defuse.id(), None, 1)
+        .await
+        .unwrap();
+    assert_eq!(first_page.len(), 1);
+    let second_page = env
+        .defuse
+        .collected_fees(env.defuse.id(), Some(&first_page[0].0), 10)
+        .await
+        .unwrap();
+    assert_eq!(
+        first_page
+            .into_iter()
+            .chain(second_page)
+            .collect::<BTreeMap<_, _>>(),
+        expected
+    );
+}
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn strict_referrals() {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (user1, user2, referral, ft1, ft2) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_token(),
+        env.create_token(),
+    );
+    let unknown_referral: AccountId = "unknown-referral.near".parse().unwrap();
+
+    let ft1_token_id = TokenId::from(Nep141TokenId::new(ft1.clone()));
+    let ft2_token_id = TokenId::from(Nep141TokenId::new(ft2.clone()));
+
+    env.initial_ft_storage_deposit(vec![user1.id(), user2.id()], vec![&ft1, &ft2])
+        .await;
+
+    futures::try_join!(
+        env.defuse_ft_deposit_to(&ft1, 1000, user1.id()),
+        env.defuse_ft_deposit_to(&ft2, 1000, user2.id()),
+    )
+    .expect("Failed to deposit tokens");
+
+    let token_diff = |deltas: [(&TokenId, i128); 2], referral: Option<AccountId>| TokenDiff {
+        diff: TokenDeltas::default()
+            .with_apply_deltas(
+                deltas
+                    .into_iter()
+                    .map(|(token_id, delta)| (token_id.clone(), delta)),
+            )
+            .unwrap(),
+        memo: None,
+        referral,
+        max_fee: None,
+        solver: None,
+        partial_fill: false,
+    };
+
+    let swap = async |referral: &AccountId| {
+        let signed1 = user1
+            .sign_defuse_payload_default(
+                env.defuse.id(),
+                [token_diff(
+                    [(&ft1_token_id, -100), (&ft2_token_id, 100)],
+                    Some(referral.clone()),
+                )],
+            )
+            .await
+            .unwrap();
+        let signed2 = user2
+            .sign_defuse_payload_default(
+                env.defuse.id(),
+                [token_diff(
+                    [(&ft1_token_id, 100), (&ft2_token_id, -100)],
+                    None,
+                )],
+            )
+            .await
+            .unwrap();
+
+        env.defuse
+            .execute_intents(env.defuse.id(), [signed1, signed2])
+            .await
+    };
+
+    // any referral is accepted in lenient mode
+    assert!(!env.defuse.strict_referrals(env.defuse.id()).await.unwrap());
+    swap(&unknown_referral).await.unwrap();
+
+    env.set_strict_referrals(env.defuse.id(), true)
+        .await
+        .unwrap();
+    assert!(env.defuse.strict_referrals(env.defuse.id()).await.unwrap());
+
+    // registered accounts are accepted in strict mode
+    swap(referral.id()).await.unwrap();
+
+    // unknown accounts are rejected in strict mode
+    swap(&unknown_referral)
+        .await
+        .assert_err_contains(DefuseError::UnknownReferral(unknown_referral.clone()).to_string());
+
+    // unless they are allowlisted
+    env.add_allowed_referrals(env.defuse.id(), &[&unknown_referral])
+        .await
+        .unwrap();
+    swap(&unknown_referral).await.unwrap();
+
+    assert_eq!(
+        env.mt_contract_batch_balance_of(
+            env.defuse.id(),
+            user1.id(),
+            [&ft1_token_id.to_string(), &ft2_token_id.to_string()]
+        )
+        .await
+        .unwrap(),
+        [700, 300]
+    );
+}
@@ -1,23 +1,46 @@
 use super::ExecuteIntentsExt;
 use crate::tests::defuse::DefuseExt;
+use crate::tests::defuse::accounts::AccountManagerExt;
+use crate::tests::defuse::env::get_account_public_key;
+use crate::tests::defuse::tokens::nep245::traits::DefuseMtOnTransferGas;
 use crate::{
     tests::defuse::env::{Env, TransferCallExpectation},
-    utils::{ft::FtExt, mt::MtExt},
+    utils::{events::CallWithEventsExt, ft::FtExt, mt::MtExt},
+};
+use defuse::core::DefuseError;
+use defuse::core::accounts::AccountEvent;
+use defuse::core::events::DefuseEvent;
+use defuse::core::intents::tokens::{
+    FtWithdraw, MtBatchTransfer, MtWithdraw, NotificationOutcome, NotificationResolvedEvent,
+    NotifyOnTransfer, Transfer,
 };
-use defuse::core::intents::tokens::{NotifyOnTransfer, Transfer};
 use defuse::core::token_id::nep245::Nep245TokenId;
 use defuse::core::token_id::{TokenId, nep141::Nep141TokenId};
 use defuse::{
     contract::config::{DefuseConfig, RolesConfig},
     core::fees::{FeesConfig, Pips},
+    intents::EventVerbosity,
 };
 use multi_token_receiver_stub::MTReceiverMode;
-use near_sdk::{AccountId, Gas};
+use near_sdk::{AccountId, Gas, NearToken};
 use rstest::rstest;
+use serde_json::json;
+use std::borrow::Cow;
 
 use defuse::core::amounts::Amounts;
 
-use crate::tests::defuse::DefuseSignerExt;
+use crate::tests::defuse::{DefuseSigner, DefuseSignerExt, SigningStandard};
+use defuse::core::{
+    Deadline,
+    crypto::PublicKey,
+    intents::{DefuseIntents, Intent},
+};
+use defuse_near_utils::NearSdkLog;
+use defuse_test_utils::asserts::ResultAssertsExt;
+use near_workspaces::{
+    Account,
+    types::{KeyType, SecretKey},
+};
 
 #[tokio::test]
 #[rstest]
@@ -71,6 +94,211 @@ async fn transfer_intent() {
     );
 }
 
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn self_transfer_is_rejected() {
+    let env = Env::builder().build().await;
+
+    let (user, ft) = futures::join!(env.create_user(), env.create_token());
+    let token_id = TokenId::from(Nep141TokenId::new(ft.clone()));
+
+    env.initial_ft_storage_deposit(vec![user.id()], vec![&ft])
+        .await;
+
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    let intents: [Intent; 3] = [
+        Transfer {
+            receiver_id: user.id().clone(),
+            tokens: Amounts::new([(token_id.clone(), 1000)].into()),
+            memo: None,
+            notification: None,
+        }
+        .into(),
+        FtWithdraw {
+            token: ft.clone(),
+            receiver_id: env.defuse.id().clone(),
+            amount: 1000.into(),
+            memo: None,
+            msg: None,
+            storage_deposit: None,
+            auto_storage_deposit: false,
+            min_gas: None,
+        }
+        .into(),
+        MtWithdraw {
+            token: "mt.near".parse().unwrap(),
+            receiver_id: user.id().clone(),
+            token_ids: vec!["1".to_string()],
+            amounts: vec![1000.into()],
+            receiver_ids: Some(vec![Some(env.defuse.id().clone())]),
+            memo: None,
+            msg: None,
+            storage_deposit: None,
+            min_gas: None,
+        }
+        .into(),
+    ];
+
+    for intent in intents {
+        let payload = user
+            .sign_defuse_payload_default(env.defuse.id(), [intent])
+            .await
+            .unwrap();
+
+        env.defuse
+            .execute_intents(env.defuse.id(), [payload])
+            .await
+            .assert_err_contains(DefuseError::SelfTransfer.to_string());
+    }
+
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), user.id(), &token_id.to_string())
+            .await
+            .unwrap(),
+        1000
+    );
+    assert_eq!(
+        env.ft_token_balance_of(&ft, env.defuse.id()).await.unwrap(),
+        1000
+    );
+}
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn transfer_intents_signed_with_mixed_standards() {
+    let env = Env::builder().build().await;
+
+    let (user, ft) = futures::join!(env.create_user(), env.create_token());
+    let token_id = TokenId::from(Nep141TokenId::new(ft.clone()));
+
+    // ERC-191 payloads are signed by secp256k1 keys, which are registered
+    // by default for corresponding implicit Ethereum accounts
+    let secret_key = SecretKey::from_random(KeyType::SECP256K1);
+    let eth_user = Account::from_secret_key(
+        PublicKey::Secp256k1(secret_key.public_key().key_data().try_into().unwrap())
+            .to_implicit_account_id(),
+        secret_key,
+        env.sandbox().worker(),
+    );
+
+    env.initial_ft_storage_deposit(vec![user.id(), eth_user.id()], vec![&ft])
+        .await;
+
+    for account_id in [user.id(), eth_user.id()] {
+        env.defuse_ft_deposit_to(&ft, 1000, account_id)
+            .await
+            .unwrap();
+    }
+
+    let transfer = |receiver_id: &AccountId, amount| Transfer {
+        receiver_id: receiver_id.clone(),
+        tokens: Amounts::new([(token_id.clone(), amount)].into()),
+        memo: None,
+        notification: None,
+    };
+
+    let deadline = Deadline::timeout(std::time::Duration::from_secs(120));
+    let eth_nonce = eth_user
+        .unique_nonce(env.defuse.id(), Some(deadline))
+        .await
+        .unwrap();
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [
+                user.sign_defuse_payload_default(env.defuse.id(), [transfer(eth_user.id(), 100)])
+                    .await
+                    .unwrap(),
+                eth_user.sign_defuse_message(
+                    SigningStandard::Erc191,
+                    env.defuse.id(),
+                    eth_nonce,
+                    deadline,
+                    DefuseIntents {
+                        intents: [transfer(user.id(), 300).into()].into(),
+                    },
+                ),
+            ],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), user.id(), &token_id.to_string())
+            .await
+            .unwrap(),
+        1200
+    );
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), eth_user.id(), &token_id.to_string())
+            .await
+            .unwrap(),
+        800
+    );
+}
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn transfer_events_observed_during_batch() {
+    let env = Env::builder().build().await;
+
+    let (user, ft) = futures::join!(env.create_user(), env.create_token());
+    let token_id = TokenId::from(Nep141TokenId::new(ft.clone()));
+
+    env.initial_ft_storage_deposit(vec![user.id()], vec![&ft])
+        .await;
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    let receivers: Vec<AccountId> = ["receiver1.near", "receiver2.near", "receiver3.near"]
+        .into_iter()
+        .map(|id| id.parse().unwrap())
+        .collect();
+    let payload = user
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            receivers.iter().map(|receiver_id| Transfer {
+                receiver_id: receiver_id.clone(),
+                tokens: Amounts::new([(token_id.clone(), 100)].into()),
+                memo: None,
+                notification: None,
+            }),
+        )
+        .await
+        .unwrap();
+
+    let mut transfers = Vec::new();
+    env.defuse
+        .call("execute_intents")
+        .args_json(json!({
+            "signed": [payload],
+        }))
+        .max_gas()
+        .transact_with_defuse_events(env.defuse.id(), |event| {
+            if let DefuseEvent::Transfer(events) = event {
+                transfers.extend(events.iter().map(|e| {
+                    assert_eq!(e.event.account_id.as_ref(), user.id());
+                    assert_eq!(e.event.event.tokens.amount_for(&token_id), 100);
+                    e.event.event.receiver_id.clone().into_owned()
+                }));
+            }
+        })
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+
+    assert_eq!(transfers, receivers);
+}
+
 #[tokio::test]
 #[rstest]
 #[trace]
@@ -90,6 +318,12 @@ async fn transfer_intent_to_defuse() {
                     fee_collector: env.id().clone(),
                 },
                 roles: RolesConfig::default(),
+                max_tokens_per_account: None,
+                event_verbosity: EventVerbosity::default(),
+                registration_reserve: None,
+                max_nonce_ttl: None,
+                require_bounded_deadline: false,
+                reject_legacy_nonces: false,
             },
             false,
         )
@@ -126,7 +360,7 @@ async fn transfer_intent_to_defuse() {
         env.defuse
             .execute_intents(env.defuse.id(), [transfer_payload])
             .await
-            .expect_err("Exceeded the prepaid gas");
+            .assert_err_contains("min_gas requested by intents 500.0 Tgas exceeds available gas");
     }
 
     // Should pass default gas limit in case of low gas
@@ -205,30 +439,35 @@ async fn transfer_intent_to_defuse() {
     intent_transfer_amount: Some(1_000),
     expected_sender_balance: 0,
     expected_receiver_balance: 1_000,
+    expected_outcome: NotificationOutcome::Accepted,
 })]
 #[case::partial_refund(TransferCallExpectation {
     mode: MTReceiverMode::ReturnValue(300.into()),
     intent_transfer_amount: Some(1_000),
     expected_sender_balance: 300,
     expected_receiver_balance: 700,
+    expected_outcome: NotificationOutcome::Refunded,
 })]
 #[case::malicious_refund(TransferCallExpectation {
     mode: MTReceiverMode::ReturnValue(2_000.into()),
     intent_transfer_amount: Some(1_000),
     expected_sender_balance: 1_000,
     expected_receiver_balance: 0,
+    expected_outcome: NotificationOutcome::OverRefunded,
 })]
 #[case::receiver_panics(TransferCallExpectation {
     mode: MTReceiverMode::Panic,
     intent_transfer_amount: Some(1_000),
     expected_sender_balance: 1000,
     expected_receiver_balance: 0,
+    expected_outcome: NotificationOutcome::Failed,
 })]
 #[case::malicious_receiver(TransferCallExpectation {
     mode: MTReceiverMode::LargeReturn,
     intent_transfer_amount: Some(1_000),
     expected_sender_balance: 1000,
     expected_receiver_balance: 0,
+    expected_outcome: NotificationOutcome::Failed,
 })]
 async fn transfer_intent_with_msg_to_receiver_smc(#[case] expectation: TransferCallExpectation) {
     let initial_amount = expectation
@@ -252,8 +491,6 @@ async fn transfer_intent_with_msg_to_receiver_smc(#[case] expectation: TransferC
 
     let ft1 = TokenId::from(Nep141TokenId::new(ft.clone()));
 
-    let msg = serde_json::to_string(&expectation.mode).unwrap();
-
     let transfer_intent = Transfer {
         receiver_id: mt_receiver.id().clone(),
         tokens: Amounts::new(
@@ -264,7 +501,7 @@ async fn transfer_intent_with_msg_to_receiver_smc(#[case] expectation: TransferC
             .collect(),
         ),
         memo: None,
-        notification: NotifyOnTransfer::new(msg).into(),
+        notification: NotifyOnTransfer::json(&expectation.mode).unwrap().into(),
     };
 
     let transfer_payload = user
@@ -272,11 +509,26 @@ async fn transfer_intent_with_msg_to_receiver_smc(#[case] expectation: TransferC
         .await
         .unwrap();
 
-    env.defuse
+    let result = env
+        .defuse
         .execute_intents(env.defuse.id(), [transfer_payload])
         .await
         .unwrap();
 
+    let notification_resolved_log = DefuseEvent::NotificationResolved(AccountEvent::new(
+        user.id(),
+        NotificationResolvedEvent {
+            receiver_id: Cow::Borrowed(mt_receiver.id()),
+            outcome: expectation.expected_outcome,
+        },
+    ))
+    .to_near_sdk_log();
+    assert!(
+        result.logs().contains(&notification_resolved_log),
+        "missing {notification_resolved_log} in {:?}",
+        result.logs(),
+    );
+
     assert_eq!(
         env.mt_contract_balance_of(env.defuse.id(), user.id(), &ft1.to_string())
             .await
@@ -291,3 +543,365 @@ async fn transfer_intent_with_msg_to_receiver_smc(#[case] expectation: TransferC
         expectation.expected_receiver_balance
     );
 }
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn transfer_intent_to_reentrant_receiver_smc() {
+    let env = Env::builder().build().await;
+
+    let (user, attacker, ft, mt_receiver) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_token(),
+        env.deploy_mt_receiver_stub()
+    );
+
+    env.initial_ft_storage_deposit(vec![user.id()], vec![&ft])
+        .await;
+
+    env.defuse_ft_deposit_to(&ft, 1_000, user.id())
+        .await
+        .unwrap();
+
+    let ft1 = TokenId::from(Nep141TokenId::new(ft.clone()));
+
+    // receiver tries to move received tokens away before they get refunded
+    let notification =
+        NotifyOnTransfer::json(&MTReceiverMode::Reenter(attacker.id().clone())).unwrap();
+
+    let transfer_payload = user
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            [Transfer {
+                receiver_id: mt_receiver.id().clone(),
+                tokens: Amounts::new(std::iter::once((ft1.clone(), 1_000)).collect()),
+                memo: None,
+                notification: notification.into(),
+            }],
+        )
+        .await
+        .unwrap();
+
+    env.defuse
+        .execute_intents(env.defuse.id(), [transfer_payload])
+        .await
+        .unwrap();
+
+    // receiver is free to move received tokens, but then the refund is
+    // capped at what's left on its balance, so nothing is minted
+    for (account_id, expected_balance) in [
+        (user.id(), 0),
+        (mt_receiver.id(), 0),
+        (attacker.id(), 1_000),
+    ] {
+        assert_eq!(
+            env.mt_contract_balance_of(env.defuse.id(), account_id, &ft1.to_string())
+                .await
+                .unwrap(),
+            expected_balance,
+        );
+    }
+}
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn reentrant_receiver_debit_caps_refund(
+    #[values("ft_withdraw", "execute_intents")] method_name: &str,
+) {
+    let env = Env::builder().build().await;
+
+    let (user, attacker, ft, mt_receiver) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_token(),
+        env.deploy_mt_receiver_stub()
+    );
+
+    env.initial_ft_storage_deposit(vec![user.id(), attacker.id()], vec![&ft])
+        .await;
+
+    env.defuse_ft_deposit_to(&ft, 1_000, user.id())
+        .await
+        .unwrap();
+
+    let ft1 = TokenId::from(Nep141TokenId::new(ft.clone()));
+
+    // receiver tries to move received tokens away with a withdrawal or
+    // an intent before they get refunded
+    let mode = match method_name {
+        "ft_withdraw" => MTReceiverMode::ReenterCall {
+            method_name: method_name.to_string(),
+            args: json!({
+                "token": ft,
+                "receiver_id": attacker.id(),
+                "amount": "1000",
+            }),
+            deposit: NearToken::from_yoctonear(1),
+        },
+        "execute_intents" => {
+            mt_receiver
+                .as_account()
+                .add_public_key(
+                    env.defuse.id(),
+                    get_account_public_key(mt_receiver.as_account()),
+                )
+                .await
+                .unwrap();
+            let payload = mt_receiver
+                .as_account()
+                .sign_defuse_payload_default(
+                    env.defuse.id(),
+                    [Transfer {
+                        receiver_id: attacker.id().clone(),
+                        tokens: Amounts::new([(ft1.clone(), 1_000)].into()),
+                        memo: None,
+                        notification: None,
+                    }],
+                )
+                .await
+                .unwrap();
+            MTReceiverMode::ReenterCall {
+                method_name: method_name.to_string(),
+                args: json!({ "signed": [payload] }),
+                deposit: NearToken::from_yoctonear(0),
+            }
+        }
+        _ => unreachable!(),
+    };
+
+    let transfer_payload = user
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            [Transfer {
+                receiver_id: mt_receiver.id().clone(),
+                tokens: Amounts::new([(ft1.clone(), 1_000)].into()),
+                memo: None,
+                notification: NotifyOnTransfer::json(&mode).unwrap().into(),
+            }],
+        )
+        .await
+        .unwrap();
+
+    env.defuse
+        .execute_intents(env.defuse.id(), [transfer_payload])
+        .await
+        .unwrap();
+
+    // refund is capped at what's left on the receiver's balance
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), user.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        0,
+    );
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), mt_receiver.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        0,
+    );
+    let attacker_balance = match method_name {
+        "ft_withdraw" => env.ft_token_balance_of(&ft, attacker.id()).await.unwrap(),
+        "execute_intents" => env
+            .mt_contract_balance_of(env.defuse.id(), attacker.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        _ => unreachable!(),
+    };
+    assert_eq!(attacker_balance, 1_000);
+}
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn mt_batch_transfer_with_per_token_notifications() {
+    let env = Env::builder().build().await;
+
+    let (user, ft1, ft2, receiver1, receiver2) = futures::join!(
+        env.create_user(),
+        env.create_token(),
+        env.create_token(),
+        env.deploy_named_mt_receiver_stub("mt_receiver_stub1"),
+        env.deploy_named_mt_receiver_stub("mt_receiver_stub2"),
+    );
+
+    env.initial_ft_storage_deposit(vec![user.id()], vec![&ft1, &ft2])
+        .await;
+
+    for ft in [&ft1, &ft2] {
+        env.defuse_ft_deposit_to(ft, 1_000, user.id())
+            .await
+            .unwrap();
+    }
+
+    let token1 = TokenId::from(Nep141TokenId::new(ft1.clone()));
+    let token2 = TokenId::from(Nep141TokenId::new(ft2.clone()));
+
+    let transfer_payload = user
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            [MtBatchTransfer {
+                receiver_id: receiver1.id().clone(),
+                token_ids: vec![token1.clone(), token2.clone()],
+                amounts: vec![1_000.into(), 1_000.into()],
+                receiver_ids: Some(vec![None, Some(receiver2.id().clone())]),
+                notifications: Some(vec![
+                    // receiver1 refunds a part of token1
+                    NotifyOnTransfer::json(&MTReceiverMode::ReturnValue(300.into()))
+                        .unwrap()
+                        .into(),
+                    // receiver2 refunds all of token2
+                    NotifyOnTransfer::json(&MTReceiverMode::Panic)
+                        .unwrap()
+                        .into(),
+                ]),
+                memo: None,
+            }],
+        )
+        .await
+        .unwrap();
+
+    env.defuse
+        .execute_intents(env.defuse.id(), [transfer_payload])
+        .await
+        .unwrap();
+
+    for (account_id, token_id, expected) in [
+        (user.id(), &token1, 300),
+        (user.id(), &token2, 1_000),
+        (receiver1.id(), &token1, 700),
+        (receiver1.id(), &token2, 0),
+        (receiver2.id(), &token1, 0),
+        (receiver2.id(), &token2, 0),
+    ] {
+        assert_eq!(
+            env.mt_contract_balance_of(env.defuse.id(), account_id, &token_id.to_string())
+                .await
+                .unwrap(),
+            expected,
+            "{account_id}: {token_id}",
+        );
+    }
+
+    // lengths of per-token fields have to match
+    let invalid_payload = user
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            [MtBatchTransfer {
+                receiver_id: receiver1.id().clone(),
+                token_ids: vec![token1.clone()],
+                amounts: vec![100.into()],
+                receiver_ids: None,
+                notifications: Some(vec![None, None]),
+                memo: None,
+            }],
+        )
+        .await
+        .unwrap();
+
+    env.defuse
+        .execute_intents(env.defuse.id(), [invalid_payload])
+        .await
+        .assert_err_contains(DefuseError::InvalidIntent.to_string());
+}
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn transfer_intent_mt_on_transfer_default_gas() {
+    const DEFAULT_GAS: Gas = Gas::from_tgas(150);
+
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (user, ft, mt_receiver) = futures::join!(
+        env.create_user(),
+        env.create_token(),
+        env.deploy_mt_receiver_stub()
+    );
+    let ft1 = TokenId::from(Nep141TokenId::new(ft.clone()));
+
+    env.initial_ft_storage_deposit(vec![user.id()], vec![&ft])
+        .await;
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        env.mt_on_transfer_default_gas(env.defuse.id())
+            .await
+            .unwrap(),
+        NotifyOnTransfer::MT_ON_TRANSFER_GAS_DEFAULT
+    );
+
+    user.set_mt_on_transfer_default_gas(env.defuse.id(), Some(DEFAULT_GAS))
+        .await
+        .expect_err("only DAO can set default gas");
+    env.set_mt_on_transfer_default_gas(env.defuse.id(), Some(Gas::from_tgas(1)))
+        .await
+        .assert_err_contains("gas is too low");
+    env.set_mt_on_transfer_default_gas(env.defuse.id(), Some(DEFAULT_GAS))
+        .await
+        .unwrap();
+    assert_eq!(
+        env.mt_on_transfer_default_gas(env.defuse.id())
+            .await
+            .unwrap(),
+        DEFAULT_GAS
+    );
+
+    let transfer = |amount| Transfer {
+        receiver_id: mt_receiver.id().clone(),
+        tokens: Amounts::new([(ft1.clone(), amount)].into()),
+        memo: None,
+        notification: NotifyOnTransfer::json(&MTReceiverMode::AcceptAll)
+            .unwrap()
+            .into(),
+    };
+    let execute = async |payload, gas: Gas| -> anyhow::Result<()> {
+        env.defuse
+            .call("execute_intents")
+            .args_json(json!({
+                "signed": [payload],
+            }))
+            .gas(gas)
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    };
+
+    // notification without `min_gas` is attached the configured gas,
+    // which doesn't fit into the prepaid gas
+    let payload = user
+        .sign_defuse_payload_default(env.defuse.id(), [transfer(100)])
+        .await
+        .unwrap();
+    execute(payload.clone(), Gas::from_tgas(100))
+        .await
+        .assert_err_contains("Exceeded the prepaid gas");
+
+    // explicit `min_gas` still takes precedence
+    let mut explicit = transfer(100);
+    explicit.notification = explicit
+        .notification
+        .map(|notification| notification.with_min_gas(Gas::from_tgas(30)));
+    execute(
+        user.sign_defuse_payload_default(env.defuse.id(), [explicit])
+            .await
+            .unwrap(),
+        Gas::from_tgas(100),
+    )
+    .await
+    .unwrap();
+
+    execute(payload, Gas::from_tgas(300)).await.unwrap();
+
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), mt_receiver.id(), &ft1.to_string())
+            .await
+            .unwrap(),
+        200
+    );
+}
@@ -2,6 +2,7 @@ pub mod accounts;
 mod env;
 mod garbage_collector;
 mod intents;
+mod pause;
 mod state;
 mod storage;
 mod tokens;
@@ -15,6 +16,7 @@ use defuse_randomness::RngCore;
 use self::accounts::AccountManagerExt;
 use crate::utils::{account::AccountExt, crypto::Signer, read_wasm};
 use arbitrary::{Arbitrary, Unstructured};
+use defuse::core::erc191::Erc191Payload;
 use defuse::core::intents::Intent;
 use defuse::core::payload::DefusePayload;
 use defuse::core::sep53::Sep53Payload;
@@ -38,6 +40,8 @@ use std::sync::LazyLock;
 static DEFUSE_WASM: LazyLock<Vec<u8>> = LazyLock::new(|| read_wasm("res/defuse"));
 static DEFUSE_LEGACY_WASM: LazyLock<Vec<u8>> =
     LazyLock::new(|| read_wasm("releases/defuse-0.2.10.wasm"));
+static DEFUSE_MOCK_TIME_WASM: LazyLock<Vec<u8>> =
+    LazyLock::new(|| read_wasm("res/defuse-mock-time/defuse"));
 
 pub trait DefuseExt: AccountManagerExt {
     async fn deploy_defuse(
@@ -47,6 +51,14 @@ pub trait DefuseExt: AccountManagerExt {
         legacy: bool,
     ) -> anyhow::Result<Contract>;
 
+    /// Deploys build with `mock_time` feature, which allows tests to
+    /// move the contract's clock forward
+    async fn deploy_defuse_mock_time(
+        &self,
+        id: &str,
+        config: DefuseConfig,
+    ) -> anyhow::Result<Contract>;
+
     async fn upgrade_defuse(&self, defuse_contract_id: &AccountId) -> anyhow::Result<()>;
 }
 
@@ -64,16 +76,18 @@ impl DefuseExt for near_workspaces::Account {
         };
 
         let contract = self.deploy_contract(id, wasm).await?;
+        init_defuse(&contract, config).await?;
 
-        contract
-            .call("new")
-            .args_json(json!({
-                "config": config,
-            }))
-            .max_gas()
-            .transact()
-            .await?
-            .into_result()?;
+        Ok(contract)
+    }
+
+    async fn deploy_defuse_mock_time(
+        &self,
+        id: &str,
+        config: DefuseConfig,
+    ) -> anyhow::Result<Contract> {
+        let contract = self.deploy_contract(id, &DEFUSE_MOCK_TIME_WASM).await?;
+        init_defuse(&contract, config).await?;
 
         Ok(contract)
     }
@@ -101,11 +115,32 @@ impl DefuseExt for Contract {
         self.as_account().deploy_defuse(id, config, legacy).await
     }
 
+    async fn deploy_defuse_mock_time(
+        &self,
+        id: &str,
+        config: DefuseConfig,
+    ) -> anyhow::Result<Self> {
+        self.as_account().deploy_defuse_mock_time(id, config).await
+    }
+
     async fn upgrade_defuse(&self, defuse_contract_id: &AccountId) -> anyhow::Result<()> {
         self.as_account().upgrade_defuse(defuse_contract_id).await
     }
 }
 
+async fn init_defuse(contract: &Contract, config: DefuseConfig) -> anyhow::Result<()> {
+    contract
+        .call("new")
+        .args_json(json!({
+            "config": config,
+        }))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+    Ok(())
+}
+
 pub trait DefuseSigner: Signer {
     #[must_use]
     fn sign_defuse_message<T>(
@@ -185,6 +220,10 @@ impl DefuseSigner for near_workspaces::Account {
                         serde_json::to_string(&Nep413DefuseMessage {
                             signer_id: self.id().clone(),
                             deadline,
+                            client_ref: None,
+                            require_prepaid_gas: None,
+                            priority: None,
+                            signed_at: Some(Deadline::now()),
                             message,
                         })
                         .unwrap(),
@@ -207,6 +246,10 @@ impl DefuseSigner for near_workspaces::Account {
                             verifying_contract: defuse_contract.clone(),
                             deadline,
                             nonce,
+                            client_ref: None,
+                            require_prepaid_gas: None,
+                            priority: None,
+                            signed_at: Some(Deadline::now()),
                             message,
                         })
                         .unwrap(),
@@ -220,6 +263,26 @@ impl DefuseSigner for near_workspaces::Account {
                         verifying_contract: defuse_contract.clone(),
                         deadline,
                         nonce,
+                        client_ref: None,
+                        require_prepaid_gas: None,
+                        priority: None,
+                        signed_at: Some(Deadline::now()),
+                        message,
+                    })
+                    .unwrap(),
+                ))
+                .into(),
+            SigningStandard::Erc191 => self
+                .sign_erc191(Erc191Payload(
+                    serde_json::to_string(&DefusePayload {
+                        signer_id: self.id().clone(),
+                        verifying_contract: defuse_contract.clone(),
+                        deadline,
+                        nonce,
+                        client_ref: None,
+                        require_prepaid_gas: None,
+                        priority: None,
+                        signed_at: Some(Deadline::now()),
                         message,
                     })
                     .unwrap(),
@@ -229,10 +292,13 @@ impl DefuseSigner for near_workspaces::Account {
     }
 }
 
-#[derive(Debug, Default, Arbitrary)]
+#[derive(Debug, Clone, Copy, Default, Arbitrary)]
 pub enum SigningStandard {
     #[default]
     Nep413,
     TonConnect,
     Sep53,
+    /// Requires signer to have a secp256k1 key
+    #[arbitrary(skip)]
+    Erc191,
 }
@@ -0,0 +1,113 @@
+use defuse::{
+    contract::Role,
+    pause::{PauseState, WITHDRAWALS_PAUSE_KEYS},
+};
+use near_sdk::AccountId;
+use rstest::rstest;
+use serde_json::json;
+
+use crate::{tests::defuse::env::Env, utils::acl::AclExt};
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn pause_state() {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let pause_manager = env.create_user().await;
+    env.acl_grant_role(env.defuse.id(), Role::PauseManager, pause_manager.id())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        env.defuse.pause_state(env.defuse.id()).await.unwrap(),
+        PauseState::default()
+    );
+
+    for key in WITHDRAWALS_PAUSE_KEYS {
+        assert!(
+            pause_manager
+                .pa_pause_feature(env.defuse.id(), key)
+                .await
+                .unwrap()
+        );
+    }
+
+    // withdrawals are paused, while transfers and intents are still active
+    assert_eq!(
+        env.defuse.pause_state(env.defuse.id()).await.unwrap(),
+        PauseState {
+            withdrawals: true,
+            ..Default::default()
+        }
+    );
+
+    assert!(
+        pause_manager
+            .pa_pause_feature(env.defuse.id(), "ALL")
+            .await
+            .unwrap()
+    );
+    assert_eq!(
+        env.defuse.pause_state(env.defuse.id()).await.unwrap(),
+        PauseState {
+            all: true,
+            intents: true,
+            transfers: true,
+            deposits: true,
+            withdrawals: true,
+        }
+    );
+}
+
+pub trait PauseStateExt {
+    async fn pause_state(&self, defuse_contract_id: &AccountId) -> anyhow::Result<PauseState>;
+
+    async fn pa_pause_feature(
+        &self,
+        defuse_contract_id: &AccountId,
+        key: &str,
+    ) -> anyhow::Result<bool>;
+}
+
+impl PauseStateExt for near_workspaces::Account {
+    async fn pause_state(&self, defuse_contract_id: &AccountId) -> anyhow::Result<PauseState> {
+        self.view(defuse_contract_id, "pause_state")
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn pa_pause_feature(
+        &self,
+        defuse_contract_id: &AccountId,
+        key: &str,
+    ) -> anyhow::Result<bool> {
+        self.call(defuse_contract_id, "pa_pause_feature")
+            .args_json(json!({
+                "key": key,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?
+            .json()
+            .map_err(Into::into)
+    }
+}
+
+impl PauseStateExt for near_workspaces::Contract {
+    async fn pause_state(&self, defuse_contract_id: &AccountId) -> anyhow::Result<PauseState> {
+        self.as_account().pause_state(defuse_contract_id).await
+    }
+
+    async fn pa_pause_feature(
+        &self,
+        defuse_contract_id: &AccountId,
+        key: &str,
+    ) -> anyhow::Result<bool> {
+        self.as_account()
+            .pa_pause_feature(defuse_contract_id, key)
+            .await
+    }
+}
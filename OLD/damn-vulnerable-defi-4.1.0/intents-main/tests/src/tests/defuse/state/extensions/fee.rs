@@ -1,5 +1,8 @@
-use defuse::core::fees::Pips;
-use near_sdk::{AccountId, NearToken};
+use defuse::core::{fees::Pips, token_id::TokenId};
+use near_sdk::{
+    AccountId, NearToken,
+    json_types::{I128, U128},
+};
 use serde_json::json;
 
 pub trait FeesManagerExt {
@@ -12,6 +15,31 @@ pub trait FeesManagerExt {
         fee_collector: &AccountId,
     ) -> anyhow::Result<()>;
     async fn fee_collector(&self, defuse_contract_id: &AccountId) -> anyhow::Result<AccountId>;
+    async fn quote_token_diff(
+        &self,
+        defuse_contract_id: &AccountId,
+        token_in: &TokenId,
+        amount_in: u128,
+        token_out: &TokenId,
+        referral: Option<&AccountId>,
+    ) -> anyhow::Result<i128>;
+    async fn collected_fees(
+        &self,
+        defuse_contract_id: &AccountId,
+        from: Option<&TokenId>,
+        limit: u32,
+    ) -> anyhow::Result<Vec<(TokenId, u128)>>;
+    async fn set_strict_referrals(
+        &self,
+        defuse_contract_id: &AccountId,
+        strict: bool,
+    ) -> anyhow::Result<()>;
+    async fn strict_referrals(&self, defuse_contract_id: &AccountId) -> anyhow::Result<bool>;
+    async fn add_allowed_referrals(
+        &self,
+        defuse_contract_id: &AccountId,
+        referrals: &[&AccountId],
+    ) -> anyhow::Result<()>;
 }
 
 impl FeesManagerExt for near_workspaces::Account {
@@ -58,6 +86,91 @@ impl FeesManagerExt for near_workspaces::Account {
             .json()
             .map_err(Into::into)
     }
+
+    async fn quote_token_diff(
+        &self,
+        defuse_contract_id: &AccountId,
+        token_in: &TokenId,
+        amount_in: u128,
+        token_out: &TokenId,
+        referral: Option<&AccountId>,
+    ) -> anyhow::Result<i128> {
+        self.view(defuse_contract_id, "quote_token_diff")
+            .args_json(json!({
+                "token_in": token_in,
+                "amount_in": U128(amount_in),
+                "token_out": token_out,
+                "referral": referral,
+            }))
+            .await?
+            .json::<I128>()
+            .map(|quote| quote.0)
+            .map_err(Into::into)
+    }
+
+    async fn collected_fees(
+        &self,
+        defuse_contract_id: &AccountId,
+        from: Option<&TokenId>,
+        limit: u32,
+    ) -> anyhow::Result<Vec<(TokenId, u128)>> {
+        self.view(defuse_contract_id, "collected_fees")
+            .args_json(json!({
+                "from": from,
+                "limit": limit,
+            }))
+            .await?
+            .json::<Vec<(TokenId, U128)>>()
+            .map(|fees| {
+                fees.into_iter()
+                    .map(|(token_id, amount)| (token_id, amount.0))
+                    .collect()
+            })
+            .map_err(Into::into)
+    }
+
+    async fn set_strict_referrals(
+        &self,
+        defuse_contract_id: &AccountId,
+        strict: bool,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "set_strict_referrals")
+            .deposit(NearToken::from_yoctonear(1))
+            .args_json(json!({
+                "strict": strict,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        Ok(())
+    }
+
+    async fn strict_referrals(&self, defuse_contract_id: &AccountId) -> anyhow::Result<bool> {
+        self.view(defuse_contract_id, "strict_referrals")
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn add_allowed_referrals(
+        &self,
+        defuse_contract_id: &AccountId,
+        referrals: &[&AccountId],
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "add_allowed_referrals")
+            .deposit(NearToken::from_yoctonear(1))
+            .args_json(json!({
+                "referrals": referrals,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        Ok(())
+    }
 }
 
 impl FeesManagerExt for near_workspaces::Contract {
@@ -82,4 +195,52 @@ impl FeesManagerExt for near_workspaces::Contract {
     async fn fee_collector(&self, defuse_contract_id: &AccountId) -> anyhow::Result<AccountId> {
         self.as_account().fee_collector(defuse_contract_id).await
     }
+
+    async fn quote_token_diff(
+        &self,
+        defuse_contract_id: &AccountId,
+        token_in: &TokenId,
+        amount_in: u128,
+        token_out: &TokenId,
+        referral: Option<&AccountId>,
+    ) -> anyhow::Result<i128> {
+        self.as_account()
+            .quote_token_diff(defuse_contract_id, token_in, amount_in, token_out, referral)
+            .await
+    }
+
+    async fn collected_fees(
+        &self,
+        defuse_contract_id: &AccountId,
+        from: Option<&TokenId>,
+        limit: u32,
+    ) -> anyhow::Result<Vec<(TokenId, u128)>> {
+        self.as_account()
+            .collected_fees(defuse_contract_id, from, limit)
+            .await
+    }
+
+    async fn set_strict_referrals(
+        &self,
+        defuse_contract_id: &AccountId,
+        strict: bool,
+    ) -> anyhow::Result<()> {
+        self.as_account()
+            .set_strict_referrals(defuse_contract_id, strict)
+            .await
+    }
+
+    async fn strict_referrals(&self, defuse_contract_id: &AccountId) -> anyhow::Result<bool> {
+        self.as_account().strict_referrals(defuse_contract_id).await
+    }
+
+    async fn add_allowed_referrals(
+        &self,
+        defuse_contract_id: &AccountId,
+        referrals: &[&AccountId],
+    ) -> anyhow::Result<()> {
+        self.as_account()
+            .add_allowed_referrals(defuse_contract_id, referrals)
+            .await
+    }
 }
@@ -1,4 +1,4 @@
-use defuse::core::Salt;
+use defuse::core::{Deadline, Salt};
 use near_sdk::{AccountId, NearToken};
 use serde_json::json;
 
@@ -11,6 +11,11 @@ pub trait SaltManagerExt {
         salts: &[Salt],
     ) -> anyhow::Result<Salt>;
 
+    async fn rotate_and_invalidate_all_salts(
+        &self,
+        defuse_contract_id: &AccountId,
+    ) -> anyhow::Result<Salt>;
+
     async fn is_valid_salt(
         &self,
         defuse_contract_id: &AccountId,
@@ -18,6 +23,23 @@ pub trait SaltManagerExt {
     ) -> anyhow::Result<bool>;
 
     async fn current_salt(&self, defuse_contract_id: &AccountId) -> anyhow::Result<Salt>;
+
+    async fn set_max_nonce_ttl(
+        &self,
+        defuse_contract_id: &AccountId,
+        max_nonce_ttl: Option<u32>,
+    ) -> anyhow::Result<()>;
+
+    async fn set_salt_grace_period(
+        &self,
+        defuse_contract_id: &AccountId,
+        grace_period: Option<u32>,
+    ) -> anyhow::Result<()>;
+
+    async fn max_allowed_deadline(
+        &self,
+        defuse_contract_id: &AccountId,
+    ) -> anyhow::Result<Deadline>;
 }
 
 impl SaltManagerExt for near_workspaces::Account {
@@ -48,6 +70,20 @@ impl SaltManagerExt for near_workspaces::Account {
             .map_err(Into::into)
     }
 
+    async fn rotate_and_invalidate_all_salts(
+        &self,
+        defuse_contract_id: &AccountId,
+    ) -> anyhow::Result<Salt> {
+        self.call(defuse_contract_id, "rotate_and_invalidate_all_salts")
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?
+            .json()
+            .map_err(Into::into)
+    }
+
     async fn is_valid_salt(
         &self,
         defuse_contract_id: &AccountId,
@@ -66,6 +102,46 @@ impl SaltManagerExt for near_workspaces::Account {
             .json()
             .map_err(Into::into)
     }
+
+    async fn set_max_nonce_ttl(
+        &self,
+        defuse_contract_id: &AccountId,
+        max_nonce_ttl: Option<u32>,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "set_max_nonce_ttl")
+            .args_json(json!({ "max_nonce_ttl": max_nonce_ttl }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    async fn set_salt_grace_period(
+        &self,
+        defuse_contract_id: &AccountId,
+        grace_period: Option<u32>,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "set_salt_grace_period")
+            .args_json(json!({ "grace_period": grace_period }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    async fn max_allowed_deadline(
+        &self,
+        defuse_contract_id: &AccountId,
+    ) -> anyhow::Result<Deadline> {
+        self.view(defuse_contract_id, "max_allowed_deadline")
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
 }
 
 impl SaltManagerExt for near_workspaces::Contract {
@@ -85,6 +161,15 @@ impl SaltManagerExt for near_workspaces::Contract {
             .await
     }
 
+    async fn rotate_and_invalidate_all_salts(
+        &self,
+        defuse_contract_id: &AccountId,
+    ) -> anyhow::Result<Salt> {
+        self.as_account()
+            .rotate_and_invalidate_all_salts(defuse_contract_id)
+            .await
+    }
+
     async fn is_valid_salt(
         &self,
         defuse_contract_id: &AccountId,
@@ -98,4 +183,33 @@ impl SaltManagerExt for near_workspaces::Contract {
     async fn current_salt(&self, defuse_contract_id: &AccountId) -> anyhow::Result<Salt> {
         self.as_account().current_salt(defuse_contract_id).await
     }
+
+    async fn set_max_nonce_ttl(
+        &self,
+        defuse_contract_id: &AccountId,
+        max_nonce_ttl: Option<u32>,
+    ) -> anyhow::Result<()> {
+        self.as_account()
+            .set_max_nonce_ttl(defuse_contract_id, max_nonce_ttl)
+            .await
+    }
+
+    async fn set_salt_grace_period(
+        &self,
+        defuse_contract_id: &AccountId,
+        grace_period: Option<u32>,
+    ) -> anyhow::Result<()> {
+        self.as_account()
+            .set_salt_grace_period(defuse_contract_id, grace_period)
+            .await
+    }
+
+    async fn max_allowed_deadline(
+        &self,
+        defuse_contract_id: &AccountId,
+    ) -> anyhow::Result<Deadline> {
+        self.as_account()
+            .max_allowed_deadline(defuse_contract_id)
+            .await
+    }
 }
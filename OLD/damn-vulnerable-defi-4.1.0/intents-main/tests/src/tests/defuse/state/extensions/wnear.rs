@@ -0,0 +1,52 @@
+use near_sdk::{AccountId, NearToken};
+use serde_json::json;
+
+pub trait WNearManagerExt {
+    async fn set_wnear_id(
+        &self,
+        defuse_contract_id: &AccountId,
+        wnear_id: &AccountId,
+    ) -> anyhow::Result<()>;
+
+    async fn wnear_id(&self, defuse_contract_id: &AccountId) -> anyhow::Result<AccountId>;
+}
+
+impl WNearManagerExt for near_workspaces::Account {
+    async fn set_wnear_id(
+        &self,
+        defuse_contract_id: &AccountId,
+        wnear_id: &AccountId,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "set_wnear_id")
+            .args_json(json!({ "wnear_id": wnear_id }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    async fn wnear_id(&self, defuse_contract_id: &AccountId) -> anyhow::Result<AccountId> {
+        self.view(defuse_contract_id, "wnear_id")
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+}
+
+impl WNearManagerExt for near_workspaces::Contract {
+    async fn set_wnear_id(
+        &self,
+        defuse_contract_id: &AccountId,
+        wnear_id: &AccountId,
+    ) -> anyhow::Result<()> {
+        self.as_account()
+            .set_wnear_id(defuse_contract_id, wnear_id)
+            .await
+    }
+
+    async fn wnear_id(&self, defuse_contract_id: &AccountId) -> anyhow::Result<AccountId> {
+        self.as_account().wnear_id(defuse_contract_id).await
+    }
+}
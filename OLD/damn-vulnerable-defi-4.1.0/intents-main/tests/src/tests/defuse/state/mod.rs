@@ -1,5 +1,6 @@
 mod extensions;
 mod fee;
+mod roles;
 mod salt;
 
-pub use extensions::{fee::FeesManagerExt, salt::SaltManagerExt};
+pub use extensions::{fee::FeesManagerExt, salt::SaltManagerExt, wnear::WNearManagerExt};
@@ -0,0 +1,117 @@
+use defuse::{contract::Role, core::events::DefuseEvent};
+use near_sdk::NearToken;
+use rstest::rstest;
+use serde_json::json;
+
+use crate::{tests::defuse::env::Env, utils::events::CallWithEventsExt};
+
+#[tokio::test]
+#[rstest]
+async fn role_grant_and_revoke_events() {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+    let user = env.create_user().await;
+
+    let mut granted = Vec::new();
+    assert!(
+        env.call(env.defuse.id(), "grant_role")
+            .args_json(json!({
+                "role": Role::SaltManager,
+                "account_id": user.id(),
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact_with_defuse_events(env.defuse.id(), |event| {
+                if let DefuseEvent::RoleGranted(event) = event {
+                    granted.push((
+                        event.account_id.into_owned(),
+                        event.event.role.into_owned(),
+                        event.event.by.into_owned(),
+                    ));
+                }
+            })
+            .await
+            .unwrap()
+            .into_result()
+            .unwrap()
+            .json::<bool>()
+            .unwrap()
+    );
+    assert_eq!(
+        granted,
+        [(
+            user.id().clone(),
+            "SaltManager".to_string(),
+            env.id().clone()
+        )],
+    );
+    assert!(
+        env.defuse
+            .view("acl_has_role")
+            .args_json(json!({
+                "role": Role::SaltManager,
+                "account_id": user.id(),
+            }))
+            .await
+            .unwrap()
+            .json::<bool>()
+            .unwrap()
+    );
+
+    let mut revoked = Vec::new();
+    assert!(
+        env.call(env.defuse.id(), "revoke_role")
+            .args_json(json!({
+                "role": Role::SaltManager,
+                "account_id": user.id(),
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact_with_defuse_events(env.defuse.id(), |event| {
+                if let DefuseEvent::RoleRevoked(event) = event {
+                    revoked.push((
+                        event.account_id.into_owned(),
+                        event.event.role.into_owned(),
+                        event.event.by.into_owned(),
+                    ));
+                }
+            })
+            .await
+            .unwrap()
+            .into_result()
+            .unwrap()
+            .json::<bool>()
+            .unwrap()
+    );
+    assert_eq!(
+        revoked,
+        [(
+            user.id().clone(),
+            "SaltManager".to_string(),
+            env.id().clone()
+        )],
+    );
+}
+
+#[tokio::test]
+#[rstest]
+async fn grant_role_requires_admin() {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+    let user = env.create_user().await;
+
+    assert!(
+        user.call(env.defuse.id(), "grant_role")
+            .args_json(json!({
+                "role": Role::SaltManager,
+                "account_id": user.id(),
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await
+            .unwrap()
+            .into_result()
+            .unwrap_err()
+            .to_string()
+            .contains("insufficient permissions")
+    );
+}
@@ -1,10 +1,28 @@
-use defuse::contract::Role;
+use std::time::Duration;
 
-use defuse_test_utils::asserts::ResultAssertsExt;
+use chrono::DateTime;
+use defuse::{
+    contract::Role,
+    core::{
+        Deadline, DefuseError,
+        intents::{DefuseIntents, Intent},
+    },
+};
+
+use defuse_test_utils::{
+    asserts::ResultAssertsExt,
+    random::{Rng, rng},
+};
 use rstest::rstest;
+use tokio::time::sleep;
 
 use crate::{
-    tests::defuse::{env::Env, state::SaltManagerExt},
+    tests::defuse::{
+        DefuseSigner, DefuseSignerExt, SigningStandard,
+        env::{Env, create_random_salted_nonce},
+        intents::ExecuteIntentsExt,
+        state::SaltManagerExt,
+    },
     utils::acl::AclExt,
 };
 
@@ -106,3 +124,228 @@ async fn invalidate_salts() {
         assert_ne!(prev_salt, current_salt);
     }
 }
+
+#[tokio::test]
+#[rstest]
+async fn rotate_and_invalidate_all_salts(#[notrace] mut rng: impl Rng) {
+    let env = Env::builder().deployer_as_super_admin().build().await;
+    let (salt_manager, user) = futures::join!(env.create_user(), env.create_user());
+
+    // only DAO or salt manager can rotate and invalidate salts
+    user.rotate_and_invalidate_all_salts(env.defuse.id())
+        .await
+        .assert_err_contains("Insufficient permissions for method");
+
+    env.acl_grant_role(env.defuse.id(), Role::SaltManager, salt_manager.id())
+        .await
+        .expect("failed to grant role");
+
+    let mut old_salts = vec![env.defuse.current_salt(env.defuse.id()).await.unwrap()];
+    for _ in 0..2 {
+        old_salts.push(
+            salt_manager
+                .update_current_salt(env.defuse.id())
+                .await
+                .unwrap(),
+        );
+    }
+
+    let new_salt = salt_manager
+        .rotate_and_invalidate_all_salts(env.defuse.id())
+        .await
+        .unwrap();
+    assert_eq!(
+        env.defuse.current_salt(env.defuse.id()).await.unwrap(),
+        new_salt
+    );
+    assert!(
+        env.defuse
+            .is_valid_salt(env.defuse.id(), &new_salt)
+            .await
+            .unwrap()
+    );
+
+    let deadline = Deadline::timeout(Duration::from_secs(120));
+    let sign = |salt, rng: &mut _| {
+        user.sign_defuse_message(
+            SigningStandard::default(),
+            env.defuse.id(),
+            create_random_salted_nonce(salt, deadline, rng),
+            deadline,
+            DefuseIntents { intents: [].into() },
+        )
+    };
+
+    for salt in old_salts {
+        assert!(
+            !env.defuse
+                .is_valid_salt(env.defuse.id(), &salt)
+                .await
+                .unwrap()
+        );
+        env.defuse
+            .execute_intents(env.defuse.id(), [sign(salt, &mut rng)])
+            .await
+            .assert_err_contains(DefuseError::InvalidSalt.to_string());
+    }
+
+    env.defuse
+        .execute_intents(env.defuse.id(), [sign(new_salt, &mut rng)])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[rstest]
+async fn salt_grace_period(#[notrace] mut rng: impl Rng) {
+    const GRACE_PERIOD_SECS: u32 = 5;
+
+    let env = Env::builder().deployer_as_super_admin().build().await;
+    let (dao, user) = futures::join!(env.create_user(), env.create_user());
+
+    // only DAO can set salt grace period
+    user.set_salt_grace_period(env.defuse.id(), Some(GRACE_PERIOD_SECS))
+        .await
+        .assert_err_contains("Insufficient permissions for method");
+
+    env.acl_grant_role(env.defuse.id(), Role::DAO, dao.id())
+        .await
+        .expect("failed to grant role");
+    dao.set_salt_grace_period(env.defuse.id(), Some(GRACE_PERIOD_SECS))
+        .await
+        .unwrap();
+
+    let old_salt = env.defuse.current_salt(env.defuse.id()).await.unwrap();
+    env.defuse
+        .update_current_salt(env.defuse.id())
+        .await
+        .unwrap();
+
+    let deadline = Deadline::timeout(Duration::from_secs(120));
+    let execute_with_old_salt = async |rng: &mut _| {
+        env.defuse_execute_intents(
+            env.defuse.id(),
+            [user.sign_defuse_message(
+                SigningStandard::default(),
+                env.defuse.id(),
+                create_random_salted_nonce(old_salt, deadline, rng),
+                deadline,
+                DefuseIntents { intents: [].into() },
+            )],
+        )
+        .await
+    };
+
+    // old salt is still valid within grace period
+    execute_with_old_salt(&mut rng).await.unwrap();
+
+    sleep(Duration::from_secs((GRACE_PERIOD_SECS + 1).into())).await;
+
+    // old salt expires without explicit invalidation
+    assert!(
+        !env.defuse
+            .is_valid_salt(env.defuse.id(), &old_salt)
+            .await
+            .unwrap()
+    );
+    execute_with_old_salt(&mut rng)
+        .await
+        .assert_err_contains(DefuseError::InvalidSalt.to_string());
+}
+
+#[tokio::test]
+#[rstest]
+async fn max_allowed_deadline() {
+    const TTL_SECS: u32 = 60;
+    let ttl = Duration::from_secs(TTL_SECS.into());
+
+    let env = Env::builder().deployer_as_super_admin().build().await;
+    let (dao, user) = futures::join!(env.create_user(), env.create_user());
+
+    assert_eq!(
+        env.defuse
+            .max_allowed_deadline(env.defuse.id())
+            .await
+            .unwrap(),
+        Deadline::MAX
+    );
+
+    // only DAO can set max nonce TTL
+    user.set_max_nonce_ttl(env.defuse.id(), Some(TTL_SECS))
+        .await
+        .assert_err_contains("Insufficient permissions for method");
+
+    env.acl_grant_role(env.defuse.id(), Role::DAO, dao.id())
+        .await
+        .expect("failed to grant role");
+    dao.set_max_nonce_ttl(env.defuse.id(), Some(TTL_SECS))
+        .await
+        .unwrap();
+
+    let block_deadline = async || {
+        Deadline::new(DateTime::from_timestamp_nanos(
+            env.sandbox().block_timestamp().await.try_into().unwrap(),
+        ))
+    };
+    let before = block_deadline().await;
+    let max_deadline = env
+        .defuse
+        .max_allowed_deadline(env.defuse.id())
+        .await
+        .unwrap();
+    let after = block_deadline().await;
+    assert!(
+        before + ttl <= max_deadline && max_deadline <= after + ttl,
+        "{max_deadline:?} is not within [{before:?}, {after:?}] + {ttl:?}",
+    );
+
+    // deadline further than TTL is rejected
+    let payload = user
+        .sign_defuse_payload_default(env.defuse.id(), Vec::<Intent>::new())
+        .await
+        .unwrap();
+    env.defuse_execute_intents(env.defuse.id(), [payload])
+        .await
+        .assert_err_contains(DefuseError::DeadlineExceedsMaxNonceTtl.to_string());
+
+    dao.set_max_nonce_ttl(env.defuse.id(), None).await.unwrap();
+    assert_eq!(
+        env.defuse
+            .max_allowed_deadline(env.defuse.id())
+            .await
+            .unwrap(),
+        Deadline::MAX
+    );
+}
+
+#[tokio::test]
+#[rstest]
+async fn require_bounded_deadline() {
+    let env = Env::builder().require_bounded_deadline().build().await;
+    let user = env.create_user().await;
+
+    // perpetual intent is rejected
+    let nonce = user
+        .unique_nonce(env.defuse.id(), Some(Deadline::MAX))
+        .await
+        .unwrap();
+    let payload = user.sign_defuse_message(
+        SigningStandard::default(),
+        env.defuse.id(),
+        nonce,
+        Deadline::MAX,
+        DefuseIntents { intents: [].into() },
+    );
+    env.defuse_execute_intents(env.defuse.id(), [payload])
+        .await
+        .assert_err_contains(DefuseError::UnboundedDeadline.to_string());
+
+    // time-bounded intent succeeds
+    let payload = user
+        .sign_defuse_payload_default(env.defuse.id(), Vec::<Intent>::new())
+        .await
+        .unwrap();
+    env.defuse_execute_intents(env.defuse.id(), [payload])
+        .await
+        .unwrap();
+}
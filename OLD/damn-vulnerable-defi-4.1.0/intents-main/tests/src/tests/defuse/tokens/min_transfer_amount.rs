@@ -0,0 +1,216 @@
+use defuse::{
+    contract::Role,
+    core::{
+        amounts::Amounts,
+        intents::tokens::Transfer,
+        token_id::{TokenId, nep141::Nep141TokenId},
+    },
+};
+use defuse_test_utils::asserts::ResultAssertsExt;
+use near_sdk::{AccountId, NearToken, json_types::U128};
+use rstest::rstest;
+use serde_json::json;
+
+use crate::{
+    tests::defuse::{DefuseSignerExt, env::Env, intents::ExecuteIntentsExt},
+    utils::{acl::AclExt, mt::MtExt},
+};
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn min_transfer_amount() {
+    const MIN_AMOUNT: u128 = 100;
+
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (user, other_user, risk_manager, ft) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_token()
+    );
+
+    env.initial_ft_storage_deposit(vec![user.id(), other_user.id()], vec![&ft])
+        .await;
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    let token_id = TokenId::from(Nep141TokenId::new(ft.clone()));
+
+    // set the minimum
+    {
+        risk_manager
+            .set_min_transfer_amount(env.defuse.id(), &token_id, Some(MIN_AMOUNT))
+            .await
+            .expect_err("doesn't have RiskManager role yet");
+
+        env.acl_grant_role(env.defuse.id(), Role::RiskManager, risk_manager.id())
+            .await
+            .unwrap();
+
+        risk_manager
+            .set_min_transfer_amount(env.defuse.id(), &token_id, Some(MIN_AMOUNT))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            env.defuse
+                .min_transfer_amount(env.defuse.id(), &token_id)
+                .await
+                .unwrap(),
+            Some(MIN_AMOUNT)
+        );
+    }
+
+    // transfers below the minimum are rejected
+    user.mt_transfer(
+        env.defuse.id(),
+        other_user.id(),
+        &token_id.to_string(),
+        MIN_AMOUNT - 1,
+        None,
+        None,
+    )
+    .await
+    .assert_err_contains("less than minimum transfer amount");
+
+    let transfer = |amount| Transfer {
+        receiver_id: other_user.id().clone(),
+        tokens: Amounts::new([(token_id.clone(), amount)].into()),
+        memo: None,
+        notification: None,
+    };
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(env.defuse.id(), [transfer(MIN_AMOUNT - 1)])
+                .await
+                .unwrap()],
+        )
+        .await
+        .assert_err_contains("less than minimum transfer amount");
+
+    // transfers at or above the minimum succeed
+    user.mt_transfer(
+        env.defuse.id(),
+        other_user.id(),
+        &token_id.to_string(),
+        MIN_AMOUNT,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    env.defuse
+        .execute_intents(
+            env.defuse.id(),
+            [user
+                .sign_defuse_payload_default(env.defuse.id(), [transfer(MIN_AMOUNT + 1)])
+                .await
+                .unwrap()],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), other_user.id(), &token_id.to_string())
+            .await
+            .unwrap(),
+        2 * MIN_AMOUNT + 1
+    );
+
+    // removing the minimum allows dust transfers again
+    risk_manager
+        .set_min_transfer_amount(env.defuse.id(), &token_id, None)
+        .await
+        .unwrap();
+
+    user.mt_transfer(
+        env.defuse.id(),
+        other_user.id(),
+        &token_id.to_string(),
+        1,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+}
+
+pub trait MinTransferAmountExt {
+    async fn min_transfer_amount(
+        &self,
+        defuse_contract_id: &AccountId,
+        token_id: &TokenId,
+    ) -> anyhow::Result<Option<u128>>;
+
+    async fn set_min_transfer_amount(
+        &self,
+        defuse_contract_id: &AccountId,
+        token_id: &TokenId,
+        amount: Option<u128>,
+    ) -> anyhow::Result<()>;
+}
+
+impl MinTransferAmountExt for near_workspaces::Account {
+    async fn min_transfer_amount(
+        &self,
+        defuse_contract_id: &AccountId,
+        token_id: &TokenId,
+    ) -> anyhow::Result<Option<u128>> {
+        self.view(defuse_contract_id, "min_transfer_amount")
+            .args_json(json!({
+                "token_id": token_id,
+            }))
+            .await?
+            .json::<Option<U128>>()
+            .map(|amount| amount.map(|amount| amount.0))
+            .map_err(Into::into)
+    }
+
+    async fn set_min_transfer_amount(
+        &self,
+        defuse_contract_id: &AccountId,
+        token_id: &TokenId,
+        amount: Option<u128>,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "set_min_transfer_amount")
+            .deposit(NearToken::from_yoctonear(1))
+            .args_json(json!({
+                "token_id": token_id,
+                "amount": amount.map(U128),
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+}
+
+impl MinTransferAmountExt for near_workspaces::Contract {
+    async fn min_transfer_amount(
+        &self,
+        defuse_contract_id: &AccountId,
+        token_id: &TokenId,
+    ) -> anyhow::Result<Option<u128>> {
+        self.as_account()
+            .min_transfer_amount(defuse_contract_id, token_id)
+            .await
+    }
+
+    async fn set_min_transfer_amount(
+        &self,
+        defuse_contract_id: &AccountId,
+        token_id: &TokenId,
+        amount: Option<u128>,
+    ) -> anyhow::Result<()> {
+        self.as_account()
+            .set_min_transfer_amount(defuse_contract_id, token_id, amount)
+            .await
+    }
+}
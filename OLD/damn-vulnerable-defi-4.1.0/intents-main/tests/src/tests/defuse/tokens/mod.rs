@@ -1,3 +1,5 @@
+mod min_transfer_amount;
 pub mod nep141;
 mod nep171;
-mod nep245;
+pub mod nep245;
+mod receipt;
@@ -9,6 +9,7 @@ use defuse::core::token_id::TokenId;
 use defuse::core::token_id::nep141::Nep141TokenId;
 
 use defuse::{contract::Role, core::intents::tokens::FtWithdraw, tokens::DepositMessage};
+use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
 use near_sdk::json_types::U128;
 use rstest::rstest;
 
@@ -129,6 +130,7 @@ async fn deposit_withdraw_intent(#[values(false, true)] no_registration: bool) {
                 memo: None,
                 msg: None,
                 storage_deposit: None,
+                auto_storage_deposit: false,
                 min_gas: None,
             }],
         )
@@ -145,6 +147,8 @@ async fn deposit_withdraw_intent(#[values(false, true)] no_registration: bool) {
                 execute_intents: [withdraw_intent_payload].into(),
                 // another promise will be created for `execute_intents()`
                 refund_if_fails: false,
+                lock: false,
+                mint_receipt: false,
             },
         )
         .await
@@ -211,6 +215,7 @@ async fn deposit_withdraw_intent_refund(#[values(false, true)] no_registration:
                 memo: None,
                 msg: None,
                 storage_deposit: None,
+                auto_storage_deposit: false,
                 min_gas: None,
             }],
         )
@@ -226,6 +231,8 @@ async fn deposit_withdraw_intent_refund(#[values(false, true)] no_registration:
                 receiver_id: user.id().clone(),
                 execute_intents: [overflow_withdraw_payload].into(),
                 refund_if_fails: true,
+                lock: false,
+                mint_receipt: false,
             },
         )
         .await
@@ -243,6 +250,177 @@ async fn deposit_withdraw_intent_refund(#[values(false, true)] no_registration:
     assert_eq!(env.ft_token_balance_of(&ft, user.id()).await.unwrap(), 1000);
 }
 
+#[tokio::test]
+#[rstest]
+async fn deposit_then_swap(#[values(false, true)] swap_matches: bool) {
+    use crate::tests::defuse::{DefuseSignerExt, tokens::nep141::traits::DefuseFtReceiver};
+    use defuse::core::intents::token_diff::{TokenDeltas, TokenDiff};
+
+    let env = Env::builder().build().await;
+
+    let (user, solver, ft1, ft2) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_token(),
+        env.create_token()
+    );
+
+    env.initial_ft_storage_deposit(vec![user.id(), solver.id()], vec![&ft1, &ft2])
+        .await;
+
+    env.poa_factory_ft_deposit(
+        env.poa_factory.id(),
+        &env.poa_ft_name(&ft1),
+        user.id(),
+        1000,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    env.defuse_ft_deposit_to(&ft2, 2000, solver.id())
+        .await
+        .unwrap();
+
+    let ft1_id = TokenId::from(Nep141TokenId::new(ft1.clone()));
+    let ft2_id = TokenId::from(Nep141TokenId::new(ft2.clone()));
+
+    let token_diff = |deltas: [(TokenId, i128); 2]| TokenDiff {
+        diff: TokenDeltas::default().with_apply_deltas(deltas).unwrap(),
+        memo: None,
+        referral: None,
+        max_fee: None,
+        solver: None,
+        partial_fill: false,
+    };
+
+    // user swaps tokens that are not deposited yet
+    let user_swap = user
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            [token_diff([
+                (ft1_id.clone(), -1000),
+                (ft2_id.clone(), 2000),
+            ])],
+        )
+        .await
+        .unwrap();
+    let solver_swap = solver
+        .sign_defuse_payload_default(
+            env.defuse.id(),
+            [token_diff([
+                (ft1_id.clone(), 1000),
+                (ft2_id.clone(), if swap_matches { -2000 } else { -1999 }),
+            ])],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        user.defuse_ft_deposit(
+            env.defuse.id(),
+            &ft1,
+            1000,
+            DepositMessage::deposit_then_swap(user.id().clone(), [user_swap, solver_swap]),
+        )
+        .await
+        .unwrap(),
+        if swap_matches { 1000 } else { 0 }
+    );
+
+    assert_eq!(
+        env.mt_contract_batch_balance_of(
+            env.defuse.id(),
+            user.id(),
+            [&ft1_id.to_string(), &ft2_id.to_string()]
+        )
+        .await
+        .unwrap(),
+        if swap_matches { [0, 2000] } else { [0, 0] }
+    );
+    assert_eq!(
+        env.ft_token_balance_of(&ft1, user.id()).await.unwrap(),
+        if swap_matches { 0 } else { 1000 }
+    );
+}
+
+#[tokio::test]
+#[rstest]
+async fn deposit_too_many_intents_refund(#[values(false, true)] refund_if_fails: bool) {
+    use crate::tests::defuse::{DefuseSignerExt, tokens::nep141::traits::DefuseFtReceiver};
+
+    let env = Env::builder().build().await;
+
+    let (user, other_user, ft) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+
+    env.initial_ft_storage_deposit(vec![user.id(), other_user.id()], vec![&ft])
+        .await;
+
+    env.poa_factory_ft_deposit(
+        env.poa_factory.id(),
+        &env.poa_ft_name(&ft),
+        user.id(),
+        1000,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let mut payloads = Vec::new();
+    for _ in 0..=DepositMessage::MAX_EXECUTE_INTENTS {
+        payloads.push(
+            user.sign_defuse_payload_default(
+                env.defuse.id(),
+                [FtWithdraw {
+                    token: ft.clone(),
+                    receiver_id: other_user.id().clone(),
+                    amount: U128(1),
+                    memo: None,
+                    msg: None,
+                    storage_deposit: None,
+                    auto_storage_deposit: false,
+                    min_gas: None,
+                }],
+            )
+            .await
+            .unwrap(),
+        );
+    }
+
+    assert_eq!(
+        user.defuse_ft_deposit(
+            env.defuse.id(),
+            &ft,
+            1000,
+            DepositMessage {
+                receiver_id: user.id().clone(),
+                execute_intents: payloads,
+                refund_if_fails,
+                lock: false,
+                mint_receipt: false,
+            },
+        )
+        .await
+        .unwrap(),
+        0
+    );
+
+    let ft_id = TokenId::from(Nep141TokenId::new(ft.clone()));
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), user.id(), &ft_id.to_string())
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(env.ft_token_balance_of(&ft, user.id()).await.unwrap(), 1000);
+    assert_eq!(
+        env.ft_token_balance_of(&ft, other_user.id()).await.unwrap(),
+        0
+    );
+}
+
 #[tokio::test]
 #[rstest]
 async fn ft_force_withdraw(#[values(false, true)] no_registration: bool) {
@@ -327,3 +505,106 @@ async fn ft_force_withdraw(#[values(false, true)] no_registration: bool) {
         1000
     );
 }
+
+#[tokio::test]
+#[rstest]
+async fn ft_metadata_cache() {
+    use crate::tests::defuse::tokens::nep141::traits::DefuseFtMetadataCacheExt;
+
+    let env = Env::builder().deployer_as_super_admin().build().await;
+
+    let (user, dao, ft) = futures::join!(env.create_user(), env.create_user(), env.create_token());
+
+    env.initial_ft_storage_deposit(vec![user.id()], vec![&ft])
+        .await;
+
+    assert!(
+        user.defuse_token_metadata(env.defuse.id(), &ft)
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    env.defuse_ft_deposit_to(&ft, 1000, user.id())
+        .await
+        .unwrap();
+
+    let expected: FungibleTokenMetadata =
+        user.view(&ft, "ft_metadata").await.unwrap().json().unwrap();
+
+    // served from the cache, no cross-contract call involved
+    let cached = user
+        .defuse_token_metadata(env.defuse.id(), &ft)
+        .await
+        .unwrap()
+        .expect("metadata should be cached on first deposit");
+    assert_eq!(cached.symbol, expected.symbol);
+    assert_eq!(cached.decimals, expected.decimals);
+
+    dao.defuse_refresh_token_metadata(env.defuse.id(), &ft)
+        .await
+        .expect_err("doesn't have DAO role yet");
+
+    env.acl_grant_role(env.defuse.id(), Role::DAO, dao.id())
+        .await
+        .unwrap();
+
+    dao.defuse_refresh_token_metadata(env.defuse.id(), &ft)
+        .await
+        .unwrap();
+
+    let refreshed = user
+        .defuse_token_metadata(env.defuse.id(), &ft)
+        .await
+        .unwrap()
+        .expect("metadata should be refreshed");
+    assert_eq!(refreshed.symbol, expected.symbol);
+    assert_eq!(refreshed.decimals, expected.decimals);
+}
+
+#[tokio::test]
+#[rstest]
+async fn check_withdrawal_readiness() {
+    use crate::tests::defuse::tokens::nep141::traits::DefuseFtWithdrawReadinessExt;
+    use defuse::tokens::nep141::WithdrawReadiness;
+    use near_contract_standards::storage_management::StorageBalanceBounds;
+
+    let env = Env::new().await;
+
+    let (user, other_user, ft) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+
+    // only `user` is registered on the token
+    env.initial_ft_storage_deposit(vec![user.id()], vec![&ft])
+        .await;
+
+    let bounds: StorageBalanceBounds = user
+        .view(&ft, "storage_balance_bounds")
+        .await
+        .unwrap()
+        .json()
+        .unwrap();
+
+    assert_eq!(
+        user.defuse_check_withdrawal_readiness(
+            env.defuse.id(),
+            &[(&ft, user.id()), (&ft, other_user.id())],
+        )
+        .await
+        .unwrap(),
+        [
+            WithdrawReadiness {
+                token: ft.clone(),
+                receiver_id: user.id().clone(),
+                registered: true,
+                storage_deposit: U128(0),
+            },
+            WithdrawReadiness {
+                token: ft.clone(),
+                receiver_id: other_user.id().clone(),
+                registered: false,
+                storage_deposit: U128(bounds.min.as_yoctonear()),
+            },
+        ]
+    );
+}
@@ -1,6 +1,7 @@
 #![allow(clippy::too_many_arguments)]
 
-use defuse::tokens::DepositMessage;
+use defuse::tokens::{DepositMessage, nep141::WithdrawReadiness};
+use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
 use near_sdk::{AccountId, NearToken, json_types::U128};
 use serde_json::json;
 
@@ -171,3 +172,77 @@ impl DefuseFtWithdrawer for near_workspaces::Contract {
             .await
     }
 }
+
+pub trait DefuseFtMetadataCacheExt {
+    async fn defuse_token_metadata(
+        &self,
+        defuse_id: &AccountId,
+        token_id: &AccountId,
+    ) -> anyhow::Result<Option<FungibleTokenMetadata>>;
+
+    async fn defuse_refresh_token_metadata(
+        &self,
+        defuse_id: &AccountId,
+        token_id: &AccountId,
+    ) -> anyhow::Result<()>;
+}
+
+impl DefuseFtMetadataCacheExt for near_workspaces::Account {
+    async fn defuse_token_metadata(
+        &self,
+        defuse_id: &AccountId,
+        token_id: &AccountId,
+    ) -> anyhow::Result<Option<FungibleTokenMetadata>> {
+        self.view(defuse_id, "token_metadata")
+            .args_json(json!({
+                "token": token_id,
+            }))
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn defuse_refresh_token_metadata(
+        &self,
+        defuse_id: &AccountId,
+        token_id: &AccountId,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_id, "refresh_token_metadata")
+            .args_json(json!({
+                "token": token_id,
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+}
+
+pub trait DefuseFtWithdrawReadinessExt {
+    async fn defuse_check_withdrawal_readiness(
+        &self,
+        defuse_id: &AccountId,
+        plan: &[(&AccountId, &AccountId)],
+    ) -> anyhow::Result<Vec<WithdrawReadiness>>;
+}
+
+impl DefuseFtWithdrawReadinessExt for near_workspaces::Account {
+    async fn defuse_check_withdrawal_readiness(
+        &self,
+        defuse_id: &AccountId,
+        plan: &[(&AccountId, &AccountId)],
+    ) -> anyhow::Result<Vec<WithdrawReadiness>> {
+        self.call(defuse_id, "check_withdrawal_readiness")
+            .args_json(json!({
+                "plan": plan,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?
+            .json()
+            .map_err(Into::into)
+    }
+}
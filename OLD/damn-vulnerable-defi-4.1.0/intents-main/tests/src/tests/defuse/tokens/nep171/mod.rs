@@ -1,9 +1,12 @@
+mod traits;
+
 use crate::tests::defuse::DefuseSignerExt;
 use crate::tests::defuse::{env::Env, intents::ExecuteIntentsExt};
 use crate::utils::{mt::MtExt, nft::NftExt};
 use defuse::core::intents::tokens::NftWithdraw;
 use defuse::core::token_id::TokenId as DefuseTokenId;
 use defuse::core::token_id::nep171::Nep171TokenId;
+use defuse::tokens::DepositMessage;
 use near_contract_standards::non_fungible_token::metadata::{
     NFT_METADATA_SPEC, NFTContractMetadata,
 };
@@ -12,6 +15,8 @@ use near_sdk::{NearToken, json_types::Base64VecU8};
 use rstest::rstest;
 use std::collections::HashMap;
 
+use self::traits::DefuseNftApprovedDepositor;
+
 const DUMMY_REFERENCE_HASH: [u8; 32] = [33; 32];
 const DUMMY_NFT1_ID: &str = "thisisdummynftid1";
 const DUMMY_NFT2_ID: &str = "thisisdummythisisdummynnthisisdummynftid2";
@@ -285,3 +290,141 @@ async fn transfer_nft_to_verifier() {
         }
     }
 }
+
+#[tokio::test]
+#[rstest]
+async fn deposit_approved_nft_to_verifier() {
+    let env = Env::builder().create_unique_users().build().await;
+
+    let (issuer, owner, other_user) =
+        futures::join!(env.create_user(), env.create_user(), env.create_user());
+
+    env.transfer_near(issuer.id(), NearToken::from_near(100))
+        .await
+        .unwrap()
+        .unwrap();
+
+    let nft_issuer_contract = issuer
+        .deploy_vanilla_nft_issuer(
+            "nft1",
+            NFTContractMetadata {
+                reference: Some("http://abc.com/xyz/".to_string()),
+                reference_hash: Some(Base64VecU8(DUMMY_REFERENCE_HASH.to_vec())),
+                spec: NFT_METADATA_SPEC.to_string(),
+                name: "Token nft1".to_string(),
+                symbol: "NFT_TKN".to_string(),
+                icon: None,
+                base_uri: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let nft1_mt_token_id = DefuseTokenId::from(
+        Nep171TokenId::new(
+            nft_issuer_contract.id().to_owned(),
+            DUMMY_NFT1_ID.to_string(),
+        )
+        .unwrap(),
+    );
+
+    issuer
+        .nft_mint(
+            nft_issuer_contract.id(),
+            &DUMMY_NFT1_ID.to_string(),
+            owner.id(),
+            &TokenMetadata::default(),
+        )
+        .await
+        .unwrap();
+
+    owner
+        .nft_approve(
+            nft_issuer_contract.id(),
+            &DUMMY_NFT1_ID.to_string(),
+            env.defuse.id(),
+        )
+        .await
+        .unwrap();
+
+    let approval_id = *owner
+        .nft_token(nft_issuer_contract.id(), &DUMMY_NFT1_ID.to_string())
+        .await
+        .unwrap()
+        .unwrap()
+        .approved_account_ids
+        .unwrap()
+        .get(env.defuse.id())
+        .unwrap();
+
+    // only the owner can deposit approved NFT
+    other_user
+        .defuse_nft_deposit_approved(
+            env.defuse.id(),
+            nft_issuer_contract.id(),
+            &DUMMY_NFT1_ID.to_string(),
+            approval_id,
+            None,
+        )
+        .await
+        .unwrap_err();
+
+    // approval_id must match
+    owner
+        .defuse_nft_deposit_approved(
+            env.defuse.id(),
+            nft_issuer_contract.id(),
+            &DUMMY_NFT1_ID.to_string(),
+            approval_id + 1,
+            None,
+        )
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        owner
+            .nft_token(nft_issuer_contract.id(), &DUMMY_NFT1_ID.to_string())
+            .await
+            .unwrap()
+            .unwrap()
+            .owner_id,
+        *owner.id()
+    );
+
+    assert!(
+        owner
+            .defuse_nft_deposit_approved(
+                env.defuse.id(),
+                nft_issuer_contract.id(),
+                &DUMMY_NFT1_ID.to_string(),
+                approval_id,
+                DepositMessage::new(other_user.id().clone()),
+            )
+            .await
+            .unwrap()
+    );
+
+    assert_eq!(
+        owner
+            .nft_token(nft_issuer_contract.id(), &DUMMY_NFT1_ID.to_string())
+            .await
+            .unwrap()
+            .unwrap()
+            .owner_id,
+        *env.defuse.id()
+    );
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(owner.id(), &nft1_mt_token_id.to_string())
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        env.defuse
+            .mt_balance_of(other_user.id(), &nft1_mt_token_id.to_string())
+            .await
+            .unwrap(),
+        1
+    );
+}
@@ -0,0 +1,56 @@
+use defuse::tokens::DepositMessage;
+use near_contract_standards::non_fungible_token::TokenId;
+use near_sdk::{AccountId, NearToken};
+use serde_json::json;
+
+pub trait DefuseNftApprovedDepositor {
+    async fn defuse_nft_deposit_approved(
+        &self,
+        defuse_id: &AccountId,
+        token: &AccountId,
+        token_id: &TokenId,
+        approval_id: u64,
+        msg: impl Into<Option<DepositMessage>>,
+    ) -> anyhow::Result<bool>;
+}
+
+impl DefuseNftApprovedDepositor for near_workspaces::Account {
+    async fn defuse_nft_deposit_approved(
+        &self,
+        defuse_id: &AccountId,
+        token: &AccountId,
+        token_id: &TokenId,
+        approval_id: u64,
+        msg: impl Into<Option<DepositMessage>>,
+    ) -> anyhow::Result<bool> {
+        self.call(defuse_id, "nft_deposit_approved")
+            .deposit(NearToken::from_yoctonear(1))
+            .args_json(json!({
+                "token": token,
+                "token_id": token_id,
+                "approval_id": approval_id,
+                "msg": msg.into().as_ref().map(ToString::to_string),
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?
+            .json()
+            .map_err(Into::into)
+    }
+}
+
+impl DefuseNftApprovedDepositor for near_workspaces::Contract {
+    async fn defuse_nft_deposit_approved(
+        &self,
+        defuse_id: &AccountId,
+        token: &AccountId,
+        token_id: &TokenId,
+        approval_id: u64,
+        msg: impl Into<Option<DepositMessage>>,
+    ) -> anyhow::Result<bool> {
+        self.as_account()
+            .defuse_nft_deposit_approved(defuse_id, token, token_id, approval_id, msg)
+            .await
+    }
+}
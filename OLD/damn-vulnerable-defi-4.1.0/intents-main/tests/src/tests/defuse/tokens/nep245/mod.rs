@@ -3,14 +3,17 @@ mod mt_transfer_resolve_gas;
 pub mod traits;
 
 use crate::tests::defuse::DefuseExt;
-use crate::tests::defuse::tokens::nep245::traits::DefuseMtWithdrawer;
+use crate::tests::defuse::tokens::nep245::traits::{DefuseMtApprover, DefuseMtWithdrawer};
 use crate::{tests::defuse::env::Env, utils::mt::MtExt};
 use defuse::contract::config::{DefuseConfig, RolesConfig};
 use defuse::core::fees::{FeesConfig, Pips};
 use defuse::core::token_id::TokenId;
 use defuse::core::token_id::nep141::Nep141TokenId;
 use defuse::core::token_id::nep245::Nep245TokenId;
+use defuse::intents::EventVerbosity;
 use defuse::nep245::Token;
+use defuse_test_utils::asserts::ResultAssertsExt;
+use near_sdk::NearToken;
 use rstest::rstest;
 
 #[tokio::test]
@@ -492,6 +495,75 @@ async fn multitoken_enumeration_with_ranges(#[values(false, true)] no_registrati
     }
 }
 
+#[tokio::test]
+#[rstest]
+async fn multitoken_enumeration_order() {
+    use crate::tests::defuse::tokens::nep141::traits::DefuseFtWithdrawer;
+
+    let env = Env::builder().create_unique_users().build().await;
+
+    let (user, ft1, ft2, ft3, ft4) = futures::join!(
+        env.create_user(),
+        env.create_token(),
+        env.create_token(),
+        env.create_token(),
+        env.create_token()
+    );
+
+    env.initial_ft_storage_deposit(vec![user.id()], vec![&ft1, &ft2, &ft3, &ft4])
+        .await;
+
+    let token = |ft: &near_sdk::AccountId| Token {
+        token_id: TokenId::from(Nep141TokenId::new(ft.clone())).to_string(),
+        owner_id: None,
+    };
+
+    // tokens are enumerated in deposit order
+    for ft in [&ft1, &ft2, &ft3] {
+        env.defuse_ft_deposit_to(ft, 1000, user.id()).await.unwrap();
+    }
+    assert_eq!(
+        user.mt_tokens_for_owner(env.defuse.id(), user.id(), ..)
+            .await
+            .unwrap(),
+        [token(&ft1), token(&ft2), token(&ft3)]
+    );
+
+    // fully withdrawn token is replaced by the last one
+    assert_eq!(
+        user.defuse_ft_withdraw(env.defuse.id(), &ft1, user.id(), 1000, None, None)
+            .await
+            .unwrap(),
+        1000
+    );
+    assert_eq!(
+        user.mt_tokens_for_owner(env.defuse.id(), user.id(), ..)
+            .await
+            .unwrap(),
+        [token(&ft3), token(&ft2)]
+    );
+
+    // new tokens are appended
+    env.defuse_ft_deposit_to(&ft4, 1000, user.id())
+        .await
+        .unwrap();
+    let expected = [token(&ft3), token(&ft2), token(&ft4)];
+    for _ in 0..2 {
+        assert_eq!(
+            user.mt_tokens_for_owner(env.defuse.id(), user.id(), ..)
+                .await
+                .unwrap(),
+            expected
+        );
+    }
+    assert_eq!(
+        user.mt_tokens_for_owner(env.defuse.id(), user.id(), 1..)
+            .await
+            .unwrap(),
+        expected[1..]
+    );
+}
+
 #[tokio::test]
 #[rstest]
 async fn multitoken_withdrawals() {
@@ -522,6 +594,12 @@ async fn multitoken_withdrawals() {
                     fee_collector: env.id().clone(),
                 },
                 roles: RolesConfig::default(),
+                max_tokens_per_account: None,
+                event_verbosity: EventVerbosity::default(),
+                registration_reserve: None,
+                max_nonce_ttl: None,
+                require_bounded_deadline: false,
+                reject_legacy_nonces: false,
             },
             false,
         )
@@ -731,6 +809,7 @@ async fn multitoken_withdrawals() {
                 defuse2.id(),
                 env.defuse.id(),
                 user2.id(),
+                None,
                 tokens.iter().cloned().map(|v| v.0).collect(),
                 tokens.iter().map(|v| v.1).collect(),
                 None,
@@ -803,6 +882,7 @@ async fn multitoken_withdrawals() {
                 defuse2.id(),
                 env.defuse.id(),
                 user2.id(),
+                None,
                 tokens.iter().cloned().map(|v| v.0).collect(),
                 tokens.iter().map(|v| v.1).collect(),
                 None,
@@ -833,6 +913,7 @@ async fn multitoken_withdrawals() {
                 defuse2.id(),
                 env.defuse.id(),
                 user2.id(),
+                None,
                 tokens.iter().cloned().map(|v| v.0).collect(),
                 tokens.iter().map(|v| v.1).collect(),
                 None,
@@ -895,3 +976,274 @@ async fn multitoken_withdrawals() {
         );
     }
 }
+
+#[tokio::test]
+#[rstest]
+async fn multitoken_withdrawal_to_multiple_receivers() {
+    let env = Env::builder().create_unique_users().build().await;
+
+    let (user1, user2, user3, ft1, ft2) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_token(),
+        env.create_token()
+    );
+
+    env.initial_ft_storage_deposit(vec![user1.id()], vec![&ft1, &ft2])
+        .await;
+
+    let defuse2 = env
+        .deploy_defuse(
+            "defuse2",
+            DefuseConfig {
+                wnear_id: env.wnear.id().clone(),
+                fees: FeesConfig {
+                    fee: Pips::ZERO,
+                    fee_collector: env.id().clone(),
+                },
+                roles: RolesConfig::default(),
+                max_tokens_per_account: None,
+                event_verbosity: EventVerbosity::default(),
+                registration_reserve: None,
+                max_nonce_ttl: None,
+                require_bounded_deadline: false,
+                reject_legacy_nonces: false,
+            },
+            false,
+        )
+        .await
+        .unwrap();
+
+    let ft1_id = TokenId::Nep141(Nep141TokenId::new(ft1.clone()));
+    let ft2_id = TokenId::Nep141(Nep141TokenId::new(ft2.clone()));
+
+    // fund user1 in defuse2 with tokens of defuse
+    for (ft, token_id) in [(&ft1, &ft1_id), (&ft2, &ft2_id)] {
+        env.defuse_ft_deposit_to(ft, 1000, user1.id())
+            .await
+            .unwrap();
+
+        user1
+            .mt_transfer_call(
+                env.defuse.id(),
+                defuse2.id(),
+                &token_id.to_string(),
+                100,
+                None,
+                None,
+                user1.id().to_string(),
+            )
+            .await
+            .unwrap();
+    }
+
+    // mismatched length of receivers
+    user1
+        .defuse_mt_withdraw(
+            defuse2.id(),
+            env.defuse.id(),
+            user2.id(),
+            Some(vec![None]),
+            vec![ft1_id.to_string(), ft2_id.to_string()],
+            vec![10, 20],
+            None,
+        )
+        .await
+        .unwrap_err();
+
+    // ft1 goes to default receiver, while ft2 goes to user3
+    let (amounts, _test_log) = user1
+        .defuse_mt_withdraw(
+            defuse2.id(),
+            env.defuse.id(),
+            user2.id(),
+            Some(vec![None, Some(user3.id().clone())]),
+            vec![ft1_id.to_string(), ft2_id.to_string()],
+            vec![10, 20],
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(amounts, [10, 20]);
+
+    for (account_id, token_id, expected) in [
+        (user2.id(), &ft1_id, 10),
+        (user2.id(), &ft2_id, 0),
+        (user3.id(), &ft1_id, 0),
+        (user3.id(), &ft2_id, 20),
+    ] {
+        assert_eq!(
+            env.defuse
+                .mt_balance_of(account_id, &token_id.to_string())
+                .await
+                .unwrap(),
+            expected,
+            "{account_id}: {token_id}",
+        );
+    }
+
+    for (token_id, expected) in [(&ft1_id, 90), (&ft2_id, 80)] {
+        assert_eq!(
+            defuse2
+                .mt_balance_of(
+                    user1.id(),
+                    &TokenId::Nep245(
+                        Nep245TokenId::new(env.defuse.id().to_owned(), token_id.to_string())
+                            .unwrap()
+                    )
+                    .to_string(),
+                )
+                .await
+                .unwrap(),
+            expected
+        );
+    }
+}
+
+#[tokio::test]
+#[rstest]
+async fn multitoken_approvals() {
+    let env = Env::new().await;
+
+    let (owner, spender, receiver, ft) = futures::join!(
+        env.create_user(),
+        env.create_user(),
+        env.create_user(),
+        env.create_token()
+    );
+
+    env.initial_ft_storage_deposit(vec![owner.id()], vec![&ft])
+        .await;
+    env.defuse_ft_deposit_to(&ft, 1000, owner.id())
+        .await
+        .unwrap();
+
+    let token_id = TokenId::from(Nep141TokenId::new(ft.clone())).to_string();
+
+    // transfer without approval
+    spender
+        .mt_transfer(
+            env.defuse.id(),
+            receiver.id(),
+            &token_id,
+            100,
+            Some((owner.id().clone(), 0)),
+            None,
+        )
+        .await
+        .assert_err_contains("is not approved");
+
+    // storage of new approvals must be paid for
+    owner
+        .defuse_mt_approve(
+            env.defuse.id(),
+            vec![token_id.clone()],
+            vec![300],
+            spender.id(),
+            NearToken::from_yoctonear(1),
+        )
+        .await
+        .assert_err_contains("insufficient deposit for storage");
+
+    let balance_before = owner.view_account().await.unwrap().balance;
+    owner
+        .defuse_mt_approve(
+            env.defuse.id(),
+            vec![token_id.clone()],
+            vec![300],
+            spender.id(),
+            NearToken::from_near(1),
+        )
+        .await
+        .unwrap();
+    // excess is refunded
+    assert!(
+        balance_before.saturating_sub(owner.view_account().await.unwrap().balance)
+            < NearToken::from_millinear(100)
+    );
+    let approval = spender
+        .defuse_mt_approval(env.defuse.id(), owner.id(), &token_id, spender.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(approval.amount.0, 300);
+
+    // transfer within approval
+    spender
+        .mt_transfer(
+            env.defuse.id(),
+            receiver.id(),
+            &token_id,
+            200,
+            Some((owner.id().clone(), approval.approval_id)),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), owner.id(), &token_id)
+            .await
+            .unwrap(),
+        800
+    );
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), receiver.id(), &token_id)
+            .await
+            .unwrap(),
+        200
+    );
+
+    // approved transfers can't notify the receiver
+    spender
+        .mt_transfer_call(
+            env.defuse.id(),
+            receiver.id(),
+            &token_id,
+            100,
+            Some((owner.id().clone(), approval.approval_id)),
+            None,
+            String::new(),
+        )
+        .await
+        .assert_err_contains("approvals are not supported");
+
+    // transfer beyond approval
+    spender
+        .mt_transfer(
+            env.defuse.id(),
+            receiver.id(),
+            &token_id,
+            101,
+            Some((owner.id().clone(), approval.approval_id)),
+            None,
+        )
+        .await
+        .assert_err_contains("is not approved");
+
+    // spend the rest of approval
+    spender
+        .mt_transfer(
+            env.defuse.id(),
+            receiver.id(),
+            &token_id,
+            100,
+            Some((owner.id().clone(), approval.approval_id)),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        spender
+            .defuse_mt_approval(env.defuse.id(), owner.id(), &token_id, spender.id())
+            .await
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), owner.id(), &token_id)
+            .await
+            .unwrap(),
+        700
+    );
+}
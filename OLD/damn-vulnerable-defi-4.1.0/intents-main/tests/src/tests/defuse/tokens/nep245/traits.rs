@@ -1,5 +1,7 @@
-use defuse::nep245::TokenId;
-use near_sdk::{AccountId, NearToken, json_types::U128};
+#![allow(clippy::too_many_arguments)]
+
+use defuse::nep245::{TokenId, approval::Approval};
+use near_sdk::{AccountId, Gas, NearToken, json_types::U128};
 use serde_json::json;
 
 use crate::utils::test_log::TestLog;
@@ -10,6 +12,7 @@ pub trait DefuseMtWithdrawer {
         defuse_id: &AccountId,
         token_id: &AccountId,
         receiver_id: &AccountId,
+        receiver_ids: Option<Vec<Option<AccountId>>>,
         token_ids: Vec<TokenId>,
         amounts: Vec<u128>,
         msg: Option<String>,
@@ -22,6 +25,7 @@ impl DefuseMtWithdrawer for near_workspaces::Account {
         defuse_id: &AccountId,
         token: &AccountId,
         receiver_id: &AccountId,
+        receiver_ids: Option<Vec<Option<AccountId>>>,
         token_ids: Vec<TokenId>,
         amounts: Vec<u128>,
         msg: Option<String>,
@@ -34,7 +38,8 @@ impl DefuseMtWithdrawer for near_workspaces::Account {
                 "receiver_id": receiver_id,
                 "token_ids": token_ids,
                 "amounts": amounts.into_iter().map(U128).collect::<Vec<_>>(),
-                "msg": msg
+                "msg": msg,
+                "receiver_ids": receiver_ids,
             }))
             .max_gas()
             .transact()
@@ -50,3 +55,102 @@ impl DefuseMtWithdrawer for near_workspaces::Account {
         ))
     }
 }
+
+pub trait DefuseMtApprover {
+    async fn defuse_mt_approve(
+        &self,
+        defuse_id: &AccountId,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<u128>,
+        account_id: &AccountId,
+        deposit: NearToken,
+    ) -> anyhow::Result<()>;
+
+    async fn defuse_mt_approval(
+        &self,
+        defuse_id: &AccountId,
+        owner_id: &AccountId,
+        token_id: &TokenId,
+        account_id: &AccountId,
+    ) -> anyhow::Result<Option<Approval>>;
+}
+
+impl DefuseMtApprover for near_workspaces::Account {
+    async fn defuse_mt_approve(
+        &self,
+        defuse_id: &AccountId,
+        token_ids: Vec<TokenId>,
+        amounts: Vec<u128>,
+        account_id: &AccountId,
+        deposit: NearToken,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_id, "mt_approve")
+            .deposit(deposit)
+            .args_json(json!({
+                "token_ids": token_ids,
+                "amounts": amounts.into_iter().map(U128).collect::<Vec<_>>(),
+                "account_id": account_id,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        Ok(())
+    }
+
+    async fn defuse_mt_approval(
+        &self,
+        defuse_id: &AccountId,
+        owner_id: &AccountId,
+        token_id: &TokenId,
+        account_id: &AccountId,
+    ) -> anyhow::Result<Option<Approval>> {
+        self.view(defuse_id, "mt_approval")
+            .args_json(json!({
+                "owner_id": owner_id,
+                "token_id": token_id,
+                "account_id": account_id,
+            }))
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+}
+
+pub trait DefuseMtOnTransferGas {
+    async fn mt_on_transfer_default_gas(&self, defuse_id: &AccountId) -> anyhow::Result<Gas>;
+
+    async fn set_mt_on_transfer_default_gas(
+        &self,
+        defuse_id: &AccountId,
+        gas: Option<Gas>,
+    ) -> anyhow::Result<()>;
+}
+
+impl DefuseMtOnTransferGas for near_workspaces::Account {
+    async fn mt_on_transfer_default_gas(&self, defuse_id: &AccountId) -> anyhow::Result<Gas> {
+        self.view(defuse_id, "mt_on_transfer_default_gas")
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn set_mt_on_transfer_default_gas(
+        &self,
+        defuse_id: &AccountId,
+        gas: Option<Gas>,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_id, "set_mt_on_transfer_default_gas")
+            .deposit(NearToken::from_yoctonear(1))
+            .args_json(json!({
+                "gas": gas,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,197 @@
+use defuse::{
+    core::token_id::{TokenId, nep141::Nep141TokenId, nep171::Nep171TokenId},
+    tokens::{DepositMessage, receipt::DepositReceipt},
+};
+use near_sdk::{AccountId, NearToken, json_types::U128};
+use rstest::rstest;
+use serde_json::json;
+
+use crate::{
+    tests::{
+        defuse::{env::Env, tokens::nep141::traits::DefuseFtReceiver},
+        poa::factory::PoAFactoryExt,
+    },
+    utils::mt::MtExt,
+};
+
+#[tokio::test]
+#[rstest]
+#[trace]
+async fn deposit_with_receipt() {
+    let env = Env::builder().build().await;
+
+    let (user, other_user, ft) =
+        futures::join!(env.create_user(), env.create_user(), env.create_token());
+
+    env.initial_ft_storage_deposit(vec![user.id()], vec![&ft])
+        .await;
+
+    env.poa_factory_ft_deposit(
+        env.poa_factory.id(),
+        &env.poa_ft_name(&ft),
+        user.id(),
+        1000,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        user.defuse_ft_deposit(
+            env.defuse.id(),
+            &ft,
+            1000,
+            DepositMessage::new(user.id().clone()).with_mint_receipt(),
+        )
+        .await
+        .unwrap(),
+        1000
+    );
+
+    let ft_id = TokenId::from(Nep141TokenId::new(ft.clone()));
+    let receipt_id =
+        TokenId::from(Nep171TokenId::new(env.defuse.id().clone(), "0".to_string()).unwrap());
+
+    // receiver holds the receipt instead of deposited tokens
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), user.id(), &ft_id.to_string())
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        env.mt_tokens_for_owner(env.defuse.id(), user.id(), ..)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|token| token.token_id)
+            .collect::<Vec<_>>(),
+        [receipt_id.to_string()]
+    );
+    assert_eq!(
+        env.defuse
+            .deposit_receipt(env.defuse.id(), "0")
+            .await
+            .unwrap(),
+        Some(DepositReceipt {
+            token_id: ft_id.clone(),
+            amount: U128(1000),
+        })
+    );
+
+    // receipts are transferable
+    user.mt_transfer(
+        env.defuse.id(),
+        other_user.id(),
+        &receipt_id.to_string(),
+        1,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    user.redeem_deposit_receipt(env.defuse.id(), "0")
+        .await
+        .expect_err("receipt was transferred");
+
+    other_user
+        .redeem_deposit_receipt(env.defuse.id(), "0")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), other_user.id(), &ft_id.to_string())
+            .await
+            .unwrap(),
+        1000
+    );
+    assert_eq!(
+        env.mt_contract_balance_of(env.defuse.id(), other_user.id(), &receipt_id.to_string())
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        env.defuse
+            .deposit_receipt(env.defuse.id(), "0")
+            .await
+            .unwrap(),
+        None
+    );
+
+    other_user
+        .redeem_deposit_receipt(env.defuse.id(), "0")
+        .await
+        .expect_err("receipt was already redeemed");
+}
+
+pub trait DepositReceiptManagerExt {
+    async fn deposit_receipt(
+        &self,
+        defuse_contract_id: &AccountId,
+        receipt_id: &str,
+    ) -> anyhow::Result<Option<DepositReceipt>>;
+
+    async fn redeem_deposit_receipt(
+        &self,
+        defuse_contract_id: &AccountId,
+        receipt_id: &str,
+    ) -> anyhow::Result<()>;
+}
+
+impl DepositReceiptManagerExt for near_workspaces::Account {
+    async fn deposit_receipt(
+        &self,
+        defuse_contract_id: &AccountId,
+        receipt_id: &str,
+    ) -> anyhow::Result<Option<DepositReceipt>> {
+        self.view(defuse_contract_id, "deposit_receipt")
+            .args_json(json!({
+                "receipt_id": receipt_id,
+            }))
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn redeem_deposit_receipt(
+        &self,
+        defuse_contract_id: &AccountId,
+        receipt_id: &str,
+    ) -> anyhow::Result<()> {
+        self.call(defuse_contract_id, "redeem_deposit_receipt")
+            .deposit(NearToken::from_yoctonear(1))
+            .args_json(json!({
+                "receipt_id": receipt_id,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+}
+
+impl DepositReceiptManagerExt for near_workspaces::Contract {
+    async fn deposit_receipt(
+        &self,
+        defuse_contract_id: &AccountId,
+        receipt_id: &str,
+    ) -> anyhow::Result<Option<DepositReceipt>> {
+        self.as_account()
+            .deposit_receipt(defuse_contract_id, receipt_id)
+            .await
+    }
+
+    async fn redeem_deposit_receipt(
+        &self,
+        defuse_contract_id: &AccountId,
+        receipt_id: &str,
+    ) -> anyhow::Result<()> {
+        self.as_account()
+            .redeem_deposit_receipt(defuse_contract_id, receipt_id)
+            .await
+    }
+}
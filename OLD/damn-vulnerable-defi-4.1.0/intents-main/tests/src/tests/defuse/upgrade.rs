@@ -82,6 +82,26 @@ async fn upgrade(ed25519_pk: PublicKey, secp256k1_pk: PublicKey, p256_pk: Public
     }
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_upgrade_preserves_fees() {
+    let fee = Pips::from_pips(123).unwrap();
+    let fee_collector: AccountId = "fee-collector.near".parse().unwrap();
+
+    // fees are set on the legacy contract before upgrade
+    let env = Env::builder()
+        .fee(fee)
+        .fee_collector(fee_collector.clone())
+        .build_with_migration()
+        .await;
+
+    assert_eq!(env.defuse.fee(env.defuse.id()).await.unwrap(), fee);
+    assert_eq!(
+        env.defuse.fee_collector(env.defuse.id()).await.unwrap(),
+        fee_collector
+    );
+}
+
 #[rstest]
 #[tokio::test]
 async fn test_upgrade_with_persistence() {
@@ -1,5 +1,6 @@
 use defuse::core::{
     crypto::Payload,
+    erc191::{Erc191Payload, SignedErc191Payload},
     nep413::{Nep413Payload, SignedNep413Payload},
     sep53::{Sep53Payload, SignedSep53Payload},
     ton_connect::{SignedTonConnectPayload, TonConnectPayload},
@@ -12,6 +13,7 @@ pub trait Signer {
     fn sign_nep413(&self, payload: Nep413Payload) -> SignedNep413Payload;
     fn sign_ton_connect(&self, payload: TonConnectPayload) -> SignedTonConnectPayload;
     fn sign_sep53(&self, payload: Sep53Payload) -> SignedSep53Payload;
+    fn sign_erc191(&self, payload: Erc191Payload) -> SignedErc191Payload;
 }
 
 impl Signer for Account {
@@ -64,4 +66,19 @@ impl Signer for Account {
             _ => unreachable!(),
         }
     }
+
+    fn sign_erc191(&self, payload: Erc191Payload) -> SignedErc191Payload {
+        let secret_key = Signer::secret_key(self);
+
+        match secret_key.sign(&payload.hash()) {
+            signature @ near_crypto::Signature::SECP256K1(_) => SignedErc191Payload {
+                payload,
+                // skip key type prefix
+                signature: near_sdk::borsh::to_vec(&signature).unwrap()[1..]
+                    .try_into()
+                    .unwrap(),
+            },
+            near_crypto::Signature::ED25519(_) => unreachable!(),
+        }
+    }
 }
@@ -0,0 +1,53 @@
+use defuse::core::events::DefuseEvent;
+use near_sdk::AccountIdRef;
+use near_workspaces::{operations::CallTransaction, result::ExecutionFinalResult};
+
+pub trait DefuseEventsExt {
+    /// Invokes `on_event` for every [`DefuseEvent`] emitted by `contract_id`
+    /// in the order receipts were executed.
+    fn for_each_defuse_event(
+        &self,
+        contract_id: &AccountIdRef,
+        on_event: impl FnMut(DefuseEvent<'_>),
+    );
+}
+
+impl DefuseEventsExt for ExecutionFinalResult {
+    fn for_each_defuse_event(
+        &self,
+        contract_id: &AccountIdRef,
+        mut on_event: impl FnMut(DefuseEvent<'_>),
+    ) {
+        self.outcomes()
+            .into_iter()
+            .filter(|outcome| outcome.executor_id == *contract_id)
+            .flat_map(|outcome| &outcome.logs)
+            .filter_map(|log| DefuseEvent::from_near_sdk_log(log))
+            .for_each(&mut on_event);
+    }
+}
+
+pub trait CallWithEventsExt {
+    /// Executes the call and feeds every [`DefuseEvent`] emitted by
+    /// `contract_id` while executing it to `on_event`.
+    ///
+    /// Sandbox only exposes logs of finalized transactions, so events are
+    /// replayed receipt by receipt once the call is done.
+    async fn transact_with_defuse_events(
+        self,
+        contract_id: &AccountIdRef,
+        on_event: impl FnMut(DefuseEvent<'_>),
+    ) -> anyhow::Result<ExecutionFinalResult>;
+}
+
+impl CallWithEventsExt for CallTransaction {
+    async fn transact_with_defuse_events(
+        self,
+        contract_id: &AccountIdRef,
+        on_event: impl FnMut(DefuseEvent<'_>),
+    ) -> anyhow::Result<ExecutionFinalResult> {
+        let result = self.transact().await?;
+        result.for_each_defuse_event(contract_id, on_event);
+        Ok(result)
+    }
+}
@@ -3,6 +3,7 @@
 pub mod account;
 pub mod acl;
 pub mod crypto;
+pub mod events;
 pub mod fixtures;
 pub mod ft;
 pub mod mt;
@@ -12,6 +13,7 @@ pub mod payload;
 mod sandbox;
 pub mod storage_management;
 pub mod test_log;
+pub mod webauthn;
 pub mod wnear;
 
 pub use sandbox::*;
@@ -50,6 +50,13 @@ pub trait NftExt {
         token_id: &TokenId,
     ) -> anyhow::Result<Option<Token>>;
 
+    async fn nft_approve(
+        &self,
+        collection: &AccountId,
+        token_id: &TokenId,
+        account_id: &AccountId,
+    ) -> anyhow::Result<()>;
+
     async fn self_nft_token(&self, token_id: &TokenId) -> anyhow::Result<Option<Token>>;
 }
 
@@ -161,6 +168,25 @@ impl NftExt for near_workspaces::Account {
     async fn self_nft_token(&self, token_id: &TokenId) -> anyhow::Result<Option<Token>> {
         self.nft_token(self.id(), token_id).await
     }
+
+    async fn nft_approve(
+        &self,
+        collection: &AccountId,
+        token_id: &TokenId,
+        account_id: &AccountId,
+    ) -> anyhow::Result<()> {
+        self.call(collection, "nft_approve")
+            .args_json(json!({
+                "token_id": token_id,
+                "account_id": account_id,
+            }))
+            .deposit(NearToken::from_millinear(10))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
 }
 
 impl NftExt for Contract {
@@ -222,4 +248,15 @@ impl NftExt for Contract {
     async fn self_nft_token(&self, token_id: &TokenId) -> anyhow::Result<Option<Token>> {
         self.as_account().self_nft_token(token_id).await
     }
+
+    async fn nft_approve(
+        &self,
+        collection: &AccountId,
+        token_id: &TokenId,
+        account_id: &AccountId,
+    ) -> anyhow::Result<()> {
+        self.as_account()
+            .nft_approve(collection, token_id, account_id)
+            .await
+    }
 }
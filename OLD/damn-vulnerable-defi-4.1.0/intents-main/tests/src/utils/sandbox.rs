@@ -2,7 +2,7 @@ use anyhow::Result;
 use std::{fs, ops::Deref, path::Path};
 
 use near_sdk::AccountId;
-use near_workspaces::{Account, Contract, Network, Worker, types::NearToken};
+use near_workspaces::{Account, Contract, Worker, types::NearToken};
 
 pub fn read_wasm(path: impl AsRef<Path>) -> Vec<u8> {
     let filename = Path::new(env!("CARGO_MANIFEST_DIR"))
@@ -33,11 +33,16 @@ impl Sandbox {
         self.worker.view_block().await.unwrap().height()
     }
 
+    /// Returns timestamp of the latest block in nanoseconds
+    pub async fn block_timestamp(&self) -> u64 {
+        self.worker.view_block().await.unwrap().timestamp()
+    }
+
     pub async fn skip_blocks(&self, num_blocks: u64) {
         self.worker.fast_forward(num_blocks).await.unwrap();
     }
 
-    pub const fn worker(&self) -> &Worker<impl Network + 'static> {
+    pub const fn worker(&self) -> &Worker<near_workspaces::network::Sandbox> {
         &self.worker
     }
 
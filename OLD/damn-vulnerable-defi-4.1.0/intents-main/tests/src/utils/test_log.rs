@@ -76,6 +76,13 @@ impl TestLog {
         &self.logs
     }
 
+    pub fn receipt_failure_errors(&self) -> impl Iterator<Item = &str> {
+        self.receipt_failure_errors
+            .iter()
+            .map(String::as_str)
+            .filter(|err| !err.is_empty())
+    }
+
     pub const fn total_gas_burnt(&self) -> &Gas {
         &self.gas_burnt_in_tx
     }
@@ -84,3 +91,50 @@ impl TestLog {
         &self.logs_and_gas_burnt_in_receipts
     }
 }
+
+/// Asserts that events recorded by `simulate_intents` are the same as
+/// ones emitted by `execute_intents`. NEP-245 events and plain logs are
+/// only emitted on execution, so they are ignored.
+#[track_caller]
+pub fn assert_simulated_logs_match(simulated: &[String], executed: &[String]) {
+    fn dip4_events(logs: &[String]) -> Vec<serde_json::Value> {
+        logs.iter()
+            .filter_map(|log| log.strip_prefix("EVENT_JSON:"))
+            .map(|json| serde_json::from_str::<serde_json::Value>(json).unwrap())
+            .filter(|event| event["standard"] == "dip4")
+            .collect()
+    }
+
+    assert_eq!(
+        dip4_events(simulated),
+        dip4_events(executed),
+        "simulated logs differ from executed ones"
+    );
+}
+
+#[test]
+fn simulated_logs_match_ignoring_execution_only_logs() {
+    let transfer = r#"EVENT_JSON:{"standard":"dip4","version":"0.3.0","event":"transfer","data":[{"intent_hash":"11111111111111111111111111111111","account_id":"alice.near","receiver_id":"bob.near","tokens":{"nep141:ft.near":"100"}}]}"#;
+    let mt_transfer = r#"EVENT_JSON:{"standard":"nep245","version":"1.0.0","event":"mt_transfer","data":[{"old_owner_id":"alice.near","new_owner_id":"bob.near","token_ids":["nep141:ft.near"],"amounts":["100"]}]}"#;
+
+    assert_simulated_logs_match(
+        &[transfer.to_string()],
+        &[
+            mt_transfer.to_string(),
+            "plain log".to_string(),
+            transfer.to_string(),
+        ],
+    );
+}
+
+#[test]
+#[should_panic(expected = "simulated logs differ from executed ones")]
+fn simulated_logs_mismatch() {
+    let transfer = |amount: u128| {
+        format!(
+            r#"EVENT_JSON:{{"standard":"dip4","version":"0.3.0","event":"transfer","data":[{{"intent_hash":"11111111111111111111111111111111","account_id":"alice.near","receiver_id":"bob.near","tokens":{{"nep141:ft.near":"{amount}"}}}}]}}"#
+        )
+    };
+
+    assert_simulated_logs_match(&[transfer(100)], &[transfer(99)]);
+}
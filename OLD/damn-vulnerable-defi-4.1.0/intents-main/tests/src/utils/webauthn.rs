@@ -0,0 +1,159 @@
+use defuse::core::{
+    Deadline, Nonce,
+    crypto::{Payload, PublicKey},
+    payload::{DefusePayload, multi::MultiPayload, webauthn::SignedWebAuthnPayload},
+    webauthn::{ClientDataType, CollectedClientData, PayloadSignature, Signature},
+};
+use defuse_test_utils::random::Rng;
+use near_sdk::{AccountId, env, serde::Serialize, serde_json};
+use p256::ecdsa::{SigningKey, signature::Signer as _};
+
+/// Passkey which signs payloads the same way browsers do with
+/// `navigator.credentials.get()`, so that `webauthn` payloads don't need
+/// to be generated externally
+pub enum WebAuthnSigner {
+    /// COSE `EdDSA` (-8) algorithm
+    Ed25519(near_crypto::SecretKey),
+    /// COSE ES256 (-7) algorithm
+    P256(SigningKey),
+}
+
+impl WebAuthnSigner {
+    const RP_ID: &str = "intents.test.near";
+    const ORIGIN: &str = "https://intents.test.near";
+
+    /// User Present and User Verified
+    const FLAGS: u8 = 0b0000_0101;
+
+    pub fn random_ed25519() -> Self {
+        Self::Ed25519(near_crypto::SecretKey::from_random(
+            near_crypto::KeyType::ED25519,
+        ))
+    }
+
+    pub fn random_p256(mut rng: impl Rng) -> Self {
+        loop {
+            // not every 32 bytes is a valid scalar
+            if let Ok(signing_key) = SigningKey::from_slice(&rng.random::<[u8; 32]>()) {
+                return Self::P256(signing_key);
+            }
+        }
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        match self {
+            Self::Ed25519(secret_key) => match secret_key.public_key() {
+                near_crypto::PublicKey::ED25519(pk) => PublicKey::Ed25519(pk.0),
+                near_crypto::PublicKey::SECP256K1(_) => unreachable!(),
+            },
+            Self::P256(signing_key) => PublicKey::P256(
+                signing_key
+                    .verifying_key()
+                    .to_encoded_point(false)
+                    .as_bytes()[1..]
+                    .try_into()
+                    .unwrap(),
+            ),
+        }
+    }
+
+    /// Implicit account controlled by the passkey
+    pub fn account_id(&self) -> AccountId {
+        self.public_key().to_implicit_account_id()
+    }
+
+    /// [authenticatorData](https://w3c.github.io/webauthn/#authenticator-data):
+    /// `rpIdHash || flags || signCount`
+    fn authenticator_data() -> Vec<u8> {
+        [
+            env::sha256_array(Self::RP_ID.as_bytes()).as_slice(),
+            &[Self::FLAGS],
+            &0u32.to_be_bytes(),
+        ]
+        .concat()
+    }
+
+    pub fn sign(&self, payload: String) -> SignedWebAuthnPayload {
+        let mut signed = SignedWebAuthnPayload {
+            payload,
+            signature: PayloadSignature {
+                authenticator_data: Self::authenticator_data(),
+                client_data_json: String::new(),
+                signature: Signature::Ed25519 {
+                    public_key: [0; 32],
+                    signature: [0; 64],
+                },
+            },
+        };
+
+        // challenge is a hash of the payload
+        signed.signature.client_data_json = serde_json::to_string(&CollectedClientData {
+            typ: ClientDataType::Get,
+            challenge: signed.hash().to_vec(),
+            origin: Self::ORIGIN.to_string(),
+        })
+        .unwrap();
+
+        let message = [
+            signed.signature.authenticator_data.as_slice(),
+            &env::sha256_array(signed.signature.client_data_json.as_bytes()),
+        ]
+        .concat();
+
+        signed.signature.signature = match self {
+            Self::Ed25519(secret_key) => match (secret_key.sign(&message), self.public_key()) {
+                (near_crypto::Signature::ED25519(signature), PublicKey::Ed25519(public_key)) => {
+                    Signature::Ed25519 {
+                        public_key,
+                        signature: signature.to_bytes(),
+                    }
+                }
+                _ => unreachable!(),
+            },
+            Self::P256(signing_key) => {
+                let signature: p256::ecdsa::Signature = signing_key.sign(&message);
+                let PublicKey::P256(public_key) = self.public_key() else {
+                    unreachable!()
+                };
+                Signature::P256 {
+                    public_key,
+                    // contract rejects malleable signatures
+                    signature: signature
+                        .normalize_s()
+                        .unwrap_or(signature)
+                        .to_bytes()
+                        .into(),
+                }
+            }
+        };
+
+        signed
+    }
+
+    pub fn sign_defuse_message<T>(
+        &self,
+        defuse_contract: &AccountId,
+        nonce: Nonce,
+        deadline: Deadline,
+        message: T,
+    ) -> MultiPayload
+    where
+        T: Serialize,
+    {
+        self.sign(
+            serde_json::to_string(&DefusePayload {
+                signer_id: self.account_id(),
+                verifying_contract: defuse_contract.clone(),
+                deadline,
+                nonce,
+                client_ref: None,
+                require_prepaid_gas: None,
+                priority: None,
+                signed_at: None,
+                message,
+            })
+            .unwrap(),
+        )
+        .into()
+    }
+}
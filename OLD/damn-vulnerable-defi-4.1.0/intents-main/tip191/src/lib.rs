@@ -4,7 +4,7 @@ use near_sdk::{env, near};
 use serde_with::serde_as;
 
 /// See [TIP-191](https://github.com/tronprotocol/tips/blob/master/tip-191.md)
-#[near(serializers = [json])]
+#[near(serializers = [borsh, json])]
 #[derive(Debug, Clone)]
 pub struct Tip191Payload(pub String);
 
@@ -36,7 +36,7 @@ impl Payload for Tip191Payload {
     not(all(feature = "abi", not(target_arch = "wasm32"))),
     serde_as(schemars = false)
 )]
-#[near(serializers = [json])]
+#[near(serializers = [borsh, json])]
 #[autoimpl(Deref using self.payload)]
 #[derive(Debug, Clone)]
 pub struct SignedTip191Payload {
@@ -1,4 +1,4 @@
-use super::MAX_ALLOWED_TOKEN_ID_LEN;
+use super::{MAX_ALLOWED_TOKEN_ID_LEN, MAX_WRAPPING_DEPTH};
 use near_account_id::ParseAccountError;
 
 #[derive(thiserror::Error, Debug)]
@@ -7,6 +7,10 @@ pub enum TokenIdError {
     AccountId(#[from] ParseAccountError),
     #[error(transparent)]
     ParseError(#[from] strum::ParseError),
+    #[error("missing ':' separator")]
+    MissingSeparator,
     #[error("token_id is too long. Max length is {MAX_ALLOWED_TOKEN_ID_LEN}, got {0}")]
     TokenIdTooLarge(usize),
+    #[error("token_id is wrapped too deep. Max depth is {MAX_WRAPPING_DEPTH}, got {0}")]
+    WrappingTooDeep(usize),
 }
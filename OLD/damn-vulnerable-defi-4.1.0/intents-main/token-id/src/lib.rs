@@ -16,6 +16,10 @@ use strum::{EnumDiscriminants, EnumIter, EnumString};
 
 const MAX_ALLOWED_TOKEN_ID_LEN: usize = 127;
 
+/// Maximum number of NEP-245 layers wrapping the underlying token,
+/// e.g. `nep245:defuse2.near:nep245:defuse1.near:nep141:ft.near` has depth 2
+pub const MAX_WRAPPING_DEPTH: usize = 4;
+
 #[cfg_attr(any(feature = "arbitrary", test), derive(arbitrary::Arbitrary))]
 #[derive(
     Clone,
@@ -43,6 +47,37 @@ pub enum TokenId {
     Nep245(Nep245TokenId),
 }
 
+impl TokenId {
+    /// Returns number of NEP-245 layers wrapping the underlying token
+    pub fn wrapping_depth(&self) -> usize {
+        let mut depth = 0;
+        let mut token_id = self.clone();
+        while let Self::Nep245(nep245) = token_id {
+            depth += 1;
+            let Ok(inner) = nep245.mt_token_id().parse() else {
+                break;
+            };
+            token_id = inner;
+        }
+        depth
+    }
+
+    /// Peels NEP-245 layers wrapping other token ids, e.g. ones deposited
+    /// from another Defuse instance, down to the underlying token. Layers
+    /// wrapping arbitrary multi-token ids are left as is.
+    #[must_use]
+    pub fn unwrap_to_base(self) -> Self {
+        let mut token_id = self;
+        while let Self::Nep245(ref nep245) = token_id {
+            let Ok(inner) = nep245.mt_token_id().parse() else {
+                break;
+            };
+            token_id = inner;
+        }
+        token_id
+    }
+}
+
 impl Debug for TokenId {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -67,14 +102,24 @@ impl Display for TokenId {
     }
 }
 
+/// Parses token id from its string form:
+///
+/// ```text
+/// token_id := "nep141:" contract_id
+///           | "nep171:" contract_id ":" nft_token_id
+///           | "nep245:" contract_id ":" mt_token_id
+/// ```
+///
+/// `nft_token_id` and `mt_token_id` are arbitrary strings (including `:`)
+/// of at most [`MAX_ALLOWED_TOKEN_ID_LEN`] bytes. `mt_token_id` can itself
+/// be a token id, i.e. NEP-245 tokens can wrap other tokens up to
+/// [`MAX_WRAPPING_DEPTH`] layers deep.
 impl FromStr for TokenId {
     type Err = TokenIdError;
 
     #[inline]
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (typ, data) = s
-            .split_once(':')
-            .ok_or(strum::ParseError::VariantNotFound)?;
+        let (typ, data) = s.split_once(':').ok_or(TokenIdError::MissingSeparator)?;
         match typ.parse()? {
             TokenIdType::Nep141 => data.parse().map(Self::Nep141),
             TokenIdType::Nep171 => data.parse().map(Self::Nep171),
@@ -160,6 +205,43 @@ mod tests {
         assert_eq!(got.to_string(), token_id_str);
     }
 
+    #[rstest]
+    #[trace]
+    fn unwrap_to_base(
+        #[values(
+            ("nep141:ft.near", "nep141:ft.near", 0),
+            ("nep171:nft.near:abc", "nep171:nft.near:abc", 0),
+            ("nep245:mt.near:abc", "nep245:mt.near:abc", 1),
+            ("nep245:defuse.near:nep141:ft.near", "nep141:ft.near", 1),
+            ("nep245:defuse2.near:nep245:defuse1.near:nep171:nft.near:abc", "nep171:nft.near:abc", 2),
+            ("nep245:defuse2.near:nep245:defuse1.near:nep245:mt.near:abc", "nep245:mt.near:abc", 3),
+        )]
+        (token_id, base, depth): (&str, &str, usize),
+    ) {
+        let token_id: TokenId = token_id.parse().unwrap();
+        assert_eq!(token_id.wrapping_depth(), depth);
+        assert_eq!(token_id.unwrap_to_base(), base.parse().unwrap());
+    }
+
+    #[test]
+    fn max_wrapping_depth() {
+        let wrap = |token_id: &str| format!("nep245:d.near:{token_id}");
+
+        let mut token_id = "nep141:ft.near".to_string();
+        for _ in 0..MAX_WRAPPING_DEPTH {
+            token_id = wrap(&token_id);
+        }
+        assert_eq!(
+            token_id.parse::<TokenId>().unwrap().wrapping_depth(),
+            MAX_WRAPPING_DEPTH
+        );
+
+        assert!(matches!(
+            wrap(&token_id).parse::<TokenId>(),
+            Err(TokenIdError::WrappingTooDeep(depth)) if depth == MAX_WRAPPING_DEPTH + 1
+        ));
+    }
+
     #[rstest]
     #[trace]
     fn borsh_roundtrip(#[from(make_arbitrary)] token_id: TokenId) {
@@ -176,6 +258,45 @@ mod tests {
         assert_eq!(got, token_id);
     }
 
+    #[rstest]
+    #[trace]
+    fn nested_nep245_display_from_str_roundtrip(#[from(make_arbitrary)] inner: TokenId) {
+        let mut token_id = inner;
+        while token_id.wrapping_depth() < MAX_WRAPPING_DEPTH {
+            // stop once wrapped token id gets too long
+            let Ok(wrapped) =
+                Nep245TokenId::new("defuse.near".parse().unwrap(), token_id.to_string())
+            else {
+                break;
+            };
+            token_id = wrapped.into();
+
+            let s = token_id.to_string();
+            let got: TokenId = s.parse().unwrap();
+            assert_eq!(got, token_id);
+            assert_eq!(got.to_string(), s);
+        }
+    }
+
+    #[rstest]
+    #[trace]
+    fn from_str_malformed(
+        #[values(
+            "",
+            "nep141",
+            "nep141:",
+            "nep141:Invalid.near",
+            "nep171:nft.near",
+            "nep245:mt.near",
+            "nep999:ft.near",
+            "NEP141:ft.near",
+            ":ft.near"
+        )]
+        s: &str,
+    ) {
+        s.parse::<TokenId>().unwrap_err();
+    }
+
     #[rstest]
     #[trace]
     fn serde_roundtrip(#[from(make_arbitrary)] token_id: TokenId) {
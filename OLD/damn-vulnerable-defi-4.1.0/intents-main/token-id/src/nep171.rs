@@ -73,9 +73,7 @@ impl FromStr for Nep171TokenId {
     type Err = TokenIdError;
 
     fn from_str(data: &str) -> Result<Self, Self::Err> {
-        let (contract_id, token_id) = data
-            .split_once(':')
-            .ok_or(strum::ParseError::VariantNotFound)?;
+        let (contract_id, token_id) = data.split_once(':').ok_or(TokenIdError::MissingSeparator)?;
         Self::new(contract_id.parse()?, token_id.to_string())
     }
 }
@@ -5,7 +5,7 @@ use std::{fmt, str::FromStr};
 use near_sdk::{AccountId, AccountIdRef, near};
 use serde_with::{DeserializeFromStr, SerializeDisplay};
 
-use crate::{MAX_ALLOWED_TOKEN_ID_LEN, error::TokenIdError};
+use crate::{MAX_ALLOWED_TOKEN_ID_LEN, MAX_WRAPPING_DEPTH, error::TokenIdError};
 
 #[cfg(any(feature = "arbitrary", test))]
 use arbitrary_with::{Arbitrary, As, LimitLen};
@@ -35,6 +35,15 @@ impl Nep245TokenId {
             return Err(TokenIdError::TokenIdTooLarge(mt_token_id.len()));
         }
 
+        let depth = match mt_token_id.parse::<crate::TokenId>() {
+            Ok(inner) => inner.wrapping_depth() + 1,
+            Err(TokenIdError::WrappingTooDeep(depth)) => depth + 1,
+            Err(_) => 1,
+        };
+        if depth > MAX_WRAPPING_DEPTH {
+            return Err(TokenIdError::WrappingTooDeep(depth));
+        }
+
         Ok(Self {
             contract_id,
             mt_token_id,
@@ -73,9 +82,7 @@ impl FromStr for Nep245TokenId {
     type Err = TokenIdError;
 
     fn from_str(data: &str) -> Result<Self, Self::Err> {
-        let (contract_id, token_id) = data
-            .split_once(':')
-            .ok_or(strum::ParseError::VariantNotFound)?;
+        let (contract_id, token_id) = data.split_once(':').ok_or(TokenIdError::MissingSeparator)?;
         Self::new(contract_id.parse()?, token_id.to_string())
     }
 }
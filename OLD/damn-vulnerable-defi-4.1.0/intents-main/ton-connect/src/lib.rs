@@ -1,16 +1,21 @@
 //! TON Connect [signData](https://github.com/ton-blockchain/ton-connect/blob/main/requests-responses.md#sign-data)
 
-use std::borrow::Cow;
+use std::{borrow::Cow, io};
 
 use chrono::{DateTime, Utc};
 use defuse_crypto::{Curve, Ed25519, Payload, SignedPayload, serde::AsCurve};
 use defuse_near_utils::UnwrapOrPanicError;
 use defuse_serde_utils::{base64::Base64, tlb::AsBoC};
+use digest::{Digest, Output};
+use ed25519_dalek::{Signature, VerifyingKey};
 use impl_tools::autoimpl;
-use near_sdk::{env, near};
+use near_sdk::{
+    borsh::{BorshDeserialize, BorshSerialize},
+    near,
+};
 use serde_with::{PickFirst, TimestampSeconds, serde_as};
 use tlb_ton::{
-    Cell, Error, MsgAddress, StringError,
+    BagOfCellsArgs, BoC, Cell, Error, MsgAddress, StringError,
     r#as::{Ref, SnakeData},
     bits::ser::BitWriterExt,
     ser::{CellBuilder, CellBuilderError, CellSerialize, CellSerializeExt},
@@ -47,8 +52,86 @@ pub struct TonConnectPayload {
     pub payload: TonConnectPayloadSchema,
 }
 
+/// `address` is encoded as `workchain_id || address` and `timestamp` as
+/// nanoseconds since UNIX epoch, since neither of them implements borsh
+impl BorshSerialize for TonConnectPayload {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.address.workchain_id.serialize(writer)?;
+        self.address.address.serialize(writer)?;
+        self.domain.serialize(writer)?;
+        self.timestamp
+            .timestamp_nanos_opt()
+            .ok_or_else(|| io::Error::other("timestamp: overflow"))?
+            .serialize(writer)?;
+        self.payload.serialize(writer)
+    }
+}
+
+impl BorshDeserialize for TonConnectPayload {
+    fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        Ok(Self {
+            address: MsgAddress {
+                workchain_id: BorshDeserialize::deserialize_reader(reader)?,
+                address: BorshDeserialize::deserialize_reader(reader)?,
+            },
+            domain: BorshDeserialize::deserialize_reader(reader)?,
+            timestamp: DateTime::from_timestamp_nanos(i64::deserialize_reader(reader)?),
+            payload: BorshDeserialize::deserialize_reader(reader)?,
+        })
+    }
+}
+
+#[cfg(all(feature = "abi", not(target_arch = "wasm32")))]
+const _: () = {
+    use near_sdk::borsh::{
+        BorshSchema,
+        schema::{Declaration, Definition, Fields, add_definition},
+    };
+    use std::collections::BTreeMap;
+
+    impl BorshSchema for TonConnectPayload {
+        fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+            add_definition(
+                Self::declaration(),
+                Definition::Struct {
+                    fields: Fields::NamedFields(vec![
+                        ("workchain_id".to_string(), i32::declaration()),
+                        ("address".to_string(), <[u8; 32]>::declaration()),
+                        ("domain".to_string(), String::declaration()),
+                        ("timestamp".to_string(), i64::declaration()),
+                        (
+                            "payload".to_string(),
+                            TonConnectPayloadSchema::declaration(),
+                        ),
+                    ]),
+                },
+                definitions,
+            );
+            i32::add_definitions_recursively(definitions);
+            <[u8; 32]>::add_definitions_recursively(definitions);
+            String::add_definitions_recursively(definitions);
+            i64::add_definitions_recursively(definitions);
+            TonConnectPayloadSchema::add_definitions_recursively(definitions);
+        }
+
+        fn declaration() -> Declaration {
+            "TonConnectPayload".to_string()
+        }
+    }
+};
+
 impl TonConnectPayload {
+    #[inline]
     fn try_hash(&self) -> Result<near_sdk::CryptoHash, StringError> {
+        // use host function for hash calculation
+        self.try_hash_with::<defuse_near_utils::digest::Sha256>()
+    }
+
+    fn try_hash_with<D>(&self) -> Result<near_sdk::CryptoHash, StringError>
+    where
+        D: Digest,
+        Output<D>: Into<[u8; 32]>,
+    {
         let timestamp: u64 = self
             .timestamp
             .timestamp()
@@ -62,8 +145,8 @@ impl TonConnectPayload {
                     TonConnectPayloadSchema::Binary { bytes } => (b"bin", bytes.as_slice()),
                     _ => unreachable!(),
                 };
-                Ok(env::sha256_array(
-                    &[
+                Ok(D::digest(
+                    [
                         [0xff, 0xff].as_slice(),
                         b"ton-connect/sign-data/",
                         &self.address.workchain_id.to_be_bytes(),
@@ -80,20 +163,18 @@ impl TonConnectPayload {
                         payload,
                     ]
                     .concat(),
-                ))
+                )
+                .into())
             }
-            TonConnectPayloadSchema::Cell { schema_crc, cell } => {
-                Ok(TonConnectCellMessage {
-                    schema_crc: *schema_crc,
-                    timestamp,
-                    user_address: Cow::Borrowed(&self.address),
-                    app_domain: Cow::Borrowed(self.domain.as_str()),
-                    payload: cell,
-                }
-                .to_cell()?
-                // use host function for recursive hash calculation
-                .hash_digest::<defuse_near_utils::digest::Sha256>())
+            TonConnectPayloadSchema::Cell { schema_crc, cell } => Ok(TonConnectCellMessage {
+                schema_crc: *schema_crc,
+                timestamp,
+                user_address: Cow::Borrowed(&self.address),
+                app_domain: Cow::Borrowed(self.domain.as_str()),
+                payload: cell,
             }
+            .to_cell()?
+            .hash_digest::<D>()),
         }
     }
 }
@@ -115,7 +196,7 @@ impl Payload for TonConnectPayload {
     not(all(feature = "abi", not(target_arch = "wasm32"))),
     serde_as(schemars = false)
 )]
-#[near(serializers = [json])]
+#[near(serializers = [borsh, json])]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TonConnectPayloadSchema {
@@ -129,10 +210,44 @@ pub enum TonConnectPayloadSchema {
     Cell {
         schema_crc: u32,
         #[serde_as(as = "AsBoC<Base64>")]
+        #[borsh(
+            serialize_with = "serialize_cell_boc",
+            deserialize_with = "deserialize_cell_boc",
+            schema(with_funcs(
+                declaration = "<Vec<u8> as ::near_sdk::borsh::BorshSchema>::declaration",
+                definitions = "<Vec<u8> as ::near_sdk::borsh::BorshSchema>::add_definitions_recursively"
+            ))
+        )]
         cell: Cell,
     },
 }
 
+fn serialize_cell_boc<W>(cell: &Cell, writer: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    BoC::from_root(cell.clone())
+        .serialize(BagOfCellsArgs {
+            has_idx: false,
+            has_crc32c: false,
+        })
+        .map_err(io::Error::other)?
+        .serialize(writer)
+}
+
+fn deserialize_cell_boc<R>(reader: &mut R) -> io::Result<Cell>
+where
+    R: io::Read,
+{
+    BoC::deserialize(Vec::<u8>::deserialize_reader(reader)?)
+        .and_then(|boc| {
+            boc.into_single_root()
+                .ok_or_else(|| Error::custom("multiple roots"))
+        })
+        .map(|root| Cell::clone(&root))
+        .map_err(io::Error::other)
+}
+
 /// ```tlb
 /// message#75569022 schema_hash:uint32 timestamp:uint64 userAddress:MsgAddress
 ///                  {n:#} appDomain:^(SnakeData ~n) payload:^Cell = Message;
@@ -183,7 +298,7 @@ where
     not(all(feature = "abi", not(target_arch = "wasm32"))),
     serde_as(schemars = false)
 )]
-#[near(serializers = [json])]
+#[near(serializers = [borsh, json])]
 #[autoimpl(Deref using self.payload)]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SignedTonConnectPayload {
@@ -203,6 +318,24 @@ impl Payload for SignedTonConnectPayload {
     }
 }
 
+impl SignedTonConnectPayload {
+    /// Same as [`SignedPayload::verify`], but implemented in pure Rust
+    /// without NEAR host functions, so it can be used off-chain (e.g. by
+    /// relayers to validate payloads before submitting them).
+    pub fn verify_offchain(&self) -> Option<<Ed25519 as Curve>::PublicKey> {
+        let hash = self.payload.try_hash_with::<sha2::Sha256>().ok()?;
+        let public_key = VerifyingKey::from_bytes(&self.public_key).ok()?;
+        if public_key.is_weak() {
+            // prevent using weak (i.e. low order) public keys
+            return None;
+        }
+        public_key
+            .verify_strict(&hash, &Signature::from_bytes(&self.signature))
+            .ok()
+            .map(|()| self.public_key)
+    }
+}
+
 impl SignedPayload for SignedTonConnectPayload {
     type PublicKey = <Ed25519 as Curve>::PublicKey;
 
@@ -221,32 +354,54 @@ mod tests {
     use defuse_test_utils::random::random_bytes;
     use hex_literal::hex;
     use near_sdk::serde_json;
-    use rstest::rstest;
+    use rstest::{fixture, rstest};
     use tlb_ton::UnixTimestamp;
 
-    #[rstest]
-    fn verify_text(random_bytes: Vec<u8>) {
-        verify(
-            &SignedTonConnectPayload {
-                payload: TonConnectPayload {
-                    address: "0:f4809e5ffac9dc42a6b1d94c5e74ad5fd86378de675c805f2274d0055cbc9378"
-                        .parse()
-                        .unwrap(),
-                    domain: "ton-connect.github.io".to_string(),
-                    timestamp: DateTime::from_timestamp(1747759882, 0).unwrap(),
-                    payload: TonConnectPayloadSchema::Text {
-                        text: "Hello, TON!".repeat(100),
-                    },
+    #[fixture]
+    fn signed_text() -> SignedTonConnectPayload {
+        SignedTonConnectPayload {
+            payload: TonConnectPayload {
+                address: "0:f4809e5ffac9dc42a6b1d94c5e74ad5fd86378de675c805f2274d0055cbc9378"
+                    .parse()
+                    .unwrap(),
+                domain: "ton-connect.github.io".to_string(),
+                timestamp: DateTime::from_timestamp(1747759882, 0).unwrap(),
+                payload: TonConnectPayloadSchema::Text {
+                    text: "Hello, TON!".repeat(100),
                 },
-                public_key: hex!(
-                    "22e795a07e832fc9084ca35a488a711f1dbedef637d4e886a6997d93ee2c2e37"
-                ),
-                signature: hex!(
-                    "7bc628f6d634ab6ddaf10463742b13f0ede3cb828737d9ce1962cc808fbfe7035e77c1a3d0b682acf02d645cc1a244992b276552c0e1c57d30b03c2820d73d01"
-                ),
             },
-            &random_bytes,
+            public_key: hex!("22e795a07e832fc9084ca35a488a711f1dbedef637d4e886a6997d93ee2c2e37"),
+            signature: hex!(
+                "7bc628f6d634ab6ddaf10463742b13f0ede3cb828737d9ce1962cc808fbfe7035e77c1a3d0b682acf02d645cc1a244992b276552c0e1c57d30b03c2820d73d01"
+            ),
+        }
+    }
+
+    #[rstest]
+    fn verify_text(signed_text: SignedTonConnectPayload, random_bytes: Vec<u8>) {
+        verify(&signed_text, &random_bytes);
+    }
+
+    #[rstest]
+    fn verify_offchain(signed_text: SignedTonConnectPayload) {
+        // hashes computed by host function and in pure Rust must match
+        assert_eq!(
+            signed_text.payload.try_hash_with::<sha2::Sha256>().unwrap(),
+            signed_text.hash(),
         );
+        assert_eq!(signed_text.verify_offchain(), Some(signed_text.public_key));
+
+        let mut tampered = signed_text.clone();
+        tampered.payload.domain = "evil.example.com".to_string();
+        assert_eq!(tampered.verify_offchain(), None);
+
+        let mut tampered = signed_text.clone();
+        tampered.payload.timestamp += chrono::Duration::seconds(1);
+        assert_eq!(tampered.verify_offchain(), None);
+
+        let mut tampered = signed_text;
+        tampered.signature[0] ^= 1;
+        assert_eq!(tampered.verify_offchain(), None);
     }
 
     #[rstest]
@@ -354,5 +509,6 @@ mod tests {
         let deserialized: SignedTonConnectPayload = serde_json::from_str(&serialized).unwrap();
         assert_eq!(&deserialized, signed);
         assert_eq!(deserialized.verify(), ok.then_some(deserialized.public_key));
+        assert_eq!(deserialized.verify_offchain(), deserialized.verify());
     }
 }
@@ -0,0 +1,102 @@
+use ethers::abi::{self, Abi, ParamType, Token};
+
+/// Splits constructor arguments off the end of contract creation bytecode
+/// and decodes them according to the constructor in `abi`.
+///
+/// The length of the creation code itself is unknown, so every 32-byte
+/// aligned suffix is tried starting from the shortest one, and the first
+/// one that decodes and re-encodes to exactly the same bytes wins.
+/// Returns `None` if the ABI has no constructor or nothing matches.
+pub fn decode_constructor_args(bytecode: &[u8], abi: &Abi) -> Option<Vec<Token>> {
+    let constructor = abi.constructor()?;
+    let types: Vec<ParamType> = constructor.inputs.iter().map(|p| p.kind.clone()).collect();
+    if types.is_empty() {
+        return Some(Vec::new());
+    }
+
+    // every argument takes at least one 32-byte word
+    (32 * types.len()..=bytecode.len())
+        .step_by(32)
+        .find_map(|len| {
+            let args = &bytecode[bytecode.len() - len..];
+            let tokens = abi::decode(&types, args).ok()?;
+            (abi::encode(&tokens) == args).then_some(tokens)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::{Address, U256};
+
+    // creation code prefix of a typical solc output
+    const CREATION_CODE: &str = "6080604052348015600f57600080fd5b50603f80601d6000396000f3fe";
+
+    fn abi(constructor_inputs: &str) -> Abi {
+        serde_json::from_str(&format!(
+            r#"[{{"type":"constructor","stateMutability":"nonpayable","inputs":{constructor_inputs}}}]"#
+        ))
+        .unwrap()
+    }
+
+    fn bytecode(args: &[Token]) -> Vec<u8> {
+        let mut bytecode = hex::decode(CREATION_CODE).unwrap();
+        bytecode.extend(abi::encode(args));
+        bytecode
+    }
+
+    #[test]
+    fn decodes_static_args() {
+        let abi = abi(r#"[
+            {"name":"owner","type":"address"},
+            {"name":"supply","type":"uint256"},
+            {"name":"paused","type":"bool"}
+        ]"#);
+        let args = vec![
+            Token::Address(Address::repeat_byte(0x11)),
+            Token::Uint(U256::from(1_000_000u64)),
+            Token::Bool(true),
+        ];
+
+        assert_eq!(decode_constructor_args(&bytecode(&args), &abi), Some(args));
+    }
+
+    #[test]
+    fn decodes_dynamic_args() {
+        let abi = abi(r#"[
+            {"name":"name","type":"string"},
+            {"name":"symbol","type":"string"},
+            {"name":"holders","type":"address[]"}
+        ]"#);
+        let args = vec![
+            Token::String("Wrapped Token".to_string()),
+            Token::String("WTKN".to_string()),
+            Token::Array(vec![
+                Token::Address(Address::repeat_byte(0x22)),
+                Token::Address(Address::repeat_byte(0x33)),
+            ]),
+        ];
+
+        assert_eq!(decode_constructor_args(&bytecode(&args), &abi), Some(args));
+    }
+
+    #[test]
+    fn no_args() {
+        assert_eq!(
+            decode_constructor_args(&bytecode(&[]), &abi("[]")),
+            Some(Vec::new())
+        );
+    }
+
+    #[test]
+    fn no_constructor() {
+        let abi: Abi = serde_json::from_str("[]").unwrap();
+        assert_eq!(decode_constructor_args(&bytecode(&[]), &abi), None);
+    }
+
+    #[test]
+    fn bytecode_too_short() {
+        let abi = abi(r#"[{"name":"owner","type":"address"},{"name":"supply","type":"uint256"}]"#);
+        assert_eq!(decode_constructor_args(&[0; 32], &abi), None);
+    }
+}
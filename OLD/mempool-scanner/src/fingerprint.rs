@@ -0,0 +1,78 @@
+use ethers::utils::keccak256;
+
+const PUSH0: u8 = 0x5f;
+const PUSH1: u8 = 0x60;
+const PUSH32: u8 = 0x7f;
+
+/// Structural hash of EVM bytecode: keccak256 of its opcode sequence with
+/// all push data stripped, so deployments of the same contract that only
+/// differ in immutables, embedded addresses or other constants share it.
+pub fn bytecode_fingerprint(code: &[u8]) -> String {
+    hex::encode(keccak256(opcodes(code)))
+}
+
+/// Disassembles `code` into a sequence of opcodes, skipping push data
+fn opcodes(code: &[u8]) -> Vec<u8> {
+    let mut ops = Vec::with_capacity(code.len());
+    let mut pc = 0;
+    while let Some(&op) = code.get(pc) {
+        ops.push(op);
+        pc += 1;
+        if (PUSH1..=PUSH32).contains(&op) {
+            // truncated push data at the end of the code is skipped as well
+            pc += usize::from(op - PUSH0);
+        }
+    }
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // PUSH20 <owner> PUSH1 0x00 SSTORE PUSH32 <immutable> PUSH1 0x80 MSTORE STOP
+    fn bytecode(owner: [u8; 20], immutable: [u8; 32]) -> Vec<u8> {
+        let mut code = vec![0x73];
+        code.extend(owner);
+        code.extend([0x60, 0x00, 0x55, 0x7f]);
+        code.extend(immutable);
+        code.extend([0x60, 0x80, 0x52, 0x00]);
+        code
+    }
+
+    #[test]
+    fn skips_push_data() {
+        assert_eq!(
+            opcodes(&bytecode([0x11; 20], [0x22; 32])),
+            [0x73, 0x60, 0x55, 0x7f, 0x60, 0x52, 0x00]
+        );
+        assert_eq!(opcodes(&[0x5f, 0x01, 0x62, 0xff]), [0x5f, 0x01, 0x62]);
+    }
+
+    #[test]
+    fn same_for_different_immutables() {
+        assert_eq!(
+            bytecode_fingerprint(&bytecode([0x11; 20], [0x22; 32])),
+            bytecode_fingerprint(&bytecode([0x33; 20], [0x44; 32])),
+        );
+    }
+
+    #[test]
+    fn differs_for_different_opcodes() {
+        let code = bytecode([0x11; 20], [0x22; 32]);
+        let mut other = code.clone();
+        // SSTORE -> MSTORE
+        other[23] = 0x52;
+
+        assert_ne!(bytecode_fingerprint(&code), bytecode_fingerprint(&other));
+    }
+
+    #[test]
+    fn push_data_looking_like_push_is_skipped() {
+        // push data containing PUSH32 opcode must not swallow following code
+        assert_eq!(
+            bytecode_fingerprint(&[0x60, 0x7f, 0x01]),
+            bytecode_fingerprint(&[0x60, 0x00, 0x01]),
+        );
+    }
+}
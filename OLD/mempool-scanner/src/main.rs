@@ -1,19 +1,60 @@
+mod constructor;
+mod fingerprint;
+mod provider_pool;
+mod replay;
+mod shutdown;
+mod spawner;
+mod stats;
+
+use constructor::decode_constructor_args;
+use fingerprint::bytecode_fingerprint;
+use ethers::abi::Abi;
 use ethers::prelude::*;
 use std::env;
 use std::fs;
 use std::io::Write;
 use std::sync::Arc;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use provider_pool::ProviderPool;
+use stats::ScannerStats;
+use spawner::BoundedSpawner;
+
+// Consecutive request errors before failing over to the next provider
+const MAX_PROVIDER_ERRORS: usize = 5;
+
+// Default limit of transactions processed at once, overridable by MAX_CONCURRENT_TASKS
+const DEFAULT_MAX_CONCURRENT_TASKS: usize = 100;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // `replay [DIR]` analyzes previously saved bytecode offline
+    if env::args().nth(1).as_deref() == Some("replay") {
+        let dir = env::args().nth(2).unwrap_or_else(|| "temp".to_string());
+        print!("{}", replay::process_saved_bytecode(Path::new(&dir)).render());
+        return Ok(());
+    }
+
     // Load env vars (optional, assuming they are set in shell)
-    let rpc_url = env::var("RPC_URL").expect("RPC_URL must be set");
+    // Comma-separated list of WebSocket endpoints, used in order for failover
+    let rpc_urls = env::var("RPC_URL").expect("RPC_URL must be set");
+    // Save deployments into per-fingerprint subdirectories to cluster similar contracts
+    let group_by_hash = env::args().any(|arg| arg == "--group-by-hash");
     
-    println!("Connecting to WebSocket...");
-    let provider = Provider::<Ws>::connect(&rpc_url).await?;
-    let provider = Arc::new(provider);
-    println!("Connected to {}", rpc_url);
+    let mut providers = Vec::new();
+    for rpc_url in rpc_urls.split(',').map(str::trim).filter(|url| !url.is_empty()) {
+        println!("Connecting to WebSocket...");
+        match Provider::<Ws>::connect(rpc_url).await {
+            Ok(provider) => {
+                println!("Connected to {}", rpc_url);
+                providers.push(provider);
+            }
+            Err(e) => eprintln!("Failed to connect to {}: {}", rpc_url, e),
+        }
+    }
+    if providers.is_empty() {
+        return Err("Failed to connect to any of RPC_URL endpoints".into());
+    }
+    let pool = Arc::new(ProviderPool::new(providers, MAX_PROVIDER_ERRORS));
 
     // Ensure temp directory exists
     let temp_dir = Path::new("temp");
@@ -21,55 +62,115 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         fs::create_dir(temp_dir)?;
     }
 
-    // Subscribe to pending transactions
-    let mut stream = provider.subscribe_pending_txs().await?;
-    println!("Listening for pending transactions...");
-
-    while let Some(tx_hash) = stream.next().await {
-        let provider = provider.clone();
-        tokio::spawn(async move {
-            if let Err(e) = process_transaction(provider, tx_hash).await {
-                // Ignore "not found" errors as txs might be dropped from mempool
-                if !e.to_string().contains("not found") {
-                    eprintln!("Error processing {:?}: {}", tx_hash, e);
+    // Periodically log aggregate stats
+    let stats = Arc::new(ScannerStats::default());
+    let interval_stats = stats.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            println!("[stats] {}", interval_stats.render());
+        }
+    });
+
+    // Stop accepting new transactions on Ctrl+C, but let in-flight ones finish
+    let shutdown = tokio::signal::ctrl_c();
+    tokio::pin!(shutdown);
+    let max_concurrent_tasks = env::var("MAX_CONCURRENT_TASKS")
+        .ok()
+        .and_then(|max| max.parse().ok())
+        .unwrap_or(DEFAULT_MAX_CONCURRENT_TASKS);
+    let mut tasks = BoundedSpawner::new(max_concurrent_tasks);
+
+    loop {
+        // Subscribe to pending transactions
+        let mut stream = match pool.current().subscribe_pending_txs().await {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("Failed to subscribe to pending transactions: {}", e);
+                pool.rotate();
+                tokio::select! {
+                    _ = &mut shutdown => break,
+                    _ = tokio::time::sleep(std::time::Duration::from_secs(1)) => continue,
+                }
+            }
+        };
+        println!("Listening for pending transactions...");
+
+        let shutdown_requested = shutdown::dispatch_until(&mut stream, shutdown.as_mut(), &mut tasks, |tx_hash| {
+            let pool = pool.clone();
+            let stats = stats.clone();
+            async move {
+                if let Err(e) = process_transaction(pool, tx_hash, group_by_hash, stats.clone()).await {
+                    // Ignore "not found" errors as txs might be dropped from mempool
+                    if !e.to_string().contains("not found") {
+                        stats.error();
+                        eprintln!("Error processing {:?}: {}", tx_hash, e);
+                    }
                 }
             }
-        });
+        }).await;
+        if shutdown_requested {
+            break;
+        }
+
+        // Subscription ends when the connection drops
+        eprintln!("Pending transactions subscription closed");
+        pool.rotate();
     }
 
+    println!("Shutting down, waiting for {} in-flight tasks...", tasks.len());
+    tasks.drain().await;
+    println!("[stats] {}", stats.render());
+
     Ok(())
 }
 
-async fn process_transaction(provider: Arc<Provider<Ws>>, tx_hash: TxHash) -> Result<(), Box<dyn std::error::Error>> {
+async fn process_transaction(provider: Arc<ProviderPool<Provider<Ws>>>, tx_hash: TxHash, group_by_hash: bool, stats: Arc<ScannerStats>) -> Result<(), Box<dyn std::error::Error>> {
     let tx = provider.get_transaction(tx_hash).await?;
 
     if let Some(tx) = tx {
         // Detect Contract Creation: 'to' field is None
         if tx.to.is_none() {
             println!("[\u{26A1}] Contract Creation Detected: {:?}", tx_hash);
+            stats.contract_detected();
+
+            let fingerprint = bytecode_fingerprint(&tx.input);
+            let out_dir = if group_by_hash {
+                Path::new("temp").join(&fingerprint)
+            } else {
+                PathBuf::from("temp")
+            };
+            fs::create_dir_all(&out_dir)?;
 
             // Save Bytecode (Input Data)
-            let bytecode_path = format!("temp/{:?}_bytecode.bin", tx_hash);
+            let bytecode_path = out_dir.join(format!("{:?}_bytecode.bin", tx_hash));
             let mut file = fs::File::create(&bytecode_path)?;
             file.write_all(&tx.input)?;
-            println!("Saved bytecode: {}", bytecode_path);
+            println!("Saved bytecode: {}", bytecode_path.display());
+            stats.bytecode_saved();
+
+            // Save structural hash of the bytecode for clustering
+            fs::write(out_dir.join(format!("{:?}_fingerprint.txt", tx_hash)), &fingerprint)?;
+            println!("Bytecode fingerprint of {:?}: {}", tx_hash, fingerprint);
 
             // Attempt to fetch source code (Requires waiting for mining)
             // In a real bot race, we analyze bytecode directly. 
             // But per requirements, we try to get source via Etherscan.
             if let Ok(api_key) = env::var("ETHERSCAN_API_KEY") {
-                tokio::spawn(async move {
-                    if let Err(e) = fetch_and_save_source(provider, tx_hash, api_key).await {
-                        eprintln!("Failed to fetch source for {:?}: {}", tx_hash, e);
-                    }
-                });
+                // Awaited rather than spawned so that shutdown waits for it too
+                let bytecode = tx.input.to_vec();
+                if let Err(e) = fetch_and_save_source(provider, tx_hash, api_key, bytecode, out_dir, stats.clone()).await {
+                    stats.error();
+                    eprintln!("Failed to fetch source for {:?}: {}", tx_hash, e);
+                }
             }
         }
     }
     Ok(())
 }
 
-async fn fetch_and_save_source(provider: Arc<Provider<Ws>>, tx_hash: TxHash, api_key: String) -> Result<(), Box<dyn std::error::Error>> {
+async fn fetch_and_save_source(provider: Arc<ProviderPool<Provider<Ws>>>, tx_hash: TxHash, api_key: String, bytecode: Vec<u8>, out_dir: PathBuf, stats: Arc<ScannerStats>) -> Result<(), Box<dyn std::error::Error>> {
     // Wait for transaction to be mined to get the contract address
     // Timeout after 60 seconds
     let receipt = tokio::time::timeout(
@@ -97,9 +198,23 @@ async fn fetch_and_save_source(provider: Arc<Provider<Ws>>, tx_hash: TxHash, api
                         if let Some(source) = first.get("SourceCode") {
                             let source_str = source.as_str().unwrap_or("");
                             if !source_str.is_empty() {
-                                let source_path = format!("temp/{:?}_source.sol", tx_hash);
+                                let source_path = out_dir.join(format!("{:?}_source.sol", tx_hash));
                                 fs::write(&source_path, source_str)?;
-                                println!("Saved source code: {}", source_path);
+                                println!("Saved source code: {}", source_path.display());
+                                stats.source_fetched();
+                            }
+                        }
+
+                        if let Some(abi) = first.get("ABI").and_then(|abi| abi.as_str()) {
+                            // Keep verified ABI for offline replay, Etherscan returns
+                            // a plain message instead for unverified contracts
+                            if serde_json::from_str::<Abi>(abi).is_ok() {
+                                fs::write(out_dir.join(format!("{:?}_abi.json", tx_hash)), abi)?;
+                            }
+
+                            // Optionally decode constructor args using the verified ABI
+                            if env::var_os("DECODE_CONSTRUCTOR_ARGS").is_some() {
+                                log_constructor_args(tx_hash, &bytecode, abi);
                             }
                         }
                     }
@@ -109,3 +224,15 @@ async fn fetch_and_save_source(provider: Arc<Provider<Ws>>, tx_hash: TxHash, api
     }
     Ok(())
 }
+
+fn log_constructor_args(tx_hash: TxHash, bytecode: &[u8], abi: &str) {
+    // Etherscan returns a plain message instead of ABI for unverified contracts
+    let Ok(abi) = serde_json::from_str::<Abi>(abi) else {
+        return;
+    };
+
+    match decode_constructor_args(bytecode, &abi) {
+        Some(args) => println!("Constructor args for {:?}: {:?}", tx_hash, args),
+        None => println!("Failed to decode constructor args for {:?}", tx_hash),
+    }
+}
@@ -0,0 +1,202 @@
+use ethers::prelude::*;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Lookups the scanner issues against an RPC endpoint.
+pub trait TxSource {
+    type Error: Display;
+
+    async fn get_transaction(&self, tx_hash: TxHash) -> Result<Option<Transaction>, Self::Error>;
+
+    async fn get_transaction_receipt(
+        &self,
+        tx_hash: TxHash,
+    ) -> Result<Option<TransactionReceipt>, Self::Error>;
+}
+
+impl TxSource for Provider<Ws> {
+    type Error = ProviderError;
+
+    async fn get_transaction(&self, tx_hash: TxHash) -> Result<Option<Transaction>, Self::Error> {
+        Middleware::get_transaction(self, tx_hash).await
+    }
+
+    async fn get_transaction_receipt(
+        &self,
+        tx_hash: TxHash,
+    ) -> Result<Option<TransactionReceipt>, Self::Error> {
+        Middleware::get_transaction_receipt(self, tx_hash).await
+    }
+}
+
+/// Set of providers where requests go to the current one and
+/// `max_errors` consecutive failures rotate to the next one.
+#[derive(Debug)]
+pub struct ProviderPool<P> {
+    providers: Vec<P>,
+    current: AtomicUsize,
+    consecutive_errors: AtomicUsize,
+    max_errors: usize,
+}
+
+impl<P> ProviderPool<P> {
+    /// # Panics
+    ///
+    /// If `providers` is empty
+    pub fn new(providers: Vec<P>, max_errors: usize) -> Self {
+        assert!(!providers.is_empty(), "provider pool must not be empty");
+        Self {
+            providers,
+            current: AtomicUsize::new(0),
+            consecutive_errors: AtomicUsize::new(0),
+            max_errors: max_errors.max(1),
+        }
+    }
+
+    #[inline]
+    pub fn current_index(&self) -> usize {
+        self.current.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub fn current(&self) -> &P {
+        &self.providers[self.current_index()]
+    }
+
+    /// Switches to the next provider in a round-robin manner
+    pub fn rotate(&self) {
+        let current = self.current_index();
+        let next = (current + 1) % self.providers.len();
+        // concurrent failures of the same provider must rotate only once
+        if self
+            .current
+            .compare_exchange(current, next, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+        {
+            self.consecutive_errors.store(0, Ordering::Relaxed);
+            eprintln!("Switching to provider #{next}");
+        }
+    }
+
+    fn record<T, E: Display>(&self, result: Result<T, E>) -> Result<T, E> {
+        match &result {
+            Ok(_) => self.consecutive_errors.store(0, Ordering::Relaxed),
+            // dropped transactions are not a fault of the provider
+            Err(e) if e.to_string().contains("not found") => {}
+            Err(_) => {
+                if self.consecutive_errors.fetch_add(1, Ordering::Relaxed) + 1 >= self.max_errors {
+                    self.rotate();
+                }
+            }
+        }
+        result
+    }
+}
+
+impl<P: TxSource> ProviderPool<P> {
+    pub async fn get_transaction(&self, tx_hash: TxHash) -> Result<Option<Transaction>, P::Error> {
+        let result = self.current().get_transaction(tx_hash).await;
+        self.record(result)
+    }
+
+    pub async fn get_transaction_receipt(
+        &self,
+        tx_hash: TxHash,
+    ) -> Result<Option<TransactionReceipt>, P::Error> {
+        let result = self.current().get_transaction_receipt(tx_hash).await;
+        self.record(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicBool;
+
+    #[derive(Default)]
+    struct MockSource {
+        failing: AtomicBool,
+        calls: AtomicUsize,
+    }
+
+    impl MockSource {
+        fn failing() -> Self {
+            Self {
+                failing: AtomicBool::new(true),
+                ..Default::default()
+            }
+        }
+
+        fn result<T: Default>(&self) -> Result<T, String> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            if self.failing.load(Ordering::Relaxed) {
+                Err("connection reset".to_string())
+            } else {
+                Ok(T::default())
+            }
+        }
+    }
+
+    impl TxSource for MockSource {
+        type Error = String;
+
+        async fn get_transaction(&self, _: TxHash) -> Result<Option<Transaction>, String> {
+            self.result()
+        }
+
+        async fn get_transaction_receipt(
+            &self,
+            _: TxHash,
+        ) -> Result<Option<TransactionReceipt>, String> {
+            self.result()
+        }
+    }
+
+    fn calls(pool: &ProviderPool<MockSource>) -> Vec<usize> {
+        pool.providers
+            .iter()
+            .map(|p| p.calls.load(Ordering::Relaxed))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn fails_over_after_consecutive_errors() {
+        let pool = ProviderPool::new(vec![MockSource::failing(), MockSource::default()], 3);
+
+        for _ in 0..2 {
+            assert!(pool.get_transaction(TxHash::zero()).await.is_err());
+        }
+        assert_eq!(pool.current_index(), 0);
+
+        assert!(pool.get_transaction_receipt(TxHash::zero()).await.is_err());
+        assert_eq!(pool.current_index(), 1);
+
+        assert!(pool.get_transaction(TxHash::zero()).await.is_ok());
+        assert_eq!(calls(&pool), [3, 1]);
+    }
+
+    #[tokio::test]
+    async fn success_resets_error_count() {
+        let pool = ProviderPool::new(vec![MockSource::default(), MockSource::default()], 2);
+
+        for _ in 0..3 {
+            pool.providers[0].failing.store(true, Ordering::Relaxed);
+            assert!(pool.get_transaction(TxHash::zero()).await.is_err());
+            pool.providers[0].failing.store(false, Ordering::Relaxed);
+            assert!(pool.get_transaction(TxHash::zero()).await.is_ok());
+        }
+
+        assert_eq!(pool.current_index(), 0);
+    }
+
+    #[tokio::test]
+    async fn wraps_around() {
+        let pool = ProviderPool::new(vec![MockSource::failing(), MockSource::failing()], 1);
+
+        assert!(pool.get_transaction(TxHash::zero()).await.is_err());
+        assert_eq!(pool.current_index(), 1);
+        assert!(pool.get_transaction(TxHash::zero()).await.is_err());
+        assert_eq!(pool.current_index(), 0);
+        assert_eq!(calls(&pool), [1, 1]);
+    }
+}
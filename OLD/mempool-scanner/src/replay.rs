@@ -0,0 +1,201 @@
+use ethers::abi::{Abi, Token};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::constructor::decode_constructor_args;
+use crate::fingerprint::bytecode_fingerprint;
+
+const BYTECODE_SUFFIX: &str = "_bytecode.bin";
+const ABI_SUFFIX: &str = "_abi.json";
+
+/// Offline analysis of a single saved bytecode file
+#[derive(Debug)]
+pub struct BytecodeAnalysis {
+    pub path: PathBuf,
+    pub size: usize,
+    pub fingerprint: String,
+    /// Decoded only if an ABI was saved next to the bytecode
+    pub constructor_args: Option<Vec<Token>>,
+}
+
+#[derive(Debug, Default)]
+pub struct AnalysisReport {
+    pub bytecodes: Vec<BytecodeAnalysis>,
+    pub errors: Vec<(PathBuf, String)>,
+}
+
+impl AnalysisReport {
+    /// Groups analyzed bytecodes by their fingerprint
+    pub fn clusters(&self) -> BTreeMap<&str, Vec<&BytecodeAnalysis>> {
+        let mut clusters: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for analysis in &self.bytecodes {
+            clusters
+                .entry(analysis.fingerprint.as_str())
+                .or_default()
+                .push(analysis);
+        }
+        clusters
+    }
+
+    pub fn render(&self) -> String {
+        let clusters = self.clusters();
+        let mut out = format!(
+            "bytecodes={} clusters={} errors={}\n",
+            self.bytecodes.len(),
+            clusters.len(),
+            self.errors.len(),
+        );
+        for (fingerprint, bytecodes) in clusters {
+            let _ = writeln!(out, "{fingerprint} ({})", bytecodes.len());
+            for analysis in bytecodes {
+                let _ = write!(out, "  {} size={}", analysis.path.display(), analysis.size);
+                if let Some(args) = &analysis.constructor_args {
+                    let _ = write!(out, " constructor_args={args:?}");
+                }
+                out.push('\n');
+            }
+        }
+        for (path, error) in &self.errors {
+            let _ = writeln!(out, "error: {}: {error}", path.display());
+        }
+        out
+    }
+}
+
+/// Analyzes all `*_bytecode.bin` files saved by the scanner under `path`,
+/// including per-fingerprint subdirectories created by `--group-by-hash`
+pub fn process_saved_bytecode(path: &Path) -> AnalysisReport {
+    let mut report = AnalysisReport::default();
+    visit(path, &mut report);
+    report.bytecodes.sort_by(|a, b| a.path.cmp(&b.path));
+    report
+}
+
+fn visit(dir: &Path, report: &mut AnalysisReport) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            report.errors.push((dir.to_path_buf(), e.to_string()));
+            return;
+        }
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            visit(&path, report);
+            continue;
+        }
+
+        let Some(prefix) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(BYTECODE_SUFFIX))
+        else {
+            continue;
+        };
+        let abi_path = path.with_file_name(format!("{prefix}{ABI_SUFFIX}"));
+
+        match analyze(&path, &abi_path) {
+            Ok(analysis) => report.bytecodes.push(analysis),
+            Err(e) => report.errors.push((path, e)),
+        }
+    }
+}
+
+fn analyze(path: &Path, abi_path: &Path) -> Result<BytecodeAnalysis, String> {
+    let bytecode = fs::read(path).map_err(|e| e.to_string())?;
+
+    let constructor_args = if abi_path.exists() {
+        let abi = fs::read_to_string(abi_path).map_err(|e| e.to_string())?;
+        let abi: Abi = serde_json::from_str(&abi).map_err(|e| format!("invalid ABI: {e}"))?;
+        decode_constructor_args(&bytecode, &abi)
+    } else {
+        None
+    };
+
+    Ok(BytecodeAnalysis {
+        path: path.to_path_buf(),
+        size: bytecode.len(),
+        fingerprint: bytecode_fingerprint(&bytecode),
+        constructor_args,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::{Address, U256};
+
+    fn fixtures() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/replay")
+    }
+
+    fn file_names(analyses: &[&BytecodeAnalysis]) -> Vec<String> {
+        analyses
+            .iter()
+            .map(|a| a.path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn analyzes_fixture_directory() {
+        let report = process_saved_bytecode(&fixtures());
+
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(report.bytecodes.len(), 3);
+
+        // same code with different immutables is clustered together
+        let clusters = report.clusters();
+        assert_eq!(clusters.len(), 2);
+        let mut clustered: Vec<_> = clusters.values().map(|c| file_names(c)).collect();
+        clustered.sort();
+        assert_eq!(
+            clustered,
+            [
+                vec!["0xaa_bytecode.bin", "0xbb_bytecode.bin"],
+                vec!["0xcc_bytecode.bin"],
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_constructor_args_with_saved_abi() {
+        let report = process_saved_bytecode(&fixtures());
+
+        let args: Vec<_> = report
+            .bytecodes
+            .iter()
+            .map(|a| a.constructor_args.clone())
+            .collect();
+        assert_eq!(
+            args,
+            [
+                None,
+                None,
+                Some(vec![
+                    Token::Address(Address::repeat_byte(0x33)),
+                    Token::Uint(U256::from(1000)),
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn render() {
+        let rendered = process_saved_bytecode(&fixtures()).render();
+
+        assert!(rendered.starts_with("bytecodes=3 clusters=2 errors=0\n"));
+        assert!(rendered.contains("0xcc_bytecode.bin size=93 constructor_args="));
+    }
+
+    #[test]
+    fn missing_directory() {
+        let report = process_saved_bytecode(&fixtures().join("missing"));
+
+        assert!(report.bytecodes.is_empty());
+        assert_eq!(report.errors.len(), 1);
+    }
+}
@@ -0,0 +1,95 @@
+use ethers::providers::StreamExt;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::spawner::BoundedSpawner;
+
+/// Spawns `handle` for every item of `stream` onto `tasks` until either the
+/// stream ends or `shutdown` completes. Returns `true` in the latter case.
+pub async fn dispatch_until<S, Sh, F, Fut>(
+    stream: &mut S,
+    mut shutdown: Pin<&mut Sh>,
+    tasks: &mut BoundedSpawner,
+    mut handle: F,
+) -> bool
+where
+    S: StreamExt + Unpin,
+    Sh: Future,
+    F: FnMut(S::Item) -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    loop {
+        tokio::select! {
+            biased;
+            _ = &mut shutdown => return true,
+            item = stream.next() => match item {
+                // stop waiting for a free slot on shutdown as well
+                Some(item) => tokio::select! {
+                    biased;
+                    () = tasks.spawn(handle(item)) => {}
+                    _ = &mut shutdown => return true,
+                },
+                None => return false,
+            },
+            // reap finished tasks so that the set does not grow unbounded
+            Some(_) = tasks.join_next(), if !tasks.is_empty() => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::stream;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use std::time::Duration;
+    use tokio::sync::oneshot;
+
+    #[tokio::test]
+    async fn waits_for_in_flight_tasks() {
+        let started = Arc::new(AtomicUsize::new(0));
+        let completed = Arc::new(AtomicUsize::new(0));
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let mut shutdown_tx = Some(shutdown_tx);
+
+        // never ends by itself, so only shutdown can stop dispatching
+        let mut items = stream::iter(0..3).chain(stream::pending());
+        let mut tasks = BoundedSpawner::new(10);
+        tokio::pin!(shutdown_rx);
+
+        let shutdown = dispatch_until(&mut items, shutdown_rx, &mut tasks, |i| {
+            started.fetch_add(1, Ordering::Relaxed);
+            if i == 2 {
+                // request shutdown while earlier tasks are still running
+                shutdown_tx.take().unwrap().send(()).unwrap();
+            }
+            let completed = completed.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                completed.fetch_add(1, Ordering::Relaxed);
+            }
+        })
+        .await;
+
+        assert!(shutdown);
+        assert_eq!(started.load(Ordering::Relaxed), 3);
+        assert_eq!(completed.load(Ordering::Relaxed), 0);
+
+        tasks.drain().await;
+        assert_eq!(completed.load(Ordering::Relaxed), 3);
+        assert!(tasks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn stops_when_stream_ends() {
+        let mut tasks = BoundedSpawner::new(10);
+        let shutdown = std::future::pending::<()>();
+        tokio::pin!(shutdown);
+
+        assert!(!dispatch_until(&mut stream::iter(0..3), shutdown, &mut tasks, |_| async {}).await);
+        tasks.drain().await;
+    }
+}
@@ -0,0 +1,92 @@
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::{JoinError, JoinSet};
+
+/// Tracks spawned tasks and limits how many of them run at once
+#[derive(Debug)]
+pub struct BoundedSpawner {
+    tasks: JoinSet<()>,
+    permits: Arc<Semaphore>,
+}
+
+impl BoundedSpawner {
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            tasks: JoinSet::new(),
+            permits: Arc::new(Semaphore::new(max_concurrent.max(1))),
+        }
+    }
+
+    /// Spawns `task` once one of running tasks completes if the limit is
+    /// reached, so that callers are backpressured
+    pub async fn spawn<F>(&mut self, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let permit = self
+            .permits
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("semaphore is never closed");
+        self.tasks.spawn(async move {
+            task.await;
+            drop(permit);
+        });
+    }
+
+    /// Waits for any of tasks to complete, returns `None` if there are none
+    pub async fn join_next(&mut self) -> Option<Result<(), JoinError>> {
+        self.tasks.join_next().await
+    }
+
+    /// Waits for all tasks to complete
+    pub async fn drain(&mut self) {
+        while self.join_next().await.is_some() {}
+    }
+
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn limits_concurrent_tasks() {
+        const LIMIT: usize = 3;
+
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+        let completed = Arc::new(AtomicUsize::new(0));
+        let mut spawner = BoundedSpawner::new(LIMIT);
+
+        for _ in 0..20 {
+            let (running, max_running, completed) =
+                (running.clone(), max_running.clone(), completed.clone());
+            spawner
+                .spawn(async move {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    completed.fetch_add(1, Ordering::SeqCst);
+                })
+                .await;
+        }
+        spawner.drain().await;
+
+        assert!(spawner.is_empty());
+        assert_eq!(completed.load(Ordering::SeqCst), 20);
+        assert_eq!(max_running.load(Ordering::SeqCst), LIMIT);
+    }
+}
@@ -0,0 +1,66 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Aggregate counters of the scanner, shared between spawned tasks.
+#[derive(Debug, Default)]
+pub struct ScannerStats {
+    contracts_detected: AtomicU64,
+    bytecodes_saved: AtomicU64,
+    sources_fetched: AtomicU64,
+    errors: AtomicU64,
+}
+
+impl ScannerStats {
+    pub fn contract_detected(&self) {
+        self.contracts_detected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn bytecode_saved(&self) {
+        self.bytecodes_saved.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn source_fetched(&self) {
+        self.sources_fetched.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn error(&self) {
+        self.errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Renders counters as a single log line
+    pub fn render(&self) -> String {
+        format!(
+            "contracts_detected={} bytecodes_saved={} sources_fetched={} errors={}",
+            self.contracts_detected.load(Ordering::Relaxed),
+            self.bytecodes_saved.load(Ordering::Relaxed),
+            self.sources_fetched.load(Ordering::Relaxed),
+            self.errors.load(Ordering::Relaxed),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render() {
+        let stats = ScannerStats::default();
+        assert_eq!(
+            stats.render(),
+            "contracts_detected=0 bytecodes_saved=0 sources_fetched=0 errors=0"
+        );
+
+        stats.contract_detected();
+        stats.contract_detected();
+        stats.bytecode_saved();
+        stats.source_fetched();
+        stats.error();
+        stats.error();
+        stats.error();
+
+        assert_eq!(
+            stats.render(),
+            "contracts_detected=2 bytecodes_saved=1 sources_fetched=1 errors=3"
+        );
+    }
+}